@@ -0,0 +1,35 @@
+// Command headless-provisioner applies a maziq template without the TUI,
+// printing one line per progress event.
+//
+//	go run ./examples/headless-provisioner -template hmziq -dry-run
+package main
+
+import (
+	"context"
+	"flag"
+	"fmt"
+	"os"
+
+	"github.com/hmziqrs/maziq"
+)
+
+func main() {
+	template := flag.String("template", "hmziq", "template to install")
+	dryRun := flag.Bool("dry-run", false, "report steps without running them")
+	flag.Parse()
+
+	plan, err := maziq.PlanTemplate(*template, maziq.ActionInstall)
+	if err != nil {
+		fmt.Fprintf(os.Stderr, "plan: %v\n", err)
+		os.Exit(1)
+	}
+	fmt.Printf("%d steps\n", len(plan.Steps))
+
+	err = maziq.ExecutePlan(context.Background(), plan, maziq.ExecuteOptions{DryRun: *dryRun}, func(ev maziq.Event) {
+		fmt.Printf("%s  %-20s %s\n", ev.Time.Format("15:04:05"), ev.SoftwareID, ev.Message)
+	})
+	if err != nil {
+		fmt.Fprintf(os.Stderr, "execute: %v\n", err)
+		os.Exit(1)
+	}
+}
@@ -0,0 +1,45 @@
+// Command status-json probes every catalog entry and prints the result as
+// JSON, suitable for dashboards or inventory tooling.
+//
+//	go run ./examples/status-json > status.json
+package main
+
+import (
+	"context"
+	"encoding/json"
+	"os"
+
+	"github.com/hmziqrs/maziq"
+)
+
+type entryStatus struct {
+	ID       string `json:"id"`
+	Name     string `json:"name"`
+	Category string `json:"category"`
+	State    string `json:"state"`
+	Version  string `json:"version,omitempty"`
+	Error    string `json:"error,omitempty"`
+}
+
+func main() {
+	statuses := maziq.ProbeStatusAll(context.Background())
+
+	var out []entryStatus
+	for _, entry := range maziq.Catalog() {
+		st := statuses[entry.ID]
+		out = append(out, entryStatus{
+			ID:       entry.ID,
+			Name:     entry.Name,
+			Category: string(entry.Category),
+			State:    st.State.String(),
+			Version:  st.Version,
+			Error:    st.Error,
+		})
+	}
+
+	enc := json.NewEncoder(os.Stdout)
+	enc.SetIndent("", "  ")
+	if err := enc.Encode(out); err != nil {
+		os.Exit(1)
+	}
+}
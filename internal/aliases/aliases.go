@@ -0,0 +1,90 @@
+// Package aliases provisions the opt-in shell aliases catalog entries
+// recommend. Each enabled entry owns one marker-delimited block per shell
+// profile, so enabling is idempotent and disabling removes exactly what
+// was added.
+package aliases
+
+import (
+	"fmt"
+	"os"
+	"path/filepath"
+	"strings"
+
+	"github.com/hmziqrs/maziq/internal/catalog"
+	"github.com/hmziqrs/maziq/internal/paths"
+	"github.com/hmziqrs/maziq/internal/shellprofile"
+)
+
+const blockPrefix = "aliases:"
+
+// target is a shell profile and the snippet field it receives.
+type target struct {
+	path    string
+	snippet func(catalog.ShellAlias) string
+	// always is set for the login shell's profile, which is created if missing.
+	always  bool
+}
+
+func targets() []target {
+	home := paths.Home()
+	return []target{
+		{filepath.Join(home, ".zshrc"), func(a catalog.ShellAlias) string { return a.Zsh }, true},
+		{filepath.Join(home, ".bashrc"), func(a catalog.ShellAlias) string { return a.Bash }, false},
+		{filepath.Join(home, ".config", "fish", "config.fish"), func(a catalog.ShellAlias) string { return a.Fish }, false},
+	}
+}
+
+// Enable writes the entry's aliases into every applicable shell profile.
+func Enable(entry catalog.Entry) error {
+	if len(entry.Aliases) == 0 {
+		return fmt.Errorf("%s does not ship any aliases", entry.ID)
+	}
+	for _, t := range targets() {
+		if _, err := os.Stat(t.path); err != nil && !t.always {
+			continue
+		}
+		var lines []string
+		for _, alias := range entry.Aliases {
+			if snippet := t.snippet(alias); snippet != "" {
+				lines = append(lines, "# "+alias.Description, snippet)
+			}
+		}
+		if len(lines) == 0 {
+			continue
+		}
+		if err := shellprofile.Upsert(t.path, blockPrefix+entry.ID, strings.Join(lines, "\n")); err != nil {
+			return err
+		}
+	}
+	return nil
+}
+
+// Disable removes the entry's alias blocks from every shell profile.
+func Disable(id string) error {
+	for _, t := range targets() {
+		if _, err := shellprofile.Remove(t.path, blockPrefix+id); err != nil {
+			return err
+		}
+	}
+	return nil
+}
+
+// Enabled returns the ids whose aliases are present in any shell profile.
+func Enabled() ([]string, error) {
+	seen := map[string]bool{}
+	var ids []string
+	for _, t := range targets() {
+		blocks, err := shellprofile.Blocks(t.path)
+		if err != nil {
+			return nil, err
+		}
+		for _, block := range blocks {
+			id, ok := strings.CutPrefix(block, blockPrefix)
+			if ok && !seen[id] {
+				seen[id] = true
+				ids = append(ids, id)
+			}
+		}
+	}
+	return ids, nil
+}
@@ -0,0 +1,81 @@
+// Package cache records which packages `maziq cache warm` fetched into a
+// package manager's own download cache, so offline runs know which
+// recipes can still succeed without the network.
+package cache
+
+import (
+	"bufio"
+	"encoding/json"
+	"os"
+	"path/filepath"
+
+	"github.com/hmziqrs/maziq/internal/paths"
+)
+
+// Item is one line of the cache manifest.
+type Item struct {
+	Software  string `json:"software"`
+	Source    string `json:"source"`
+	// Package is the formula or cask whose download was fetched, or the
+	// installer URL for script recipes.
+	Package   string `json:"package"`
+	Timestamp int64  `json:"timestamp"`
+}
+
+// Append adds item to the manifest, creating it if needed.
+func Append(item Item) error {
+	path := paths.CacheManifestFile()
+	if err := os.MkdirAll(filepath.Dir(path), 0o755); err != nil {
+		return err
+	}
+	line, err := json.Marshal(item)
+	if err != nil {
+		return err
+	}
+	f, err := os.OpenFile(path, os.O_CREATE|os.O_APPEND|os.O_WRONLY, 0o644)
+	if err != nil {
+		return err
+	}
+	defer f.Close()
+	_, err = f.Write(append(line, '\n'))
+	return err
+}
+
+// Load reads every item, oldest first. A missing manifest is empty and
+// malformed lines are skipped.
+func Load() ([]Item, error) {
+	f, err := os.Open(paths.CacheManifestFile())
+	if os.IsNotExist(err) {
+		return nil, nil
+	}
+	if err != nil {
+		return nil, err
+	}
+	defer f.Close()
+
+	var out []Item
+	scanner := bufio.NewScanner(f)
+	for scanner.Scan() {
+		var item Item
+		if json.Unmarshal(scanner.Bytes(), &item) == nil && item.Software != "" {
+			out = append(out, item)
+		}
+	}
+	return out, scanner.Err()
+}
+
+// Has reports whether items hold package fetched through source.
+func Has(items []Item, source, pkg string) bool {
+	for _, item := range items {
+		if item.Source == source && item.Package == pkg {
+			return true
+		}
+	}
+	return false
+}
+
+// Clear removes everything maziq cached. Package managers keep their own
+// copies of what they fetched.
+func Clear() error {
+	return os.RemoveAll(paths.CacheDir())
+}
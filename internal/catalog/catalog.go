@@ -0,0 +1,457 @@
+// Package catalog defines the software entries maziq knows how to manage.
+package catalog
+
+import (
+	"fmt"
+	"sort"
+	"strings"
+
+	"github.com/hmziqrs/maziq/internal/errs"
+)
+
+// Kind distinguishes how an entry is shipped and detected.
+type Kind int
+
+const (
+	KindCLI Kind = iota
+	KindGUI
+	KindSDK
+)
+
+func (k Kind) String() string {
+	switch k {
+	case KindGUI:
+		return "gui"
+	case KindSDK:
+		return "sdk"
+	default:
+		return "cli"
+	}
+}
+
+// Category groups entries in the catalog screen and listings.
+type Category string
+
+const (
+	CategoryCore      Category = "Core"
+	CategoryBrowsers  Category = "Browsers"
+	CategoryEditors   Category = "Editors"
+	CategoryDevTools  Category = "Dev Tools"
+	CategoryLanguages Category = "Languages"
+	CategoryMobile    Category = "Mobile"
+	CategoryAI        Category = "AI CLIs"
+)
+
+// Source names the backend a recipe goes through.
+type Source string
+
+const (
+	SourceBrew     Source = "brew"
+	SourceBrewCask Source = "brew-cask"
+	SourceCargo    Source = "cargo"
+	SourceNpm      Source = "npm"
+	SourcePipx     Source = "pipx"
+	SourceRustup   Source = "rustup"
+	SourceMas      Source = "mas"
+	SourceMise     Source = "mise"
+	SourceAsdf     Source = "asdf"
+	SourceNix      Source = "nix"
+	SourcePort     Source = "port"
+	SourceScript   Source = "script"
+	SourceSystem   Source = "system"
+	SourceGit      Source = "git"
+	SourceRelease  Source = "github-release"
+)
+
+// Recipe is one way of performing an action for an entry.
+type Recipe struct {
+	Source    Source
+	Command   string
+	// AppID is the Mac App Store id for SourceMas recipes.
+	AppID     string
+	// Package is the flake reference for SourceNix recipes, the port name
+	// for SourcePort recipes, the PyPI name for SourcePipx recipes and the
+	// formula, cask, crate or npm package name for their sources.
+	Package   string
+	// Installer, when set, replaces Command at run time: the script is
+	// downloaded, checked against its pinned SHA256 and only then run.
+	// Command still shows the upstream one-liner it stands for.
+	Installer *Installer
+	// Git, when set, replaces Command at run time for SourceGit recipes:
+	// the manager clones, builds and places the artifacts itself. Command
+	// still summarizes what it does.
+	Git       *GitBuild
+	// Release, when set, replaces Command at run time for SourceRelease
+	// recipes: the manager downloads and verifies the asset itself.
+	Release   *GitHubRelease
+	// Env is set for the command on top of maziq's own environment, e.g.
+	// NONINTERACTIVE=1 so an installer does not wait for a keypress.
+	Env       map[string]string
+	// Mode is the shell Command runs in; empty is sh -c.
+	Mode      ShellMode
+	// Dir is the working directory Command runs in, created if missing;
+	// a leading "~/" is the home directory. Empty inherits maziq's.
+	Dir       string
+	// TempDir runs Command in a fresh directory removed once it is done,
+	// e.g. to clone a repository and build it from source.
+	TempDir   bool
+}
+
+// ShellMode selects the shell, and how it starts, that runs a recipe.
+type ShellMode string
+
+const (
+	// ShellSh runs the command with sh -c, without any profile.
+	ShellSh ShellMode = ""
+	// ShellBashLogin runs it with bash -lc, after the login profile, for
+	// commands that need the PATH it sets up.
+	ShellBashLogin ShellMode = "bash-login"
+	// ShellZshInteractive runs it with zsh -ic, after ~/.zshrc, for shell
+	// functions such as nvm that only interactive shells define.
+	ShellZshInteractive ShellMode = "zsh-interactive"
+	// ShellFish runs it with fish -c, for fish syntax.
+	ShellFish ShellMode = "fish"
+)
+
+// ShellModes lists every mode by name, sh first.
+var ShellModes = []ShellMode{ShellSh, ShellBashLogin, ShellZshInteractive, ShellFish}
+
+// ParseShellMode validates a mode name; "" and "sh" are ShellSh.
+func ParseShellMode(name string) (ShellMode, error) {
+	if name == "sh" {
+		return ShellSh, nil
+	}
+	for _, mode := range ShellModes {
+		if string(mode) == name {
+			return mode, nil
+		}
+	}
+	return ShellSh, fmt.Errorf("unknown shell mode %q (expected sh, bash-login, zsh-interactive or fish)", name)
+}
+
+// Argv returns the program and flags that run a script in mode, the
+// script following as the last argument.
+func (m ShellMode) Argv() []string {
+	switch m {
+	case ShellBashLogin:
+		return []string{"bash", "-lc"}
+	case ShellZshInteractive:
+		return []string{"zsh", "-ic"}
+	case ShellFish:
+		return []string{"fish", "-c"}
+	}
+	return []string{"sh", "-c"}
+}
+
+// String names m the way ParseShellMode accepts it.
+func (m ShellMode) String() string {
+	if m == ShellSh {
+		return "sh"
+	}
+	return string(m)
+}
+
+// Installer is an upstream install script run from a verified download
+// instead of being piped from curl into a shell.
+type Installer struct {
+	URL   string
+	// Shell runs the downloaded script, e.g. "bash" or "python3".
+	Shell string
+	Args  []string
+}
+
+// GitBuild is software built from a git repository, for tools no package
+// manager carries. The checkout is kept so updates pull and rebuild.
+type GitBuild struct {
+	Repo      string
+	// Ref is the branch or tag to check out; empty is the default branch.
+	Ref       string
+	// Build runs in the checkout; empty when the artifacts need no build.
+	Build     string
+	// Artifacts are paths in the checkout copied into ~/.local/bin.
+	Artifacts []string
+}
+
+// GitHubRelease is software published only as binaries attached to GitHub
+// releases. The latest release's asset for the host is downloaded, checked
+// against the SHA256 GitHub or the release publishes, and its binaries
+// copied into ~/.local/bin.
+type GitHubRelease struct {
+	// Repo is "owner/name".
+	Repo     string
+	// Asset matches the asset's name: * is any text, {os} the host's OS
+	// and {arch} its architecture, under each of their usual spellings
+	// (darwin or macos; arm64 or aarch64, amd64 or x86_64).
+	Asset    string
+	// Binaries are the executables to install, found by name anywhere in
+	// an archive. An asset that is a bare binary is installed as the first.
+	Binaries []string
+}
+
+// ProbeDepth classifies how expensive a version probe is.
+type ProbeDepth int
+
+const (
+	// ProbeQuick probes run on every status refresh.
+	ProbeQuick ProbeDepth = iota
+	// ProbeDeep probes take seconds and only run on demand or in the background.
+	ProbeDeep
+)
+
+func (d ProbeDepth) String() string {
+	if d == ProbeDeep {
+		return "deep"
+	}
+	return "quick"
+}
+
+// Probe describes how to detect the installed version of an entry.
+type Probe struct {
+	// Command prints the installed version; a failure means not installed.
+	Command     string
+	// AppPath is an .app bundle whose version is read via mdls.
+	AppPath     string
+	// MasAppID is looked up in `mas list` output.
+	MasAppID    string
+	// PipxPackage is looked up in `pipx list --json`; Command, if set, is
+	// the fallback for installs made outside pipx.
+	PipxPackage string
+	Depth       ProbeDepth
+}
+
+// Describe summarizes the probe for display, in the order it is tried.
+func (p Probe) Describe() string {
+	var parts []string
+	if p.AppPath != "" {
+		parts = append(parts, "app bundle "+p.AppPath)
+	}
+	if p.MasAppID != "" {
+		parts = append(parts, "mas app "+p.MasAppID)
+	}
+	if p.PipxPackage != "" {
+		parts = append(parts, "pipx package "+p.PipxPackage)
+	}
+	if p.Command != "" {
+		parts = append(parts, p.Command)
+	}
+	if len(parts) == 0 {
+		return "none"
+	}
+	return strings.Join(parts, " → ") + " (" + p.Depth.String() + ")"
+}
+
+// Binary returns the program the probe command runs, e.g. "bat" for
+// "bat --version", or "" without a command. Cargo subcommands name their
+// own binary: "cargo watch --version" is cargo-watch.
+func (p Probe) Binary() string {
+	fields := strings.Fields(p.Command)
+	switch {
+	case len(fields) == 0:
+		return ""
+	case fields[0] == "cargo" && len(fields) > 1 && !strings.HasPrefix(fields[1], "-"):
+		return "cargo-" + fields[1]
+	}
+	return fields[0]
+}
+
+// ShellAlias is an opt-in alias, function or keybinding an entry recommends.
+// Each field holds the snippet for one shell; empty means unsupported there.
+type ShellAlias struct {
+	Description string
+	Zsh         string
+	Bash        string
+	Fish        string
+}
+
+// Hook is a command run after an entry installs successfully, e.g. to
+// register a shell or generate key bindings.
+type Hook struct {
+	Description string
+	Command     string
+}
+
+// Adapters entries can name. Each is registered by the manager.
+const (
+	AdapterBrew   = "brew"
+	AdapterCargo  = "cargo"
+	AdapterNpm    = "npm"
+	AdapterScript = "script"
+)
+
+// Entry is a single piece of software in the catalog.
+type Entry struct {
+	ID           string
+	Name         string
+	Category     Category
+	// Tags are free-form labels, e.g. "ai" or "terminal", independent of
+	// the single category.
+	Tags         []string
+	Kind         Kind
+	Summary      string
+	// Homepage and Docs are the entry's web site and documentation; either
+	// may be empty.
+	Homepage     string
+	Docs         string
+	// License is an SPDX expression, "Proprietary", or empty when unknown.
+	License      string
+	Dependencies []string
+	// Recommended entries improve this one (e.g. faster installs) but are
+	// only installed when asked for.
+	Recommended  []string
+	// Adapter names the manager adapter that probes and changes the entry;
+	// empty uses Probe and the recipes as they are.
+	Adapter      string
+	Probe        Probe
+	Install      []Recipe
+	Update       []Recipe
+	Uninstall    []Recipe
+	// Removes are the directories uninstalling deletes along with the
+	// software, such as an SDK's toolchains and caches; a leading "~/" is
+	// the home directory.
+	Removes      []string
+	Aliases      []ShellAlias
+	// PostInstall hooks run, in order, after a successful install.
+	PostInstall  []Hook
+	// Test exercises the installed software and exits zero when it works.
+	// Entries without one are tested with their probe command.
+	Test         string
+	// Conflicts lists entries that must not be installed alongside this one.
+	Conflicts    []string
+	// Requirements are checked before a plan installing the entry runs.
+	Requirements Requirements
+	// Size is the approximate disk footprint; zero fields are unknown.
+	Size         Size
+}
+
+// Size is an approximate disk footprint in bytes.
+type Size struct {
+	Download  int64
+	Installed int64
+}
+
+// Requirements describe what the machine needs for an entry to install.
+type Requirements struct {
+	// MinMacOS is the oldest supported macOS version, e.g. "14.5".
+	MinMacOS string
+	// Rosetta marks Intel-only software on Apple Silicon.
+	Rosetta  bool
+}
+
+func init() {
+	entries = append(entries, runtimeEntries()...)
+	for i := range entries {
+		entries[i].Dependencies = impliedDependencies(entries[i])
+		entries[i].Tags = entryTags(entries[i])
+		entries[i] = withMetadata(entries[i])
+		if entries[i].Test == "" {
+			entries[i].Test = entries[i].Probe.Command
+		}
+	}
+	symmetricConflicts()
+}
+
+// symmetricConflicts records every conflict on both entries involved.
+func symmetricConflicts() {
+	index := map[string]int{}
+	for i, e := range entries {
+		index[e.ID] = i
+	}
+	for i := range entries {
+		for _, other := range entries[i].Conflicts {
+			j, ok := index[other]
+			if !ok || entries[j].ConflictsWith(entries[i].ID) {
+				continue
+			}
+			entries[j].Conflicts = append(entries[j].Conflicts, entries[i].ID)
+		}
+	}
+}
+
+// ConflictsWith reports whether e must not be installed alongside id.
+func (e Entry) ConflictsWith(id string) bool {
+	for _, c := range e.Conflicts {
+		if c == id {
+			return true
+		}
+	}
+	return false
+}
+
+// impliedDependencies adds the backend tools an entry's recipes rely on,
+// so catalog authors don't have to repeat them on every entry.
+func impliedDependencies(e Entry) []string {
+	deps := e.Dependencies
+	has := func(id string) bool {
+		for _, d := range deps {
+			if d == id {
+				return true
+			}
+		}
+		return false
+	}
+	backends := map[Source]string{SourceMas: "mas", SourcePipx: "pipx", SourceGit: "xcode_clt"}
+	for _, r := range e.Install {
+		if tool, ok := backends[r.Source]; ok && e.ID != tool && !has(tool) {
+			deps = append(deps, tool)
+		}
+	}
+	return deps
+}
+
+// HasSource reports whether any of the entry's install recipes uses source.
+func (e Entry) HasSource(source Source) bool {
+	for _, r := range e.Install {
+		if r.Source == source {
+			return true
+		}
+	}
+	return false
+}
+
+// All returns every catalog entry in display order.
+func All() []Entry {
+	out := make([]Entry, len(entries))
+	copy(out, entries)
+	return out
+}
+
+// UnknownError reports a software id missing from the catalog.
+type UnknownError struct {
+	ID string
+}
+
+func (e *UnknownError) Error() string {
+	return fmt.Sprintf("unknown software %q", e.ID)
+}
+
+func (e *UnknownError) ErrorCode() errs.Code {
+	return errs.CodeUnknown
+}
+
+func (e *UnknownError) ErrorContext() errs.Context {
+	return errs.Context{Entry: e.ID}
+}
+
+// Lookup finds an entry by id.
+func Lookup(id string) (Entry, bool) {
+	for _, e := range entries {
+		if e.ID == id {
+			return e, true
+		}
+	}
+	return Entry{}, false
+}
+
+// Categories returns the categories in use, sorted by name.
+func Categories() []Category {
+	seen := map[Category]bool{}
+	var out []Category
+	for _, e := range entries {
+		if !seen[e.Category] {
+			seen[e.Category] = true
+			out = append(out, e.Category)
+		}
+	}
+	sort.Slice(out, func(i, j int) bool { return out[i] < out[j] })
+	return out
+}
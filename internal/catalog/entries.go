@@ -0,0 +1,442 @@
+package catalog
+
+import (
+	"fmt"
+	"path"
+	"strings"
+)
+
+// brewNoUpdate keeps brew from refreshing every tap before a command that
+// does not need fresh formulae.
+var brewNoUpdate = map[string]string{"HOMEBREW_NO_AUTO_UPDATE": "1"}
+
+// cargoGitCLI makes cargo fetch git dependencies with the git binary, which
+// honours the user's credentials and proxy settings.
+var cargoGitCLI = map[string]string{"CARGO_NET_GIT_FETCH_WITH_CLI": "true"}
+
+// cask builds a GUI entry installed through a Homebrew cask.
+func cask(id, name, token, app string, category Category, summary string) Entry {
+	return Entry{
+		ID:           id,
+		Name:         name,
+		Category:     category,
+		Kind:         KindGUI,
+		Summary:      summary,
+		Dependencies: []string{"homebrew"},
+		Adapter:      AdapterBrew,
+		Probe:        Probe{AppPath: "/Applications/" + app},
+		Install:      []Recipe{{Source: SourceBrewCask, Package: token, Command: "brew install --cask " + token}},
+		Update:       []Recipe{{Source: SourceBrewCask, Package: token, Command: "brew upgrade --cask " + token}},
+		Uninstall:    []Recipe{{Source: SourceBrewCask, Package: token, Command: "brew uninstall --cask " + token, Env: brewNoUpdate}},
+	}
+}
+
+// formula builds a CLI entry installed through a Homebrew formula.
+func formula(id, name, token, probe string, category Category, summary string) Entry {
+	return Entry{
+		ID:           id,
+		Name:         name,
+		Category:     category,
+		Kind:         KindCLI,
+		Summary:      summary,
+		Dependencies: []string{"homebrew"},
+		Adapter:      AdapterBrew,
+		Probe:        Probe{Command: probe},
+		Install:      []Recipe{{Source: SourceBrew, Package: token, Command: "brew install " + token}},
+		Update:       []Recipe{{Source: SourceBrew, Package: token, Command: "brew upgrade " + token}},
+		Uninstall:    []Recipe{{Source: SourceBrew, Package: token, Command: "brew uninstall " + token, Env: brewNoUpdate}},
+	}
+}
+
+// crate builds a CLI entry installed with cargo install, or with
+// cargo-binstall's prebuilt binaries when that is available.
+func crate(id, name, pkg, probe string, summary string) Entry {
+	// Prebuilt binaries through cargo-binstall when it is around, falling
+	// back to a source build when it isn't or has nothing for this target.
+	binstall := "{ command -v cargo-binstall >/dev/null && cargo binstall --no-confirm %[1]s; } || cargo install --locked %[2]s%[1]s"
+	install := fmt.Sprintf(binstall, pkg, "")
+	update := fmt.Sprintf(binstall, pkg, "--force ")
+	e := Entry{
+		ID:           id,
+		Name:         name,
+		Category:     CategoryDevTools,
+		Kind:         KindCLI,
+		Summary:      summary,
+		Dependencies: []string{"rust_stable"},
+		Recommended:  []string{"cargo_binstall"},
+		Adapter:      AdapterCargo,
+		Probe:        Probe{Command: probe},
+		Install:      []Recipe{{Source: SourceCargo, Package: pkg, Command: install, Env: cargoGitCLI}},
+		Update:       []Recipe{{Source: SourceCargo, Package: pkg, Command: update, Env: cargoGitCLI}},
+		Uninstall:    []Recipe{{Source: SourceCargo, Package: pkg, Command: "cargo uninstall " + pkg}},
+	}
+	if id == "cargo_binstall" {
+		e.Recommended = nil
+	}
+	return e
+}
+
+// npmGlobal builds a CLI entry installed as a global npm package.
+func npmGlobal(id, name, pkg, probe string, category Category, summary string) Entry {
+	return Entry{
+		ID:           id,
+		Name:         name,
+		Category:     category,
+		Kind:         KindCLI,
+		Summary:      summary,
+		Dependencies: []string{"nvm"},
+		Adapter:      AdapterNpm,
+		// The npm adapter asks the registry for the latest version.
+		Probe:        Probe{Command: probe, Depth: ProbeDeep},
+		Install:      []Recipe{{Source: SourceNpm, Package: pkg, Command: "npm install -g " + pkg}},
+		Update:       []Recipe{{Source: SourceNpm, Package: pkg, Command: fmt.Sprintf("npm install -g %s@latest", pkg)}},
+		Uninstall:    []Recipe{{Source: SourceNpm, Package: pkg, Command: "npm uninstall -g " + pkg}},
+	}
+}
+
+// masApp builds a GUI entry installed from the Mac App Store via mas.
+// The dependency on mas itself is added automatically.
+func masApp(id, name, appID string, category Category, summary string) Entry {
+	return Entry{
+		ID:        id,
+		Name:      name,
+		Category:  category,
+		Kind:      KindGUI,
+		Summary:   summary,
+		Probe:     Probe{MasAppID: appID},
+		Install:   []Recipe{{Source: SourceMas, AppID: appID, Command: "mas install " + appID}},
+		Update:    []Recipe{{Source: SourceMas, AppID: appID, Command: "mas upgrade " + appID}},
+		Uninstall: []Recipe{{Source: SourceMas, AppID: appID, Command: "sudo mas uninstall " + appID}},
+	}
+}
+
+// pipxTool builds a Python CLI entry installed into its own venv via pipx.
+// Extra recipes (e.g. official curl installers) are tried after pipx.
+func pipxTool(id, name, pkg, probe string, category Category, summary string, fallback ...Recipe) Entry {
+	return Entry{
+		ID:        id,
+		Name:      name,
+		Category:  category,
+		Kind:      KindCLI,
+		Summary:   summary,
+		Probe:     Probe{PipxPackage: pkg, Command: probe},
+		Install:   append([]Recipe{{Source: SourcePipx, Package: pkg, Command: "pipx install " + pkg}}, fallback...),
+		Update:    []Recipe{{Source: SourcePipx, Package: pkg, Command: "pipx upgrade " + pkg}},
+		Uninstall: []Recipe{{Source: SourcePipx, Package: pkg, Command: "pipx uninstall " + pkg}},
+	}
+}
+
+// gitTool builds a CLI entry cloned from a git repository and built from
+// source, for tools no package manager carries. The commands only show
+// what the manager does; see GitBuild.
+func gitTool(id, name string, git GitBuild, probe string, category Category, summary string) Entry {
+	build := ""
+	if git.Build != "" {
+		build = " && " + git.Build
+	}
+	place := " && install -m 755 " + strings.Join(git.Artifacts, " ") + " ~/.local/bin/"
+	var placed []string
+	for _, artifact := range git.Artifacts {
+		placed = append(placed, "~/.local/bin/"+path.Base(artifact))
+	}
+	return Entry{
+		ID:        id,
+		Name:      name,
+		Category:  category,
+		Kind:      KindCLI,
+		Summary:   summary,
+		Probe:     Probe{Command: probe},
+		Install:   []Recipe{{Source: SourceGit, Git: &git, Command: "git clone " + git.Repo + build + place}},
+		Update:    []Recipe{{Source: SourceGit, Git: &git, Command: "git pull --ff-only" + build + place}},
+		Uninstall: []Recipe{{Source: SourceGit, Git: &git, Command: "rm -f " + strings.Join(placed, " ")}},
+	}
+}
+
+// releaseTool builds a CLI entry installed from the binaries attached to
+// its GitHub releases. The commands only show what the manager does; see
+// GitHubRelease.
+func releaseTool(id, name string, release GitHubRelease, probe string, category Category, summary string) Entry {
+	var placed []string
+	for _, bin := range release.Binaries {
+		placed = append(placed, "~/.local/bin/"+bin)
+	}
+	fetch := fmt.Sprintf("download %s from the latest release of github.com/%s && install -m 755 %s ~/.local/bin/",
+		release.Asset, release.Repo, strings.Join(release.Binaries, " "))
+	return Entry{
+		ID:        id,
+		Name:      name,
+		Category:  category,
+		Kind:      KindCLI,
+		Summary:   summary,
+		Probe:     Probe{Command: probe},
+		Install:   []Recipe{{Source: SourceRelease, Release: &release, Command: fetch}},
+		Update:    []Recipe{{Source: SourceRelease, Release: &release, Command: fetch}},
+		Uninstall: []Recipe{{Source: SourceRelease, Release: &release, Command: "rm -f " + strings.Join(placed, " ")}},
+	}
+}
+
+// withNix adds opt-in Nix recipes, used when the nix backend is preferred.
+func withNix(e Entry, attr string) Entry {
+	pkg := "nixpkgs#" + attr
+	nix := "nix --extra-experimental-features 'nix-command flakes' profile"
+	e.Install = append(e.Install, Recipe{Source: SourceNix, Package: pkg, Command: nix + " install " + pkg})
+	e.Update = append(e.Update, Recipe{Source: SourceNix, Package: pkg, Command: nix + " upgrade " + attr})
+	e.Uninstall = append(e.Uninstall, Recipe{Source: SourceNix, Package: pkg, Command: nix + " remove " + attr})
+	return e
+}
+
+// withPort adds MacPorts recipes, preferred when Homebrew is absent.
+func withPort(e Entry, port string) Entry {
+	e.Install = append(e.Install, Recipe{Source: SourcePort, Package: port, Command: "sudo port install " + port})
+	e.Update = append(e.Update, Recipe{Source: SourcePort, Package: port, Command: "sudo port upgrade " + port})
+	e.Uninstall = append(e.Uninstall, Recipe{Source: SourcePort, Package: port, Command: "sudo port uninstall " + port})
+	return e
+}
+
+// withConflicts marks entries that must not be installed alongside e. The
+// relation is made symmetric when the catalog is initialized.
+func withConflicts(e Entry, ids ...string) Entry {
+	e.Conflicts = append(e.Conflicts, ids...)
+	return e
+}
+
+// withPostInstall attaches hooks run after a successful install.
+func withPostInstall(e Entry, hooks ...Hook) Entry {
+	e.PostInstall = append(e.PostInstall, hooks...)
+	return e
+}
+
+// withRequirements records what the machine needs for e to install.
+func withRequirements(e Entry, r Requirements) Entry {
+	e.Requirements = r
+	return e
+}
+
+// withSize records the approximate download and installed size of e, in
+// MiB. Entries installed through Homebrew can leave it out: their download
+// size is looked up when a plan is printed.
+func withSize(e Entry, downloadMiB, installedMiB int64) Entry {
+	e.Size = Size{Download: downloadMiB << 20, Installed: installedMiB << 20}
+	return e
+}
+
+// withTest replaces the probe command as e's test with one that actually
+// exercises the software.
+func withTest(e Entry, command string) Entry {
+	e.Test = command
+	return e
+}
+
+// withAliases attaches recommended shell aliases to an entry.
+func withAliases(e Entry, aliases ...ShellAlias) Entry {
+	e.Aliases = aliases
+	return e
+}
+
+// nvmInstaller installs and updates nvm; rerunning it upgrades in place.
+var nvmInstaller = Recipe{
+	Source:    SourceScript,
+	Command:   "curl -o- https://raw.githubusercontent.com/nvm-sh/nvm/v0.40.3/install.sh | bash",
+	Installer: &Installer{URL: "https://raw.githubusercontent.com/nvm-sh/nvm/v0.40.3/install.sh", Shell: "bash"},
+}
+
+var entries = []Entry{
+	{
+		ID:       "homebrew",
+		Name:     "Homebrew",
+		Category: CategoryCore,
+		Kind:     KindCLI,
+		Summary:  "The missing package manager for macOS.",
+		Adapter:  AdapterScript,
+		Probe:    Probe{Command: "brew --version"},
+		Install: []Recipe{{
+			Source:    SourceScript,
+			Command:   `/bin/bash -c "$(curl -fsSL https://raw.githubusercontent.com/Homebrew/install/HEAD/install.sh)"`,
+			Installer: &Installer{URL: "https://raw.githubusercontent.com/Homebrew/install/HEAD/install.sh", Shell: "/bin/bash"},
+			Env:       map[string]string{"NONINTERACTIVE": "1"},
+		}},
+		Update: []Recipe{{Source: SourceBrew, Command: "brew update"}},
+		Uninstall: []Recipe{{
+			Source:    SourceScript,
+			Command:   `/bin/bash -c "$(curl -fsSL https://raw.githubusercontent.com/Homebrew/install/HEAD/uninstall.sh)"`,
+			Installer: &Installer{URL: "https://raw.githubusercontent.com/Homebrew/install/HEAD/uninstall.sh", Shell: "/bin/bash"},
+			Env:       map[string]string{"NONINTERACTIVE": "1"},
+		}},
+	},
+	{
+		ID:       "xcode_clt",
+		Name:     "Xcode Command Line Tools",
+		Category: CategoryCore,
+		Kind:     KindSDK,
+		Summary:  "Compilers, git and headers required by most toolchains.",
+		Probe: Probe{
+			Command: "pkgutil --pkg-info=com.apple.pkg.CLTools_Executables | awk '/version/ {print $2}'",
+			Depth:   ProbeDeep,
+		},
+		Install:   []Recipe{{Source: SourceSystem, Command: "xcode-select --install"}},
+		Update:    []Recipe{{Source: SourceSystem, Command: "softwareupdate --install --all"}},
+		Uninstall: []Recipe{{Source: SourceSystem, Command: "sudo rm -rf /Library/Developer/CommandLineTools"}},
+	},
+	withPort(formula("mas", "mas", "mas", "mas version", CategoryCore, "Mac App Store command line interface."), "mas"),
+	withSize(withRequirements(masApp("xcode", "Xcode", "497799835", CategoryDevTools, "Apple's IDE and SDKs for all Apple platforms."), Requirements{MinMacOS: "14.5"}), 3500, 12000),
+	withSize(masApp("keynote", "Keynote", "409183694", CategoryDevTools, "Apple's presentation app."), 650, 700),
+	withSize(masApp("slack", "Slack", "803453959", CategoryDevTools, "Team messaging."), 200, 500),
+	withSize(cask("brave", "Brave", "brave-browser", "Brave Browser.app", CategoryBrowsers, "Privacy-focused Chromium browser."), 250, 700),
+	withSize(cask("firefox", "Firefox", "firefox", "Firefox.app", CategoryBrowsers, "Mozilla's web browser."), 140, 420),
+	withSize(cask("chrome", "Google Chrome", "google-chrome", "Google Chrome.app", CategoryBrowsers, "Google's web browser."), 230, 650),
+	withSize(cask("cursor", "Cursor", "cursor", "Cursor.app", CategoryEditors, "AI-first code editor based on VS Code."), 250, 700),
+	withSize(cask("windsurf", "Windsurf", "windsurf", "Windsurf.app", CategoryEditors, "Agentic IDE by Codeium."), 200, 650),
+	withSize(cask("visual_studio_code", "Visual Studio Code", "visual-studio-code", "Visual Studio Code.app", CategoryEditors, "Microsoft's code editor."), 150, 600),
+	// Both Zed channels share ~/.config/zed and its extension data.
+	withSize(withConflicts(cask("zed_stable", "Zed", "zed", "Zed.app", CategoryEditors, "High-performance collaborative editor."), "zed_preview"), 70, 250),
+	withSize(cask("zed_preview", "Zed Preview", "zed@preview", "Zed Preview.app", CategoryEditors, "Preview channel of the Zed editor."), 70, 250),
+	withSize(cask("raycast", "Raycast", "raycast", "Raycast.app", CategoryDevTools, "Extendable launcher replacing Spotlight."), 80, 220),
+	// Both provide the docker CLI and fight over the docker socket.
+	withTest(withSize(withConflicts(cask("docker_desktop", "Docker Desktop", "docker", "Docker.app", CategoryDevTools, "Container runtime and tooling."), "colima"), 600, 2000), "docker run --rm hello-world"),
+	formula("colima", "Colima", "colima", "colima version", CategoryDevTools, "Lightweight container runtime on Lima VMs."),
+	withSize(cask("postman", "Postman", "postman", "Postman.app", CategoryDevTools, "API client and collaboration platform."), 200, 600),
+	withSize(cask("yaak", "Yaak", "yaak", "Yaak.app", CategoryDevTools, "Offline-first API client."), 40, 120),
+	gitTool("git_open", "git-open", GitBuild{Repo: "https://github.com/paulirish/git-open.git", Artifacts: []string{"git-open"}},
+		"command -v git-open", CategoryDevTools, "Open the repository's page on GitHub, GitLab or Bitbucket."),
+	releaseTool("yq", "yq", GitHubRelease{Repo: "mikefarah/yq", Asset: "yq_{os}_{arch}", Binaries: []string{"yq"}},
+		"yq --version", CategoryDevTools, "Command-line YAML, JSON and XML processor."),
+	{
+		ID:       "rustup",
+		Name:     "Rustup",
+		Category: CategoryLanguages,
+		Kind:     KindSDK,
+		Summary:  "Rust toolchain installer.",
+		Adapter:  AdapterScript,
+		Probe:    Probe{Command: "rustup --version"},
+		Install: []Recipe{{
+			Source:    SourceScript,
+			Command:   "curl --proto '=https' --tlsv1.2 -sSf https://sh.rustup.rs | sh -s -- -y",
+			Installer: &Installer{URL: "https://sh.rustup.rs", Shell: "sh", Args: []string{"-y"}},
+		}},
+		Update:    []Recipe{{Source: SourceRustup, Command: "rustup self update"}},
+		Uninstall: []Recipe{{Source: SourceRustup, Command: "rustup self uninstall -y"}},
+		Removes:   []string{"~/.rustup", "~/.cargo"},
+	},
+	{
+		ID:           "rust_stable",
+		Name:         "Rust (stable)",
+		Category:     CategoryLanguages,
+		Kind:         KindSDK,
+		Summary:      "Stable Rust compiler and cargo.",
+		Dependencies: []string{"rustup"},
+		Probe:        Probe{Command: "rustc --version"},
+		Install:      []Recipe{{Source: SourceRustup, Command: "rustup toolchain install stable"}},
+		Update:       []Recipe{{Source: SourceRustup, Command: "rustup update stable"}},
+		Uninstall:    []Recipe{{Source: SourceRustup, Command: "rustup toolchain uninstall stable"}},
+		Removes:      []string{"~/.rustup/toolchains/stable-*"},
+	},
+	withNix(crate("cargo_just", "just", "just", "just --version", "Handy command runner."), "just"),
+	crate("cargo_binstall", "cargo-binstall", "cargo-binstall", "cargo binstall -V", "Binary installs for Rust crates."),
+	crate("cargo_watch", "cargo-watch", "cargo-watch", "cargo watch --version", "Re-run cargo commands on file changes."),
+	crate("dioxus_cli", "Dioxus CLI", "dioxus-cli", "dx --version", "Build and serve Dioxus apps."),
+	crate("trunk", "Trunk", "trunk", "trunk --version", "WASM web application bundler for Rust."),
+	crate("cargo_leptos", "cargo-leptos", "cargo-leptos", "cargo leptos --version", "Build tool for Leptos web apps."),
+	crate("simple_http_server", "simple-http-server", "simple-http-server", "simple-http-server --version", "Static file server."),
+	{
+		ID:        "nvm",
+		Name:      "nvm",
+		Category:  CategoryLanguages,
+		Kind:      KindSDK,
+		Summary:   "Node Version Manager.",
+		Adapter:   AdapterScript,
+		Probe:     Probe{Command: `. "$HOME/.nvm/nvm.sh" && nvm --version`},
+		Install:   []Recipe{nvmInstaller},
+		Update:    []Recipe{nvmInstaller},
+		Uninstall: []Recipe{{Source: SourceScript, Command: `rm -rf "$HOME/.nvm"`}},
+		Removes:   []string{"~/.nvm"},
+	},
+	{
+		ID:       "bun",
+		Name:     "Bun",
+		Category: CategoryLanguages,
+		Kind:     KindSDK,
+		Summary:  "Fast JavaScript runtime and package manager.",
+		Adapter:  AdapterScript,
+		Probe:    Probe{Command: "bun --version"},
+		Install: []Recipe{{
+			Source:    SourceScript,
+			Command:   "curl -fsSL https://bun.sh/install | bash",
+			Installer: &Installer{URL: "https://bun.sh/install", Shell: "bash"},
+		}},
+		Update:    []Recipe{{Source: SourceScript, Command: "bun upgrade"}},
+		Uninstall: []Recipe{{Source: SourceScript, Command: `rm -rf "$HOME/.bun"`}},
+		Removes:   []string{"~/.bun"},
+	},
+	withAliases(
+		withPort(withNix(formula("bat", "bat", "bat", "bat --version", CategoryDevTools, "cat clone with syntax highlighting."), "bat"), "bat"),
+		ShellAlias{
+			Description: "Use bat for cat",
+			Zsh:         "alias cat='bat --paging=never'",
+			Bash:        "alias cat='bat --paging=never'",
+			Fish:        "alias cat 'bat --paging=never'",
+		},
+	),
+	withAliases(
+		withPort(withNix(formula("eza", "eza", "eza", "eza --version", CategoryDevTools, "Modern ls replacement."), "eza"), "eza"),
+		ShellAlias{
+			Description: "Use eza for ls",
+			Zsh:         "alias ls='eza --group-directories-first'\nalias ll='eza -l --git'",
+			Bash:        "alias ls='eza --group-directories-first'\nalias ll='eza -l --git'",
+			Fish:        "alias ls 'eza --group-directories-first'\nalias ll 'eza -l --git'",
+		},
+	),
+	withAliases(
+		withPostInstall(
+			withPort(withNix(formula("fzf", "fzf", "fzf", "fzf --version", CategoryDevTools, "Command-line fuzzy finder."), "fzf"), "fzf"),
+			Hook{
+				Description: "Generate fzf key bindings and completion",
+				Command:     `prefix="$(brew --prefix 2>/dev/null)" || exit 0; "$prefix/opt/fzf/install" --key-bindings --completion --no-update-rc`,
+			},
+		),
+		ShellAlias{
+			Description: "fzf key bindings and fuzzy completion",
+			Zsh:         "source <(fzf --zsh)",
+			Bash:        `eval "$(fzf --bash)"`,
+			Fish:        "fzf --fish | source",
+		},
+	),
+	withPostInstall(
+		withPort(withNix(formula("fish", "fish", "fish", "fish --version", CategoryDevTools, "Friendly interactive shell."), "fish"), "fish"),
+		Hook{
+			Description: "Allow fish as a login shell",
+			Command:     `fish_path="$(command -v fish)" && { grep -qx "$fish_path" /etc/shells || echo "$fish_path" | sudo tee -a /etc/shells >/dev/null; }`,
+		},
+	),
+	withPort(formula("mise", "mise", "mise", "mise --version", CategoryLanguages, "Polyglot runtime and tool version manager."), "mise"),
+	withPort(formula("asdf", "asdf", "asdf", "asdf --version", CategoryLanguages, "Extendable runtime version manager."), "asdf"),
+	withPort(formula("pipx", "pipx", "pipx", "pipx --version", CategoryLanguages, "Install Python CLIs in isolated environments."), "pipx"),
+	pipxTool("poetry", "Poetry", "poetry", "poetry --version", CategoryLanguages, "Python packaging and dependency management.",
+		Recipe{
+			Source:    SourceScript,
+			Command:   "curl -sSL https://install.python-poetry.org | python3 -",
+			Installer: &Installer{URL: "https://install.python-poetry.org", Shell: "python3"},
+		}),
+	withPort(withNix(formula("go", "Go", "go", "go version", CategoryLanguages, "The Go programming language."), "go"), "go"),
+	{
+		ID:           "flutter",
+		Name:         "Flutter",
+		Category:     CategoryMobile,
+		Kind:         KindSDK,
+		Summary:      "Google's UI toolkit for mobile, web and desktop.",
+		Dependencies: []string{"homebrew"},
+		Probe:        Probe{Command: "flutter --version --machine", Depth: ProbeDeep},
+		Install:      []Recipe{{Source: SourceBrewCask, Command: "brew install --cask flutter"}},
+		Update:       []Recipe{{Source: SourceBrewCask, Command: "brew upgrade --cask flutter"}},
+		Uninstall:    []Recipe{{Source: SourceBrewCask, Command: "brew uninstall --cask flutter"}},
+	},
+	withSize(cask("android_studio", "Android Studio", "android-studio", "Android Studio.app", CategoryMobile, "Official Android IDE."), 1200, 3200),
+	npmGlobal("react_native_cli", "React Native CLI", "@react-native-community/cli", "react-native --version", CategoryMobile, "React Native command line tools."),
+	npmGlobal("electron_forge", "Electron Forge", "@electron-forge/cli", "electron-forge --version", CategoryDevTools, "Tooling for building Electron apps."),
+	npmGlobal("codex_cli", "Codex CLI", "@openai/codex", "codex --version", CategoryAI, "OpenAI's coding agent for the terminal."),
+	npmGlobal("claude_cli", "Claude CLI", "@anthropic-ai/claude-code", "claude --version", CategoryAI, "Anthropic's coding agent for the terminal."),
+	npmGlobal("claude_multi_cli", "Claude Multi", "claude-multi", "claude-multi --version", CategoryAI, "Run several Claude CLI accounts side by side."),
+	pipxTool("kimi_cli", "Kimi CLI", "kimi-cli", "kimi --version", CategoryAI, "Moonshot's coding agent for the terminal."),
+	npmGlobal("gemini_cli", "Gemini CLI", "@google/gemini-cli", "gemini --version", CategoryAI, "Google's coding agent for the terminal."),
+	npmGlobal("qwen_cli", "Qwen Code", "@qwen-code/qwen-code", "qwen --version", CategoryAI, "Qwen's coding agent for the terminal."),
+	npmGlobal("opencode_cli", "opencode", "opencode-ai", "opencode --version", CategoryAI, "Open source coding agent for the terminal."),
+}
@@ -0,0 +1,81 @@
+package catalog
+
+// about is where an entry comes from and the terms it ships under.
+type about struct {
+	homepage string
+	docs     string
+	license  string
+}
+
+// metadata describes catalog entries by id. License is an SPDX expression,
+// or "Proprietary" for closed-source software.
+var metadata = map[string]about{
+	"homebrew":           {"https://brew.sh", "https://docs.brew.sh", "BSD-2-Clause"},
+	"xcode_clt":          {"https://developer.apple.com/xcode/", "https://developer.apple.com/documentation/xcode", "Proprietary"},
+	"mas":                {"https://github.com/mas-cli/mas", "", "MIT"},
+	"xcode":              {"https://developer.apple.com/xcode/", "https://developer.apple.com/documentation/xcode", "Proprietary"},
+	"keynote":            {"https://www.apple.com/keynote/", "https://support.apple.com/guide/keynote/welcome/mac", "Proprietary"},
+	"slack":              {"https://slack.com", "https://slack.com/help", "Proprietary"},
+	"brave":              {"https://brave.com", "https://support.brave.com", "MPL-2.0"},
+	"firefox":            {"https://www.mozilla.org/firefox/", "https://support.mozilla.org/products/firefox", "MPL-2.0"},
+	"chrome":             {"https://www.google.com/chrome/", "https://support.google.com/chrome", "Proprietary"},
+	"cursor":             {"https://cursor.com", "https://docs.cursor.com", "Proprietary"},
+	"windsurf":           {"https://windsurf.com", "https://docs.windsurf.com", "Proprietary"},
+	"visual_studio_code": {"https://code.visualstudio.com", "https://code.visualstudio.com/docs", "Proprietary"},
+	"zed_stable":         {"https://zed.dev", "https://zed.dev/docs", "GPL-3.0-or-later"},
+	"zed_preview":        {"https://zed.dev/releases/preview", "https://zed.dev/docs", "GPL-3.0-or-later"},
+	"raycast":            {"https://www.raycast.com", "https://manual.raycast.com", "Proprietary"},
+	"docker_desktop":     {"https://www.docker.com/products/docker-desktop/", "https://docs.docker.com/desktop/", "Proprietary"},
+	"colima":             {"https://github.com/abiosoft/colima", "", "MIT"},
+	"postman":            {"https://www.postman.com", "https://learning.postman.com/docs/", "Proprietary"},
+	"yaak":               {"https://yaak.app", "", "MIT"},
+	"git_open":           {"https://github.com/paulirish/git-open", "", "MIT"},
+	"yq":                 {"https://github.com/mikefarah/yq", "https://mikefarah.gitbook.io/yq", "MIT"},
+	"rustup":             {"https://rustup.rs", "https://rust-lang.github.io/rustup/", "MIT OR Apache-2.0"},
+	"rust_stable":        {"https://www.rust-lang.org", "https://doc.rust-lang.org", "MIT OR Apache-2.0"},
+	"cargo_just":         {"https://github.com/casey/just", "https://just.systems/man/en/", "CC0-1.0"},
+	"cargo_binstall":     {"https://github.com/cargo-bins/cargo-binstall", "", "GPL-3.0-only"},
+	"cargo_watch":        {"https://github.com/watchexec/cargo-watch", "", "CC0-1.0"},
+	"dioxus_cli":         {"https://dioxuslabs.com", "https://dioxuslabs.com/learn/", "MIT OR Apache-2.0"},
+	"trunk":              {"https://trunkrs.dev", "https://trunkrs.dev/guide/", "MIT OR Apache-2.0"},
+	"cargo_leptos":       {"https://github.com/leptos-rs/cargo-leptos", "https://book.leptos.dev", "MIT"},
+	"simple_http_server": {"https://github.com/TheWaWaR/simple-http-server", "", "MIT"},
+	"nvm":                {"https://github.com/nvm-sh/nvm", "", "MIT"},
+	"bun":                {"https://bun.sh", "https://bun.sh/docs", "MIT"},
+	"bat":                {"https://github.com/sharkdp/bat", "", "MIT OR Apache-2.0"},
+	"eza":                {"https://eza.rocks", "https://github.com/eza-community/eza#readme", "EUPL-1.2"},
+	"fzf":                {"https://github.com/junegunn/fzf", "https://junegunn.github.io/fzf/", "MIT"},
+	"fish":               {"https://fishshell.com", "https://fishshell.com/docs/current/", "GPL-2.0-only"},
+	"mise":               {"https://mise.jdx.dev", "https://mise.jdx.dev/getting-started.html", "MIT"},
+	"asdf":               {"https://asdf-vm.com", "https://asdf-vm.com/guide/getting-started.html", "MIT"},
+	"pipx":               {"https://pipx.pypa.io", "https://pipx.pypa.io/stable/", "MIT"},
+	"poetry":             {"https://python-poetry.org", "https://python-poetry.org/docs/", "MIT"},
+	"go":                 {"https://go.dev", "https://go.dev/doc/", "BSD-3-Clause"},
+	"flutter":            {"https://flutter.dev", "https://docs.flutter.dev", "BSD-3-Clause"},
+	"android_studio":     {"https://developer.android.com/studio", "https://developer.android.com/studio/intro", "Proprietary"},
+	"react_native_cli":   {"https://github.com/react-native-community/cli", "https://reactnative.dev/docs/environment-setup", "MIT"},
+	"electron_forge":     {"https://www.electronforge.io", "https://www.electronforge.io/cli", "MIT"},
+	"codex_cli":          {"https://github.com/openai/codex", "", "Apache-2.0"},
+	"claude_cli":         {"https://www.anthropic.com/claude-code", "https://docs.anthropic.com/en/docs/claude-code/overview", "Proprietary"},
+	"claude_multi_cli":   {"https://www.npmjs.com/package/claude-multi", "", ""},
+	"kimi_cli":           {"https://github.com/MoonshotAI/kimi-cli", "", "Apache-2.0"},
+	"gemini_cli":         {"https://github.com/google-gemini/gemini-cli", "https://github.com/google-gemini/gemini-cli/tree/main/docs", "Apache-2.0"},
+	"qwen_cli":           {"https://github.com/QwenLM/qwen-code", "", "Apache-2.0"},
+	"opencode_cli":       {"https://opencode.ai", "https://opencode.ai/docs", "MIT"},
+}
+
+// withMetadata fills e's homepage, docs and license from the metadata
+// table where the entry leaves them out.
+func withMetadata(e Entry) Entry {
+	m := metadata[e.ID]
+	if e.Homepage == "" {
+		e.Homepage = m.homepage
+	}
+	if e.Docs == "" {
+		e.Docs = m.docs
+	}
+	if e.License == "" {
+		e.License = m.license
+	}
+	return e
+}
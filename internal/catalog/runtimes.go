@@ -0,0 +1,114 @@
+package catalog
+
+import "fmt"
+
+// RuntimeStrategy selects how language runtimes are provisioned.
+type RuntimeStrategy string
+
+const (
+	// RuntimeNative uses the per-language installers (nvm, rustup, brew).
+	RuntimeNative RuntimeStrategy = "native"
+	// RuntimeMise provisions every runtime through mise.
+	RuntimeMise RuntimeStrategy = "mise"
+	// RuntimeAsdf provisions every runtime through asdf.
+	RuntimeAsdf RuntimeStrategy = "asdf"
+)
+
+// ParseRuntimeStrategy validates a strategy name; empty means native.
+func ParseRuntimeStrategy(value string) (RuntimeStrategy, error) {
+	switch RuntimeStrategy(value) {
+	case "", RuntimeNative:
+		return RuntimeNative, nil
+	case RuntimeMise, RuntimeAsdf:
+		return RuntimeStrategy(value), nil
+	}
+	return RuntimeNative, fmt.Errorf("unknown runtime strategy %q (expected native, mise or asdf)", value)
+}
+
+// Runtime is a language runtime a version manager can provide.
+type Runtime struct {
+	Name    string
+	Display string
+	// Native lists the catalog ids the runtime replaces under mise/asdf.
+	Native  []string
+	// Tool is the mise tool spec installed globally.
+	Tool    string
+	// Plugin is the asdf plugin name.
+	Plugin  string
+	// Binary prints the runtime version once installed.
+	Binary  string
+	// Test runs a trivial program, proving the runtime works.
+	Test    string
+}
+
+// Runtimes are the language runtimes with mise/asdf-managed entries.
+var Runtimes = []Runtime{
+	{Name: "node", Display: "Node.js", Native: []string{"nvm"}, Tool: "node@lts", Plugin: "nodejs", Binary: "node --version", Test: "node -e 1"},
+	{Name: "python", Display: "Python", Tool: "python@latest", Plugin: "python", Binary: "python --version", Test: "python -c 1"},
+	{Name: "go", Display: "Go", Native: []string{"go"}, Tool: "go@latest", Plugin: "golang", Binary: "go version", Test: "go version"},
+	{Name: "rust", Display: "Rust", Native: []string{"rustup", "rust_stable"}, Tool: "rust@stable", Plugin: "rust", Binary: "rustc --version", Test: "rustc --version"},
+}
+
+// RuntimeEntryID is the catalog id of the entry providing runtime r via strategy.
+func RuntimeEntryID(strategy RuntimeStrategy, r Runtime) string {
+	return string(strategy) + "_" + r.Name
+}
+
+// RuntimeSubstitutes maps native runtime ids to their managed replacements.
+func RuntimeSubstitutes(strategy RuntimeStrategy) map[string]string {
+	if strategy == RuntimeNative || strategy == "" {
+		return nil
+	}
+	out := map[string]string{}
+	for _, r := range Runtimes {
+		for _, id := range r.Native {
+			out[id] = RuntimeEntryID(strategy, r)
+		}
+	}
+	return out
+}
+
+func miseRuntime(r Runtime) Entry {
+	return Entry{
+		ID:           RuntimeEntryID(RuntimeMise, r),
+		Name:         r.Display + " (mise)",
+		Category:     CategoryLanguages,
+		Tags:         []string{"runtime"},
+		Kind:         KindSDK,
+		Summary:      r.Display + " managed by mise.",
+		Dependencies: []string{"mise"},
+		Probe:        Probe{Command: fmt.Sprintf("mise exec %s -- %s", r.Tool, r.Binary)},
+		Test:         fmt.Sprintf("mise exec %s -- %s", r.Tool, r.Test),
+		Install:      []Recipe{{Source: SourceMise, Package: r.Tool, Command: "mise use --global " + r.Tool}},
+		Update:       []Recipe{{Source: SourceMise, Package: r.Tool, Command: "mise upgrade " + r.Name}},
+		Uninstall:    []Recipe{{Source: SourceMise, Package: r.Tool, Command: fmt.Sprintf("mise unuse --global %s && mise uninstall --all %s", r.Name, r.Name)}},
+		Removes:      []string{"~/.local/share/mise/installs/" + r.Name},
+	}
+}
+
+func asdfRuntime(r Runtime) Entry {
+	install := fmt.Sprintf("asdf plugin add %[1]s; asdf install %[1]s latest && asdf set --home %[1]s latest", r.Plugin)
+	return Entry{
+		ID:           RuntimeEntryID(RuntimeAsdf, r),
+		Name:         r.Display + " (asdf)",
+		Category:     CategoryLanguages,
+		Tags:         []string{"runtime"},
+		Kind:         KindSDK,
+		Summary:      r.Display + " managed by asdf.",
+		Dependencies: []string{"asdf"},
+		Probe:        Probe{Command: "asdf current " + r.Plugin},
+		Test:         "asdf exec " + r.Test,
+		Install:      []Recipe{{Source: SourceAsdf, Package: r.Plugin, Command: install}},
+		Update:       []Recipe{{Source: SourceAsdf, Package: r.Plugin, Command: install}},
+		Uninstall:    []Recipe{{Source: SourceAsdf, Package: r.Plugin, Command: "asdf plugin remove " + r.Plugin}},
+		Removes:      []string{"~/.asdf/installs/" + r.Plugin, "~/.asdf/plugins/" + r.Plugin},
+	}
+}
+
+func runtimeEntries() []Entry {
+	var out []Entry
+	for _, r := range Runtimes {
+		out = append(out, miseRuntime(r), asdfRuntime(r))
+	}
+	return out
+}
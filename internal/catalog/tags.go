@@ -0,0 +1,93 @@
+package catalog
+
+import (
+	"slices"
+	"sort"
+)
+
+// heavyDownload is the download size from which entries are tagged
+// "heavy-download".
+const heavyDownload = 1 << 30
+
+// tags labels catalog entries by id. Runtime entries carry their own
+// "runtime" tag and "heavy-download" is derived from entry sizes.
+var tags = map[string][]string{
+	"homebrew":           {"package-manager"},
+	"xcode_clt":          {"toolchain"},
+	"mas":                {"app-store"},
+	"xcode":              {"app-store", "editor", "ide", "mobile"},
+	"keynote":            {"app-store"},
+	"slack":              {"app-store", "chat"},
+	"brave":              {"browser"},
+	"firefox":            {"browser"},
+	"chrome":             {"browser"},
+	"cursor":             {"ai", "editor"},
+	"windsurf":           {"ai", "editor"},
+	"visual_studio_code": {"editor"},
+	"zed_stable":         {"ai", "editor"},
+	"zed_preview":        {"ai", "editor"},
+	"raycast":            {"launcher"},
+	"docker_desktop":     {"containers"},
+	"colima":             {"containers"},
+	"postman":            {"api-client"},
+	"yaak":               {"api-client"},
+	"git_open":           {"git", "terminal"},
+	"yq":                 {"terminal"},
+	"rustup":             {"toolchain", "version-manager"},
+	"rust_stable":        {"toolchain"},
+	"cargo_just":         {"terminal"},
+	"cargo_binstall":     {"package-manager"},
+	"cargo_watch":        {"terminal"},
+	"dioxus_cli":         {"web"},
+	"trunk":              {"web"},
+	"cargo_leptos":       {"web"},
+	"simple_http_server": {"terminal"},
+	"nvm":                {"version-manager"},
+	"bun":                {"runtime"},
+	"bat":                {"terminal"},
+	"eza":                {"terminal"},
+	"fzf":                {"terminal"},
+	"fish":               {"shell", "terminal"},
+	"mise":               {"version-manager"},
+	"asdf":               {"version-manager"},
+	"pipx":               {"package-manager"},
+	"poetry":             {"package-manager"},
+	"go":                 {"toolchain"},
+	"flutter":            {"mobile", "toolchain"},
+	"android_studio":     {"editor", "ide", "mobile"},
+	"react_native_cli":   {"mobile"},
+	"electron_forge":     {"desktop"},
+	"codex_cli":          {"agent", "ai", "terminal"},
+	"claude_cli":         {"agent", "ai", "terminal"},
+	"claude_multi_cli":   {"agent", "ai", "terminal"},
+	"kimi_cli":           {"agent", "ai", "terminal"},
+	"gemini_cli":         {"agent", "ai", "terminal"},
+	"qwen_cli":           {"agent", "ai", "terminal"},
+	"opencode_cli":       {"agent", "ai", "terminal"},
+}
+
+// entryTags returns e's own tags merged with the catalog's tag table and
+// the derived ones, sorted.
+func entryTags(e Entry) []string {
+	out := append(append([]string(nil), e.Tags...), tags[e.ID]...)
+	if e.Size.Download >= heavyDownload {
+		out = append(out, "heavy-download")
+	}
+	sort.Strings(out)
+	return slices.Compact(out)
+}
+
+// HasTag reports whether e is labelled tag.
+func (e Entry) HasTag(tag string) bool {
+	return slices.Contains(e.Tags, tag)
+}
+
+// Tags returns every tag in use, sorted.
+func Tags() []string {
+	var out []string
+	for _, e := range entries {
+		out = append(out, e.Tags...)
+	}
+	sort.Strings(out)
+	return slices.Compact(out)
+}
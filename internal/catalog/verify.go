@@ -0,0 +1,127 @@
+package catalog
+
+import (
+	"fmt"
+	"os/exec"
+	"slices"
+	"strings"
+
+	"github.com/hmziqrs/maziq/internal/safety"
+)
+
+// backends maps each package-manager source to the program its recipes
+// run. Script and system recipes run whatever they need.
+var backends = map[Source]string{
+	SourceBrew:     "brew",
+	SourceBrewCask: "brew",
+	SourceCargo:    "cargo",
+	SourceNpm:      "npm",
+	SourcePipx:     "pipx",
+	SourceRustup:   "rustup",
+	SourceMas:      "mas",
+	SourceMise:     "mise",
+	SourceAsdf:     "asdf",
+	SourceNix:      "nix",
+	SourcePort:     "port",
+}
+
+// Problem is something Verify found wrong with an entry.
+type Problem struct {
+	ID      string
+	// Where names the recipe or hook, e.g. "install [brew]".
+	Where   string
+	Message string
+}
+
+func (p Problem) String() string {
+	if p.Where == "" {
+		return p.ID + ": " + p.Message
+	}
+	return fmt.Sprintf("%s %s: %s", p.ID, p.Where, p.Message)
+}
+
+// Verify checks every recipe and post-install hook of entries: that it
+// parses as shell, that package-manager recipes run their own backend,
+// that rm -rf and sudo are only used in reviewable ways, and that every
+// backend an entry installs through can also update and uninstall it.
+func Verify(entries []Entry) []Problem {
+	var problems []Problem
+	for _, e := range entries {
+		report := func(where, format string, args ...any) {
+			problems = append(problems, Problem{ID: e.ID, Where: where, Message: fmt.Sprintf(format, args...)})
+		}
+		for _, action := range []struct {
+			name    string
+			recipes []Recipe
+		}{{"install", e.Install}, {"update", e.Update}, {"uninstall", e.Uninstall}} {
+			for _, r := range action.recipes {
+				where := fmt.Sprintf("%s [%s]", action.name, r.Source)
+				for _, msg := range verifyScript(r.Command, r.Mode) {
+					report(where, "%s", msg)
+				}
+				if r.TempDir && r.Dir != "" {
+					report(where, "sets both Dir and TempDir")
+				}
+				if rel := r.Release; rel != nil && (rel.Asset == "" || len(rel.Binaries) == 0) {
+					report(where, "GitHub release names no asset or no binaries")
+				}
+				if backend, ok := backends[r.Source]; ok && r.Installer == nil {
+					for _, c := range safety.Commands(r.Command) {
+						if c.Program != backend && c.Program != "command" {
+							report(where, "runs %s instead of %s", c.Program, backend)
+						}
+					}
+				}
+			}
+		}
+		for _, h := range e.PostInstall {
+			for _, msg := range verifyScript(h.Command, ShellSh) {
+				report("post-install", "%s", msg)
+			}
+		}
+		for _, r := range e.Install {
+			if _, ok := backends[r.Source]; !ok {
+				continue
+			}
+			for _, other := range []struct {
+				name    string
+				recipes []Recipe
+			}{{"update", e.Update}, {"uninstall", e.Uninstall}} {
+				if !slices.ContainsFunc(other.recipes, func(o Recipe) bool { return o.Source == r.Source }) {
+					report("", "installs through %s but has no %s recipe for it", r.Source, other.name)
+				}
+			}
+		}
+	}
+	return problems
+}
+
+// verifyScript parses script with the -n flag of mode's shell and flags
+// unquoted expansions handed to rm -rf and sudo outside
+// safety.SudoPrograms.
+func verifyScript(script string, mode ShellMode) []string {
+	var out []string
+	if output, err := exec.Command(mode.Argv()[0], "-n", "-c", script).CombinedOutput(); err != nil {
+		out = append(out, "does not parse: "+strings.TrimSpace(string(output)))
+	}
+	for _, c := range safety.Commands(script) {
+		if c.Sudo && !slices.Contains(safety.SudoPrograms, c.Program) {
+			out = append(out, "sudo "+c.Program+" is not in the sudo allowlist")
+		}
+		if c.Program != "rm" || !slices.ContainsFunc(c.Args, recursiveFlag) {
+			continue
+		}
+		for _, arg := range c.Args {
+			if !strings.HasPrefix(arg, "-") && !strings.HasPrefix(arg, `"`) && !strings.HasPrefix(arg, "'") &&
+				(strings.ContainsAny(arg, "$*~") || arg == "/") {
+				out = append(out, "rm -rf on the unquoted "+arg)
+			}
+		}
+	}
+	return out
+}
+
+// recursiveFlag matches rm's -r, -rf, -fr and -R flags.
+func recursiveFlag(arg string) bool {
+	return strings.HasPrefix(arg, "-") && !strings.HasPrefix(arg, "--") && strings.ContainsAny(arg, "rR")
+}
@@ -0,0 +1,24 @@
+package catalog
+
+import (
+	"os/exec"
+	"testing"
+)
+
+// TestCatalogVerifies keeps `maziq catalog verify` clean: every recipe and
+// hook of the built-in catalog must pass it.
+func TestCatalogVerifies(t *testing.T) {
+	entries := All()
+	for _, e := range entries {
+		for _, recipes := range [][]Recipe{e.Install, e.Update, e.Uninstall} {
+			for _, r := range recipes {
+				if _, err := exec.LookPath(r.Mode.Argv()[0]); err != nil {
+					t.Skipf("%s %s needs %s to be parsed: %v", e.ID, r.Source, r.Mode.Argv()[0], err)
+				}
+			}
+		}
+	}
+	for _, p := range Verify(entries) {
+		t.Error(p)
+	}
+}
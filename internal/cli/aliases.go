@@ -0,0 +1,64 @@
+package cli
+
+import (
+	"fmt"
+
+	"github.com/hmziqrs/maziq/internal/aliases"
+	"github.com/hmziqrs/maziq/internal/catalog"
+)
+
+func runAliases(_ session, args []string) error {
+	if len(args) == 0 {
+		return usagef("maziq aliases <list|enable|disable> [id]")
+	}
+	switch args[0] {
+	case "list":
+		return runAliasesList()
+	case "enable", "disable":
+		if len(args) != 2 {
+			return usagef("maziq aliases %s <id>", args[0])
+		}
+		entry, ok := catalog.Lookup(args[1])
+		if !ok {
+			return &catalog.UnknownError{ID: args[1]}
+		}
+		if args[0] == "enable" {
+			if err := aliases.Enable(entry); err != nil {
+				return err
+			}
+			fmt.Printf("Enabled aliases for %s. Open a new shell to use them.\n", entry.ID)
+			return nil
+		}
+		if err := aliases.Disable(entry.ID); err != nil {
+			return err
+		}
+		fmt.Printf("Removed aliases for %s.\n", entry.ID)
+		return nil
+	}
+	return fmt.Errorf("unknown aliases subcommand %q", args[0])
+}
+
+func runAliasesList() error {
+	enabled, err := aliases.Enabled()
+	if err != nil {
+		return err
+	}
+	on := map[string]bool{}
+	for _, id := range enabled {
+		on[id] = true
+	}
+	for _, entry := range catalog.All() {
+		if len(entry.Aliases) == 0 {
+			continue
+		}
+		mark := "[ ]"
+		if on[entry.ID] {
+			mark = "[x]"
+		}
+		fmt.Printf("%s %s\n", mark, entry.ID)
+		for _, alias := range entry.Aliases {
+			fmt.Printf("      %s\n", alias.Description)
+		}
+	}
+	return nil
+}
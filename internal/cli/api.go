@@ -0,0 +1,231 @@
+package cli
+
+import (
+	"context"
+	"encoding/json"
+	"errors"
+	"fmt"
+	"io"
+	"os"
+	"slices"
+	"sort"
+	"strings"
+	"time"
+
+	"github.com/hmziqrs/maziq/internal/catalog"
+	"github.com/hmziqrs/maziq/internal/history"
+	"github.com/hmziqrs/maziq/internal/manager"
+	"github.com/hmziqrs/maziq/internal/options"
+)
+
+// apiVersion is the schema version of `maziq api` requests and responses.
+// Clients send the version they speak; a newer one is refused rather than
+// half understood. Field names below are part of the contract.
+const apiVersion = 1
+
+// apiRequest is one `maziq api` call, read as JSON from a file or stdin.
+type apiRequest struct {
+	Version int      `json:"version"`
+	// Method is "status", "install" or "history".
+	Method  string   `json:"method"`
+	// IDs selects entries for status and install.
+	IDs     []string `json:"ids,omitempty"`
+	// Query narrows status to entries whose id or name contains it.
+	Query   string   `json:"query,omitempty"`
+	// Limit caps history; zero means 20.
+	Limit   int      `json:"limit,omitempty"`
+	DryRun  bool     `json:"dry_run,omitempty"`
+}
+
+// apiResponse is always printed, also on failure, so clients only ever
+// parse one shape.
+type apiResponse struct {
+	Version int    `json:"version"`
+	OK      bool   `json:"ok"`
+	Error   string `json:"error,omitempty"`
+	Result  any    `json:"result,omitempty"`
+}
+
+type apiStatus struct {
+	ID       string `json:"id"`
+	Name     string `json:"name"`
+	Category string `json:"category"`
+	State    string `json:"state"`
+	Version  string `json:"version,omitempty"`
+	Latest   string `json:"latest,omitempty"`
+	Error    string `json:"error,omitempty"`
+}
+
+type apiOutcome struct {
+	ID     string `json:"id"`
+	Result string `json:"result"`
+	Reason string `json:"reason,omitempty"`
+}
+
+type apiHistory struct {
+	ID       string    `json:"id"`
+	Action   string    `json:"action"`
+	Version  string    `json:"version,omitempty"`
+	Source   string    `json:"source"`
+	Time     time.Time `json:"time"`
+	Failed   bool      `json:"failed"`
+	Template string    `json:"template,omitempty"`
+}
+
+// runAPI answers one JSON request on stdout. It never prompts, which suits
+// launcher extensions (Raycast, Alfred) that spawn maziq per action.
+func runAPI(s session, args []string) error {
+	if len(args) != 1 {
+		return usagef("maziq api <request.json | ->")
+	}
+	var in io.Reader = os.Stdin
+	if args[0] != "-" {
+		f, err := os.Open(args[0])
+		if err != nil {
+			return err
+		}
+		defer f.Close()
+		in = f
+	}
+
+	resp := apiResponse{Version: apiVersion}
+	var req apiRequest
+	var result any
+	err := json.NewDecoder(in).Decode(&req)
+	if err == nil {
+		result, err = serveAPI(s.opts, req)
+	}
+	if err != nil {
+		resp.Error = err.Error()
+	} else {
+		resp.OK, resp.Result = true, result
+	}
+	enc := json.NewEncoder(os.Stdout)
+	if encErr := enc.Encode(resp); encErr != nil {
+		return encErr
+	}
+	// The error is also returned for the exit code and stderr; stdout only
+	// ever holds the response.
+	return err
+}
+
+func serveAPI(opts options.Run, req apiRequest) (any, error) {
+	switch {
+	case req.Version == 0:
+		return nil, errors.New("version is required")
+	case req.Version > apiVersion:
+		return nil, fmt.Errorf("version %d is newer than this maziq speaks (%d); upgrade maziq", req.Version, apiVersion)
+	}
+	opts.NonInteractive = true
+	switch req.Method {
+	case "status":
+		return apiStatuses(opts, req)
+	case "install":
+		return apiInstall(opts, req)
+	case "history":
+		return apiHistories(req)
+	}
+	return nil, fmt.Errorf("unknown method %q", req.Method)
+}
+
+func apiStatuses(opts options.Run, req apiRequest) ([]apiStatus, error) {
+	entries, err := apiEntries(req.IDs)
+	if err != nil {
+		return nil, err
+	}
+	if q := strings.ToLower(req.Query); q != "" {
+		var matched []catalog.Entry
+		for _, e := range entries {
+			if strings.Contains(e.ID, q) || strings.Contains(strings.ToLower(e.Name), q) {
+				matched = append(matched, e)
+			}
+		}
+		entries = matched
+	}
+	statuses := manager.NewProber(opts.Jobs).ProbeAll(context.Background(), entries, manager.ProbeModeQuick)
+	out := []apiStatus{}
+	for _, e := range entries {
+		st := statuses[e.ID]
+		out = append(out, apiStatus{ID: e.ID, Name: e.Name, Category: string(e.Category), State: st.State.String(), Version: st.Version, Latest: st.Latest, Error: st.Error})
+	}
+	return out, nil
+}
+
+// apiInstall installs the requested entries and their dependencies. Sudo
+// needs cached credentials or --sudo-askpass since nothing can prompt.
+func apiInstall(opts options.Run, req apiRequest) ([]apiOutcome, error) {
+	if len(req.IDs) == 0 {
+		return nil, errors.New("ids is required for install")
+	}
+	plan, err := manager.Resolve(req.IDs, manager.ActionInstall, opts)
+	if err != nil {
+		return nil, err
+	}
+	if conflicts := plan.Conflicts(); len(conflicts) > 0 {
+		return nil, &manager.ConflictError{Conflicts: conflicts}
+	}
+	if !req.DryRun && manager.PlanNeedsSudo(plan, opts.Backend) && !manager.SudoCached(context.Background()) {
+		if opts.Askpass == "" {
+			return nil, fmt.Errorf("%w: run `sudo -v` first or pass --sudo-askpass", manager.ErrSudoUnavailable)
+		}
+		if err := manager.SudoAuthenticate(context.Background(), "", opts.Askpass); err != nil {
+			return nil, err
+		}
+	}
+	opts.DryRun = req.DryRun
+	m := manager.New(opts)
+	var events []manager.TaskEvent
+	runErr := m.RunPlan(context.Background(), plan, func(ev manager.TaskEvent) {
+		events = append(events, ev)
+	})
+	out := []apiOutcome{}
+	for _, o := range manager.Summarize(plan.IDs(), events) {
+		out = append(out, apiOutcome{ID: o.ID, Result: string(o.Result), Reason: o.Reason})
+	}
+	// Failed steps are reported per entry; only a run that broke off
+	// altogether fails the call.
+	var failed *manager.RunError
+	if runErr != nil && !errors.As(runErr, &failed) {
+		return nil, runErr
+	}
+	return out, nil
+}
+
+func apiHistories(req apiRequest) ([]apiHistory, error) {
+	records, err := history.Load()
+	if err != nil {
+		return nil, err
+	}
+	limit := req.Limit
+	if limit <= 0 {
+		limit = 20
+	}
+	sort.SliceStable(records, func(i, j int) bool { return records[i].Timestamp > records[j].Timestamp })
+	out := []apiHistory{}
+	for _, r := range records {
+		if len(out) == limit {
+			break
+		}
+		if len(req.IDs) > 0 && !slices.Contains(req.IDs, r.Software) {
+			continue
+		}
+		out = append(out, apiHistory{ID: r.Software, Action: r.Action, Version: r.Version, Source: r.Source, Time: time.Unix(r.Timestamp, 0).UTC(), Failed: r.Failed, Template: r.Template})
+	}
+	return out, nil
+}
+
+// apiEntries looks up ids, or returns the whole catalog without any.
+func apiEntries(ids []string) ([]catalog.Entry, error) {
+	if len(ids) == 0 {
+		return catalog.All(), nil
+	}
+	var entries []catalog.Entry
+	for _, id := range ids {
+		e, ok := catalog.Lookup(id)
+		if !ok {
+			return nil, &catalog.UnknownError{ID: id}
+		}
+		entries = append(entries, e)
+	}
+	return entries, nil
+}
@@ -0,0 +1,105 @@
+package cli
+
+import (
+	"flag"
+	"fmt"
+	"sort"
+
+	"github.com/hmziqrs/maziq/internal/catalog"
+	"github.com/hmziqrs/maziq/internal/manager"
+	"github.com/hmziqrs/maziq/internal/safety"
+	"github.com/hmziqrs/maziq/internal/templates"
+)
+
+// shellString is a command found in the catalog or a template, with where
+// it came from.
+type shellString struct {
+	origin  string
+	command string
+}
+
+// catalogShellStrings lists every command the catalog can run: recipes,
+// probes, tests and post-install hooks.
+func catalogShellStrings() []shellString {
+	var out []shellString
+	add := func(origin, command string) {
+		if command != "" {
+			out = append(out, shellString{origin, command})
+		}
+	}
+	for _, entry := range catalog.All() {
+		for _, action := range []manager.Action{manager.ActionInstall, manager.ActionUpdate, manager.ActionUninstall} {
+			for _, recipe := range manager.Recipes(entry, action) {
+				add(fmt.Sprintf("%s %s [%s]", entry.ID, action, recipe.Source), recipe.Command)
+			}
+		}
+		add(entry.ID+" probe", entry.Probe.Command)
+		if entry.Test != entry.Probe.Command {
+			add(entry.ID+" test", entry.Test)
+		}
+		for _, hook := range entry.PostInstall {
+			add(entry.ID+" post-install", hook.Command)
+		}
+	}
+	return out
+}
+
+// templateShellStrings lists the post-install hooks of every template.
+func templateShellStrings() ([]shellString, error) {
+	var out []shellString
+	for _, name := range templates.List() {
+		t, err := templates.Load(name)
+		if err != nil {
+			return nil, err
+		}
+		ids := make([]string, 0, len(t.PostInstall))
+		for id := range t.PostInstall {
+			ids = append(ids, id)
+		}
+		sort.Strings(ids)
+		for _, id := range ids {
+			for _, command := range t.PostInstall[id] {
+				out = append(out, shellString{fmt.Sprintf("template %s post-install %s", name, id), command})
+			}
+		}
+	}
+	return out, nil
+}
+
+// runAudit prints every catalog and template command that falls outside
+// the safety allowlist, and fails when there is any.
+func runAudit(_ session, args []string) error {
+	fs := flag.NewFlagSet("audit", flag.ContinueOnError)
+	all := fs.Bool("all", false, "also print the commands the allowlist accepts")
+	if err := fs.Parse(args); err != nil {
+		return err
+	}
+	if fs.NArg() > 0 {
+		return usagef("maziq audit [--all]")
+	}
+	fromTemplates, err := templateShellStrings()
+	if err != nil {
+		return err
+	}
+	found := append(catalogShellStrings(), fromTemplates...)
+	flagged := 0
+	for _, s := range found {
+		findings := safety.Check(s.command)
+		if len(findings) == 0 {
+			if *all {
+				fmt.Printf("✓ %s\n    %s\n", s.origin, s.command)
+			}
+			continue
+		}
+		flagged++
+		fmt.Printf("✗ %s\n    %s\n", s.origin, s.command)
+		for _, f := range findings {
+			fmt.Printf("      %s: %s\n", f.Reason, f.Segment)
+		}
+	}
+	fmt.Printf("\n%d of %d commands fall outside the allowlist.\n", flagged, len(found))
+	if flagged > 0 {
+		return fmt.Errorf("%d commands need review", flagged)
+	}
+	return nil
+}
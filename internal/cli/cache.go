@@ -0,0 +1,110 @@
+package cli
+
+import (
+	"context"
+	"flag"
+	"fmt"
+	"os"
+	"text/tabwriter"
+	"time"
+
+	"github.com/hmziqrs/maziq/internal/cache"
+	"github.com/hmziqrs/maziq/internal/manager"
+	"github.com/hmziqrs/maziq/internal/options"
+	"github.com/hmziqrs/maziq/internal/paths"
+	"github.com/hmziqrs/maziq/internal/templates"
+)
+
+func runCache(s session, args []string) error {
+	if len(args) == 0 {
+		return usagef("maziq cache <warm|status|clear> [flags]")
+	}
+	switch args[0] {
+	case "warm":
+		return runCacheWarm(s.opts, args[1:])
+	case "status":
+		return runCacheStatus()
+	case "clear":
+		if err := cache.Clear(); err != nil {
+			return err
+		}
+		fmt.Println("Removed " + paths.CacheDir() + ". Homebrew keeps its own downloads until `brew cleanup`.")
+		return nil
+	}
+	return fmt.Errorf("unknown cache subcommand %q", args[0])
+}
+
+// runCacheWarm downloads what installing a template or a list of entries,
+// dependencies included, needs so a later --offline run can do it without
+// a connection.
+func runCacheWarm(opts options.Run, args []string) error {
+	fs := flag.NewFlagSet("cache warm", flag.ContinueOnError)
+	name := fs.String("template", "", "warm every entry of this template")
+	dryRun := fs.Bool("dry-run", opts.DryRun, "print the fetch commands without running them")
+	ids, err := parseInterspersed(fs, args)
+	if err != nil {
+		return err
+	}
+	if opts.Offline {
+		return fmt.Errorf("cache warm downloads; run it without --offline")
+	}
+	var plan manager.Plan
+	switch {
+	case *name != "" && len(ids) == 0:
+		t, err := templates.Load(*name)
+		if err != nil {
+			return err
+		}
+		plan, err = manager.ResolveTemplate(t, manager.ActionInstall, opts)
+		if err != nil {
+			return err
+		}
+	case *name == "" && len(ids) > 0:
+		if plan, err = manager.Resolve(ids, manager.ActionInstall, opts); err != nil {
+			return err
+		}
+	default:
+		return usagef("maziq cache warm [--dry-run] --template NAME | <id>...")
+	}
+
+	opts.DryRun = *dryRun
+	m := manager.New(opts)
+	var online int
+	for _, step := range plan.Steps {
+		started := time.Now()
+		result := m.Warm(context.Background(), step.Entry)
+		if !result.Cached {
+			online++
+			fmt.Printf("✗ %-22s %s\n", step.Entry.ID, result.Error)
+			continue
+		}
+		fmt.Printf("✓ %-22s %s (%s)\n", step.Entry.ID, result.Source, time.Since(started).Round(time.Second))
+	}
+	fmt.Printf("\n%d of %d entries can install offline.\n", len(plan.Steps)-online, len(plan.Steps))
+	if online > 0 {
+		fmt.Println("The others need a connection when they install.")
+	}
+	return nil
+}
+
+func runCacheStatus() error {
+	items, err := cache.Load()
+	if err != nil {
+		return err
+	}
+	scripts, err := os.ReadDir(paths.InstallerCacheDir())
+	if err != nil && !os.IsNotExist(err) {
+		return err
+	}
+	fmt.Printf("Installer scripts: %d in %s\n", len(scripts), paths.InstallerCacheDir())
+	if len(items) == 0 {
+		fmt.Println("No packages fetched yet; run `maziq cache warm`.")
+		return nil
+	}
+	w := tabwriter.NewWriter(os.Stdout, 0, 0, 2, ' ', 0)
+	fmt.Fprintln(w, "ID\tSOURCE\tPACKAGE\tFETCHED")
+	for _, item := range items {
+		fmt.Fprintf(w, "%s\t%s\t%s\t%s\n", item.Software, item.Source, item.Package, time.Unix(item.Timestamp, 0).Format("2006-01-02 15:04"))
+	}
+	return w.Flush()
+}
@@ -0,0 +1,103 @@
+package cli
+
+import (
+	"context"
+	"flag"
+	"fmt"
+	"sort"
+
+	"github.com/hmziqrs/maziq/internal/catalog"
+	"github.com/hmziqrs/maziq/internal/installers"
+	"github.com/hmziqrs/maziq/internal/manager"
+)
+
+func runCatalog(_ session, args []string) error {
+	if len(args) == 0 {
+		return usagef("maziq catalog refresh-hashes [--out FILE] [--dry-run] | verify")
+	}
+	switch args[0] {
+	case "refresh-hashes":
+		return runCatalogRefreshHashes(args[1:])
+	case "verify":
+		return runCatalogVerify(args[1:])
+	}
+	return fmt.Errorf("unknown catalog subcommand %q", args[0])
+}
+
+// runCatalogRefreshHashes downloads every installer script the catalog
+// runs and pins its current SHA256. Review what changed before trusting
+// the new pins: a hash only proves the script is the one you looked at.
+func runCatalogRefreshHashes(args []string) error {
+	fs := flag.NewFlagSet("catalog refresh-hashes", flag.ContinueOnError)
+	out := fs.String("out", "", "write the pins to `FILE` instead of the user's pins file")
+	dryRun := fs.Bool("dry-run", false, "print the hashes without saving them")
+	if err := fs.Parse(args); err != nil {
+		return err
+	}
+
+	urls := map[string]bool{}
+	for _, entry := range catalog.All() {
+		for _, action := range []manager.Action{manager.ActionInstall, manager.ActionUpdate, manager.ActionUninstall} {
+			for _, recipe := range manager.Recipes(entry, action) {
+				if recipe.Installer != nil {
+					urls[recipe.Installer.URL] = true
+				}
+			}
+		}
+	}
+	sorted := make([]string, 0, len(urls))
+	for url := range urls {
+		sorted = append(sorted, url)
+	}
+	sort.Strings(sorted)
+
+	pins, err := installers.Pins()
+	if err != nil {
+		return err
+	}
+	var failed int
+	for _, url := range sorted {
+		_, sum, err := installers.Fetch(context.Background(), url)
+		if err != nil {
+			fmt.Printf("✗ %s: %v\n", url, err)
+			failed++
+			continue
+		}
+		switch old := pins[url]; old {
+		case sum:
+			fmt.Printf("  %s unchanged\n", url)
+		case "":
+			fmt.Printf("+ %s %s\n", url, sum)
+		default:
+			fmt.Printf("~ %s %s → %s\n", url, old, sum)
+		}
+		pins[url] = sum
+	}
+	if !*dryRun {
+		if err := installers.Save(*out, pins); err != nil {
+			return err
+		}
+	}
+	if failed > 0 {
+		return fmt.Errorf("%d of %d installers could not be downloaded", failed, len(sorted))
+	}
+	return nil
+}
+
+// runCatalogVerify lints every recipe and hook of the catalog and fails
+// when anything needs fixing; see catalog.Verify.
+func runCatalogVerify(args []string) error {
+	if len(args) > 0 {
+		return usagef("maziq catalog verify")
+	}
+	entries := catalog.All()
+	problems := catalog.Verify(entries)
+	for _, p := range problems {
+		fmt.Printf("✗ %s\n", p)
+	}
+	if len(problems) > 0 {
+		return fmt.Errorf("%d problems in %d entries", len(problems), len(entries))
+	}
+	fmt.Printf("✓ %d entries verified\n", len(entries))
+	return nil
+}
@@ -0,0 +1,304 @@
+// Package cli parses maziq's command line and runs its non-interactive
+// subcommands, or the TUI when no subcommand is given.
+package cli
+
+import (
+	"errors"
+	"flag"
+	"fmt"
+	"io"
+	"os"
+	"strings"
+
+	"github.com/hmziqrs/maziq/internal/config"
+	"github.com/hmziqrs/maziq/internal/logging"
+	"github.com/hmziqrs/maziq/internal/options"
+	"github.com/hmziqrs/maziq/internal/paths"
+	"github.com/hmziqrs/maziq/internal/tui"
+)
+
+type command struct {
+	name     string
+	summary  string
+	// synopsis lists the invocation forms, without the leading "maziq".
+	synopsis []string
+	run      func(s session, args []string) error
+}
+
+// session is what Run hands every subcommand: config.toml as loaded and
+// the run options built from it and the global flags. Subcommands take
+// their flag defaults from it and override a copy of opts, so the command
+// line always wins and no state outlives the call.
+type session struct {
+	cfg  config.Config
+	opts options.Run
+}
+
+var commands = []command{
+	{"software", "List, install, update or uninstall catalog entries", []string{
+		"software list [--category CATEGORY] [--tag TAG]",
+		"software show|info [--json] <id>",
+		"software graph [--format dot|mermaid] [--template NAME]",
+		"software install|update|uninstall [--dry-run] [--yes] [--print-only] <id>...",
+		"software test <id>...",
+		"software e2e [--skip-remove] [--json] [--yes] <id>",
+	}, runSoftware},
+	{"onboard", "Install or update everything in a template", []string{
+		"onboard fresh [--template NAME] [--locked] [--lockfile FILE] [--dry-run] [--yes] [--with-recommended]",
+		"onboard update [--template NAME] [--dry-run] [--yes] [--with-recommended]",
+		"onboard plan [--template NAME] [--action install|update] [--export FILE] [--with-recommended]",
+		"onboard apply [--dry-run] [--yes] <plan.json>",
+		"onboard guided [--template NAME] [--restart]",
+	}, runOnboard},
+	{"fleet", "Run a template on every host of an inventory over ssh", []string{
+		"fleet --inventory FILE [--template NAME] [--update] [--parallel N] [--dry-run] [--yes]",
+	}, runFleet},
+	{"status", "Show what is installed, or how the machine drifted from a template", []string{
+		"status",
+		"status --diff TEMPLATE [--apply] [--dry-run] [--yes]",
+	}, runStatus},
+	{"discover", "Match the apps in /Applications against the catalog and show who installed them", []string{
+		"discover [--all]",
+	}, runDiscover},
+	{"upgrade", "Update installed software", []string{
+		"upgrade --all [--dry-run] [--yes]",
+		"upgrade <id>... [--dry-run] [--yes]",
+	}, runUpgrade},
+	{"schedule", "Run upgrades periodically through a launchd agent", []string{
+		"schedule enable [--daily|--weekly]",
+		"schedule status",
+		"schedule disable",
+	}, runSchedule},
+	{"templates", "List templates or freeze the installed software into a new one", []string{
+		"templates list",
+		"templates freeze <name> [--category CATEGORY] [--description TEXT] [--no-pins] [--force]",
+	}, runTemplates},
+	{"config", "Diff and apply configuration profiles (macOS defaults, git identities, editors) and proxy settings (experimental)", []string{
+		"config list",
+		"config diff <profile> [--identity NAME]",
+		"config apply <profile> [--identity NAME] [--dry-run] [--yes]",
+		"config proxy [--http URL] [--https URL] [--no-proxy HOSTS] [--unset] [--dry-run] [--yes]",
+	}, runConfig},
+	{"aliases", "Opt in or out of the shell aliases entries recommend", []string{
+		"aliases list",
+		"aliases enable|disable <id>",
+	}, runAliases},
+	{"serve", "Serve a local API for other frontends (catalog, statuses, actions, events)", []string{
+		"serve [--socket PATH | --addr 127.0.0.1:PORT]",
+	}, runServe},
+	{"api", "Answer one versioned JSON request (status, install, history) for launcher extensions", []string{
+		"api <request.json | ->",
+	}, runAPI},
+	{"stats", "Summarize the local install history: totals, backend timings, failures", []string{
+		"stats",
+	}, runStats},
+	{"history", "Check the install history's hash chain for tampering", []string{
+		"history verify",
+	}, runHistory},
+	{"snapshot", "List or restore the GUI apps recorded before they were uninstalled", []string{
+		"snapshot list",
+		"snapshot restore [--dry-run] <id>",
+	}, runSnapshot},
+	{"catalog", "Maintain the catalog's pinned installer hashes and lint its recipes", []string{
+		"catalog refresh-hashes [--out FILE] [--dry-run]",
+		"catalog verify",
+	}, runCatalog},
+	{"cache", "Download installers and Homebrew bottles ahead of time for --offline runs", []string{
+		"cache warm [--dry-run] --template NAME | <id>...",
+		"cache status",
+		"cache clear",
+	}, runCache},
+	{"audit", "List catalog and template commands outside the safety allowlist", []string{
+		"audit [--all]",
+	}, runAudit},
+	{"report-issue", "Draft a GitHub issue about an entry's latest failure", []string{
+		"report-issue [--open] <id>",
+	}, runReportIssue},
+	{"purge-self", "Remove everything maziq created (state, logs, profile lines, agents)", []string{
+		"purge-self [--dry-run] [--yes]",
+	}, runPurgeSelf},
+}
+
+func init() {
+	// Registered here because docs renders the table itself, which would
+	// otherwise be an initialization cycle.
+	commands = append(commands, command{"docs", "Render man pages or the catalog as Markdown", []string{
+		"docs man [--out DIR]",
+		"docs catalog [--out FILE]",
+	}, runDocs})
+}
+
+// Run dispatches args to a subcommand and returns the process exit code.
+// Global flags (e.g. --backend) come before the subcommand; without one
+// the TUI starts with them, unless nothing may prompt.
+func Run(args []string) int {
+	cfg, err := config.Load()
+	if err != nil {
+		fmt.Fprintf(os.Stderr, "Error: %v\n", err)
+		return exitFailure
+	}
+	opts, args, err := parseGlobal(cfg, args)
+	if errors.Is(err, flag.ErrHelp) {
+		printUsage(os.Stdout)
+		return 0
+	}
+	if err != nil {
+		fmt.Fprintf(os.Stderr, "Error: %v\n\n", err)
+		printUsage(os.Stderr)
+		return exitUsage
+	}
+	if closeLog, err := logging.Setup(opts.Verbosity); err == nil {
+		defer closeLog()
+	}
+	cfg.Proxy.Export()
+
+	if len(args) == 0 && opts.NonInteractive {
+		// There is no TUI to fall back to.
+		printUsage(os.Stderr)
+		return exitUsage
+	}
+	if len(args) == 2 && args[0] == "help" && args[1] == "exit-codes" {
+		printExitCodes(os.Stdout)
+		return exitOK
+	}
+	if len(args) == 0 {
+		if err := tui.Run(cfg, opts); err != nil {
+			fmt.Fprintf(os.Stderr, "Error: %v\n", err)
+			return exitFailure
+		}
+		return exitOK
+	}
+	if args[0] == "help" || args[0] == "-h" || args[0] == "--help" {
+		printUsage(os.Stdout)
+		return exitOK
+	}
+	for _, cmd := range commands {
+		if cmd.name == args[0] {
+			err := cmd.run(session{cfg: cfg, opts: opts}, args[1:])
+			switch {
+			case errors.Is(err, errAborted):
+				fmt.Fprintln(os.Stderr, "Aborted.")
+			case err != nil && !errors.Is(err, flag.ErrHelp):
+				fmt.Fprintf(os.Stderr, "Error: %v\n", err)
+			}
+			return exitCode(err)
+		}
+	}
+	fmt.Fprintf(os.Stderr, "Unknown command %q\n\n", args[0])
+	printUsage(os.Stderr)
+	return exitUsage
+}
+
+// parseGlobal builds the run options from cfg and the global flags at the
+// front of args, and returns them with the remaining arguments.
+func parseGlobal(cfg config.Config, args []string) (options.Run, []string, error) {
+	opts := cfg.RunOptions()
+	global := flag.NewFlagSet("maziq", flag.ContinueOnError)
+	global.SetOutput(io.Discard)
+	backend := global.String("backend", string(cfg.Backend), "preferred install backend: brew or nix")
+	global.IntVar(&opts.Jobs, "jobs", opts.Jobs, "how many status probes run at once")
+	global.BoolVar(&opts.ContinueOnError, "continue-on-error", opts.ContinueOnError, "keep running past failed steps; --continue-on-error=false stops at the first")
+	global.StringVar(&opts.Verbosity, "log-level", opts.Verbosity, "diagnostic log level: debug, info, warn or error")
+	global.BoolVar(&opts.Offline, "offline", opts.Offline, "install only from what maziq cache warm downloaded; never touch the network")
+	global.BoolVar(&opts.Force, "force", false, "run install steps for software that is already installed")
+	global.BoolVar(&opts.ForceAdopted, "force-adopted", false, "let uninstalls remove software maziq did not install")
+	global.BoolVar(&opts.ConfirmSDKRemoval, "confirm-sdk-removal", false, "let uninstalls remove SDKs along with their toolchains and caches")
+	global.StringVar(&opts.EventLog, "events", "", "append every run's progress events to this file as JSON lines")
+	report := global.String("report", "", "write a report of every run; junit=FILE writes JUnit XML")
+	global.StringVar(&opts.Askpass, "sudo-askpass", "", "SUDO_ASKPASS helper used for commands that need root")
+	global.BoolVar(&opts.NonInteractive, "non-interactive", Unattended(), "never prompt; fail instead of asking (default when CI is set or stdin is not a terminal)")
+	global.BoolVar(&opts.AssumeYes, "yes", false, "answer yes to every confirmation")
+	if err := global.Parse(args); err != nil {
+		return opts, nil, err
+	}
+	b, err := options.ParseBackend(*backend)
+	if err != nil {
+		return opts, nil, err
+	}
+	opts.Backend = b
+	if opts.Jobs < 1 {
+		return opts, nil, fmt.Errorf("--jobs must be at least 1")
+	}
+	if _, err := logging.ParseLevel(opts.Verbosity); err != nil {
+		return opts, nil, err
+	}
+	if *report != "" {
+		if opts.JUnitReport, err = parseReport(*report); err != nil {
+			return opts, nil, err
+		}
+	}
+	return opts, global.Args(), nil
+}
+
+// parseReport checks a --report value, FORMAT=FILE, and returns the file.
+// junit is the only format so far.
+func parseReport(spec string) (string, error) {
+	format, path, ok := strings.Cut(spec, "=")
+	if !ok || path == "" {
+		return "", fmt.Errorf("--report takes FORMAT=FILE, e.g. junit=report.xml")
+	}
+	if format != "junit" {
+		return "", fmt.Errorf("unknown --report format %q; supported: junit", format)
+	}
+	return path, nil
+}
+
+// Unattended reports whether maziq runs without anyone to answer prompts:
+// in CI (CI or MAZIQ_NON_INTERACTIVE set) or with stdin not a terminal.
+func Unattended() bool {
+	for _, name := range []string{"CI", "MAZIQ_NON_INTERACTIVE"} {
+		if v := os.Getenv(name); v != "" && v != "0" && v != "false" {
+			return true
+		}
+	}
+	info, err := os.Stdin.Stat()
+	return err != nil || info.Mode()&os.ModeCharDevice == 0
+}
+
+func printUsage(w io.Writer) {
+	fmt.Fprintln(w, "Usage: maziq [global flags] [command] [flags]")
+	fmt.Fprintln(w)
+	fmt.Fprintln(w, "Run without a command to start the interactive TUI.")
+	fmt.Fprintln(w, "With --non-interactive nothing prompts: confirmations need --yes and sudo")
+	fmt.Fprintln(w, "needs cached credentials or --sudo-askpass.")
+	fmt.Fprintln(w)
+	fmt.Fprintln(w, "Global flags (defaults come from "+paths.ConfigFile()+"):")
+	fmt.Fprintln(w, "  --backend brew|nix  preferred install backend")
+	fmt.Fprintln(w, "  --jobs N            how many status probes run at once")
+	fmt.Fprintln(w, "  --continue-on-error keep running past failed steps (=false stops at the first)")
+	fmt.Fprintln(w, "  --log-level LEVEL   debug, info, warn or error; logs go to "+paths.LogFile())
+	fmt.Fprintln(w, "  --offline           install only from the cache filled by 'maziq cache warm'")
+	fmt.Fprintln(w, "  --force             run install steps for software that is already installed")
+	fmt.Fprintln(w, "  --force-adopted     let uninstalls remove software maziq did not install")
+	fmt.Fprintln(w, "  --confirm-sdk-removal")
+	fmt.Fprintln(w, "                      let uninstalls remove SDKs with their toolchains and caches")
+	fmt.Fprintln(w, "  --events FILE       append run progress events to FILE as JSON lines")
+	fmt.Fprintln(w, "  --report junit=FILE write each run's steps to FILE as JUnit XML test cases")
+	fmt.Fprintln(w, "  --sudo-askpass PATH SUDO_ASKPASS helper for commands that need root")
+	fmt.Fprintln(w, "  --non-interactive   never prompt and never start the TUI")
+	fmt.Fprintln(w, "  --yes               answer yes to every confirmation")
+	fmt.Fprintln(w)
+	fmt.Fprintln(w, "Commands:")
+	for _, cmd := range commands {
+		fmt.Fprintf(w, "  %-14s %s\n", cmd.name, cmd.summary)
+	}
+	fmt.Fprintln(w)
+	fmt.Fprintln(w, "Run 'maziq help exit-codes' for the exit codes scripts can branch on.")
+}
+
+// parseInterspersed parses fs while allowing flags to follow positional
+// arguments, returning the positional arguments in order.
+func parseInterspersed(fs *flag.FlagSet, args []string) ([]string, error) {
+	var positional []string
+	for {
+		if err := fs.Parse(args); err != nil {
+			return nil, err
+		}
+		args = fs.Args()
+		if len(args) == 0 {
+			return positional, nil
+		}
+		positional = append(positional, args[0])
+		args = args[1:]
+	}
+}
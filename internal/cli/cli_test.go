@@ -0,0 +1,63 @@
+package cli
+
+import (
+	"reflect"
+	"testing"
+
+	"github.com/hmziqrs/maziq/internal/config"
+	"github.com/hmziqrs/maziq/internal/options"
+)
+
+func TestParseGlobal(t *testing.T) {
+	cfg := config.Default()
+	cfg.Jobs = 3
+	cfg.DryRun = true
+	cfg.LogLevel = "warn"
+
+	opts, args, err := parseGlobal(cfg, []string{"--non-interactive=false", "status"})
+	if err != nil {
+		t.Fatal(err)
+	}
+	if opts.Jobs != 3 || !opts.DryRun || opts.Verbosity != "warn" || opts.Force {
+		t.Errorf("without flags the options follow config.toml, got %+v", opts)
+	}
+	if !reflect.DeepEqual(args, []string{"status"}) {
+		t.Errorf("args = %q, want [status]", args)
+	}
+
+	opts, args, err = parseGlobal(cfg, []string{
+		"--backend", "nix", "--jobs", "2", "--force", "--log-level", "debug",
+		"--report", "junit=out.xml", "--yes", "--non-interactive", "upgrade", "--all",
+	})
+	if err != nil {
+		t.Fatal(err)
+	}
+	want := cfg.RunOptions()
+	want.Backend = options.BackendNix
+	want.Jobs = 2
+	want.Force = true
+	want.Verbosity = "debug"
+	want.JUnitReport = "out.xml"
+	want.AssumeYes = true
+	want.NonInteractive = true
+	if !reflect.DeepEqual(opts, want) {
+		t.Errorf("flags override config.toml:\n got %+v\nwant %+v", opts, want)
+	}
+	if !reflect.DeepEqual(args, []string{"upgrade", "--all"}) {
+		t.Errorf("args = %q, want [upgrade --all]", args)
+	}
+}
+
+func TestParseGlobalRejects(t *testing.T) {
+	for _, args := range [][]string{
+		{"--backend", "apt"},
+		{"--jobs", "0"},
+		{"--log-level", "loud"},
+		{"--report", "tap=out.txt"},
+		{"--no-such-flag"},
+	} {
+		if _, _, err := parseGlobal(config.Default(), args); err == nil {
+			t.Errorf("parseGlobal(%q) succeeded", args)
+		}
+	}
+}
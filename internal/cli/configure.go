@@ -0,0 +1,157 @@
+package cli
+
+import (
+	"context"
+	"flag"
+	"fmt"
+	"os"
+	"text/tabwriter"
+
+	"github.com/hmziqrs/maziq/internal/config"
+	"github.com/hmziqrs/maziq/internal/configurator"
+	"github.com/hmziqrs/maziq/internal/paths"
+)
+
+func runConfig(s session, args []string) error {
+	if len(args) == 0 {
+		return usagef("maziq config <list|diff|apply|proxy> [profile] [--identity NAME] [--dry-run] [--yes]")
+	}
+	switch args[0] {
+	case "proxy":
+		return runConfigProxy(s, args[1:])
+	case "list":
+		w := tabwriter.NewWriter(os.Stdout, 0, 0, 2, ' ', 0)
+		fmt.Fprintln(w, "NAME\tTYPE\tDESCRIPTION")
+		for _, name := range configurator.List() {
+			p, err := configurator.Load(name)
+			if err != nil {
+				fmt.Fprintf(w, "%s\t-\tinvalid: %v\n", name, err)
+				continue
+			}
+			fmt.Fprintf(w, "%s\t%s\t%s\n", p.Name, p.Type, p.Description)
+		}
+		return w.Flush()
+	case "diff", "apply":
+		fs := flag.NewFlagSet("config "+args[0], flag.ContinueOnError)
+		dryRun := fs.Bool("dry-run", s.opts.DryRun, "show the differences without applying them")
+		yes := fs.Bool("yes", false, "skip the confirmation prompt")
+		identity := fs.String("identity", "", "git profiles: identity used outside the identities' directories")
+		names, err := parseInterspersed(fs, args[1:])
+		if err != nil {
+			return err
+		}
+		if len(names) != 1 {
+			return usagef("maziq config %s <profile>", args[0])
+		}
+		p, err := configurator.Load(names[0])
+		if err != nil {
+			return err
+		}
+		ctx := context.Background()
+		opts := configurator.Options{Identity: *identity}
+		changes, err := configurator.Diff(ctx, p, opts)
+		if err != nil {
+			return err
+		}
+		pending := printChanges(changes)
+		if args[0] == "diff" || *dryRun || pending == 0 {
+			return nil
+		}
+		fmt.Println("\nThe configurator is experimental: these settings change system behavior.")
+		if !*yes && !confirm(s.opts, fmt.Sprintf("Apply %d change(s)?", pending)) {
+			return errAborted
+		}
+		written, err := configurator.Apply(ctx, p, opts)
+		for _, c := range written {
+			fmt.Printf("✓ %s %s = %s\n", c.Scope, c.Key, c.Desired)
+		}
+		return err
+	}
+	return fmt.Errorf("unknown config subcommand %q", args[0])
+}
+
+// printChanges renders a current-vs-desired table and returns how many
+// settings would change.
+func printChanges(changes []configurator.Change) int {
+	w := tabwriter.NewWriter(os.Stdout, 0, 0, 2, ' ', 0)
+	fmt.Fprintln(w, "\tSCOPE\tKEY\tCURRENT\tDESIRED")
+	pending := 0
+	for _, c := range changes {
+		mark, current := " ", c.Current
+		if !c.Set {
+			current = "(unset)"
+		}
+		if c.Pending {
+			mark = "~"
+			pending++
+		}
+		fmt.Fprintf(w, "%s\t%s\t%s\t%s\t%s\n", mark, c.Scope, c.Key, current, c.Desired)
+	}
+	w.Flush()
+	if pending == 0 {
+		fmt.Println("\nEverything already matches.")
+	} else {
+		fmt.Printf("\n%d of %d settings differ (marked ~).\n", pending, len(changes))
+	}
+	return pending
+}
+
+// runConfigProxy records proxy settings in config.toml, which maziq
+// exports to every command it runs, and writes them for git, npm and the
+// shell profiles so the tools work behind the proxy outside maziq too.
+// Without flags it re-applies the configured proxy.
+func runConfigProxy(s session, args []string) error {
+	fs := flag.NewFlagSet("config proxy", flag.ContinueOnError)
+	httpProxy := fs.String("http", s.cfg.Proxy.HTTP, "proxy `URL` for http traffic")
+	httpsProxy := fs.String("https", s.cfg.Proxy.HTTPS, "proxy `URL` for https traffic")
+	noProxy := fs.String("no-proxy", s.cfg.Proxy.NoProxy, "comma-separated `HOSTS` reached directly")
+	unset := fs.Bool("unset", false, "remove every proxy setting maziq wrote")
+	dryRun := fs.Bool("dry-run", s.opts.DryRun, "show the differences without applying them")
+	yes := fs.Bool("yes", false, "skip the confirmation prompt")
+	if err := fs.Parse(args); err != nil {
+		return err
+	}
+	if fs.NArg() > 0 {
+		return usagef("maziq config proxy [--http URL] [--https URL] [--no-proxy HOSTS] [--unset] [--dry-run] [--yes]")
+	}
+	proxy := config.Proxy{HTTP: *httpProxy, HTTPS: *httpsProxy, NoProxy: *noProxy}
+	if *unset {
+		proxy = config.Proxy{}
+	}
+	for _, raw := range []string{proxy.HTTP, proxy.HTTPS} {
+		if raw != "" {
+			if err := config.ValidateProxyURL(raw); err != nil {
+				return err
+			}
+		}
+	}
+
+	ctx := context.Background()
+	changes, err := configurator.DiffProxy(ctx, proxy)
+	if err != nil {
+		return err
+	}
+	pending := printChanges(changes)
+	if *dryRun {
+		return nil
+	}
+	if proxy != s.cfg.Proxy {
+		cfg := s.cfg
+		cfg.Proxy = proxy
+		if err := config.Save(cfg); err != nil {
+			return err
+		}
+		fmt.Println("\nSaved to " + paths.ConfigFile() + ".")
+	}
+	if pending == 0 {
+		return nil
+	}
+	if !*yes && !confirm(s.opts, fmt.Sprintf("Apply %d change(s)?", pending)) {
+		return errAborted
+	}
+	written, err := configurator.ApplyProxy(ctx, proxy)
+	for _, c := range written {
+		fmt.Printf("✓ %s %s = %s\n", c.Scope, c.Key, orNone(c.Desired))
+	}
+	return err
+}
@@ -0,0 +1,48 @@
+package cli
+
+import (
+	"context"
+	"flag"
+	"fmt"
+	"os"
+	"text/tabwriter"
+
+	"github.com/hmziqrs/maziq/internal/manager"
+)
+
+// runDiscover lists the apps in /Applications that the catalog knows and
+// who installed them; --all also lists the ones it does not know.
+func runDiscover(_ session, args []string) error {
+	fs := flag.NewFlagSet("discover", flag.ContinueOnError)
+	all := fs.Bool("all", false, "also list apps the catalog does not know")
+	if err := fs.Parse(args); err != nil {
+		return err
+	}
+	if fs.NArg() > 0 {
+		return usagef("maziq discover [--all]")
+	}
+	apps, err := manager.Discover(context.Background())
+	if err != nil {
+		return err
+	}
+	w := tabwriter.NewWriter(os.Stdout, 0, 0, 2, ' ', 0)
+	fmt.Fprintln(w, "ID\tAPP\tPROVENANCE")
+	var known, outside int
+	for _, app := range apps {
+		switch {
+		case app.Entry != "":
+			known++
+			if app.Provenance.Origin != manager.OriginMaziq {
+				outside++
+			}
+			fmt.Fprintf(w, "%s\t%s\t%s\n", app.Entry, app.Path, app.Provenance)
+		case *all:
+			fmt.Fprintf(w, "-\t%s\tnot in the catalog\n", app.Path)
+		}
+	}
+	if err := w.Flush(); err != nil {
+		return err
+	}
+	fmt.Printf("\n%d of %d apps are in the catalog; %d of them were installed outside maziq.\n", known, len(apps), outside)
+	return nil
+}
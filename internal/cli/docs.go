@@ -0,0 +1,238 @@
+package cli
+
+import (
+	"flag"
+	"fmt"
+	"io"
+	"os"
+	"path/filepath"
+	"strings"
+
+	"github.com/hmziqrs/maziq/internal/catalog"
+	"github.com/hmziqrs/maziq/internal/manager"
+)
+
+func runDocs(_ session, args []string) error {
+	if len(args) == 0 {
+		return usagef("maziq docs <man|catalog> [--out PATH]")
+	}
+	switch args[0] {
+	case "man":
+		fs := flag.NewFlagSet("docs man", flag.ContinueOnError)
+		out := fs.String("out", "", "write maziq.1 and one page per command into this directory")
+		if err := fs.Parse(args[1:]); err != nil {
+			return err
+		}
+		if *out == "" {
+			writeMainManPage(os.Stdout)
+			return nil
+		}
+		return writeManPages(*out)
+	case "catalog":
+		fs := flag.NewFlagSet("docs catalog", flag.ContinueOnError)
+		out := fs.String("out", "", "write the Markdown to this file instead of stdout")
+		if err := fs.Parse(args[1:]); err != nil {
+			return err
+		}
+		if *out == "" {
+			writeCatalogMarkdown(os.Stdout)
+			return nil
+		}
+		f, err := os.Create(*out)
+		if err != nil {
+			return err
+		}
+		writeCatalogMarkdown(f)
+		return f.Close()
+	}
+	return fmt.Errorf("unknown docs subcommand %q", args[0])
+}
+
+func writeManPages(dir string) error {
+	if err := os.MkdirAll(dir, 0o755); err != nil {
+		return err
+	}
+	write := func(name string, render func(io.Writer)) error {
+		f, err := os.Create(filepath.Join(dir, name))
+		if err != nil {
+			return err
+		}
+		render(f)
+		if err := f.Close(); err != nil {
+			return err
+		}
+		fmt.Println("wrote", filepath.Join(dir, name))
+		return nil
+	}
+	if err := write("maziq.1", writeMainManPage); err != nil {
+		return err
+	}
+	for _, cmd := range commands {
+		if err := write("maziq-"+cmd.name+".1", func(w io.Writer) { writeCommandManPage(w, cmd) }); err != nil {
+			return err
+		}
+	}
+	return nil
+}
+
+// roff escapes text for use in a man page line.
+func roff(text string) string {
+	text = strings.ReplaceAll(text, `\`, `\e`)
+	text = strings.ReplaceAll(text, "-", `\-`)
+	if strings.HasPrefix(text, ".") || strings.HasPrefix(text, "'") {
+		text = `\&` + text
+	}
+	return text
+}
+
+func writeMainManPage(w io.Writer) {
+	fmt.Fprintln(w, `.TH MAZIQ 1 "" "maziq" "User Commands"`)
+	fmt.Fprintln(w, ".SH NAME")
+	fmt.Fprintln(w, `maziq \- macOS provisioning and automation tool`)
+	fmt.Fprintln(w, ".SH SYNOPSIS")
+	fmt.Fprintln(w, `.B maziq`)
+	fmt.Fprintln(w, `[\fIglobal options\fR] [\fIcommand\fR] [\fIflags\fR]`)
+	fmt.Fprintln(w, ".SH DESCRIPTION")
+	fmt.Fprintln(w, "Run without a command to start the interactive TUI.")
+	fmt.Fprintln(w, "Every command is also available non-interactively, as listed below.")
+	fmt.Fprintln(w, ".SH COMMANDS")
+	for _, cmd := range commands {
+		fmt.Fprintln(w, ".TP")
+		fmt.Fprintf(w, ".B %s\n", roff(cmd.name))
+		fmt.Fprintln(w, roff(cmd.summary)+".")
+		fmt.Fprintf(w, "See \\fBmaziq\\-%s\\fR(1).\n", roff(cmd.name))
+	}
+	fmt.Fprintln(w, ".SH GLOBAL OPTIONS")
+	fmt.Fprintln(w, ".TP")
+	fmt.Fprintln(w, `\fB\-\-backend\fR \fIbrew\fR|\fInix\fR`)
+	fmt.Fprintln(w, "Preferred install backend for entries that offer several sources.")
+	fmt.Fprintln(w, "MacPorts is used automatically when Homebrew is absent.")
+	fmt.Fprintln(w, ".TP")
+	fmt.Fprintln(w, `\fB\-\-jobs\fR \fIn\fR`)
+	fmt.Fprintln(w, "How many status probes run at once.")
+	fmt.Fprintln(w, ".TP")
+	fmt.Fprintln(w, `\fB\-\-continue\-on\-error\fR[=\fIfalse\fR]`)
+	fmt.Fprintln(w, "Keep running a plan past failed steps, skipping only the steps that depend on them, and print a summary at the end.")
+	fmt.Fprintln(w, "On by default; \\fB\\-\\-continue\\-on\\-error=false\\fR stops at the first failure.")
+	fmt.Fprintln(w, ".TP")
+	fmt.Fprintln(w, `\fB\-\-log\-level\fR \fIlevel\fR`)
+	fmt.Fprintln(w, `One of debug, info, warn or error.`)
+	fmt.Fprintln(w, ".TP")
+	fmt.Fprintln(w, `\fB\-\-sudo\-askpass\fR \fIpath\fR`)
+	fmt.Fprintln(w, "SUDO_ASKPASS helper for commands that need root.")
+	fmt.Fprintln(w, "Without it, maziq asks for the password once before the run and keeps the credentials cached.")
+	fmt.Fprintln(w, ".TP")
+	fmt.Fprintln(w, `\fB\-\-non\-interactive\fR`)
+	fmt.Fprintln(w, `Never prompt and never start the TUI. Confirmations fail unless \fB\-\-yes\fR is given,`)
+	fmt.Fprintln(w, `and sudo needs cached credentials or \fB\-\-sudo\-askpass\fR.`)
+	fmt.Fprintln(w, `This is the default when \fBCI\fR or \fBMAZIQ_NON_INTERACTIVE\fR is set or stdin is not a terminal.`)
+	fmt.Fprintln(w, ".TP")
+	fmt.Fprintln(w, `\fB\-\-yes\fR`)
+	fmt.Fprintln(w, "Answer yes to every confirmation.")
+	fmt.Fprintln(w, ".SH EXIT STATUS")
+	for _, status := range exitCodes {
+		fmt.Fprintln(w, ".TP")
+		fmt.Fprintf(w, ".B %d\n", status.code)
+		fmt.Fprintln(w, roff(status.text))
+	}
+	fmt.Fprintln(w, ".SH FILES")
+	fmt.Fprintln(w, ".TP")
+	fmt.Fprintln(w, `.I ~/.config/maziq/config.toml`)
+	fmt.Fprintln(w, "Persistent preferences: role (set by the TUI's first-run wizard), template, dry_run, backend, jobs, continue_on_error, theme (dark, light or mono) and log_level.")
+	fmt.Fprintln(w, "Command-line flags override them.")
+	fmt.Fprintln(w, ".TP")
+	fmt.Fprintln(w, `.I ~/.config/maziq/templates/`)
+	fmt.Fprintln(w, "User templates, which take precedence over the built-in ones.")
+	fmt.Fprintln(w, ".TP")
+	fmt.Fprintln(w, `.I ~/.config/maziq/profiles/`)
+	fmt.Fprintln(w, "User configurator profiles, which take precedence over the built-in ones.")
+	fmt.Fprintln(w, ".TP")
+	fmt.Fprintln(w, `.I ~/.local/state/maziq/install_history.jsonl`)
+	fmt.Fprintln(w, "History of every install, update and uninstall with the command's exit code and output tail, used for time estimates and for investigating failures.")
+	fmt.Fprintln(w, ".TP")
+	fmt.Fprintln(w, `.I ~/Library/LaunchAgents/dev.hmziq.maziq.upgrade.plist`)
+	fmt.Fprintln(w, "Scheduled maintenance agent managed by \\fBmaziq\\-schedule\\fR(1); its runs log to \\fI~/.local/state/maziq/logs/schedule.log\\fR.")
+	fmt.Fprintln(w, ".TP")
+	fmt.Fprintln(w, `.I ~/.local/state/maziq/logs/maziq.log`)
+	fmt.Fprintln(w, "Diagnostic log, written at the configured log level.")
+	fmt.Fprintln(w, ".SH SEE ALSO")
+	var refs []string
+	for _, cmd := range commands {
+		refs = append(refs, fmt.Sprintf("\\fBmaziq\\-%s\\fR(1)", roff(cmd.name)))
+	}
+	fmt.Fprintln(w, strings.Join(refs, ", "))
+}
+
+func writeCommandManPage(w io.Writer, cmd command) {
+	fmt.Fprintf(w, ".TH MAZIQ\\-%s 1 \"\" \"maziq\" \"User Commands\"\n", strings.ToUpper(roff(cmd.name)))
+	fmt.Fprintln(w, ".SH NAME")
+	fmt.Fprintf(w, "maziq\\-%s \\- %s\n", roff(cmd.name), roff(strings.ToLower(cmd.summary[:1])+cmd.summary[1:]))
+	fmt.Fprintln(w, ".SH SYNOPSIS")
+	fmt.Fprintln(w, ".nf")
+	for _, line := range cmd.synopsis {
+		fmt.Fprintf(w, "\\fBmaziq\\fR %s\n", roff(line))
+	}
+	fmt.Fprintln(w, ".fi")
+	fmt.Fprintln(w, ".SH DESCRIPTION")
+	fmt.Fprintln(w, roff(cmd.summary)+".")
+	fmt.Fprintln(w, ".SH SEE ALSO")
+	fmt.Fprintln(w, `\fBmaziq\fR(1)`)
+}
+
+// writeCatalogMarkdown renders every entry, its dependencies and recipes,
+// grouped by category.
+func writeCatalogMarkdown(w io.Writer) {
+	entries := catalog.All()
+	fmt.Fprintln(w, "# maziq software catalog")
+	fmt.Fprintln(w)
+	fmt.Fprintf(w, "Generated by `maziq docs catalog` — %d entries.\n", len(entries))
+	for _, category := range catalog.Categories() {
+		fmt.Fprintf(w, "\n## %s\n", category)
+		for _, entry := range entries {
+			if entry.Category != category {
+				continue
+			}
+			fmt.Fprintf(w, "\n### %s (`%s`)\n\n", entry.Name, entry.ID)
+			if entry.Summary != "" {
+				fmt.Fprintf(w, "%s\n\n", entry.Summary)
+			}
+			fmt.Fprintf(w, "- **Kind:** %s\n", entry.Kind)
+			if entry.Homepage != "" {
+				fmt.Fprintf(w, "- **Homepage:** <%s>\n", entry.Homepage)
+			}
+			if entry.Docs != "" {
+				fmt.Fprintf(w, "- **Docs:** <%s>\n", entry.Docs)
+			}
+			if entry.License != "" {
+				fmt.Fprintf(w, "- **License:** %s\n", entry.License)
+			}
+			deps := "none"
+			if len(entry.Dependencies) > 0 {
+				deps = "`" + strings.Join(entry.Dependencies, "`, `") + "`"
+			}
+			fmt.Fprintf(w, "- **Depends on:** %s\n", deps)
+			if len(entry.Recommended) > 0 {
+				fmt.Fprintf(w, "- **Recommends:** `%s`\n", strings.Join(entry.Recommended, "`, `"))
+			}
+			if len(entry.Conflicts) > 0 {
+				fmt.Fprintf(w, "- **Conflicts with:** `%s`\n", strings.Join(entry.Conflicts, "`, `"))
+			}
+			fmt.Fprintf(w, "- **Probe:** `%s`\n", entry.Probe.Describe())
+			for _, hook := range entry.PostInstall {
+				fmt.Fprintf(w, "- **Post-install:** %s (`%s`)\n", hook.Description, hook.Command)
+			}
+			for _, alias := range entry.Aliases {
+				fmt.Fprintf(w, "- **Alias:** %s\n", alias.Description)
+			}
+			fmt.Fprintln(w)
+			fmt.Fprintln(w, "| Action | Source | Command |")
+			fmt.Fprintln(w, "|---|---|---|")
+			for _, action := range []manager.Action{manager.ActionInstall, manager.ActionUpdate, manager.ActionUninstall} {
+				for _, recipe := range manager.Recipes(entry, action) {
+					command := strings.ReplaceAll(recipe.Command, "|", `\|`)
+					fmt.Fprintf(w, "| %s | %s | `%s` |\n", action, recipe.Source, command)
+				}
+			}
+		}
+	}
+}
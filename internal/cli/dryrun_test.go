@@ -0,0 +1,44 @@
+package cli
+
+import (
+	"testing"
+
+	"github.com/hmziqrs/maziq/internal/config"
+	"github.com/hmziqrs/maziq/internal/testenv"
+)
+
+// dryRunSession is a session as `maziq --non-interactive` with dry_run
+// set in config.toml would build it.
+func dryRunSession() session {
+	cfg := config.Default()
+	cfg.DryRun = true
+	opts := cfg.RunOptions()
+	opts.NonInteractive = true
+	return session{cfg: cfg, opts: opts}
+}
+
+func TestOnboardDryRunWritesNothing(t *testing.T) {
+	for _, sub := range []string{"fresh", "update"} {
+		t.Run(sub, func(t *testing.T) {
+			env := testenv.New(t)
+			if err := runOnboard(dryRunSession(), []string{sub, "--with-recommended"}); err != nil {
+				t.Fatal(err)
+			}
+			env.AssertUntouched(t)
+		})
+	}
+}
+
+// The guided mode asks, runs and saves nothing in a dry run, so it also
+// works without a terminal.
+func TestGuidedDryRunWritesNothing(t *testing.T) {
+	for name, args := range map[string][]string{"resume": nil, "restart": {"--restart"}} {
+		t.Run(name, func(t *testing.T) {
+			env := testenv.New(t)
+			if err := runOnboardGuided(dryRunSession(), args); err != nil {
+				t.Fatal(err)
+			}
+			env.AssertUntouched(t)
+		})
+	}
+}
@@ -0,0 +1,136 @@
+package cli
+
+import (
+	"context"
+	"encoding/json"
+	"flag"
+	"fmt"
+	"io"
+	"os"
+	"time"
+
+	"github.com/hmziqrs/maziq/internal/catalog"
+	"github.com/hmziqrs/maziq/internal/manager"
+	"github.com/hmziqrs/maziq/internal/options"
+)
+
+// e2eResult is the machine-readable outcome of `software e2e`. Field names
+// are part of the CLI's contract.
+type e2eResult struct {
+	ID         string     `json:"id"`
+	Passed     bool       `json:"passed"`
+	DurationMs int64      `json:"duration_ms"`
+	Stages     []e2eStage `json:"stages"`
+}
+
+type e2eStage struct {
+	Action     string `json:"action"`
+	Result     string `json:"result"`
+	Source     string `json:"source,omitempty"`
+	Command    string `json:"command,omitempty"`
+	ExitCode   int    `json:"exit_code"`
+	Version    string `json:"version,omitempty"`
+	Error      string `json:"error,omitempty"`
+	Hint       string `json:"hint,omitempty"`
+	DurationMs int64  `json:"duration_ms"`
+}
+
+// runSoftwareE2E runs an entry's lifecycle test without the TUI: install,
+// test, update and uninstall, streaming every event and ending with a
+// summary of each stage.
+func runSoftwareE2E(opts options.Run, args []string) error {
+	fs := flag.NewFlagSet("software e2e", flag.ContinueOnError)
+	skipRemove := fs.Bool("skip-remove", false, "leave the entry installed instead of uninstalling it at the end")
+	asJSON := fs.Bool("json", false, "print the summary as JSON on stdout and stream events to stderr")
+	yes := fs.Bool("yes", false, "skip the confirmation prompt for the uninstall stage")
+	ids, err := parseInterspersed(fs, args)
+	if err != nil {
+		return err
+	}
+	if len(ids) != 1 {
+		return usagef("maziq software e2e [--skip-remove] [--json] [--yes] <id>")
+	}
+	entry, ok := catalog.Lookup(ids[0])
+	if !ok {
+		return &catalog.UnknownError{ID: ids[0]}
+	}
+
+	actions := manager.LifecycleActions(*skipRemove)
+	plan := manager.Plan{Action: manager.ActionInstall}
+	for _, action := range actions {
+		if action != manager.ActionTest {
+			plan.Steps = append(plan.Steps, manager.Step{Entry: entry, Action: action})
+		}
+	}
+	if entry.Kind == catalog.KindSDK && !*skipRemove && !opts.ConfirmSDKRemoval {
+		// The uninstall stage may only remove an SDK the install stage put
+		// there; one installed before would take its toolchains with it.
+		if st := manager.NewProber(opts.Jobs).Probe(context.Background(), entry); st.State == manager.StateInstalled || st.State == manager.StateOutdated {
+			return &manager.SDKRemovalError{ID: entry.ID, Removes: entry.Removes}
+		}
+	}
+	if !*yes && !confirmDestructive(opts, plan.Steps) {
+		return errAborted
+	}
+	stop, err := withSudo(opts, plan)
+	if err != nil {
+		return err
+	}
+	defer stop()
+
+	var stream io.Writer = os.Stdout
+	if *asJSON {
+		stream = os.Stderr
+	}
+	started := time.Now()
+	stages, err := manager.New(opts).Lifecycle(context.Background(), entry, *skipRemove, func(ev manager.TaskEvent) {
+		fmt.Fprintf(stream, "%s %s %s\n", ev.Time.UTC().Format(time.RFC3339), ev.SoftwareID, ev.Message)
+	})
+
+	result := e2eResult{ID: entry.ID, Passed: err == nil, DurationMs: time.Since(started).Milliseconds()}
+	for i, action := range actions {
+		stage := e2eStage{Action: string(action), Result: string(manager.ResultNotRun)}
+		if i < len(stages) {
+			s := stages[i]
+			stage.Result = string(manager.ResultSucceeded)
+			if !s.Success {
+				stage.Result = string(manager.ResultFailed)
+			}
+			stage.Source = s.Event.Source
+			stage.Command = s.Event.Command
+			stage.ExitCode = s.Event.ExitCode
+			stage.Version = s.Version
+			stage.Error = s.Error
+			stage.Hint = s.Event.Hint
+			stage.DurationMs = s.Duration.Milliseconds()
+		}
+		result.Stages = append(result.Stages, stage)
+	}
+
+	if *asJSON {
+		enc := json.NewEncoder(os.Stdout)
+		enc.SetIndent("", "  ")
+		if encErr := enc.Encode(result); encErr != nil && err == nil {
+			err = encErr
+		}
+		return err
+	}
+	verdict := "passed"
+	if !result.Passed {
+		verdict = "failed"
+	}
+	fmt.Printf("\nE2E %s %s in %s\n", entry.ID, verdict, time.Duration(result.DurationMs)*time.Millisecond)
+	fmt.Printf("  %-10s %-10s %-8s %s\n", "STAGE", "RESULT", "TIME", "DETAIL")
+	for _, s := range result.Stages {
+		detail := s.Version
+		if s.Error != "" {
+			detail = s.Error
+		}
+		if s.Hint != "" {
+			detail += " (hint: " + s.Hint + ")"
+		}
+		took := (time.Duration(s.DurationMs) * time.Millisecond).Round(time.Second)
+		fmt.Printf("  %-10s %-10s %-8s %s\n", s.Action, s.Result, took, detail)
+	}
+	return err
+}
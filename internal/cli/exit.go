@@ -0,0 +1,105 @@
+package cli
+
+import (
+	"errors"
+	"flag"
+	"fmt"
+	"io"
+
+	"github.com/hmziqrs/maziq/internal/crash"
+	"github.com/hmziqrs/maziq/internal/errs"
+)
+
+// Exit codes returned by Run, one per failure class so CI jobs and MDM
+// scripts can react without parsing output. They are documented by
+// `maziq help exit-codes` and the man page; never renumber them.
+const (
+	exitOK         = 0
+	exitFailure    = 1  // anything not classified below
+	exitPartial    = 2  // a run finished but some steps failed
+	exitPlan       = 3  // the plan could not be built: conflicts, cycles
+	exitUnknown    = 4  // an unknown software id or template
+	exitPermission = 5  // administrator rights or file access were denied
+	exitStep       = 6  // a step failed and stopped the run
+	exitDrift      = 7  // status --diff found differences
+	exitPreflight  = 8  // the machine failed a preflight check
+	exitAborted    = 9  // a confirmation was declined, or needed without --yes
+	exitConfig     = 10 // config.toml, a template or a profile is invalid
+	exitUsage      = 64
+	exitCrash      = crash.ExitCode
+)
+
+// exitCodes describes every exit code, in order.
+var exitCodes = []struct {
+	code int
+	text string
+}{
+	{exitOK, "Success."},
+	{exitFailure, "Any failure not listed below."},
+	{exitPartial, "The run finished but some steps failed (see --continue-on-error)."},
+	{exitPlan, "The plan could not be built: conflicting entries or a dependency cycle."},
+	{exitUnknown, "An unknown software id, template or profile was given."},
+	{exitPermission, "Administrator rights were needed but unavailable, or a file could not be accessed."},
+	{exitStep, "An install, update or uninstall command failed and stopped the run."},
+	{exitDrift, "status --diff found the machine differs from the template."},
+	{exitPreflight, "A preflight check failed: macOS version, disk space, Rosetta or network."},
+	{exitAborted, "A confirmation was declined, or needed without --yes."},
+	{exitConfig, "config.toml, a template or a configuration profile is invalid."},
+	{exitUsage, "Invalid command line."},
+	{exitCrash, "maziq crashed; a crash report was written (see the message)."},
+}
+
+// printExitCodes lists the exit codes for `maziq help exit-codes`.
+func printExitCodes(w io.Writer) {
+	fmt.Fprintln(w, "Exit codes:")
+	for _, c := range exitCodes {
+		fmt.Fprintf(w, "  %3d  %s\n", c.code, c.text)
+	}
+}
+
+// errAborted is returned when the user declines a confirmation, or when one
+// is needed in non-interactive mode without --yes.
+var errAborted = errs.New(errs.CodeAborted, "aborted")
+
+// usageError reports a malformed command line.
+type usageError struct {
+	usage string
+}
+
+func (e *usageError) Error() string {
+	return "usage: " + e.usage
+}
+
+func (e *usageError) ErrorCode() errs.Code {
+	return errs.CodeUsage
+}
+
+func usagef(format string, args ...any) error {
+	return &usageError{usage: fmt.Sprintf(format, args...)}
+}
+
+// exitByCode maps every error code to its exit code.
+var exitByCode = map[errs.Code]int{
+	errs.CodeUsage:      exitUsage,
+	errs.CodeAborted:    exitAborted,
+	errs.CodePlan:       exitPlan,
+	errs.CodeUnknown:    exitUnknown,
+	errs.CodePermission: exitPermission,
+	errs.CodePartial:    exitPartial,
+	errs.CodeStep:       exitStep,
+	errs.CodeDrift:      exitDrift,
+	errs.CodePreflight:  exitPreflight,
+	errs.CodeConfig:     exitConfig,
+}
+
+// exitCode classifies err into one of the exit codes above by its error
+// code.
+func exitCode(err error) int {
+	if err == nil || errors.Is(err, flag.ErrHelp) {
+		return exitOK
+	}
+	if code, ok := exitByCode[errs.CodeOf(err)]; ok {
+		return code
+	}
+	return exitFailure
+}
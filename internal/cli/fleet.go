@@ -0,0 +1,111 @@
+package cli
+
+import (
+	"context"
+	"flag"
+	"fmt"
+	"os"
+	"strings"
+	"sync"
+	"text/tabwriter"
+	"time"
+
+	"github.com/hmziqrs/maziq/internal/fleet"
+	"github.com/hmziqrs/maziq/internal/tui"
+)
+
+// runFleet runs a template on every host of an inventory over ssh. Each
+// host needs maziq installed and key-based ssh access; the remote runs are
+// unattended, so sudo there needs cached credentials or an askpass helper.
+func runFleet(s session, args []string) error {
+	fs := flag.NewFlagSet("fleet", flag.ContinueOnError)
+	inventory := fs.String("inventory", "", "file listing one host per line")
+	name := fs.String("template", s.cfg.Template, "template to apply on every host")
+	update := fs.Bool("update", false, "run onboard update instead of onboard fresh")
+	parallel := fs.Int("parallel", 4, "how many hosts run at once")
+	dryRun := fs.Bool("dry-run", s.opts.DryRun, "only print each host's plan")
+	yes := fs.Bool("yes", false, "skip the confirmation prompt")
+	if err := fs.Parse(args); err != nil {
+		return err
+	}
+	if *inventory == "" || fs.NArg() > 0 {
+		return usagef("maziq fleet --inventory FILE [--template NAME] [--update] [--parallel N] [--dry-run] [--yes]")
+	}
+	if *parallel < 1 {
+		return fmt.Errorf("--parallel must be at least 1")
+	}
+	hosts, err := fleet.LoadInventory(*inventory)
+	if err != nil {
+		return err
+	}
+
+	sub := "fresh"
+	if *update {
+		sub = "update"
+	}
+	remote := []string{"onboard", sub, "--template", *name}
+	if *dryRun {
+		remote = append(remote, "--dry-run")
+	}
+	title := fmt.Sprintf("Fleet: onboard %s --template %s on %d hosts", sub, *name, len(hosts))
+	if !*dryRun && !*yes {
+		fmt.Println(title)
+		for _, h := range hosts {
+			fmt.Printf("  %-20s %s\n", h.Name, h.Target)
+		}
+		if !confirm(s.opts, "Run on all of these hosts?") {
+			return errAborted
+		}
+	}
+
+	run := func(ctx context.Context, emit func(fleet.Event)) []fleet.Result {
+		return fleet.Run(ctx, hosts, remote, *parallel, emit)
+	}
+	var results []fleet.Result
+	if s.opts.NonInteractive {
+		var mu sync.Mutex
+		results = run(context.Background(), func(ev fleet.Event) {
+			mu.Lock()
+			defer mu.Unlock()
+			fmt.Printf("%s %-20s %s\n", ev.Time.UTC().Format(time.RFC3339), ev.Host, strings.TrimSpace(ev.Step+" "+ev.Message))
+		})
+	} else if results, err = tui.RunFleet(title, hosts, run); err != nil {
+		return err
+	}
+	if err := printMatrix(results); err != nil {
+		return err
+	}
+	return fleet.Err(results)
+}
+
+// printMatrix prints each host's outcome and its result per step.
+func printMatrix(results []fleet.Result) error {
+	ids := fleet.Matrix(results)
+	fmt.Printf("\n%d of %d hosts failed\n", fleet.Failed(results), len(results))
+	w := tabwriter.NewWriter(os.Stdout, 0, 0, 2, ' ', 0)
+	fmt.Fprint(w, "HOST\tRESULT\tTIME")
+	for _, id := range ids {
+		fmt.Fprint(w, "\t"+id)
+	}
+	fmt.Fprintln(w)
+	for _, r := range results {
+		outcome := "ok"
+		if r.Err != nil {
+			outcome = "failed"
+		}
+		fmt.Fprintf(w, "%s\t%s\t%s", r.Host, outcome, r.Duration.Round(time.Second))
+		for _, id := range ids {
+			fmt.Fprint(w, "\t"+r.Cell(id))
+		}
+		fmt.Fprintln(w)
+	}
+	if err := w.Flush(); err != nil {
+		return err
+	}
+	for _, r := range results {
+		if r.Err != nil {
+			fmt.Printf("  %s: %v\n", r.Host, r.Err)
+		}
+	}
+	return nil
+}
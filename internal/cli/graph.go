@@ -0,0 +1,116 @@
+package cli
+
+import (
+	"flag"
+	"fmt"
+	"io"
+	"os"
+	"strings"
+
+	"github.com/hmziqrs/maziq/internal/catalog"
+	"github.com/hmziqrs/maziq/internal/manager"
+	"github.com/hmziqrs/maziq/internal/options"
+	"github.com/hmziqrs/maziq/internal/templates"
+)
+
+// graphNode is one entry in a dependency graph; order is its 1-based
+// position in the resolved plan, or 0 for whole-catalog graphs.
+type graphNode struct {
+	entry catalog.Entry
+	order int
+}
+
+// graphEdge points from a dependency to the entry that needs it, so the
+// arrows follow installation order.
+type graphEdge struct {
+	from, to string
+}
+
+func runSoftwareGraph(opts options.Run, args []string) error {
+	fs := flag.NewFlagSet("software graph", flag.ContinueOnError)
+	format := fs.String("format", "dot", "output format: dot or mermaid")
+	name := fs.String("template", "", "only graph this template's resolved plan")
+	if err := fs.Parse(args); err != nil {
+		return err
+	}
+
+	var nodes []graphNode
+	substitutes := map[string]string{}
+	if *name == "" {
+		for _, entry := range catalog.All() {
+			nodes = append(nodes, graphNode{entry: entry})
+		}
+	} else {
+		t, err := templates.Load(*name)
+		if err != nil {
+			return err
+		}
+		plan, err := manager.ResolveTemplate(t, manager.ActionInstall, opts)
+		if err != nil {
+			return err
+		}
+		for i, step := range plan.Steps {
+			nodes = append(nodes, graphNode{entry: step.Entry, order: i + 1})
+		}
+		substitutes = catalog.RuntimeSubstitutes(t.Runtimes)
+	}
+
+	present := map[string]bool{}
+	for _, n := range nodes {
+		present[n.entry.ID] = true
+	}
+	var edges []graphEdge
+	for _, n := range nodes {
+		for _, dep := range n.entry.Dependencies {
+			if replacement, ok := substitutes[dep]; ok {
+				dep = replacement
+			}
+			if present[dep] {
+				edges = append(edges, graphEdge{from: dep, to: n.entry.ID})
+			}
+		}
+	}
+
+	switch *format {
+	case "dot":
+		writeDot(os.Stdout, nodes, edges)
+	case "mermaid":
+		writeMermaid(os.Stdout, nodes, edges)
+	default:
+		return fmt.Errorf("unknown graph format %q (expected dot or mermaid)", *format)
+	}
+	return nil
+}
+
+func (n graphNode) label() string {
+	if n.order > 0 {
+		return fmt.Sprintf("%d. %s", n.order, n.entry.Name)
+	}
+	return n.entry.Name
+}
+
+func writeDot(w io.Writer, nodes []graphNode, edges []graphEdge) {
+	quote := func(s string) string {
+		return `"` + strings.ReplaceAll(s, `"`, `\"`) + `"`
+	}
+	fmt.Fprintln(w, "digraph maziq {")
+	fmt.Fprintln(w, "  rankdir=LR;")
+	fmt.Fprintln(w, "  node [shape=box, style=rounded];")
+	for _, n := range nodes {
+		fmt.Fprintf(w, "  %s [label=%s];\n", quote(n.entry.ID), quote(n.label()))
+	}
+	for _, e := range edges {
+		fmt.Fprintf(w, "  %s -> %s;\n", quote(e.from), quote(e.to))
+	}
+	fmt.Fprintln(w, "}")
+}
+
+func writeMermaid(w io.Writer, nodes []graphNode, edges []graphEdge) {
+	fmt.Fprintln(w, "graph LR")
+	for _, n := range nodes {
+		fmt.Fprintf(w, "  %s[\"%s\"]\n", n.entry.ID, strings.ReplaceAll(n.label(), `"`, "#quot;"))
+	}
+	for _, e := range edges {
+		fmt.Fprintf(w, "  %s --> %s\n", e.from, e.to)
+	}
+}
@@ -0,0 +1,401 @@
+package cli
+
+import (
+	"context"
+	"errors"
+	"flag"
+	"fmt"
+	"os"
+	"os/exec"
+	"path/filepath"
+	"strings"
+	"time"
+
+	"github.com/hmziqrs/maziq/internal/clipboard"
+	"github.com/hmziqrs/maziq/internal/guided"
+	"github.com/hmziqrs/maziq/internal/manager"
+	"github.com/hmziqrs/maziq/internal/options"
+	"github.com/hmziqrs/maziq/internal/paths"
+	"github.com/hmziqrs/maziq/internal/prompt"
+	"github.com/hmziqrs/maziq/internal/templates"
+)
+
+// errGuidedPaused stops the guided mode when the user asks to continue
+// another day.
+var errGuidedPaused = errors.New("paused")
+
+// guidedStep is one stage of `onboard guided`.
+type guidedStep struct {
+	id    string
+	title string
+	// satisfied reports that the machine already has what the step sets
+	// up, so resuming never redoes it; nil when only progress can tell.
+	satisfied func() bool
+	run       func() error
+	// preview describes what run would do, for dry runs.
+	preview   func() error
+}
+
+// runOnboardGuided walks a new engineer through the org template, git,
+// ssh, credentials and the template's manual checklist, saving progress
+// after every step so it can be resumed. A dry run only lists the steps
+// with what each would do; it asks, runs and saves nothing.
+func runOnboardGuided(s session, args []string) error {
+	fs := flag.NewFlagSet("onboard guided", flag.ContinueOnError)
+	name := fs.String("template", "", "template to install (default: the one the saved progress uses, else the configured one)")
+	restart := fs.Bool("restart", false, "forget the saved progress and start over")
+	if err := fs.Parse(args); err != nil {
+		return err
+	}
+	if fs.NArg() > 0 {
+		return usagef("maziq onboard guided [--template NAME] [--restart]")
+	}
+	if s.opts.NonInteractive && !s.opts.DryRun {
+		return errors.New("onboard guided asks questions along the way; run it in a terminal")
+	}
+	if *restart && !s.opts.DryRun {
+		if err := guided.Reset(); err != nil {
+			return err
+		}
+	}
+	progress, err := guided.Load()
+	if err != nil {
+		return err
+	}
+	if *restart {
+		progress.Done, progress.Checklist = map[string]time.Time{}, map[string]time.Time{}
+	}
+	switch {
+	case *name != "":
+		progress.Template = *name
+	case progress.Template == "":
+		progress.Template = s.cfg.Template
+	}
+	t, err := templates.Load(progress.Template)
+	if err != nil {
+		return err
+	}
+
+	steps := []guidedStep{
+		{id: "template", title: fmt.Sprintf("Install the %s template", t.Name),
+			run: func() error { return guidedTemplate(s, t) }, preview: func() error { return previewTemplate(s, t) }},
+		{id: "git", title: "Set your git name and email", satisfied: gitIdentitySet,
+			run: func() error { return guidedGit(s.opts) }, preview: previewGit},
+		{id: "ssh", title: "Create an SSH key", satisfied: sshKeyExists,
+			run: func() error { return guidedSSH(s.opts) }, preview: previewSSH},
+		{id: "credentials", title: "Set up git and GitHub credentials", satisfied: credentialsSet,
+			run: guidedCredentials, preview: previewCredentials},
+		{id: "checklist", title: "Work through the manual checklist",
+			run: func() error { return guidedChecklist(s.opts, t.Checklist, &progress) }, preview: func() error { return previewChecklist(t.Checklist, progress) }},
+	}
+	if s.opts.DryRun {
+		return previewGuided(t, steps, progress)
+	}
+	fmt.Printf("Guided onboarding with the %s template (started %s)\n", t.Name, progress.Started.Format("2006-01-02"))
+	fmt.Println("Answer n to skip a step for now, q to stop; progress is saved after every step.")
+	var pending []string
+	for i, step := range steps {
+		if _, ok := progress.Done[step.id]; ok || step.satisfied != nil && step.satisfied() {
+			progress.Done[step.id] = doneAt(progress.Done[step.id])
+			fmt.Printf("\n✓ %d/%d %s\n", i+1, len(steps), step.title)
+			continue
+		}
+		fmt.Printf("\n→ %d/%d %s\n", i+1, len(steps), step.title)
+		switch ask(s.opts, "Do it now? [Y/n/q]", "y") {
+		case "n", "no":
+			pending = append(pending, step.title)
+			continue
+		case "q", "quit":
+			return pauseGuided(progress)
+		}
+		err := step.run()
+		if saveErr := guided.Save(progress); saveErr != nil {
+			return saveErr
+		}
+		switch {
+		case errors.Is(err, errGuidedPaused):
+			return pauseGuided(progress)
+		case err != nil:
+			fmt.Printf("✗ %s: %v\n", step.title, err)
+			pending = append(pending, step.title)
+			continue
+		}
+		progress.Done[step.id] = time.Now()
+		if err := guided.Save(progress); err != nil {
+			return err
+		}
+	}
+	if err := guided.Save(progress); err != nil {
+		return err
+	}
+	if len(pending) > 0 {
+		fmt.Println("\nStill to do:")
+		for _, title := range pending {
+			fmt.Println("  " + title)
+		}
+		fmt.Println("Run `maziq onboard guided` again to pick them up.")
+		return nil
+	}
+	fmt.Println("\nAll done. Welcome aboard!")
+	return nil
+}
+
+// previewGuided prints every step of the guided mode with what it would
+// do, without asking, running or saving anything.
+func previewGuided(t templates.Template, steps []guidedStep, progress guided.Progress) error {
+	fmt.Printf("[dry-run] Guided onboarding with the %s template; nothing is run or saved.\n", t.Name)
+	for i, step := range steps {
+		if _, ok := progress.Done[step.id]; ok || step.satisfied != nil && step.satisfied() {
+			fmt.Printf("\n✓ %d/%d %s\n", i+1, len(steps), step.title)
+			continue
+		}
+		fmt.Printf("\n→ %d/%d %s\n", i+1, len(steps), step.title)
+		if err := step.preview(); err != nil {
+			fmt.Printf("✗ %s: %v\n", step.title, err)
+		}
+	}
+	return nil
+}
+
+func pauseGuided(progress guided.Progress) error {
+	if err := guided.Save(progress); err != nil {
+		return err
+	}
+	fmt.Println("Progress saved; run `maziq onboard guided` to resume.")
+	return nil
+}
+
+// doneAt keeps a recorded time, or stamps steps found already satisfied.
+func doneAt(t time.Time) time.Time {
+	if t.IsZero() {
+		return time.Now()
+	}
+	return t
+}
+
+// ask prints question and returns the lowercased answer, or def for an
+// empty one. End of input answers q.
+func ask(opts options.Run, question, def string) string {
+	answer, err := prompt.Text(opts, question, "")
+	switch {
+	case err != nil:
+		return "q"
+	case answer == "":
+		return def
+	}
+	return strings.ToLower(answer)
+}
+
+func guidedTemplate(s session, t templates.Template) error {
+	opts := manager.TemplateOptions(t)
+	opts.Run = s.opts
+	plan, err := manager.ResolveWith(t.Software, manager.ActionInstall, opts)
+	if err != nil {
+		return err
+	}
+	plan.Template = t.Name
+	fmt.Printf("%d steps; already installed software is skipped.\n", len(plan.Steps))
+	return executePlan(s, plan, false)
+}
+
+// previewTemplate prints the plan guidedTemplate would run.
+func previewTemplate(s session, t templates.Template) error {
+	opts := manager.TemplateOptions(t)
+	opts.Run = s.opts
+	plan, err := manager.ResolveWith(t.Software, manager.ActionInstall, opts)
+	if err != nil {
+		return err
+	}
+	plan.Template = t.Name
+	printPlan(s.opts, plan)
+	fmt.Println("\nPreflight:")
+	printChecks(manager.Preflight(context.Background(), plan, s.opts))
+	return nil
+}
+
+// gitConfig returns a global git setting, or "" when unset.
+func gitConfig(key string) string {
+	out, err := exec.Command("git", "config", "--global", key).Output()
+	if err != nil {
+		return ""
+	}
+	return strings.TrimSpace(string(out))
+}
+
+func gitIdentitySet() bool {
+	return gitConfig("user.name") != "" && gitConfig("user.email") != ""
+}
+
+func guidedGit(opts options.Run) error {
+	for _, setting := range []struct{ key, question string }{
+		{"user.name", "Your full name:"},
+		{"user.email", "Your work email:"},
+	} {
+		value := gitConfig(setting.key)
+		if value != "" {
+			fmt.Printf("%s is %s\n", setting.key, value)
+			continue
+		}
+		answer, err := prompt.Ask(opts, prompt.Prompt{Kind: prompt.KindText, Label: setting.question, Validate: required})
+		if err != nil {
+			return err
+		}
+		value = answer.Text
+		if err := exec.Command("git", "config", "--global", setting.key, value).Run(); err != nil {
+			return err
+		}
+	}
+	return nil
+}
+
+func previewGit() error {
+	for _, key := range []string{"user.name", "user.email"} {
+		if value := gitConfig(key); value != "" {
+			fmt.Printf("%s is %s\n", key, value)
+			continue
+		}
+		fmt.Printf("[dry-run] would ask for %s and run git config --global %s\n", key, key)
+	}
+	return nil
+}
+
+// required rejects an empty answer.
+func required(value string) error {
+	if value == "" {
+		return errors.New("an answer is required")
+	}
+	return nil
+}
+
+// sshKeyFile is the key `onboard guided` creates.
+func sshKeyFile() string {
+	return filepath.Join(paths.Home(), ".ssh", "id_ed25519")
+}
+
+func sshKeyExists() bool {
+	_, err := os.Stat(sshKeyFile() + ".pub")
+	return err == nil
+}
+
+// guidedSSH creates an ed25519 key, asking for its passphrase in the
+// terminal, stores the passphrase in the keychain and copies the public
+// key for pasting into GitHub.
+func guidedSSH(opts options.Run) error {
+	key := sshKeyFile()
+	if err := os.MkdirAll(filepath.Dir(key), 0o700); err != nil {
+		return err
+	}
+	if err := interactive("ssh-keygen", "-t", "ed25519", "-C", gitConfig("user.email"), "-f", key); err != nil {
+		return err
+	}
+	if err := interactive("ssh-add", "--apple-use-keychain", key); err != nil {
+		fmt.Printf("Could not add the key to the agent (%v); ssh will ask for the passphrase instead.\n", err)
+	}
+	pub, err := os.ReadFile(key + ".pub")
+	if err != nil {
+		return err
+	}
+	if clipboard.Copy(string(pub)) == nil {
+		fmt.Println("The public key is on your clipboard.")
+	}
+	fmt.Printf("%s\nAdd it at https://github.com/settings/ssh/new\n", strings.TrimSpace(string(pub)))
+	if !confirm(opts, "Added it?") {
+		return errGuidedPaused
+	}
+	return nil
+}
+
+func previewSSH() error {
+	fmt.Printf("[dry-run] would create %s with ssh-keygen -t ed25519, add it to the agent and copy the public key\n", sshKeyFile())
+	return nil
+}
+
+// interactive runs a command attached to the terminal.
+func interactive(name string, args ...string) error {
+	c := exec.CommandContext(context.Background(), name, args...)
+	c.Stdin, c.Stdout, c.Stderr = os.Stdin, os.Stdout, os.Stderr
+	return c.Run()
+}
+
+func credentialsSet() bool {
+	if gitConfig("credential.helper") == "" {
+		return false
+	}
+	if _, err := exec.LookPath("gh"); err != nil {
+		return true
+	}
+	return exec.Command("gh", "auth", "status").Run() == nil
+}
+
+// guidedCredentials keeps git's HTTPS credentials in the keychain and
+// signs the GitHub CLI in when it is installed.
+func guidedCredentials() error {
+	if gitConfig("credential.helper") == "" {
+		if err := exec.Command("git", "config", "--global", "credential.helper", "osxkeychain").Run(); err != nil {
+			return err
+		}
+		fmt.Println("git now keeps HTTPS credentials in the keychain.")
+	}
+	if _, err := exec.LookPath("gh"); err != nil {
+		fmt.Println("The GitHub CLI is not installed; skipping gh auth login.")
+		return nil
+	}
+	if exec.Command("gh", "auth", "status").Run() == nil {
+		return nil
+	}
+	return interactive("gh", "auth", "login")
+}
+
+func previewCredentials() error {
+	if gitConfig("credential.helper") == "" {
+		fmt.Println("[dry-run] would run git config --global credential.helper osxkeychain")
+	}
+	if _, err := exec.LookPath("gh"); err == nil && exec.Command("gh", "auth", "status").Run() != nil {
+		fmt.Println("[dry-run] would run gh auth login")
+	}
+	return nil
+}
+
+// guidedChecklist asks about every manual item not checked off yet. The
+// step is done once all of them are.
+func guidedChecklist(opts options.Run, items []string, progress *guided.Progress) error {
+	if len(items) == 0 {
+		fmt.Println("The template has no manual checklist.")
+		return nil
+	}
+	open := 0
+	for _, item := range items {
+		if _, ok := progress.Checklist[item]; ok {
+			fmt.Printf("  ✓ %s\n", item)
+			continue
+		}
+		switch ask(opts, fmt.Sprintf("  □ %s — done? [y/N/q]", item), "n") {
+		case "y", "yes":
+			progress.Checklist[item] = time.Now()
+		case "q", "quit":
+			return errGuidedPaused
+		default:
+			open++
+		}
+	}
+	if open > 0 {
+		return fmt.Errorf("%d of %d items still open", open, len(items))
+	}
+	return nil
+}
+
+// previewChecklist lists the manual items not checked off yet.
+func previewChecklist(items []string, progress guided.Progress) error {
+	if len(items) == 0 {
+		fmt.Println("The template has no manual checklist.")
+		return nil
+	}
+	for _, item := range items {
+		mark := "□"
+		if _, ok := progress.Checklist[item]; ok {
+			mark = "✓"
+		}
+		fmt.Printf("  %s %s\n", mark, item)
+	}
+	return nil
+}
@@ -0,0 +1,33 @@
+package cli
+
+import (
+	"fmt"
+
+	"github.com/hmziqrs/maziq/internal/history"
+	"github.com/hmziqrs/maziq/internal/paths"
+)
+
+func runHistory(_ session, args []string) error {
+	if len(args) != 1 || args[0] != "verify" {
+		return usagef("maziq history verify")
+	}
+	v, err := history.Verify()
+	if err != nil {
+		return err
+	}
+	if v.Legacy > 0 {
+		fmt.Printf("%d older record(s) predate the hash chain and cannot be checked.\n", v.Legacy)
+	}
+	if len(v.Breaks) > 0 {
+		fmt.Printf("%s was modified:\n", paths.HistoryFile())
+		for _, b := range v.Breaks {
+			fmt.Printf("  line %d: %s\n", b.Line, b.Message)
+		}
+		return fmt.Errorf("the history failed verification in %d place(s)", len(v.Breaks))
+	}
+	fmt.Printf("✓ %d chained record(s) intact\n", v.Chained)
+	if v.Head != "" {
+		fmt.Printf("head %s\n", v.Head)
+	}
+	return nil
+}
@@ -0,0 +1,166 @@
+package cli
+
+import (
+	"context"
+	"encoding/json"
+	"os"
+
+	"github.com/hmziqrs/maziq/internal/catalog"
+	"github.com/hmziqrs/maziq/internal/manager"
+)
+
+// entryInfo is the machine-readable form of a catalog entry printed by
+// `software info --json`. Field names are part of the CLI's contract.
+type entryInfo struct {
+	ID           string                  `json:"id"`
+	Name         string                  `json:"name"`
+	Category     string                  `json:"category"`
+	Tags         []string                `json:"tags"`
+	Kind         string                  `json:"kind"`
+	Summary      string                  `json:"summary"`
+	Homepage     string                  `json:"homepage,omitempty"`
+	Docs         string                  `json:"docs,omitempty"`
+	License      string                  `json:"license,omitempty"`
+	Dependencies []string                `json:"dependencies"`
+	Recommended  []string                `json:"recommended"`
+	Conflicts    []string                `json:"conflicts"`
+	Adapter      string                  `json:"adapter"`
+	Probe        probeInfo               `json:"probe"`
+	Recipes      map[string][]recipeInfo `json:"recipes"`
+	PostInstall  []hookInfo              `json:"post_install"`
+	Test         string                  `json:"test,omitempty"`
+	Aliases      []string                `json:"aliases"`
+	Platform     platformInfo            `json:"platform"`
+	Size         sizeInfo                `json:"size"`
+	Status       statusInfo              `json:"status"`
+}
+
+type probeInfo struct {
+	Command     string `json:"command,omitempty"`
+	AppPath     string `json:"app_path,omitempty"`
+	MasAppID    string `json:"mas_app_id,omitempty"`
+	PipxPackage string `json:"pipx_package,omitempty"`
+	Depth       string `json:"depth"`
+}
+
+type recipeInfo struct {
+	Source      string            `json:"source"`
+	Command     string            `json:"command"`
+	AppID       string            `json:"app_id,omitempty"`
+	Package     string            `json:"package,omitempty"`
+	Env         map[string]string `json:"env,omitempty"`
+	Shell       string            `json:"shell"`
+	Dir         string            `json:"dir,omitempty"`
+	TempDir     bool              `json:"temp_dir,omitempty"`
+	Destructive bool              `json:"destructive"`
+	Sudo        bool              `json:"sudo"`
+}
+
+type hookInfo struct {
+	Description string `json:"description"`
+	Command     string `json:"command"`
+}
+
+type platformInfo struct {
+	OS       string `json:"os"`
+	MinMacOS string `json:"min_macos,omitempty"`
+	Rosetta  bool   `json:"rosetta"`
+}
+
+type sizeInfo struct {
+	Download  int64 `json:"download_bytes,omitempty"`
+	Installed int64 `json:"installed_bytes,omitempty"`
+}
+
+type statusInfo struct {
+	State   string `json:"state"`
+	Version string `json:"version,omitempty"`
+	Latest  string `json:"latest,omitempty"`
+	Error   string `json:"error,omitempty"`
+	Warning string `json:"warning,omitempty"`
+}
+
+// newEntryInfo collects everything known about entry, with its probed
+// status.
+func newEntryInfo(entry catalog.Entry, status manager.Status) entryInfo {
+	info := entryInfo{
+		ID:           entry.ID,
+		Name:         entry.Name,
+		Category:     string(entry.Category),
+		Tags:         nonNil(entry.Tags),
+		Kind:         entry.Kind.String(),
+		Summary:      entry.Summary,
+		Homepage:     entry.Homepage,
+		Docs:         entry.Docs,
+		License:      entry.License,
+		Dependencies: nonNil(entry.Dependencies),
+		Recommended:  nonNil(entry.Recommended),
+		Conflicts:    nonNil(entry.Conflicts),
+		Adapter:      orNone(entry.Adapter),
+		Probe: probeInfo{
+			Command:     entry.Probe.Command,
+			AppPath:     entry.Probe.AppPath,
+			MasAppID:    entry.Probe.MasAppID,
+			PipxPackage: entry.Probe.PipxPackage,
+			Depth:       entry.Probe.Depth.String(),
+		},
+		Recipes:     map[string][]recipeInfo{},
+		PostInstall: []hookInfo{},
+		Test:        entry.Test,
+		Aliases:     []string{},
+		Platform: platformInfo{
+			OS:       "darwin",
+			MinMacOS: entry.Requirements.MinMacOS,
+			Rosetta:  entry.Requirements.Rosetta,
+		},
+		Size: sizeInfo{Download: entry.Size.Download, Installed: entry.Size.Installed},
+		Status: statusInfo{
+			State:   status.State.String(),
+			Version: status.Version,
+			Latest:  status.Latest,
+			Error:   status.Error,
+			Warning: status.Warning,
+		},
+	}
+	for _, action := range []manager.Action{manager.ActionInstall, manager.ActionUpdate, manager.ActionUninstall} {
+		recipes := []recipeInfo{}
+		for _, recipe := range manager.Recipes(entry, action) {
+			recipes = append(recipes, recipeInfo{
+				Source:      string(recipe.Source),
+				Command:     recipe.Command,
+				AppID:       recipe.AppID,
+				Package:     recipe.Package,
+				Env:         recipe.Env,
+				Shell:       recipe.Mode.String(),
+				Dir:         recipe.Dir,
+				TempDir:     recipe.TempDir,
+				Destructive: manager.Destructive(action, recipe),
+				Sudo:        manager.NeedsSudo(recipe.Command),
+			})
+		}
+		info.Recipes[string(action)] = recipes
+	}
+	for _, hook := range entry.PostInstall {
+		info.PostInstall = append(info.PostInstall, hookInfo{Description: hook.Description, Command: hook.Command})
+	}
+	for _, alias := range entry.Aliases {
+		info.Aliases = append(info.Aliases, alias.Description)
+	}
+	return info
+}
+
+// printEntryJSON writes entry's info as indented JSON to stdout.
+func printEntryJSON(entry catalog.Entry) error {
+	status := manager.NewProber(0).Probe(context.Background(), entry)
+	enc := json.NewEncoder(os.Stdout)
+	enc.SetIndent("", "  ")
+	return enc.Encode(newEntryInfo(entry, status))
+}
+
+// nonNil keeps empty lists as [] rather than null in JSON output.
+func nonNil[T any](s []T) []T {
+	if s == nil {
+		return []T{}
+	}
+	return s
+}
@@ -0,0 +1,410 @@
+package cli
+
+import (
+	"context"
+	"encoding/json"
+	"flag"
+	"fmt"
+	"os"
+	"strings"
+	"time"
+
+	"github.com/hmziqrs/maziq/internal/config"
+	"github.com/hmziqrs/maziq/internal/manager"
+	"github.com/hmziqrs/maziq/internal/notify"
+	"github.com/hmziqrs/maziq/internal/options"
+	"github.com/hmziqrs/maziq/internal/paths"
+	"github.com/hmziqrs/maziq/internal/templates"
+)
+
+func runOnboard(s session, args []string) error {
+	if len(args) == 0 {
+		return usagef("maziq onboard <fresh|update|plan|apply|guided> [--template NAME] [--dry-run] [--yes] [--with-recommended]")
+	}
+	var action manager.Action
+	switch args[0] {
+	case "plan":
+		return runOnboardPlan(s, args[1:])
+	case "apply":
+		return runOnboardApply(s, args[1:])
+	case "guided":
+		return runOnboardGuided(s, args[1:])
+	case "fresh":
+		action = manager.ActionInstall
+	case "update":
+		action = manager.ActionUpdate
+	default:
+		return fmt.Errorf("unknown onboard subcommand %q", args[0])
+	}
+
+	fs := flag.NewFlagSet("onboard "+args[0], flag.ContinueOnError)
+	name := fs.String("template", s.cfg.Template, "template to apply")
+	dryRun := fs.Bool("dry-run", s.opts.DryRun, "print the plan without running it")
+	yes := fs.Bool("yes", false, "skip the confirmation prompt for destructive commands")
+	withRecommended := fs.Bool("with-recommended", false, "also install recommended entries")
+	locked := fs.Bool("locked", false, "install the versions in the lockfile where backends allow")
+	lockfile := fs.String("lockfile", "", "lockfile to write after a successful run, or read with --locked (default: one per template in the state directory)")
+	if err := fs.Parse(args[1:]); err != nil {
+		return err
+	}
+	if action != manager.ActionInstall && (*locked || *lockfile != "") {
+		return usagef("--locked and --lockfile only apply to onboard fresh")
+	}
+	s.opts.DryRun = *dryRun
+
+	t, err := templates.Load(*name)
+	if err != nil {
+		return err
+	}
+	opts := manager.TemplateOptions(t)
+	opts.Run = s.opts
+	opts.WithRecommended = *withRecommended
+	plan, err := manager.ResolveWith(t.Software, action, opts)
+	if err != nil {
+		return err
+	}
+	plan.Template = t.Name
+	if *lockfile == "" {
+		*lockfile = paths.LockFile(t.Name)
+	}
+	if *locked {
+		lf, err := manager.ReadLockfile(*lockfile)
+		if err != nil {
+			return err
+		}
+		var notes []string
+		if plan, notes, err = plan.Lock(lf, s.opts.Backend); err != nil {
+			return err
+		}
+		fmt.Printf("Installing the versions locked in %s\n", *lockfile)
+		for _, note := range notes {
+			fmt.Println("  " + note)
+		}
+	}
+
+	if *dryRun {
+		fmt.Printf("Plan for template %q (%d steps):\n", t.Name, len(plan.Steps))
+		printPlan(s.opts, plan)
+		fmt.Println("\nPreflight:")
+		printChecks(manager.Preflight(context.Background(), plan, s.opts))
+		return nil
+	}
+	if err := executePlan(s, plan, *yes); err != nil {
+		return err
+	}
+	if action == manager.ActionInstall {
+		lf := manager.LockPlan(context.Background(), plan, s.opts)
+		if err := manager.WriteLockfile(*lockfile, lf); err != nil {
+			return fmt.Errorf("the run succeeded but the lockfile could not be written: %w", err)
+		}
+		fmt.Printf("Locked %d versions in %s\n", len(lf.Entries), *lockfile)
+	}
+	return nil
+}
+
+// runOnboardPlan resolves a template the way `onboard fresh` would and
+// exports the result, with the exact command of every step, for review.
+func runOnboardPlan(s session, args []string) error {
+	fs := flag.NewFlagSet("onboard plan", flag.ContinueOnError)
+	name := fs.String("template", s.cfg.Template, "template to plan")
+	actionName := fs.String("action", "install", "install or update")
+	export := fs.String("export", "", "write the plan to this file instead of stdout")
+	withRecommended := fs.Bool("with-recommended", false, "also plan recommended entries")
+	if err := fs.Parse(args); err != nil {
+		return err
+	}
+	if fs.NArg() > 0 {
+		return usagef("maziq onboard plan [--template NAME] [--action install|update] [--export FILE] [--with-recommended]")
+	}
+	action := manager.Action(*actionName)
+	if action != manager.ActionInstall && action != manager.ActionUpdate {
+		return fmt.Errorf("--action must be install or update, not %q", *actionName)
+	}
+
+	t, err := templates.Load(*name)
+	if err != nil {
+		return err
+	}
+	opts := manager.TemplateOptions(t)
+	opts.Run = s.opts
+	opts.WithRecommended = *withRecommended
+	plan, err := manager.ResolveWith(t.Software, action, opts)
+	if err != nil {
+		return err
+	}
+	plan.Template = t.Name
+	pf, err := manager.ExportPlan(plan, s.opts.Backend)
+	if err != nil {
+		return err
+	}
+	if *export == "" {
+		enc := json.NewEncoder(os.Stdout)
+		enc.SetIndent("", "  ")
+		return enc.Encode(pf)
+	}
+	if err := manager.WritePlanFile(*export, pf); err != nil {
+		return err
+	}
+	fmt.Printf("Wrote %d steps for template %q to %s.\n", len(pf.Steps), t.Name, *export)
+	fmt.Printf("Run it unchanged with: maziq onboard apply %s\n", *export)
+	return nil
+}
+
+// runOnboardApply runs an exported plan exactly: each step runs only the
+// recipe recorded in the file, whatever the local catalog now says.
+func runOnboardApply(s session, args []string) error {
+	fs := flag.NewFlagSet("onboard apply", flag.ContinueOnError)
+	dryRun := fs.Bool("dry-run", s.opts.DryRun, "print the plan without running it")
+	yes := fs.Bool("yes", false, "run without asking for confirmation")
+	rest, err := parseInterspersed(fs, args)
+	if err != nil {
+		return err
+	}
+	if len(rest) != 1 {
+		return usagef("maziq onboard apply [--dry-run] [--yes] <plan.json>")
+	}
+	s.opts.DryRun = *dryRun
+
+	pf, err := manager.ReadPlanFile(rest[0])
+	if err != nil {
+		return err
+	}
+	if pf.Backend != "" {
+		s.opts.Backend = options.Backend(pf.Backend)
+	}
+	plan, err := manager.ImportPlan(pf)
+	if err != nil {
+		return fmt.Errorf("%s: %w", rest[0], err)
+	}
+
+	fmt.Printf("Plan from %s (template %q, exported %s, %d steps):\n",
+		rest[0], orNone(pf.Template), pf.Created.Local().Format("2006-01-02 15:04"), len(plan.Steps))
+	printPlan(s.opts, plan)
+	if drifted := pf.Drifted(); len(drifted) > 0 {
+		fmt.Println("\nThese commands differ from what the local catalog would run; the plan's are used:")
+		for _, d := range drifted {
+			fmt.Printf("  %s\n", d)
+		}
+	}
+	if *dryRun {
+		fmt.Println("\nPreflight:")
+		printChecks(manager.Preflight(context.Background(), plan, s.opts))
+		return nil
+	}
+	// The commands come from a file, so always ask unless told not to.
+	if !*yes {
+		prefetch := manager.StartPrefetch(plan, s.opts)
+		if !confirm(s.opts, fmt.Sprintf("Run these %d steps?", len(plan.Steps))) {
+			return errAborted
+		}
+		prefetch.Stop()
+	}
+	return executePlan(s, plan, *yes)
+}
+
+// printPlan lists plan's steps with what each would do with opts given the
+// current status, their commands and time estimates and, for installs, the
+// estimated disk usage.
+func printPlan(opts options.Run, plan manager.Plan) {
+	estimates := manager.Estimate(plan)
+	fmt.Fprintf(os.Stderr, "Checking %d entries…\n", len(plan.Steps))
+	previews := manager.PreviewPlan(context.Background(), plan, opts)
+	var total time.Duration
+	unknown, skipped := 0, 0
+	for i, step := range plan.Steps {
+		recipes := manager.PreferredRecipes(step.Entry, step.Action, opts.Backend)
+		command := "(no recipe)"
+		if len(recipes) > 0 {
+			command = recipes[0].Command
+		}
+		eta := estimates[step.Entry.ID]
+		etaText := manager.FormatETA(eta)
+		if previews[i].Skip {
+			skipped++
+			etaText = "-"
+		} else {
+			total += eta
+			if eta == 0 {
+				unknown++
+			}
+		}
+		fmt.Printf("%3d. %-9s %-22s %-8s %-42s %s\n", i+1, step.Action, step.Entry.ID, etaText, previews[i].Verdict, command)
+		for _, hook := range step.Hooks {
+			fmt.Printf("     %-9s %-22s %-8s %-42s %s\n", "hook", "", "", "", hook.Command)
+		}
+		for _, name := range step.Profiles {
+			fmt.Printf("     %-9s %-22s %-8s %-42s %s\n", "profile", "", "", "", name)
+		}
+	}
+	if skipped > 0 {
+		fmt.Printf("\n%d of %d steps would be skipped as already done.", skipped, len(plan.Steps))
+	}
+	fmt.Printf("\nEstimated time: %s", manager.FormatETA(total))
+	if unknown > 0 {
+		fmt.Printf(" (%d of %d steps have no recorded duration)", unknown, len(plan.Steps)-skipped)
+	}
+	fmt.Println()
+	if usage := manager.Usage(plan, manager.FetchSizes(context.Background(), plan)); usage != (manager.DiskUsage{}) {
+		fmt.Printf("Estimated disk usage: %s download, %s installed", manager.FormatSize(usage.Download), manager.FormatSize(usage.Installed))
+		if usage.Unknown > 0 {
+			fmt.Printf(" (%d of %d steps have no size information)", usage.Unknown, len(plan.Steps))
+		}
+		fmt.Println()
+	}
+	if len(plan.Recommended) > 0 {
+		fmt.Println("\nRecommended (add with --with-recommended):")
+		for _, rec := range plan.Recommended {
+			fmt.Printf("  %-22s for %s\n", rec.ID, strings.Join(rec.For, ", "))
+		}
+	}
+}
+
+// printChecks prints one line per preflight check.
+func printChecks(checks []manager.Check) {
+	for _, c := range checks {
+		fmt.Printf("  %s\n", c)
+	}
+}
+
+// executePlan runs the preflight checks, then confirms, authenticates and
+// runs plan with s's options, printing one line per progress event.
+func executePlan(s session, plan manager.Plan, yes bool) error {
+	checks := manager.Preflight(context.Background(), plan, s.opts)
+	if err := manager.PreflightErr(checks); err != nil {
+		fmt.Fprintln(os.Stderr, "Preflight checks failed:")
+		for _, c := range checks {
+			fmt.Fprintf(os.Stderr, "  %s\n", c)
+		}
+		return err
+	}
+	// Download the first steps while the user reads the prompts below.
+	var prefetch *manager.Prefetch
+	if !s.opts.NonInteractive {
+		prefetch = manager.StartPrefetch(plan, s.opts)
+	}
+	if !yes && (!confirmDestructive(s.opts, plan.Steps) || !confirmConflicts(s.opts, plan)) {
+		return errAborted
+	}
+	stop, err := withSudo(s.opts, plan)
+	prefetch.Stop()
+	if err != nil {
+		return err
+	}
+	defer stop()
+	export, closeExport, err := eventExport(s.opts.EventLog)
+	if err != nil {
+		return err
+	}
+	defer closeExport()
+	m := manager.New(s.opts)
+	m.SkipPreflight = true
+	started := time.Now()
+	var events []manager.TaskEvent
+	err = m.RunPlan(context.Background(), plan, func(ev manager.TaskEvent) {
+		events = append(events, ev)
+		export(ev)
+		if s.opts.NonInteractive {
+			// One timestamped line per event, for CI and MDM logs.
+			fmt.Printf("%s %s %s\n", ev.Time.UTC().Format(time.RFC3339), ev.SoftwareID, ev.Message)
+			return
+		}
+		if ev.Kind == manager.EventPlanComputed || ev.Kind == manager.EventRunFinished {
+			// The summary below covers the run as a whole.
+			return
+		}
+		fmt.Printf("%-20s %s\n", ev.SoftwareID, ev.Message)
+	})
+	if len(plan.Steps) > 1 {
+		printSummary(manager.Summarize(plan.IDs(), events))
+	}
+	if path := s.opts.JUnitReport; path != "" {
+		if reportErr := writeJUnit(path, plan, events); reportErr != nil {
+			fmt.Fprintf(os.Stderr, "could not write the JUnit report: %v\n", reportErr)
+		}
+	}
+	if s.opts.NonInteractive && !s.cfg.Webhook.IsZero() {
+		if postErr := postSummary(s.cfg.Webhook, plan, events, started, err); postErr != nil {
+			fmt.Fprintf(os.Stderr, "could not post the run summary: %v\n", postErr)
+		}
+	}
+	return err
+}
+
+// postSummary sends the outcome of a run of plan to hook. runErr is what
+// the run returned.
+func postSummary(hook config.Webhook, plan manager.Plan, events []manager.TaskEvent, started time.Time, runErr error) error {
+	outcomes := manager.Summarize(plan.IDs(), events)
+	counts := manager.Tally(outcomes)
+	host, _ := os.Hostname()
+	s := notify.Summary{
+		Action:    string(plan.Action),
+		Template:  plan.Template,
+		Host:      host,
+		Started:   started.UTC(),
+		Duration:  time.Since(started).Seconds(),
+		Succeeded: counts[manager.ResultSucceeded],
+		Skipped:   counts[manager.ResultSkipped],
+		Failed:    counts[manager.ResultFailed],
+		NotRun:    counts[manager.ResultNotRun],
+	}
+	for _, o := range outcomes {
+		if o.Result == manager.ResultFailed {
+			s.Failures = append(s.Failures, o.ID+": "+o.Reason)
+		}
+	}
+	if runErr != nil {
+		s.Error = runErr.Error()
+	}
+	return notify.Post(context.Background(), hook, s)
+}
+
+// writeJUnit saves the run of plan as a JUnit report at path.
+func writeJUnit(path string, plan manager.Plan, events []manager.TaskEvent) error {
+	name := "maziq " + string(plan.Action)
+	if plan.Template != "" {
+		name += " " + plan.Template
+	}
+	f, err := os.Create(path)
+	if err != nil {
+		return err
+	}
+	if err := manager.WriteJUnit(f, name, plan, events); err != nil {
+		f.Close()
+		return err
+	}
+	return f.Close()
+}
+
+// eventExport returns a function that appends events to path, the
+// --events file, or discards them when none was given.
+func eventExport(path string) (export func(manager.TaskEvent), closeFn func(), err error) {
+	if path == "" {
+		return func(manager.TaskEvent) {}, func() {}, nil
+	}
+	f, err := os.OpenFile(path, os.O_CREATE|os.O_APPEND|os.O_WRONLY, 0o644)
+	if err != nil {
+		return nil, nil, err
+	}
+	w := manager.NewEventWriter(f)
+	failed := false
+	return func(ev manager.TaskEvent) {
+		if err := w.Write(ev); err != nil && !failed {
+			failed = true
+			fmt.Fprintf(os.Stderr, "could not export events to %s: %v\n", path, err)
+		}
+	}, func() { f.Close() }, nil
+}
+
+// printSummary prints the outcome of every step of a finished run.
+func printSummary(outcomes []manager.Outcome) {
+	counts := manager.Tally(outcomes)
+	fmt.Printf("\nSummary: %d succeeded, %d skipped, %d failed", counts[manager.ResultSucceeded], counts[manager.ResultSkipped], counts[manager.ResultFailed])
+	if n := counts[manager.ResultNotRun]; n > 0 {
+		fmt.Printf(", %d not run", n)
+	}
+	fmt.Println()
+	fmt.Printf("  %-22s %-10s %s\n", "ID", "RESULT", "REASON")
+	for _, o := range outcomes {
+		fmt.Printf("  %-22s %-10s %s\n", o.ID, o.Result, o.Reason)
+	}
+}
@@ -0,0 +1,68 @@
+package cli
+
+import (
+	"flag"
+	"fmt"
+	"os"
+
+	"github.com/hmziqrs/maziq/internal/options"
+	"github.com/hmziqrs/maziq/internal/prompt"
+	"github.com/hmziqrs/maziq/internal/purge"
+)
+
+func runPurgeSelf(s session, args []string) error {
+	fs := flag.NewFlagSet("purge-self", flag.ContinueOnError)
+	dryRun := fs.Bool("dry-run", false, "list what would be removed without removing it")
+	yes := fs.Bool("yes", false, "skip the confirmation prompt")
+	if err := fs.Parse(args); err != nil {
+		return err
+	}
+
+	artifacts, err := purge.Discover()
+	if err != nil {
+		return err
+	}
+	if len(artifacts) == 0 {
+		fmt.Println("Nothing to purge: no maziq artifacts found.")
+		return nil
+	}
+
+	fmt.Println("maziq artifacts (installed software is left untouched):")
+	for _, a := range artifacts {
+		fmt.Println("  " + a.String())
+	}
+	if *dryRun {
+		fmt.Println("\nDry run: nothing was removed.")
+		return nil
+	}
+	if !*yes && !confirm(s.opts, "Remove all of the above?") {
+		return errAborted
+	}
+
+	var failed int
+	for _, a := range artifacts {
+		if err := purge.Remove(a, s.opts.PermanentDelete); err != nil {
+			failed++
+			fmt.Fprintf(os.Stderr, "  ✗ %s: %v\n", a.Path, err)
+			continue
+		}
+		fmt.Printf("  ✓ removed %s\n", a.Path)
+	}
+	if failed > 0 {
+		return fmt.Errorf("%d artifact(s) could not be removed", failed)
+	}
+	return nil
+}
+
+// confirm asks a yes/no question on stdin, defaulting to no. Global --yes
+// answers yes; in non-interactive mode the answer is no.
+func confirm(opts options.Run, question string) bool {
+	if opts.AssumeYes {
+		return true
+	}
+	if opts.NonInteractive {
+		fmt.Fprintf(os.Stderr, "%s Confirmation required; pass --yes to proceed.\n", question)
+		return false
+	}
+	return prompt.Confirm(opts, question)
+}
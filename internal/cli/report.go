@@ -0,0 +1,43 @@
+package cli
+
+import (
+	"context"
+	"flag"
+	"fmt"
+	"os/exec"
+
+	"github.com/hmziqrs/maziq/internal/catalog"
+	"github.com/hmziqrs/maziq/internal/manager"
+)
+
+// runReportIssue drafts a GitHub issue about id's latest failure from the
+// history and prints it with a link that opens it pre-filled.
+func runReportIssue(_ session, args []string) error {
+	fs := flag.NewFlagSet("report-issue", flag.ContinueOnError)
+	open := fs.Bool("open", false, "open the pre-filled issue in the browser")
+	if err := fs.Parse(args); err != nil {
+		return err
+	}
+	if fs.NArg() != 1 {
+		return usagef("maziq report-issue [--open] <id>")
+	}
+	id := fs.Arg(0)
+	if _, ok := catalog.Lookup(id); !ok {
+		return &catalog.UnknownError{ID: id}
+	}
+	b, ok, err := manager.LastBreakage(id)
+	if err != nil {
+		return err
+	}
+	if !ok {
+		return fmt.Errorf("%s has no recorded failure since it last succeeded", id)
+	}
+	ctx := context.Background()
+	title, body := manager.IssueReport(ctx, b)
+	link := manager.IssueURL(title, body)
+	fmt.Printf("%s\n\n%s\n%s\n", title, body, link)
+	if *open {
+		return exec.CommandContext(ctx, "open", link).Run()
+	}
+	return nil
+}
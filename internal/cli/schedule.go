@@ -0,0 +1,70 @@
+package cli
+
+import (
+	"flag"
+	"fmt"
+	"os"
+	"path/filepath"
+
+	"github.com/hmziqrs/maziq/internal/schedule"
+)
+
+func runSchedule(_ session, args []string) error {
+	if len(args) == 0 {
+		return usagef("maziq schedule <enable|status|disable> [--daily|--weekly]")
+	}
+	switch args[0] {
+	case "enable":
+		fs := flag.NewFlagSet("schedule enable", flag.ContinueOnError)
+		daily := fs.Bool("daily", false, "run every day")
+		weekly := fs.Bool("weekly", false, "run every Sunday (the default)")
+		if err := fs.Parse(args[1:]); err != nil {
+			return err
+		}
+		if *daily && *weekly {
+			return usagef("maziq schedule enable [--daily|--weekly]")
+		}
+		freq := schedule.Weekly
+		if *daily {
+			freq = schedule.Daily
+		}
+		exe, err := os.Executable()
+		if err != nil {
+			return err
+		}
+		if exe, err = filepath.EvalSymlinks(exe); err != nil {
+			return err
+		}
+		if err := schedule.Enable(exe, freq); err != nil {
+			return err
+		}
+		fmt.Printf("✓ %s maintenance enabled: %s upgrade --all --non-interactive\n", freq, exe)
+		fmt.Printf("  agent: %s\n  log:   %s\n", schedule.PlistPath(), schedule.LogPath())
+		return nil
+	case "status":
+		st := schedule.Current()
+		if !st.Installed {
+			fmt.Println("Scheduled maintenance is disabled. Enable it with `maziq schedule enable --weekly`.")
+			return nil
+		}
+		loaded := "loaded"
+		if !st.Loaded {
+			loaded = "not loaded (run `maziq schedule enable` again)"
+		}
+		fmt.Printf("Scheduled maintenance: %s, %s\n", st.Frequency, loaded)
+		fmt.Printf("  agent: %s\n  log:   %s\n", schedule.PlistPath(), schedule.LogPath())
+		if st.LastRun.IsZero() {
+			fmt.Println("  last run: never")
+		} else {
+			fmt.Printf("  last run: %s\n", st.LastRun.Format("2006-01-02 15:04"))
+		}
+		return nil
+	case "disable":
+		if err := schedule.Disable(); err != nil {
+			return err
+		}
+		fmt.Println("✓ scheduled maintenance disabled")
+		return nil
+	}
+	return fmt.Errorf("unknown schedule subcommand %q", args[0])
+}
@@ -0,0 +1,110 @@
+package cli
+
+import (
+	"context"
+	"crypto/rand"
+	"encoding/hex"
+	"errors"
+	"flag"
+	"fmt"
+	"net"
+	"net/http"
+	"os"
+	"os/signal"
+	"path/filepath"
+	"syscall"
+
+	"github.com/hmziqrs/maziq/internal/paths"
+	"github.com/hmziqrs/maziq/internal/server"
+)
+
+// runServe serves the local API until interrupted. The unix socket is only
+// accessible to the current user; --addr must be a loopback address. Each
+// run writes a fresh bearer token, readable only by the user, that clients
+// must send.
+func runServe(s session, args []string) error {
+	fs := flag.NewFlagSet("serve", flag.ContinueOnError)
+	socket := fs.String("socket", paths.SocketFile(), "unix socket to listen on")
+	addr := fs.String("addr", "", "listen on this loopback TCP address (e.g. 127.0.0.1:7777) instead of the socket")
+	if err := fs.Parse(args); err != nil {
+		return err
+	}
+	if fs.NArg() > 0 {
+		return usagef("maziq serve [--socket PATH | --addr 127.0.0.1:PORT]")
+	}
+
+	var ln net.Listener
+	var err error
+	if *addr != "" {
+		host, _, splitErr := net.SplitHostPort(*addr)
+		if splitErr != nil || !server.IsLoopback(host) {
+			return fmt.Errorf("--addr must be a loopback address such as 127.0.0.1:7777")
+		}
+		ln, err = net.Listen("tcp", *addr)
+	} else {
+		if err := os.MkdirAll(filepath.Dir(*socket), 0o700); err != nil {
+			return err
+		}
+		if conn, dialErr := net.Dial("unix", *socket); dialErr == nil {
+			conn.Close()
+			return fmt.Errorf("another maziq serve is already listening on %s", *socket)
+		}
+		// A socket left behind by a crashed server.
+		os.Remove(*socket)
+		// The socket is created 0600 rather than narrowed after the fact,
+		// so there is no moment another user could connect.
+		old := syscall.Umask(0o177)
+		ln, err = net.Listen("unix", *socket)
+		syscall.Umask(old)
+		if err == nil {
+			defer os.Remove(*socket)
+		}
+	}
+	if err != nil {
+		return err
+	}
+	token, err := writeServeToken()
+	if err != nil {
+		ln.Close()
+		return err
+	}
+	defer os.Remove(paths.ServeTokenFile())
+
+	srv := server.New(s.opts, token)
+	defer srv.Close()
+	httpServer := &http.Server{Handler: srv.Handler()}
+
+	ctx, stop := signal.NotifyContext(context.Background(), os.Interrupt, syscall.SIGTERM)
+	defer stop()
+	go func() {
+		<-ctx.Done()
+		httpServer.Shutdown(context.Background())
+	}()
+
+	fmt.Printf("Serving the maziq API (%s) on %s; Ctrl-C stops.\n", server.APIVersion, ln.Addr())
+	fmt.Printf("Clients authenticate with the bearer token in %s.\n", paths.ServeTokenFile())
+	if err := httpServer.Serve(ln); !errors.Is(err, http.ErrServerClosed) {
+		return err
+	}
+	return nil
+}
+
+// writeServeToken generates this run's bearer token and writes it where
+// only the user can read it.
+func writeServeToken() (string, error) {
+	buf := make([]byte, 32)
+	if _, err := rand.Read(buf); err != nil {
+		return "", err
+	}
+	token := hex.EncodeToString(buf)
+	file := paths.ServeTokenFile()
+	if err := os.MkdirAll(filepath.Dir(file), 0o700); err != nil {
+		return "", err
+	}
+	// Removed first so an existing file's wider mode is not kept.
+	os.Remove(file)
+	if err := os.WriteFile(file, []byte(token+"\n"), 0o600); err != nil {
+		return "", err
+	}
+	return token, nil
+}
@@ -0,0 +1,86 @@
+package cli
+
+import (
+	"context"
+	"flag"
+	"fmt"
+	"os"
+	"text/tabwriter"
+	"time"
+
+	"github.com/hmziqrs/maziq/internal/manager"
+	"github.com/hmziqrs/maziq/internal/options"
+	"github.com/hmziqrs/maziq/internal/snapshot"
+)
+
+func runSnapshot(s session, args []string) error {
+	if len(args) == 0 {
+		return usagef("maziq snapshot <list|restore> [flags] [id]")
+	}
+	switch args[0] {
+	case "list":
+		return runSnapshotList()
+	case "restore":
+		return runSnapshotRestore(s.opts, args[1:])
+	}
+	return fmt.Errorf("unknown snapshot subcommand %q", args[0])
+}
+
+func runSnapshotList() error {
+	all, err := snapshot.Load()
+	if err != nil {
+		return err
+	}
+	if len(all) == 0 {
+		fmt.Println("No snapshots yet; GUI apps are recorded when maziq uninstalls them.")
+		return nil
+	}
+	w := tabwriter.NewWriter(os.Stdout, 0, 0, 2, ' ', 0)
+	fmt.Fprintln(w, "ID\tVERSION\tCASK\tAPP\tTAKEN")
+	for _, s := range all {
+		taken := time.Unix(s.Timestamp, 0).Format("2006-01-02 15:04")
+		fmt.Fprintf(w, "%s\t%s\t%s\t%s\t%s\n", s.Software, orNone(s.Version), orNone(s.Cask), orNone(s.AppPath), taken)
+	}
+	return w.Flush()
+}
+
+// runSnapshotRestore reinstalls an app from its latest snapshot and says
+// whether the recorded version came back.
+func runSnapshotRestore(opts options.Run, args []string) error {
+	fs := flag.NewFlagSet("snapshot restore", flag.ContinueOnError)
+	dryRun := fs.Bool("dry-run", opts.DryRun, "print the command without running it")
+	ids, err := parseInterspersed(fs, args)
+	if err != nil {
+		return err
+	}
+	if len(ids) != 1 {
+		return usagef("maziq snapshot restore [--dry-run] <id>")
+	}
+	s, ok, err := snapshot.Latest(ids[0])
+	if err != nil {
+		return err
+	}
+	if !ok {
+		return fmt.Errorf("no snapshot of %s; see `maziq snapshot list`", ids[0])
+	}
+
+	opts.DryRun = *dryRun
+	m := manager.New(opts)
+	event, status, err := m.Restore(context.Background(), s)
+	if *dryRun {
+		fmt.Printf("[dry-run] restore %s %s via %s: %s\n", s.Software, orNone(s.Version), event.Source, event.Command)
+		return err
+	}
+	if err != nil {
+		return err
+	}
+	fmt.Printf("✓ restored %s via %s\n", s.Software, event.Source)
+	switch {
+	case s.Version == "" || status.Version == s.Version:
+		fmt.Printf("  version %s\n", orNone(status.Version))
+	default:
+		fmt.Printf("  ! installed %s, the snapshot recorded %s; %s only installs its current version\n",
+			orNone(status.Version), s.Version, event.Source)
+	}
+	return nil
+}
@@ -0,0 +1,391 @@
+package cli
+
+import (
+	"context"
+	"errors"
+	"flag"
+	"fmt"
+	"os"
+	"strings"
+	"text/tabwriter"
+	"time"
+
+	"github.com/hmziqrs/maziq/internal/catalog"
+	"github.com/hmziqrs/maziq/internal/clipboard"
+	"github.com/hmziqrs/maziq/internal/manager"
+	"github.com/hmziqrs/maziq/internal/options"
+)
+
+func runSoftware(s session, args []string) error {
+	if len(args) == 0 {
+		return usagef("maziq software <list|show|info|graph|install|update|uninstall|test|e2e> [flags] [ids...]")
+	}
+	switch args[0] {
+	case "list":
+		return runSoftwareList(args[1:])
+	case "show", "info":
+		return runSoftwareShow(args[1:])
+	case "graph":
+		return runSoftwareGraph(s.opts, args[1:])
+	case "install":
+		return runSoftwareAction(s.opts, manager.ActionInstall, args[1:])
+	case "update":
+		return runSoftwareAction(s.opts, manager.ActionUpdate, args[1:])
+	case "uninstall":
+		return runSoftwareAction(s.opts, manager.ActionUninstall, args[1:])
+	case "test":
+		return runSoftwareTest(s.opts, args[1:])
+	case "e2e":
+		return runSoftwareE2E(s.opts, args[1:])
+	}
+	return fmt.Errorf("unknown software subcommand %q", args[0])
+}
+
+func runSoftwareList(args []string) error {
+	fs := flag.NewFlagSet("software list", flag.ContinueOnError)
+	category := fs.String("category", "", "only list entries from this category")
+	tag := fs.String("tag", "", "only list entries with this tag")
+	if err := fs.Parse(args); err != nil {
+		return err
+	}
+	w := tabwriter.NewWriter(os.Stdout, 0, 0, 2, ' ', 0)
+	fmt.Fprintln(w, "ID\tNAME\tCATEGORY\tKIND\tTAGS")
+	for _, entry := range catalog.All() {
+		if *category != "" && string(entry.Category) != *category {
+			continue
+		}
+		if *tag != "" && !entry.HasTag(*tag) {
+			continue
+		}
+		fmt.Fprintf(w, "%s\t%s\t%s\t%s\t%s\n", entry.ID, entry.Name, entry.Category, entry.Kind, strings.Join(entry.Tags, ","))
+	}
+	return w.Flush()
+}
+
+func runSoftwareShow(args []string) error {
+	fs := flag.NewFlagSet("software show", flag.ContinueOnError)
+	asJSON := fs.Bool("json", false, "print every recipe, probe and requirement as JSON")
+	ids, err := parseInterspersed(fs, args)
+	if err != nil {
+		return err
+	}
+	if len(ids) != 1 {
+		return usagef("maziq software show|info [--json] <id>")
+	}
+	entry, ok := catalog.Lookup(ids[0])
+	if !ok {
+		return &catalog.UnknownError{ID: ids[0]}
+	}
+	if *asJSON {
+		return printEntryJSON(entry)
+	}
+	status := manager.NewProber(0).Probe(context.Background(), entry)
+
+	fmt.Printf("%s (%s)\n", entry.Name, entry.ID)
+	if entry.Summary != "" {
+		fmt.Printf("  %s\n", entry.Summary)
+	}
+	fmt.Println()
+	w := tabwriter.NewWriter(os.Stdout, 0, 0, 2, ' ', 0)
+	fmt.Fprintf(w, "Category\t%s\n", entry.Category)
+	fmt.Fprintf(w, "Kind\t%s\n", entry.Kind)
+	fmt.Fprintf(w, "Tags\t%s\n", orNone(strings.Join(entry.Tags, ", ")))
+	fmt.Fprintf(w, "Homepage\t%s\n", orNone(entry.Homepage))
+	if entry.Docs != "" {
+		fmt.Fprintf(w, "Docs\t%s\n", entry.Docs)
+	}
+	fmt.Fprintf(w, "License\t%s\n", orNone(entry.License))
+	if status.Latest != "" && status.Latest != status.Version {
+		fmt.Fprintf(w, "Status\t%s %s (latest %s)\n", status.State, status.Version, status.Latest)
+	} else {
+		fmt.Fprintf(w, "Status\t%s %s\n", status.State, status.Version)
+	}
+	if status.Warning != "" {
+		fmt.Fprintf(w, "Warning\t%s\n", status.Warning)
+	}
+	if status.State == manager.StateInstalled || status.State == manager.StateOutdated {
+		if provenances, err := manager.LoadProvenances(context.Background()); err == nil {
+			fmt.Fprintf(w, "Provenance\t%s\n", provenances.Of(entry))
+		}
+	}
+	fmt.Fprintf(w, "Dependencies\t%s\n", orNone(strings.Join(entry.Dependencies, ", ")))
+	fmt.Fprintf(w, "Recommended\t%s\n", orNone(strings.Join(entry.Recommended, ", ")))
+	fmt.Fprintf(w, "Conflicts\t%s\n", orNone(strings.Join(entry.Conflicts, ", ")))
+	fmt.Fprintf(w, "Adapter\t%s\n", orNone(entry.Adapter))
+	fmt.Fprintf(w, "Probe\t%s\n", entry.Probe.Describe())
+	for _, action := range []manager.Action{manager.ActionInstall, manager.ActionUpdate, manager.ActionUninstall} {
+		recipes := manager.Recipes(entry, action)
+		if len(recipes) == 0 {
+			fmt.Fprintf(w, "%s\t%s\n", action, orNone(""))
+		}
+		for i, recipe := range recipes {
+			label := ""
+			if i == 0 {
+				label = string(action)
+			}
+			var notes []string
+			if recipe.Mode != catalog.ShellSh {
+				notes = append(notes, recipe.Mode.String())
+			}
+			switch {
+			case recipe.TempDir:
+				notes = append(notes, "in a temporary directory")
+			case recipe.Dir != "":
+				notes = append(notes, "in "+recipe.Dir)
+			}
+			note := ""
+			if len(notes) > 0 {
+				note = " (" + strings.Join(notes, ", ") + ")"
+			}
+			fmt.Fprintf(w, "%s\t[%s] %s%s\n", label, recipe.Source, recipe.Command, note)
+		}
+	}
+	for _, hook := range entry.PostInstall {
+		fmt.Fprintf(w, "Post-install\t%s: %s\n", hook.Description, hook.Command)
+	}
+	for _, alias := range entry.Aliases {
+		fmt.Fprintf(w, "Alias\t%s\n", alias.Description)
+	}
+	fmt.Fprintf(w, "Test\t%s\n", orNone(entry.Test))
+	return w.Flush()
+}
+
+// runSoftwareTest runs the test command of each id, reporting the ones
+// that are installed but don't work.
+func runSoftwareTest(opts options.Run, args []string) error {
+	if len(args) == 0 {
+		return usagef("maziq software test <id>...")
+	}
+	var entries []catalog.Entry
+	for _, id := range args {
+		entry, ok := catalog.Lookup(id)
+		if !ok {
+			return &catalog.UnknownError{ID: id}
+		}
+		entries = append(entries, entry)
+	}
+	m := manager.New(opts)
+	var failures []error
+	for _, entry := range entries {
+		event, err := m.Test(context.Background(), entry)
+		if err != nil {
+			fmt.Printf("✗ %s: %v\n", entry.ID, err)
+			switch {
+			case event.Hint != "":
+				fmt.Println("  hint: " + event.Hint)
+			case event.Stderr != "":
+				fmt.Println(strings.TrimRight(event.Stderr, "\n"))
+			}
+			failures = append(failures, err)
+			continue
+		}
+		fmt.Printf("✓ %s: %s\n", entry.ID, entry.Test)
+	}
+	switch len(failures) {
+	case 0:
+		return nil
+	case 1:
+		return failures[0]
+	}
+	return &manager.RunError{Failed: failures}
+}
+
+func orNone(s string) string {
+	if s == "" {
+		return "none"
+	}
+	return s
+}
+
+func runSoftwareAction(opts options.Run, action manager.Action, args []string) error {
+	fs := flag.NewFlagSet("software "+string(action), flag.ContinueOnError)
+	dryRun := fs.Bool("dry-run", opts.DryRun, "print the commands without running them")
+	yes := fs.Bool("yes", false, "skip the confirmation prompt for destructive commands")
+	printOnly := fs.Bool("print-only", false, "print the commands and copy them to the clipboard instead of running them")
+	ids, err := parseInterspersed(fs, args)
+	if err != nil {
+		return err
+	}
+	if len(ids) == 0 {
+		return usagef("maziq software %s [--dry-run] [--yes] [--print-only] <id>...", action)
+	}
+	if *printOnly {
+		return printManualCommands(opts, action, ids)
+	}
+
+	var steps []manager.Step
+	for _, id := range ids {
+		entry, ok := catalog.Lookup(id)
+		if !ok {
+			return &catalog.UnknownError{ID: id}
+		}
+		step := manager.Step{Entry: entry, Action: action}
+		if action == manager.ActionInstall {
+			step.Hooks = manager.InstallHooks(entry, opts.ClearQuarantine)
+		}
+		steps = append(steps, step)
+	}
+	plan := manager.Plan{Action: action, Steps: steps}
+	if conflicts := plan.Conflicts(); len(conflicts) > 0 {
+		return &manager.ConflictError{Conflicts: conflicts}
+	}
+	opts.DryRun = *dryRun
+	if err := showSDKRemovals(opts, steps); err != nil {
+		return err
+	}
+	if !*dryRun && !*yes && (!confirmDestructive(opts, steps) || !confirmConflicts(opts, plan)) {
+		return errAborted
+	}
+
+	if !*dryRun {
+		stop, err := withSudo(opts, plan)
+		if err != nil {
+			return err
+		}
+		defer stop()
+	}
+
+	m := manager.New(opts)
+	var previews []manager.Preview
+	if *dryRun {
+		previews = manager.PreviewPlan(context.Background(), plan, opts)
+	}
+	for i, step := range steps {
+		started := time.Now()
+		event, err := m.Apply(context.Background(), step.Entry, action)
+		if *dryRun {
+			var adopted *manager.AdoptedError
+			var sdk *manager.SDKRemovalError
+			if errors.As(err, &adopted) || errors.As(err, &sdk) {
+				fmt.Printf("[dry-run] refused: %v\n", err)
+				continue
+			}
+			fmt.Printf("[dry-run] %s %s via %s: %s (%s)\n", action, step.Entry.ID, event.Source, event.Command, previews[i].Verdict)
+			for _, hook := range step.Hooks {
+				fmt.Printf("[dry-run]   post-install: %s\n", hook.Command)
+			}
+			continue
+		}
+		if event.Command != "" {
+			if err := m.RecordStep(context.Background(), step, event, time.Since(started)); err != nil {
+				fmt.Fprintf(os.Stderr, "could not record history: %v\n", err)
+			}
+		}
+		if err != nil {
+			return err
+		}
+		fmt.Printf("✓ %s %s via %s\n", action, step.Entry.ID, event.Source)
+		manager.RefreshPath()
+		for i, hook := range m.RunHooks(context.Background(), step) {
+			if hook.Success {
+				fmt.Printf("  ✓ post-install: %s\n", step.Hooks[i].Description)
+			} else {
+				fmt.Printf("  ✗ post-install %q failed: %s\n", step.Hooks[i].Description, hook.Error)
+			}
+		}
+	}
+	return nil
+}
+
+// printManualCommands prints the command that performs action on each id
+// and copies them all, one per line, for running them by hand.
+func printManualCommands(opts options.Run, action manager.Action, ids []string) error {
+	var commands []string
+	for _, id := range ids {
+		entry, ok := catalog.Lookup(id)
+		if !ok {
+			return &catalog.UnknownError{ID: id}
+		}
+		command, ok := manager.ManualCommand(entry, action, opts.Backend)
+		if !ok {
+			return fmt.Errorf("%s has no %s recipe", id, action)
+		}
+		commands = append(commands, command)
+	}
+	text := strings.Join(commands, "\n")
+	fmt.Println(text)
+	if err := clipboard.Copy(text + "\n"); err != nil {
+		fmt.Fprintf(os.Stderr, "could not copy to the clipboard: %v\n", err)
+	}
+	return nil
+}
+
+// confirmDestructive lists the destructive commands steps may run and asks
+// before going ahead. Steps without any return true straight away.
+func confirmDestructive(opts options.Run, steps []manager.Step) bool {
+	var lines []string
+	for _, step := range steps {
+		for _, recipe := range manager.PreferredRecipes(step.Entry, step.Action, opts.Backend) {
+			if manager.Destructive(step.Action, recipe) {
+				lines = append(lines, fmt.Sprintf("  %s %s: %s", step.Action, step.Entry.ID, recipe.Command))
+			}
+		}
+	}
+	if len(lines) == 0 {
+		return true
+	}
+	fmt.Println("The following destructive commands may run:")
+	for _, line := range lines {
+		fmt.Println(line)
+	}
+	return confirm(opts, "Continue?")
+}
+
+// showSDKRemovals lists the directories uninstalling SDKs deletes, before
+// anything runs and whether or not the removal is confirmed. Outside dry
+// runs it refuses SDK removals --confirm-sdk-removal did not confirm.
+func showSDKRemovals(opts options.Run, steps []manager.Step) error {
+	var refused error
+	for _, step := range steps {
+		if step.Action != manager.ActionUninstall || step.Entry.Kind != catalog.KindSDK {
+			continue
+		}
+		fmt.Printf("Uninstalling %s deletes:\n", step.Entry.ID)
+		if len(step.Entry.Removes) == 0 {
+			fmt.Println("  its toolchains and caches")
+		}
+		for _, dir := range step.Entry.Removes {
+			fmt.Println("  " + dir)
+		}
+		if refused == nil && !opts.DryRun && !opts.ConfirmSDKRemoval {
+			refused = &manager.SDKRemovalError{ID: step.Entry.ID, Removes: step.Entry.Removes}
+		}
+	}
+	return refused
+}
+
+// confirmConflicts warns about installed software that conflicts with the
+// plan and asks before going ahead.
+func confirmConflicts(opts options.Run, plan manager.Plan) bool {
+	conflicts := manager.InstalledConflicts(context.Background(), plan)
+	if len(conflicts) == 0 {
+		return true
+	}
+	fmt.Println("Installed software conflicts with this plan:")
+	for _, c := range conflicts {
+		fmt.Printf("  %s conflicts with %s, which is already installed\n", c.ID, c.With)
+	}
+	return confirm(opts, "Install anyway?")
+}
+
+// withSudo caches sudo credentials before a run that needs them and keeps
+// them fresh until the returned stop function is called.
+func withSudo(opts options.Run, plan manager.Plan) (stop func(), err error) {
+	if !manager.PlanNeedsSudo(plan, opts.Backend) {
+		return func() {}, nil
+	}
+	ctx, cancel := context.WithCancel(context.Background())
+	if !manager.SudoCached(ctx) {
+		if opts.NonInteractive && opts.Askpass == "" {
+			cancel()
+			return nil, fmt.Errorf("%w: run `sudo -v` first or pass --sudo-askpass", manager.ErrSudoUnavailable)
+		}
+		fmt.Println("Some steps need administrator rights.")
+		if err := manager.SudoAuthenticate(ctx, "", opts.Askpass); err != nil {
+			cancel()
+			return nil, err
+		}
+	}
+	go manager.SudoKeepAlive(ctx)
+	return cancel, nil
+}
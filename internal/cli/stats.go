@@ -0,0 +1,65 @@
+package cli
+
+import (
+	"fmt"
+	"os"
+	"text/tabwriter"
+	"time"
+
+	"github.com/hmziqrs/maziq/internal/history"
+	"github.com/hmziqrs/maziq/internal/manager"
+	"github.com/hmziqrs/maziq/internal/paths"
+)
+
+// runStats prints what the local history says about past runs. Nothing is
+// sent anywhere.
+func runStats(_ session, args []string) error {
+	if len(args) > 0 {
+		return usagef("maziq stats")
+	}
+	records, err := history.Load()
+	if err != nil {
+		return err
+	}
+	if len(records) == 0 {
+		fmt.Println("No history yet in " + paths.HistoryFile() + ".")
+		return nil
+	}
+	s := history.Summarize(records)
+
+	w := tabwriter.NewWriter(os.Stdout, 0, 0, 2, ' ', 0)
+	fmt.Fprintf(w, "Installs\t%d\n", s.Installs)
+	fmt.Fprintf(w, "Updates\t%d\n", s.Updates)
+	fmt.Fprintf(w, "Uninstalls\t%d\n", s.Uninstalls)
+	fmt.Fprintf(w, "Failed runs\t%d of %d\n", s.Failures, s.Records)
+	if o := s.LastOnboarding; o != nil {
+		fmt.Fprintf(w, "Last onboarding\t%s on %s, %s, %d steps (%d failed)\n",
+			o.Template, o.Started.Format("2006-01-02 15:04"), manager.FormatETA(o.Duration), o.Steps, o.Failed)
+	} else {
+		fmt.Fprintln(w, "Last onboarding\tnever")
+	}
+	if err := w.Flush(); err != nil {
+		return err
+	}
+
+	fmt.Println()
+	w = tabwriter.NewWriter(os.Stdout, 0, 0, 2, ' ', 0)
+	fmt.Fprintln(w, "BACKEND\tRUNS\tAVERAGE")
+	for _, b := range s.Backends {
+		fmt.Fprintf(w, "%s\t%d\t%s\n", orNone(b.Source), b.Runs, b.Average.Round(time.Second))
+	}
+	if err := w.Flush(); err != nil {
+		return err
+	}
+
+	if len(s.MostFailed) > 0 {
+		fmt.Println()
+		w = tabwriter.NewWriter(os.Stdout, 0, 0, 2, ' ', 0)
+		fmt.Fprintln(w, "MOST FAILED\tFAILURES\tRUNS")
+		for _, f := range s.MostFailed {
+			fmt.Fprintf(w, "%s\t%d\t%d\n", f.Software, f.Failures, f.Runs)
+		}
+		return w.Flush()
+	}
+	return nil
+}
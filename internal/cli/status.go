@@ -0,0 +1,113 @@
+package cli
+
+import (
+	"context"
+	"flag"
+	"fmt"
+	"os"
+	"text/tabwriter"
+
+	"github.com/hmziqrs/maziq/internal/catalog"
+	"github.com/hmziqrs/maziq/internal/errs"
+	"github.com/hmziqrs/maziq/internal/manager"
+	"github.com/hmziqrs/maziq/internal/templates"
+)
+
+// errDrift is returned by `status --diff` when the machine differs from the
+// template and --apply was not given.
+var errDrift = errs.New(errs.CodeDrift, "the machine has drifted from the template")
+
+func runStatus(s session, args []string) error {
+	fs := flag.NewFlagSet("status", flag.ContinueOnError)
+	diff := fs.String("diff", "", "compare the machine against this template")
+	apply := fs.Bool("apply", false, "with --diff, install missing and update outdated entries")
+	dryRun := fs.Bool("dry-run", s.opts.DryRun, "with --apply, print the remediation plan without running it")
+	yes := fs.Bool("yes", false, "skip the confirmation prompt for destructive commands")
+	if err := fs.Parse(args); err != nil {
+		return err
+	}
+	if fs.NArg() > 0 || *apply && *diff == "" {
+		return usagef("maziq status [--diff TEMPLATE [--apply] [--dry-run] [--yes]]")
+	}
+	s.opts.DryRun = *dryRun
+
+	ctx := context.Background()
+	entries := catalog.All()
+	fmt.Fprintf(os.Stderr, "Probing %d entries…\n", len(entries))
+	if *diff == "" {
+		statuses := manager.NewProber(s.opts.Jobs).ProbeAll(ctx, entries, manager.ProbeModeFull)
+		w := tabwriter.NewWriter(os.Stdout, 0, 0, 2, ' ', 0)
+		fmt.Fprintln(w, "ID\tSTATE\tVERSION\tWARNING")
+		for _, entry := range entries {
+			st := statuses[entry.ID]
+			fmt.Fprintf(w, "%s\t%s\t%s\t%s\n", entry.ID, st.State, st.Version, st.Warning)
+		}
+		return w.Flush()
+	}
+
+	t, err := templates.Load(*diff)
+	if err != nil {
+		return err
+	}
+	drift, err := manager.DetectDrift(ctx, t, s.opts)
+	if err != nil {
+		return err
+	}
+	if err := printDrift(drift); err != nil {
+		return err
+	}
+	if drift.Empty() {
+		return nil
+	}
+	if !*apply {
+		return errDrift
+	}
+
+	plans, err := drift.Remediation()
+	if err != nil {
+		return err
+	}
+	if len(plans) == 0 {
+		fmt.Println("\nOnly extra software differs; nothing to install or update.")
+		return nil
+	}
+	for _, plan := range plans {
+		if *dryRun {
+			fmt.Printf("\nRemediation: %s %d entries\n", plan.Action, len(plan.Steps))
+			printPlan(s.opts, plan)
+			continue
+		}
+		if err := executePlan(s, plan, *yes); err != nil {
+			return err
+		}
+	}
+	return nil
+}
+
+// printDrift reports every difference from the template, grouped by kind.
+func printDrift(drift manager.Drift) error {
+	if drift.Empty() {
+		fmt.Printf("No drift: the machine matches template %q.\n", drift.Template)
+		return nil
+	}
+	fmt.Printf("Drift from template %q:\n", drift.Template)
+	w := tabwriter.NewWriter(os.Stdout, 0, 0, 2, ' ', 0)
+	for _, group := range []struct {
+		title string
+		items []manager.DriftItem
+	}{
+		{"Missing", drift.Missing},
+		{"Outdated", drift.Outdated},
+		{"Extra", drift.Extra},
+		{"Unknown", drift.Unknown},
+	} {
+		if len(group.items) == 0 {
+			continue
+		}
+		fmt.Fprintf(w, "\n%s (%d):\n", group.title, len(group.items))
+		for _, item := range group.items {
+			fmt.Fprintf(w, "  %s\t%s\n", item.ID, item.Detail)
+		}
+	}
+	return w.Flush()
+}
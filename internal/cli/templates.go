@@ -0,0 +1,91 @@
+package cli
+
+import (
+	"context"
+	"flag"
+	"fmt"
+	"os"
+	"time"
+
+	"github.com/hmziqrs/maziq/internal/catalog"
+	"github.com/hmziqrs/maziq/internal/manager"
+	"github.com/hmziqrs/maziq/internal/options"
+	"github.com/hmziqrs/maziq/internal/templates"
+)
+
+func runTemplates(s session, args []string) error {
+	if len(args) == 0 {
+		return usagef("maziq templates <list|freeze> [flags]")
+	}
+	switch args[0] {
+	case "list":
+		for _, name := range templates.List() {
+			fmt.Println(name)
+		}
+		return nil
+	case "freeze":
+		return runTemplatesFreeze(s.opts, args[1:])
+	}
+	return fmt.Errorf("unknown templates subcommand %q", args[0])
+}
+
+// runTemplatesFreeze snapshots the installed catalog entries into a new
+// template, pinning each one to its detected version.
+func runTemplatesFreeze(opts options.Run, args []string) error {
+	fs := flag.NewFlagSet("templates freeze", flag.ContinueOnError)
+	category := fs.String("category", "", "only include entries from this category")
+	description := fs.String("description", "", "template description")
+	noPins := fs.Bool("no-pins", false, "do not record detected versions")
+	force := fs.Bool("force", false, "overwrite an existing template with the same name")
+	positional, err := parseInterspersed(fs, args)
+	if err != nil {
+		return err
+	}
+	if len(positional) != 1 {
+		return usagef("maziq templates freeze <name> [--category C] [--no-pins] [--force]")
+	}
+	name := positional[0]
+
+	var entries []catalog.Entry
+	for _, entry := range catalog.All() {
+		if *category == "" || string(entry.Category) == *category {
+			entries = append(entries, entry)
+		}
+	}
+	if len(entries) == 0 {
+		return fmt.Errorf("no catalog entries in category %q", *category)
+	}
+
+	fmt.Fprintf(os.Stderr, "Probing %d entries…\n", len(entries))
+	statuses := manager.NewProber(opts.Jobs).ProbeAll(context.Background(), entries, manager.ProbeModeFull)
+
+	t := templates.Template{
+		Name:        name,
+		Description: *description,
+		Versions:    map[string]string{},
+	}
+	if t.Description == "" {
+		host, _ := os.Hostname()
+		t.Description = fmt.Sprintf("Frozen from %s on %s.", host, time.Now().Format("2006-01-02"))
+	}
+	for _, entry := range entries {
+		st := statuses[entry.ID]
+		if st.State != manager.StateInstalled && st.State != manager.StateOutdated {
+			continue
+		}
+		t.Software = append(t.Software, entry.ID)
+		if !*noPins && st.Version != "" {
+			t.Versions[entry.ID] = st.Version
+		}
+	}
+	if len(t.Software) == 0 {
+		return fmt.Errorf("no installed entries found to freeze")
+	}
+
+	path, err := templates.Save(t, *force)
+	if err != nil {
+		return err
+	}
+	fmt.Printf("Froze %d entries into template %q (%s)\n", len(t.Software), name, path)
+	return nil
+}
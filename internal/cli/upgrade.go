@@ -0,0 +1,51 @@
+package cli
+
+import (
+	"context"
+	"flag"
+	"fmt"
+	"os"
+
+	"github.com/hmziqrs/maziq/internal/catalog"
+	"github.com/hmziqrs/maziq/internal/manager"
+)
+
+func runUpgrade(s session, args []string) error {
+	fs := flag.NewFlagSet("upgrade", flag.ContinueOnError)
+	all := fs.Bool("all", false, "update every installed catalog entry")
+	dryRun := fs.Bool("dry-run", s.opts.DryRun, "print the plan without running it")
+	yes := fs.Bool("yes", false, "skip the confirmation prompt for destructive commands")
+	ids, err := parseInterspersed(fs, args)
+	if err != nil {
+		return err
+	}
+	if *all == (len(ids) > 0) {
+		return usagef("maziq upgrade --all | <id>... [--dry-run] [--yes]")
+	}
+	s.opts.DryRun = *dryRun
+
+	if *all {
+		entries := catalog.All()
+		fmt.Fprintf(os.Stderr, "Probing %d entries…\n", len(entries))
+		statuses := manager.NewProber(s.opts.Jobs).ProbeAll(context.Background(), entries, manager.ProbeModeFull)
+		for _, entry := range entries {
+			if st := statuses[entry.ID].State; st == manager.StateInstalled || st == manager.StateOutdated {
+				ids = append(ids, entry.ID)
+			}
+		}
+		if len(ids) == 0 {
+			fmt.Println("Nothing installed to upgrade.")
+			return nil
+		}
+	}
+	plan, err := manager.Resolve(ids, manager.ActionUpdate, s.opts)
+	if err != nil {
+		return err
+	}
+	if *dryRun {
+		fmt.Printf("Upgrade plan (%d steps):\n", len(plan.Steps))
+		printPlan(s.opts, plan)
+		return nil
+	}
+	return executePlan(s, plan, *yes)
+}
@@ -0,0 +1,14 @@
+// Package clipboard puts text on the macOS clipboard.
+package clipboard
+
+import (
+	"os/exec"
+	"strings"
+)
+
+// Copy replaces the clipboard's contents with text.
+func Copy(text string) error {
+	cmd := exec.Command("pbcopy")
+	cmd.Stdin = strings.NewReader(text)
+	return cmd.Run()
+}
@@ -0,0 +1,262 @@
+// Package config loads the machine's persistent preferences from
+// ~/.config/maziq/config.toml. Command-line flags override every value.
+package config
+
+import (
+	"fmt"
+	"os"
+	"path/filepath"
+	"strings"
+
+	"github.com/hmziqrs/maziq/internal/catalog"
+	"github.com/hmziqrs/maziq/internal/errs"
+	"github.com/hmziqrs/maziq/internal/logging"
+	"github.com/hmziqrs/maziq/internal/options"
+	"github.com/hmziqrs/maziq/internal/paths"
+	"github.com/hmziqrs/maziq/internal/templates"
+	"github.com/hmziqrs/maziq/internal/tomlite"
+)
+
+// Themes the TUI knows how to render.
+var Themes = []string{"dark", "light", "mono"}
+
+// Config is the parsed config.toml.
+type Config struct {
+	// Role is the machine kind picked in the TUI's first-run wizard; empty
+	// until the wizard has run, "custom" when it was skipped.
+	Role            string
+	// Template is applied by onboard and preselected in the TUI.
+	Template        string
+	// DryRun makes runs print their commands instead of executing them.
+	DryRun          bool
+	Backend         options.Backend
+	// Jobs bounds how many status probes run at once.
+	Jobs            int
+	// ContinueOnError keeps runs going past failed steps.
+	ContinueOnError bool
+	// Offline restricts runs to what `maziq cache warm` downloaded.
+	Offline         bool
+	// PermanentDelete makes recipes and purge-self delete data outright
+	// instead of moving it to the Trash.
+	PermanentDelete bool
+	Theme           string
+	LogLevel        string
+	// ClearQuarantine lists trusted GUI entries whose app has its
+	// quarantine attribute cleared after installing.
+	ClearQuarantine []string
+	// Favorites are catalog ids pinned to the top of the TUI's catalog.
+	Favorites       []string
+	Proxy           Proxy
+	// Webhook receives a summary of every non-interactive run.
+	Webhook         Webhook
+}
+
+// Default returns the preferences used when config.toml is absent.
+func Default() Config {
+	return Config{
+		Template:        templates.DefaultName,
+		Jobs:            8,
+		ContinueOnError: true,
+		Theme:           "dark",
+		LogLevel:        "info",
+	}
+}
+
+// RunOptions returns the run options c sets; flags then override them.
+func (c Config) RunOptions() options.Run {
+	run := options.Defaults()
+	run.DryRun = c.DryRun
+	run.Backend = c.Backend
+	run.Jobs = c.Jobs
+	run.ContinueOnError = c.ContinueOnError
+	run.Offline = c.Offline
+	run.PermanentDelete = c.PermanentDelete
+	run.ClearQuarantine = c.ClearQuarantine
+	run.Verbosity = c.LogLevel
+	return run
+}
+
+// Load reads config.toml. A missing file yields the defaults; any other
+// problem is an error naming the file.
+func Load() (Config, error) {
+	cfg := Default()
+	path := paths.ConfigFile()
+	data, err := os.ReadFile(path)
+	if os.IsNotExist(err) {
+		return cfg, nil
+	}
+	if err != nil {
+		return cfg, err
+	}
+	doc, err := tomlite.Parse(data)
+	if err != nil {
+		return cfg, errs.Wrap(errs.CodeConfig, fmt.Errorf("%s: %w", path, err))
+	}
+	if err := cfg.decode(doc); err != nil {
+		return cfg, errs.Wrap(errs.CodeConfig, fmt.Errorf("%s: %w", path, err))
+	}
+	return cfg, nil
+}
+
+func (c *Config) decode(doc tomlite.Table) error {
+	for key := range doc {
+		var ok bool
+		switch key {
+		case "role":
+			c.Role, ok = doc.Str(key)
+		case "template":
+			c.Template, ok = doc.Str(key)
+		case "dry_run":
+			c.DryRun, ok = doc.Bool(key)
+		case "backend":
+			var value string
+			if value, ok = doc.Str(key); ok {
+				b, err := options.ParseBackend(value)
+				if err != nil {
+					return err
+				}
+				c.Backend = b
+			}
+		case "jobs":
+			var jobs int64
+			if jobs, ok = doc.Int(key); ok && jobs < 1 {
+				return fmt.Errorf("jobs must be at least 1, got %d", jobs)
+			}
+			c.Jobs = int(jobs)
+		case "continue_on_error":
+			c.ContinueOnError, ok = doc.Bool(key)
+		case "offline":
+			c.Offline, ok = doc.Bool(key)
+		case "permanent_delete":
+			c.PermanentDelete, ok = doc.Bool(key)
+		case "theme":
+			if c.Theme, ok = doc.Str(key); ok && !validTheme(c.Theme) {
+				return fmt.Errorf("unknown theme %q (expected dark, light or mono)", c.Theme)
+			}
+		case "clear_quarantine":
+			if _, ok = doc[key].([]any); ok {
+				c.ClearQuarantine = doc.Strings(key)
+				for _, id := range c.ClearQuarantine {
+					if entry, found := catalog.Lookup(id); !found || entry.Probe.AppPath == "" {
+						return fmt.Errorf("clear_quarantine: %q is not a GUI app in the catalog", id)
+					}
+				}
+			}
+		case "favorites":
+			if _, ok = doc[key].([]any); ok {
+				c.Favorites = doc.Strings(key)
+				for _, id := range c.Favorites {
+					if _, found := catalog.Lookup(id); !found {
+						return fmt.Errorf("favorites: %q is not in the catalog", id)
+					}
+				}
+			}
+		case "proxy":
+			var table tomlite.Table
+			if table, ok = doc.Table(key); ok {
+				proxy, err := decodeProxy(table)
+				if err != nil {
+					return err
+				}
+				c.Proxy = proxy
+			}
+		case "webhook":
+			var table tomlite.Table
+			if table, ok = doc.Table(key); ok {
+				webhook, err := decodeWebhook(table)
+				if err != nil {
+					return err
+				}
+				c.Webhook = webhook
+			}
+		case "log_level":
+			if c.LogLevel, ok = doc.Str(key); ok {
+				if _, err := logging.ParseLevel(c.LogLevel); err != nil {
+					return err
+				}
+			}
+		default:
+			return fmt.Errorf("unknown key %q", key)
+		}
+		if !ok {
+			return fmt.Errorf("%s has the wrong type", key)
+		}
+	}
+	return nil
+}
+
+// Encode renders c as TOML.
+func Encode(c Config) []byte {
+	backend := string(c.Backend)
+	if backend == "" {
+		backend = "brew"
+	}
+	var b strings.Builder
+	if c.Role != "" {
+		fmt.Fprintf(&b, "role = %s\n", tomlite.Quote(c.Role))
+	}
+	fmt.Fprintf(&b, "template = %s\n", tomlite.Quote(c.Template))
+	fmt.Fprintf(&b, "dry_run = %t\n", c.DryRun)
+	fmt.Fprintf(&b, "backend = %s\n", tomlite.Quote(backend))
+	fmt.Fprintf(&b, "jobs = %d\n", c.Jobs)
+	fmt.Fprintf(&b, "continue_on_error = %t\n", c.ContinueOnError)
+	if c.Offline {
+		fmt.Fprintf(&b, "offline = %t\n", c.Offline)
+	}
+	if c.PermanentDelete {
+		fmt.Fprintf(&b, "permanent_delete = %t\n", c.PermanentDelete)
+	}
+	fmt.Fprintf(&b, "theme = %s\n", tomlite.Quote(c.Theme))
+	fmt.Fprintf(&b, "log_level = %s\n", tomlite.Quote(c.LogLevel))
+	if len(c.ClearQuarantine) > 0 {
+		fmt.Fprintf(&b, "clear_quarantine = %s\n", quoteList(c.ClearQuarantine))
+	}
+	if len(c.Favorites) > 0 {
+		fmt.Fprintf(&b, "favorites = %s\n", quoteList(c.Favorites))
+	}
+	if !c.Proxy.IsZero() {
+		encodeProxy(&b, c.Proxy)
+	}
+	if !c.Webhook.IsZero() {
+		encodeWebhook(&b, c.Webhook)
+	}
+	return []byte(b.String())
+}
+
+// quoteList renders values as a TOML array of strings.
+func quoteList(values []string) string {
+	quoted := make([]string, len(values))
+	for i, v := range values {
+		quoted[i] = tomlite.Quote(v)
+	}
+	return "[" + strings.Join(quoted, ", ") + "]"
+}
+
+// Save writes c to config.toml, replacing the file.
+func Save(c Config) error {
+	path := paths.ConfigFile()
+	if err := os.MkdirAll(filepath.Dir(path), 0o755); err != nil {
+		return err
+	}
+	return os.WriteFile(path, Encode(c), 0o644)
+}
+
+// SaveFavorites replaces the favorites in config.toml, keeping everything
+// else as the file has it.
+func SaveFavorites(ids []string) error {
+	c, err := Load()
+	if err != nil {
+		return err
+	}
+	c.Favorites = ids
+	return Save(c)
+}
+
+func validTheme(name string) bool {
+	for _, theme := range Themes {
+		if theme == name {
+			return true
+		}
+	}
+	return false
+}
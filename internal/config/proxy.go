@@ -0,0 +1,104 @@
+package config
+
+import (
+	"fmt"
+	"net/url"
+	"os"
+	"strings"
+
+	"github.com/hmziqrs/maziq/internal/tomlite"
+)
+
+// Proxy is the outbound proxy maziq, and every command it spawns, goes
+// through. Empty fields leave the environment's own settings alone.
+type Proxy struct {
+	HTTP    string
+	HTTPS   string
+	// NoProxy is a comma-separated list of hosts reached directly.
+	NoProxy string
+}
+
+// IsZero reports whether no proxy is configured.
+func (p Proxy) IsZero() bool {
+	return p == Proxy{}
+}
+
+// Env returns the proxy variables to set, in both the upper- and the
+// lowercase spelling since tools disagree on which one they read (curl
+// ignores HTTP_PROXY, for one).
+func (p Proxy) Env() []string {
+	var env []string
+	for _, v := range []struct{ name, value string }{
+		{"HTTP_PROXY", p.HTTP},
+		{"HTTPS_PROXY", p.HTTPS},
+		{"NO_PROXY", p.NoProxy},
+	} {
+		if v.value != "" {
+			env = append(env, v.name+"="+v.value, strings.ToLower(v.name)+"="+v.value)
+		}
+	}
+	return env
+}
+
+// Export sets p's variables in the process environment, which every
+// spawned command inherits and Go's own HTTP client reads.
+func (p Proxy) Export() {
+	for _, kv := range p.Env() {
+		name, value, _ := strings.Cut(kv, "=")
+		os.Setenv(name, value)
+	}
+}
+
+// ValidateProxyURL checks a proxy address such as http://proxy.corp:3128.
+func ValidateProxyURL(raw string) error {
+	u, err := url.Parse(raw)
+	if err != nil || u.Host == "" {
+		return fmt.Errorf("proxy %q is not a URL like http://proxy.example.com:3128", raw)
+	}
+	switch u.Scheme {
+	case "http", "https", "socks5", "socks5h":
+		return nil
+	}
+	return fmt.Errorf("proxy %q: unsupported scheme %q", raw, u.Scheme)
+}
+
+func decodeProxy(t tomlite.Table) (Proxy, error) {
+	var p Proxy
+	for key := range t {
+		var ok bool
+		switch key {
+		case "http":
+			p.HTTP, ok = t.Str(key)
+		case "https":
+			p.HTTPS, ok = t.Str(key)
+		case "no_proxy":
+			p.NoProxy, ok = t.Str(key)
+		default:
+			return p, fmt.Errorf("proxy: unknown key %q", key)
+		}
+		if !ok {
+			return p, fmt.Errorf("proxy.%s has the wrong type", key)
+		}
+	}
+	for _, raw := range []string{p.HTTP, p.HTTPS} {
+		if raw != "" {
+			if err := ValidateProxyURL(raw); err != nil {
+				return p, err
+			}
+		}
+	}
+	return p, nil
+}
+
+func encodeProxy(b *strings.Builder, p Proxy) {
+	b.WriteString("\n[proxy]\n")
+	if p.HTTP != "" {
+		fmt.Fprintf(b, "http = %s\n", tomlite.Quote(p.HTTP))
+	}
+	if p.HTTPS != "" {
+		fmt.Fprintf(b, "https = %s\n", tomlite.Quote(p.HTTPS))
+	}
+	if p.NoProxy != "" {
+		fmt.Fprintf(b, "no_proxy = %s\n", tomlite.Quote(p.NoProxy))
+	}
+}
@@ -0,0 +1,84 @@
+package config
+
+import (
+	"fmt"
+	"net/url"
+	"strings"
+
+	"github.com/hmziqrs/maziq/internal/tomlite"
+)
+
+// WebhookFormats are the formats a run summary can be posted in.
+var WebhookFormats = []string{"slack", "discord", "json"}
+
+// Webhook is where non-interactive runs post a summary when they end.
+type Webhook struct {
+	URL    string
+	// Format is slack, discord or json (the summary as is); empty picks
+	// slack or discord from the URL's host, json otherwise.
+	Format string
+}
+
+// IsZero reports whether no webhook is configured.
+func (w Webhook) IsZero() bool {
+	return w.URL == ""
+}
+
+// Resolved returns w with Format filled in.
+func (w Webhook) Resolved() Webhook {
+	if w.Format != "" {
+		return w
+	}
+	w.Format = "json"
+	if u, err := url.Parse(w.URL); err == nil {
+		switch {
+		case strings.HasSuffix(u.Host, "hooks.slack.com"):
+			w.Format = "slack"
+		case strings.HasSuffix(u.Host, "discord.com"), strings.HasSuffix(u.Host, "discordapp.com"):
+			w.Format = "discord"
+		}
+	}
+	return w
+}
+
+func decodeWebhook(t tomlite.Table) (Webhook, error) {
+	var w Webhook
+	for key := range t {
+		var ok bool
+		switch key {
+		case "url":
+			w.URL, ok = t.Str(key)
+		case "format":
+			w.Format, ok = t.Str(key)
+		default:
+			return w, fmt.Errorf("webhook: unknown key %q", key)
+		}
+		if !ok {
+			return w, fmt.Errorf("webhook.%s has the wrong type", key)
+		}
+	}
+	if u, err := url.Parse(w.URL); err != nil || (u.Scheme != "https" && u.Scheme != "http") || u.Host == "" {
+		return w, fmt.Errorf("webhook: url %q is not an http(s) URL", w.URL)
+	}
+	if w.Format != "" && !validWebhookFormat(w.Format) {
+		return w, fmt.Errorf("webhook: unknown format %q (expected slack, discord or json)", w.Format)
+	}
+	return w, nil
+}
+
+func encodeWebhook(b *strings.Builder, w Webhook) {
+	b.WriteString("\n[webhook]\n")
+	fmt.Fprintf(b, "url = %s\n", tomlite.Quote(w.URL))
+	if w.Format != "" {
+		fmt.Fprintf(b, "format = %s\n", tomlite.Quote(w.Format))
+	}
+}
+
+func validWebhookFormat(name string) bool {
+	for _, format := range WebhookFormats {
+		if format == name {
+			return true
+		}
+	}
+	return false
+}
@@ -0,0 +1,201 @@
+// Package configurator applies configuration profiles to the machine. It is
+// experimental: profiles change system settings, not just software.
+//
+// Profile types: "macos-defaults", a list of `defaults write` tweaks;
+// "git", global git settings plus per-directory identities; and "editor",
+// extensions and settings files for an editor. Each can be diffed against
+// the current values before applying.
+package configurator
+
+import (
+	"context"
+	"fmt"
+	"io/fs"
+	"os"
+	"path/filepath"
+	"sort"
+	"strings"
+
+	"github.com/hmziqrs/maziq/internal/errs"
+	"github.com/hmziqrs/maziq/internal/paths"
+	"github.com/hmziqrs/maziq/internal/tomlite"
+	builtin "github.com/hmziqrs/maziq/profiles"
+)
+
+// Type names a kind of profile.
+type Type string
+
+const (
+	TypeMacOSDefaults Type = "macos-defaults"
+	TypeGit           Type = "git"
+	TypeEditor        Type = "editor"
+)
+
+// Profile is a named set of settings of one type.
+type Profile struct {
+	Name        string
+	Type        Type
+	Description string
+	// Defaults and Restart are used by macos-defaults profiles. Restart
+	// lists apps killed after applying so they reload their preferences
+	// (Dock, Finder, SystemUIServer, ...).
+	Defaults    []Default
+	Restart     []string
+	// Git is used by git profiles.
+	Git         GitSettings
+	// Editor is used by editor profiles.
+	Editor      EditorSettings
+}
+
+// Options tweak how a profile is applied.
+type Options struct {
+	// Identity selects the git identity used outside every identity's
+	// directories; empty means the profile's default.
+	Identity string
+}
+
+// Change compares one setting's current value with the profile's.
+type Change struct {
+	// Scope is the defaults domain, or the git config file.
+	Scope   string
+	Key     string
+	Desired string
+	// Current is the value found on the machine; empty when Set is false.
+	Current string
+	Set     bool
+	Pending bool
+}
+
+// Diff reads the current value of every setting p would write.
+func Diff(ctx context.Context, p Profile, opts Options) ([]Change, error) {
+	switch p.Type {
+	case TypeGit:
+		return diffGit(ctx, p, opts)
+	case TypeEditor:
+		return diffEditor(ctx, p)
+	}
+	return diffDefaults(ctx, p), nil
+}
+
+// Apply writes every pending setting and returns the changes it made.
+func Apply(ctx context.Context, p Profile, opts Options) ([]Change, error) {
+	switch p.Type {
+	case TypeGit:
+		return applyGit(ctx, p, opts)
+	case TypeEditor:
+		return applyEditor(ctx, p)
+	}
+	return applyDefaults(ctx, p)
+}
+
+// Default is one `defaults write` setting.
+type Default struct {
+	Domain string
+	Key    string
+	// Type is the defaults value type: bool, int, float or string.
+	Type   string
+	Value  string
+}
+
+// Dir is where user profiles live; they shadow built-ins of the same name.
+func Dir() string {
+	return filepath.Join(paths.ConfigDir(), "profiles")
+}
+
+// List returns the names of every available profile, user and built-in.
+func List() []string {
+	seen := map[string]bool{}
+	builtins, _ := fs.Glob(builtin.FS, "*.toml")
+	users, _ := filepath.Glob(filepath.Join(Dir(), "*.toml"))
+	for _, path := range append(builtins, users...) {
+		seen[strings.TrimSuffix(filepath.Base(path), ".toml")] = true
+	}
+	names := make([]string, 0, len(seen))
+	for name := range seen {
+		names = append(names, name)
+	}
+	sort.Strings(names)
+	return names
+}
+
+// Load finds a profile by name in the user directory, then the built-ins.
+func Load(name string) (Profile, error) {
+	data, err := os.ReadFile(filepath.Join(Dir(), name+".toml"))
+	if os.IsNotExist(err) {
+		data, err = fs.ReadFile(builtin.FS, name+".toml")
+		if err != nil {
+			return Profile{}, errs.Wrap(errs.CodeUnknown, fmt.Errorf("profile %q not found", name))
+		}
+	} else if err != nil {
+		return Profile{}, err
+	}
+	p, err := Parse(data)
+	return p, errs.Wrap(errs.CodeConfig, err)
+}
+
+// Parse decodes and validates a profile.
+func Parse(data []byte) (Profile, error) {
+	doc, err := tomlite.Parse(data)
+	if err != nil {
+		return Profile{}, err
+	}
+	var p Profile
+	p.Name, _ = doc.Str("name")
+	kind, _ := doc.Str("type")
+	p.Type = Type(kind)
+	p.Description, _ = doc.Str("description")
+	p.Restart = doc.Strings("restart")
+	if p.Name == "" {
+		return Profile{}, fmt.Errorf("profile is missing a name")
+	}
+	switch p.Type {
+	case TypeMacOSDefaults:
+		for i, table := range doc.Tables("defaults") {
+			d, err := parseDefault(table)
+			if err != nil {
+				return Profile{}, fmt.Errorf("profile %q, defaults #%d: %w", p.Name, i+1, err)
+			}
+			p.Defaults = append(p.Defaults, d)
+		}
+	case TypeGit:
+		if p.Git, err = parseGit(doc); err != nil {
+			return Profile{}, fmt.Errorf("profile %q: %w", p.Name, err)
+		}
+	case TypeEditor:
+		if p.Editor, err = parseEditor(doc); err != nil {
+			return Profile{}, fmt.Errorf("profile %q: %w", p.Name, err)
+		}
+	default:
+		return Profile{}, fmt.Errorf("profile %q has unsupported type %q (expected %s, %s or %s)", p.Name, kind, TypeMacOSDefaults, TypeGit, TypeEditor)
+	}
+	return p, nil
+}
+
+func parseDefault(t tomlite.Table) (Default, error) {
+	var d Default
+	d.Domain, _ = t.Str("domain")
+	d.Key, _ = t.Str("key")
+	if d.Domain == "" || d.Key == "" {
+		return d, fmt.Errorf("domain and key are required")
+	}
+	// The value's TOML type implies the defaults type unless given.
+	switch v := t["value"].(type) {
+	case bool:
+		d.Type, d.Value = "bool", fmt.Sprint(v)
+	case int64:
+		d.Type, d.Value = "int", fmt.Sprint(v)
+	case string:
+		d.Type, d.Value = "string", v
+	default:
+		return d, fmt.Errorf("%s %s: value must be a string, integer or boolean", d.Domain, d.Key)
+	}
+	if explicit, ok := t.Str("type"); ok {
+		d.Type = explicit
+	}
+	switch d.Type {
+	case "bool", "int", "float", "string":
+	default:
+		return d, fmt.Errorf("%s %s: unknown type %q", d.Domain, d.Key, d.Type)
+	}
+	return d, nil
+}
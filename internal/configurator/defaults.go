@@ -0,0 +1,82 @@
+package configurator
+
+import (
+	"context"
+	"fmt"
+	"os"
+	"os/exec"
+	"strconv"
+	"strings"
+
+	"github.com/hmziqrs/maziq/internal/paths"
+)
+
+// diffDefaults reads the current value of every setting in p, in order.
+func diffDefaults(ctx context.Context, p Profile) []Change {
+	changes := make([]Change, len(p.Defaults))
+	for i, d := range p.Defaults {
+		c := Change{Scope: d.Domain, Key: d.Key, Desired: d.Value}
+		out, err := exec.CommandContext(ctx, "defaults", "read", d.Domain, d.Key).Output()
+		if err == nil {
+			c.Current = strings.TrimSpace(string(out))
+			c.Set = true
+		}
+		c.Pending = !c.Set || !equal(d, c.Current)
+		changes[i] = c
+	}
+	return changes
+}
+
+// applyDefaults writes every pending setting, then restarts the profile's
+// apps if anything changed.
+func applyDefaults(ctx context.Context, p Profile) ([]Change, error) {
+	var written []Change
+	for i, c := range diffDefaults(ctx, p) {
+		if !c.Pending {
+			continue
+		}
+		d := p.Defaults[i]
+		value := expand(d.Value)
+		if d.Domain == "com.apple.screencapture" && d.Key == "location" {
+			// screencapture silently falls back to the Desktop otherwise.
+			if err := os.MkdirAll(value, 0o755); err != nil {
+				return written, err
+			}
+		}
+		out, err := exec.CommandContext(ctx, "defaults", "write", d.Domain, d.Key, "-"+d.Type, value).CombinedOutput()
+		if err != nil {
+			return written, fmt.Errorf("defaults write %s %s: %v: %s", d.Domain, d.Key, err, strings.TrimSpace(string(out)))
+		}
+		written = append(written, c)
+	}
+	if len(written) > 0 {
+		for _, app := range p.Restart {
+			// Not running is fine; it picks the values up on launch.
+			_ = exec.CommandContext(ctx, "killall", app).Run()
+		}
+	}
+	return written, nil
+}
+
+// expand resolves a leading ~/ to the home directory.
+func expand(value string) string {
+	if rest, ok := strings.CutPrefix(value, "~/"); ok {
+		return paths.Home() + "/" + rest
+	}
+	return value
+}
+
+// equal compares a desired value with what `defaults read` printed, which
+// renders booleans as 1/0 and floats without trailing zeros.
+func equal(d Default, current string) bool {
+	switch d.Type {
+	case "bool":
+		want, err := strconv.ParseBool(d.Value)
+		return err == nil && current == map[bool]string{true: "1", false: "0"}[want]
+	case "int", "float":
+		want, err1 := strconv.ParseFloat(d.Value, 64)
+		got, err2 := strconv.ParseFloat(current, 64)
+		return err1 == nil && err2 == nil && want == got
+	}
+	return expand(d.Value) == current
+}
@@ -0,0 +1,197 @@
+package configurator
+
+import (
+	"bytes"
+	"context"
+	"fmt"
+	"io/fs"
+	"os"
+	"os/exec"
+	"path/filepath"
+	"strings"
+	"time"
+
+	"github.com/hmziqrs/maziq/internal/history"
+	"github.com/hmziqrs/maziq/internal/paths"
+	"github.com/hmziqrs/maziq/internal/tomlite"
+	builtin "github.com/hmziqrs/maziq/profiles"
+)
+
+// EditorSettings is the body of an editor profile.
+type EditorSettings struct {
+	// Editor is the catalog id of the editor the profile provisions.
+	Editor      string
+	Extensions  []string
+	// Settings and Keybindings name JSON files next to the profile that
+	// replace the editor's own.
+	Settings    string
+	Keybindings string
+}
+
+// editor describes where a supported editor keeps its configuration.
+type editor struct {
+	// cli installs extensions; empty for editors without such a command.
+	cli     string
+	userDir string
+	keymap  string
+}
+
+var editors = map[string]editor{
+	"visual_studio_code": {"code", "Library/Application Support/Code/User", "keybindings.json"},
+	"cursor":             {"cursor", "Library/Application Support/Cursor/User", "keybindings.json"},
+	"windsurf":           {"windsurf", "Library/Application Support/Windsurf/User", "keybindings.json"},
+	"zed_stable":         {"", ".config/zed", "keymap.json"},
+	"zed_preview":        {"", ".config/zed", "keymap.json"},
+}
+
+func parseEditor(doc tomlite.Table) (EditorSettings, error) {
+	var e EditorSettings
+	e.Editor, _ = doc.Str("editor")
+	e.Extensions = doc.Strings("extensions")
+	e.Settings, _ = doc.Str("settings")
+	e.Keybindings, _ = doc.Str("keybindings")
+	ed, ok := editors[e.Editor]
+	if !ok {
+		return e, fmt.Errorf("unsupported editor %q", e.Editor)
+	}
+	if ed.cli == "" && len(e.Extensions) > 0 {
+		return e, fmt.Errorf("%s has no extension command; list extensions under auto_install_extensions in its settings.json", e.Editor)
+	}
+	return e, nil
+}
+
+// EditorProfiles returns the editor profiles among names that provision
+// the given catalog entry.
+func EditorProfiles(names []string, entryID string) ([]string, error) {
+	var out []string
+	for _, name := range names {
+		p, err := Load(name)
+		if err != nil {
+			return nil, err
+		}
+		if p.Type != TypeEditor {
+			return nil, fmt.Errorf("profile %q is a %s profile, not an editor one", name, p.Type)
+		}
+		if p.Editor.Editor == entryID {
+			out = append(out, name)
+		}
+	}
+	return out, nil
+}
+
+// editorFiles maps each configured file's destination to its source.
+func editorFiles(e EditorSettings) map[string]string {
+	ed := editors[e.Editor]
+	dir := filepath.Join(paths.Home(), ed.userDir)
+	files := map[string]string{}
+	if e.Settings != "" {
+		files[filepath.Join(dir, "settings.json")] = e.Settings
+	}
+	if e.Keybindings != "" {
+		files[filepath.Join(dir, ed.keymap)] = e.Keybindings
+	}
+	return files
+}
+
+// readProfileFile reads a file referenced by a profile, from the user
+// profile directory first and the built-ins second.
+func readProfileFile(rel string) ([]byte, error) {
+	data, err := os.ReadFile(filepath.Join(Dir(), rel))
+	if os.IsNotExist(err) {
+		if data, err = fs.ReadFile(builtin.FS, rel); err != nil {
+			return nil, fmt.Errorf("profile file %q not found", rel)
+		}
+	}
+	return data, err
+}
+
+func diffEditor(ctx context.Context, p Profile) ([]Change, error) {
+	e := p.Editor
+	ed := editors[e.Editor]
+	var changes []Change
+	if len(e.Extensions) > 0 {
+		out, err := exec.CommandContext(ctx, ed.cli, "--list-extensions").Output()
+		if err != nil {
+			return nil, fmt.Errorf("%s --list-extensions: %w (is %s installed?)", ed.cli, err, e.Editor)
+		}
+		installed := map[string]bool{}
+		for _, line := range strings.Fields(string(out)) {
+			installed[strings.ToLower(line)] = true
+		}
+		for _, ext := range e.Extensions {
+			c := Change{Scope: ed.cli, Key: "extension", Desired: ext}
+			if installed[strings.ToLower(ext)] {
+				c.Current, c.Set = ext, true
+			}
+			c.Pending = !c.Set
+			changes = append(changes, c)
+		}
+	}
+	for dest, src := range editorFiles(e) {
+		want, err := readProfileFile(src)
+		if err != nil {
+			return nil, err
+		}
+		c := Change{Scope: e.Editor, Key: dest, Desired: src}
+		if have, err := os.ReadFile(dest); err == nil {
+			c.Set = true
+			c.Current = "differs"
+			if bytes.Equal(have, want) {
+				c.Current = src
+			}
+		}
+		c.Pending = c.Current != src
+		changes = append(changes, c)
+	}
+	return changes, nil
+}
+
+// applyEditor installs missing extensions and replaces the configured
+// files, keeping a .bak of each replaced file. The run is recorded in the
+// history like any other action.
+func applyEditor(ctx context.Context, p Profile) ([]Change, error) {
+	started := time.Now()
+	changes, err := diffEditor(ctx, p)
+	if err != nil {
+		return nil, err
+	}
+	ed := editors[p.Editor.Editor]
+	var written []Change
+	for _, c := range changes {
+		if !c.Pending {
+			continue
+		}
+		if c.Key == "extension" {
+			if out, err := exec.CommandContext(ctx, ed.cli, "--install-extension", c.Desired).CombinedOutput(); err != nil {
+				return written, fmt.Errorf("%s --install-extension %s: %v: %s", ed.cli, c.Desired, err, strings.TrimSpace(string(out)))
+			}
+		} else {
+			data, err := readProfileFile(c.Desired)
+			if err != nil {
+				return written, err
+			}
+			if c.Set {
+				if err := os.Rename(c.Key, c.Key+".bak"); err != nil {
+					return written, err
+				}
+			}
+			if err := os.MkdirAll(filepath.Dir(c.Key), 0o755); err != nil {
+				return written, err
+			}
+			if err := os.WriteFile(c.Key, data, 0o644); err != nil {
+				return written, err
+			}
+		}
+		written = append(written, c)
+	}
+	if len(written) > 0 {
+		err = history.Append(history.Record{
+			Software:   p.Editor.Editor,
+			Action:     "provision",
+			Source:     "profile:" + p.Name,
+			Timestamp:  time.Now().Unix(),
+			DurationMs: time.Since(started).Milliseconds(),
+		})
+	}
+	return written, err
+}
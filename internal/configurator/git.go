@@ -0,0 +1,223 @@
+package configurator
+
+import (
+	"context"
+	"fmt"
+	"os"
+	"os/exec"
+	"path/filepath"
+	"strings"
+
+	"github.com/hmziqrs/maziq/internal/paths"
+	"github.com/hmziqrs/maziq/internal/shellprofile"
+	"github.com/hmziqrs/maziq/internal/tomlite"
+)
+
+// gitBlock names the managed block written into ~/.gitconfig.
+const gitBlock = "git"
+
+// GitSettings is the body of a git profile.
+type GitSettings struct {
+	DefaultBranch   string
+	PullRebase      bool
+	// DefaultIdentity is used outside every identity's directories.
+	DefaultIdentity string
+	Identities      []Identity
+}
+
+// Identity is one git author, e.g. "work" or "personal".
+type Identity struct {
+	Name        string
+	User        string
+	Email       string
+	SigningKey  string
+	// Directories switch to this identity for repositories below them,
+	// through includeIf "gitdir:" blocks.
+	Directories []string
+}
+
+func parseGit(doc tomlite.Table) (GitSettings, error) {
+	g := GitSettings{DefaultBranch: "master", PullRebase: true}
+	if branch, ok := doc.Str("default_branch"); ok {
+		g.DefaultBranch = branch
+	}
+	if rebase, ok := doc.Bool("pull_rebase"); ok {
+		g.PullRebase = rebase
+	}
+	g.DefaultIdentity, _ = doc.Str("default_identity")
+	seen := map[string]bool{}
+	for i, table := range doc.Tables("identities") {
+		var id Identity
+		id.Name, _ = table.Str("name")
+		id.User, _ = table.Str("user")
+		id.Email, _ = table.Str("email")
+		id.SigningKey, _ = table.Str("signing_key")
+		id.Directories = table.Strings("directories")
+		if id.Name == "" {
+			return g, fmt.Errorf("identity #%d is missing a name", i+1)
+		}
+		if seen[id.Name] {
+			return g, fmt.Errorf("identity %q is declared twice", id.Name)
+		}
+		seen[id.Name] = true
+		g.Identities = append(g.Identities, id)
+	}
+	if len(g.Identities) == 0 {
+		return g, fmt.Errorf("no [[identities]] declared")
+	}
+	if g.DefaultIdentity == "" {
+		g.DefaultIdentity = g.Identities[0].Name
+	}
+	if !seen[g.DefaultIdentity] {
+		return g, fmt.Errorf("default_identity %q is not declared", g.DefaultIdentity)
+	}
+	return g, nil
+}
+
+// identity returns the named identity, or the default one.
+func (g GitSettings) identity(name string) (Identity, error) {
+	if name == "" {
+		name = g.DefaultIdentity
+	}
+	for _, id := range g.Identities {
+		if id.Name == name {
+			if id.User == "" || id.Email == "" {
+				return id, fmt.Errorf("identity %q needs user and email; copy the profile into %s and fill them in", id.Name, Dir())
+			}
+			return id, nil
+		}
+	}
+	names := make([]string, len(g.Identities))
+	for i, id := range g.Identities {
+		names[i] = id.Name
+	}
+	return Identity{}, fmt.Errorf("unknown identity %q (expected one of %s)", name, strings.Join(names, ", "))
+}
+
+// identityFile holds the settings included for an identity's directories.
+func identityFile(id Identity) string {
+	return filepath.Join(paths.ConfigDir(), "git", id.Name+".gitconfig")
+}
+
+// gitdir normalizes a directory for includeIf, which only matches every
+// repository below it when the pattern ends in a slash.
+func gitdir(dir string) string {
+	if !strings.HasSuffix(dir, "/") {
+		dir += "/"
+	}
+	return dir
+}
+
+// gitSetting is one value in ~/.gitconfig (file empty) or an identity file.
+type gitSetting struct {
+	file  string
+	key   string
+	value string
+}
+
+func userSettings(file string, id Identity) []gitSetting {
+	out := []gitSetting{
+		{file, "user.name", id.User},
+		{file, "user.email", id.Email},
+	}
+	if id.SigningKey != "" {
+		out = append(out, gitSetting{file, "user.signingkey", id.SigningKey}, gitSetting{file, "commit.gpgsign", "true"})
+	}
+	return out
+}
+
+// gitSettings lists everything applying p with the chosen identity writes.
+func gitSettings(g GitSettings, active Identity) []gitSetting {
+	out := userSettings("", active)
+	out = append(out,
+		gitSetting{"", "init.defaultBranch", g.DefaultBranch},
+		gitSetting{"", "pull.rebase", fmt.Sprint(g.PullRebase)},
+	)
+	for _, id := range g.Identities {
+		if len(id.Directories) == 0 || id.User == "" || id.Email == "" {
+			continue
+		}
+		for _, dir := range id.Directories {
+			out = append(out, gitSetting{"", "includeIf.gitdir:" + gitdir(dir) + ".path", identityFile(id)})
+		}
+		out = append(out, userSettings(identityFile(id), id)...)
+	}
+	return out
+}
+
+func diffGit(ctx context.Context, p Profile, opts Options) ([]Change, error) {
+	active, err := p.Git.identity(opts.Identity)
+	if err != nil {
+		return nil, err
+	}
+	var changes []Change
+	for _, s := range gitSettings(p.Git, active) {
+		scope, args := "~/.gitconfig", []string{"config", "--global"}
+		if s.file != "" {
+			scope, args = s.file, []string{"config", "--file", s.file}
+		}
+		c := Change{Scope: scope, Key: s.key, Desired: s.value}
+		if out, err := exec.CommandContext(ctx, "git", append(args, "--get", s.key)...).Output(); err == nil {
+			c.Current = strings.TrimSpace(string(out))
+			c.Set = true
+		}
+		c.Pending = !c.Set || c.Current != s.value
+		changes = append(changes, c)
+	}
+	return changes, nil
+}
+
+// applyGit rewrites the identity files and maziq's block in ~/.gitconfig.
+// The block goes last, so it wins over the user's own settings.
+func applyGit(ctx context.Context, p Profile, opts Options) ([]Change, error) {
+	changes, err := diffGit(ctx, p, opts)
+	if err != nil {
+		return nil, err
+	}
+	active, _ := p.Git.identity(opts.Identity)
+
+	var block strings.Builder
+	writeUser(&block, active)
+	fmt.Fprintf(&block, "[init]\n\tdefaultBranch = %s\n", gitQuote(p.Git.DefaultBranch))
+	fmt.Fprintf(&block, "[pull]\n\trebase = %t\n", p.Git.PullRebase)
+	for _, id := range p.Git.Identities {
+		if len(id.Directories) == 0 || id.User == "" || id.Email == "" {
+			continue
+		}
+		var file strings.Builder
+		file.WriteString("# Written by maziq from the " + p.Name + " profile; edits are overwritten.\n")
+		writeUser(&file, id)
+		if err := os.MkdirAll(filepath.Dir(identityFile(id)), 0o755); err != nil {
+			return nil, err
+		}
+		if err := os.WriteFile(identityFile(id), []byte(file.String()), 0o644); err != nil {
+			return nil, err
+		}
+		for _, dir := range id.Directories {
+			fmt.Fprintf(&block, "[includeIf %s]\n\tpath = %s\n", gitQuote("gitdir:"+gitdir(dir)), gitQuote(identityFile(id)))
+		}
+	}
+	if err := shellprofile.Upsert(paths.GitConfig(), gitBlock, block.String()); err != nil {
+		return nil, err
+	}
+
+	var written []Change
+	for _, c := range changes {
+		if c.Pending {
+			written = append(written, c)
+		}
+	}
+	return written, nil
+}
+
+func writeUser(b *strings.Builder, id Identity) {
+	fmt.Fprintf(b, "[user]\n\tname = %s\n\temail = %s\n", gitQuote(id.User), gitQuote(id.Email))
+	if id.SigningKey != "" {
+		fmt.Fprintf(b, "\tsigningkey = %s\n[commit]\n\tgpgsign = true\n", gitQuote(id.SigningKey))
+	}
+}
+
+// gitQuote renders s as a git config value or subsection name.
+func gitQuote(s string) string {
+	return `"` + strings.NewReplacer(`\`, `\\`, `"`, `\"`).Replace(s) + `"`
+}
@@ -0,0 +1,205 @@
+package configurator
+
+import (
+	"context"
+	"os"
+	"os/exec"
+	"path/filepath"
+	"regexp"
+	"strings"
+
+	"github.com/hmziqrs/maziq/internal/config"
+	"github.com/hmziqrs/maziq/internal/paths"
+	"github.com/hmziqrs/maziq/internal/shellprofile"
+)
+
+// proxyBlock names the managed blocks holding proxy settings in
+// ~/.gitconfig, ~/.npmrc and the shell profiles.
+const proxyBlock = "proxy"
+
+// npmProxyKeys maps npm's config keys to the proxy setting they take.
+var npmProxyKeys = []struct {
+	key   string
+	value func(config.Proxy) string
+}{
+	{"proxy", func(p config.Proxy) string { return p.HTTP }},
+	{"https-proxy", func(p config.Proxy) string { return p.HTTPS }},
+	{"noproxy", func(p config.Proxy) string { return p.NoProxy }},
+}
+
+// proxyShell is a shell profile and how it exports a variable.
+type proxyShell struct {
+	path   string
+	export func(name, value string) string
+	// always is set for the login shell's profile, which is created if missing.
+	always bool
+}
+
+func proxyShells() []proxyShell {
+	home := paths.Home()
+	sh := func(name, value string) string { return "export " + name + "=" + shQuote(value) }
+	fish := func(name, value string) string { return "set -gx " + name + " " + shQuote(value) }
+	return []proxyShell{
+		{filepath.Join(home, ".zshrc"), sh, true},
+		{filepath.Join(home, ".bashrc"), sh, false},
+		{filepath.Join(home, ".config", "fish", "config.fish"), fish, false},
+	}
+}
+
+// gitProxyLine matches the setting written into maziq's ~/.gitconfig block.
+var gitProxyLine = regexp.MustCompile(`(?m)^\s*proxy = "(.*)"$`)
+
+// npmrcLine matches a setting in maziq's ~/.npmrc block.
+var npmrcLine = regexp.MustCompile(`^([a-z-]+)=(.*)$`)
+
+// exportLine matches the lines proxyShell.export writes.
+var exportLine = regexp.MustCompile(`^(?:export |set -gx )([A-Z_]+)[ =]'(.*)'$`)
+
+// DiffProxy compares p with the proxy settings of git, npm and the shell
+// profiles. Homebrew has no proxy setting of its own: it hands the
+// environment's variables to curl, so the shell profiles cover it.
+func DiffProxy(ctx context.Context, p config.Proxy) ([]Change, error) {
+	var changes []Change
+
+	git := Change{Scope: "~/.gitconfig", Key: "http.proxy", Desired: gitProxy(p)}
+	body, _, err := shellprofile.Block(paths.GitConfig(), proxyBlock)
+	if err != nil {
+		return nil, err
+	}
+	if m := gitProxyLine.FindStringSubmatch(body); m != nil {
+		git.Current, git.Set = strings.NewReplacer(`\"`, `"`, `\\`, `\`).Replace(m[1]), true
+	}
+	changes = appendProxyChange(changes, git)
+
+	body, written, err := shellprofile.Block(paths.NpmRC(), proxyBlock)
+	if err != nil {
+		return nil, err
+	}
+	if _, err := exec.LookPath("npm"); err == nil || written {
+		current := map[string]string{}
+		for _, line := range strings.Split(body, "\n") {
+			if m := npmrcLine.FindStringSubmatch(strings.TrimSpace(line)); m != nil {
+				current[m[1]] = m[2]
+			}
+		}
+		for _, k := range npmProxyKeys {
+			c := Change{Scope: "~/.npmrc", Key: k.key, Desired: k.value(p)}
+			c.Current, c.Set = current[k.key]
+			changes = appendProxyChange(changes, c)
+		}
+	}
+
+	for _, shell := range proxyShells() {
+		if _, err := os.Stat(shell.path); err != nil && !shell.always {
+			continue
+		}
+		body, _, err := shellprofile.Block(shell.path, proxyBlock)
+		if err != nil {
+			return nil, err
+		}
+		current := map[string]string{}
+		for _, line := range strings.Split(body, "\n") {
+			if m := exportLine.FindStringSubmatch(strings.TrimSpace(line)); m != nil {
+				current[m[1]] = m[2]
+			}
+		}
+		for _, kv := range proxyVars(p) {
+			c := Change{Scope: "~/" + relHome(shell.path), Key: kv[0], Desired: kv[1]}
+			c.Current, c.Set = current[kv[0]]
+			changes = appendProxyChange(changes, c)
+		}
+	}
+	return changes, nil
+}
+
+// ApplyProxy writes every pending proxy setting and returns the changes
+// it made. Empty settings remove what maziq wrote earlier.
+func ApplyProxy(ctx context.Context, p config.Proxy) ([]Change, error) {
+	changes, err := DiffProxy(ctx, p)
+	if err != nil {
+		return nil, err
+	}
+	if gitProxy(p) == "" {
+		_, err = shellprofile.Remove(paths.GitConfig(), proxyBlock)
+	} else {
+		err = shellprofile.Upsert(paths.GitConfig(), proxyBlock, "[http]\n\tproxy = "+gitQuote(gitProxy(p))+"\n")
+	}
+	if err != nil {
+		return nil, err
+	}
+	var npmrc []string
+	for _, c := range changes {
+		if c.Scope == "~/.npmrc" && c.Desired != "" {
+			npmrc = append(npmrc, c.Key+"="+c.Desired)
+		}
+	}
+	if len(npmrc) == 0 {
+		_, err = shellprofile.Remove(paths.NpmRC(), proxyBlock)
+	} else {
+		err = shellprofile.Upsert(paths.NpmRC(), proxyBlock, strings.Join(npmrc, "\n"))
+	}
+	if err != nil {
+		return nil, err
+	}
+	for _, shell := range proxyShells() {
+		if _, err := os.Stat(shell.path); err != nil && !shell.always {
+			continue
+		}
+		if p.IsZero() {
+			_, err = shellprofile.Remove(shell.path, proxyBlock)
+		} else {
+			var lines []string
+			for _, kv := range p.Env() {
+				name, value, _ := strings.Cut(kv, "=")
+				lines = append(lines, shell.export(name, value))
+			}
+			err = shellprofile.Upsert(shell.path, proxyBlock, strings.Join(lines, "\n"))
+		}
+		if err != nil {
+			return nil, err
+		}
+	}
+
+	var written []Change
+	for _, c := range changes {
+		if c.Pending {
+			written = append(written, c)
+		}
+	}
+	return written, nil
+}
+
+// appendProxyChange adds c unless there is nothing to write or remove.
+func appendProxyChange(changes []Change, c Change) []Change {
+	if c.Desired == "" && !c.Set {
+		return changes
+	}
+	c.Pending = c.Current != c.Desired || c.Set != (c.Desired != "")
+	return append(changes, c)
+}
+
+// gitProxy is the proxy git uses for every remote; git reads no_proxy from
+// the environment.
+func gitProxy(p config.Proxy) string {
+	if p.HTTPS != "" {
+		return p.HTTPS
+	}
+	return p.HTTP
+}
+
+// proxyVars lists the uppercase proxy variables, set or not, with p's value.
+func proxyVars(p config.Proxy) [][2]string {
+	return [][2]string{{"HTTP_PROXY", p.HTTP}, {"HTTPS_PROXY", p.HTTPS}, {"NO_PROXY", p.NoProxy}}
+}
+
+func relHome(path string) string {
+	if rel, err := filepath.Rel(paths.Home(), path); err == nil {
+		return rel
+	}
+	return path
+}
+
+// shQuote wraps s in single quotes for sh and fish.
+func shQuote(s string) string {
+	return "'" + strings.ReplaceAll(s, "'", `'\''`) + "'"
+}
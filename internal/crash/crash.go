@@ -0,0 +1,92 @@
+// Package crash turns panics into crash reports: a file with the panic,
+// its stack and the last task events, so a crash leaves something to file
+// an issue with instead of a broken terminal and a lost trace.
+package crash
+
+import (
+	"fmt"
+	"os"
+	"path/filepath"
+	"runtime"
+	"runtime/debug"
+	"slices"
+	"strings"
+	"sync"
+	"time"
+
+	"github.com/hmziqrs/maziq/internal/paths"
+)
+
+// ExitCode is the exit status after a crash (EX_SOFTWARE).
+const ExitCode = 70
+
+// keep is how many recent events a report includes.
+const keep = 50
+
+var (
+	mu     sync.Mutex
+	recent []string
+)
+
+// Note remembers a line of recent activity for the next crash report.
+func Note(line string) {
+	mu.Lock()
+	defer mu.Unlock()
+	recent = append(recent, time.Now().Format("15:04:05")+" "+line)
+	if len(recent) > keep {
+		recent = recent[len(recent)-keep:]
+	}
+}
+
+// Write saves a report for a panic with value and stack and returns its
+// path.
+func Write(value any, stack []byte) (string, error) {
+	mu.Lock()
+	events := slices.Clone(recent)
+	mu.Unlock()
+
+	var b strings.Builder
+	fmt.Fprintf(&b, "maziq crash report, %s\n", time.Now().UTC().Format(time.RFC3339))
+	if info, ok := debug.ReadBuildInfo(); ok {
+		fmt.Fprintf(&b, "version: %s\n", info.Main.Version)
+	}
+	fmt.Fprintf(&b, "go: %s %s/%s\n", runtime.Version(), runtime.GOOS, runtime.GOARCH)
+	fmt.Fprintf(&b, "command: %s\n\n", strings.Join(os.Args, " "))
+	fmt.Fprintf(&b, "panic: %v\n\n%s\n", value, stack)
+	b.WriteString("recent events:\n")
+	if len(events) == 0 {
+		b.WriteString("  (none)\n")
+	}
+	for _, line := range events {
+		b.WriteString("  " + line + "\n")
+	}
+
+	dir := paths.CrashDir()
+	if err := os.MkdirAll(dir, 0o755); err != nil {
+		return "", err
+	}
+	path := filepath.Join(dir, "crash-"+time.Now().Format("20060102-150405")+".txt")
+	return path, os.WriteFile(path, []byte(b.String()), 0o644)
+}
+
+// Report writes a report for a recovered panic and tells the user where it
+// is on stderr. The terminal must already be usable again.
+func Report(value any, stack []byte) {
+	fmt.Fprintf(os.Stderr, "maziq crashed: %v\n", value)
+	path, err := Write(value, stack)
+	if err != nil {
+		fmt.Fprintf(os.Stderr, "%s\ncould not write a crash report: %v\n", stack, err)
+		return
+	}
+	fmt.Fprintf(os.Stderr, "A crash report was written to %s\nPlease attach it to an issue at https://github.com/hmziqrs/maziq/issues\n", path)
+}
+
+// Exit reports a panic that reached main and exits; defer it first thing.
+// Anything that changes the terminal must restore it in a deferred call of
+// its own, which runs before this one.
+func Exit() {
+	if r := recover(); r != nil {
+		Report(r, debug.Stack())
+		os.Exit(ExitCode)
+	}
+}
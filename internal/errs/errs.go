@@ -0,0 +1,170 @@
+// Package errs gives maziq's errors a stable code and the context they
+// happened in, so the CLI can pick a precise exit code and the TUI can say
+// which entry, command and phase failed without parsing messages.
+package errs
+
+import (
+	"errors"
+	"io/fs"
+	"strings"
+)
+
+// Code classifies an error. Codes are stable: scripts see them through
+// exit codes and the API, so never rename one.
+type Code string
+
+// Codes, in precedence order: an error chain carrying several reports the
+// first of them.
+const (
+	CodeUsage      Code = "usage"      // the command line is malformed
+	CodeAborted    Code = "aborted"    // a confirmation was declined
+	CodePlan       Code = "plan"       // conflicts or a dependency cycle
+	CodeUnknown    Code = "unknown"    // an unknown software id or template
+	CodePermission Code = "permission" // sudo or file access was denied
+	CodePartial    Code = "partial"    // a run finished with failed steps
+	CodeStep       Code = "step"       // a step failed and stopped the run
+	CodeDrift      Code = "drift"      // the machine differs from a template
+	CodePreflight  Code = "preflight"  // the machine failed a preflight check
+	CodeConfig     Code = "config"     // config.toml, a template or a profile is invalid
+)
+
+var precedence = []Code{
+	CodeUsage, CodeAborted, CodePlan, CodeUnknown, CodePermission,
+	CodePartial, CodeStep, CodeDrift, CodePreflight, CodeConfig,
+}
+
+// Context locates a failure. Every field is optional.
+type Context struct {
+	// Entry is the software id the failure concerns.
+	Entry   string
+	// Command is the shell command that failed.
+	Command string
+	// Phase is what was running: an action, a hook, preflight, ...
+	Phase   string
+}
+
+// IsZero reports whether c locates nothing.
+func (c Context) IsZero() bool {
+	return c == Context{}
+}
+
+// String renders c as "install git: brew install git", leaving out what
+// is unknown.
+func (c Context) String() string {
+	where := strings.TrimSpace(c.Phase + " " + c.Entry)
+	switch {
+	case c.Command == "":
+		return where
+	case where == "":
+		return c.Command
+	}
+	return where + ": " + c.Command
+}
+
+// Error is an error with a code and context. Errors defined elsewhere
+// (manager.StepError, catalog.UnknownError, ...) carry theirs by
+// implementing ErrorCode and ErrorContext instead.
+type Error struct {
+	Code Code
+	Err  error
+	Context
+}
+
+func (e *Error) Error() string {
+	return e.Err.Error()
+}
+
+func (e *Error) Unwrap() error {
+	return e.Err
+}
+
+// ErrorCode returns e.Code.
+func (e *Error) ErrorCode() Code {
+	return e.Code
+}
+
+// ErrorContext returns e.Context.
+func (e *Error) ErrorContext() Context {
+	return e.Context
+}
+
+// New returns an error with code and text, for sentinels.
+func New(code Code, text string) error {
+	return &Error{Code: code, Err: errors.New(text)}
+}
+
+// Wrap returns err with code, or nil for a nil err.
+func Wrap(code Code, err error) error {
+	if err == nil {
+		return nil
+	}
+	return &Error{Code: code, Err: err}
+}
+
+type coded interface {
+	ErrorCode() Code
+}
+
+type located interface {
+	ErrorContext() Context
+}
+
+// CodeOf returns the code of err, or "" when nothing in its chain has
+// one. A chain with several codes reports the one first in precedence
+// order; permission errors from the OS count as CodePermission.
+func CodeOf(err error) Code {
+	found := map[Code]bool{}
+	walk(err, func(e error) {
+		if c, ok := e.(coded); ok {
+			found[c.ErrorCode()] = true
+		}
+	})
+	if err != nil && errors.Is(err, fs.ErrPermission) {
+		found[CodePermission] = true
+	}
+	for _, code := range precedence {
+		if found[code] {
+			return code
+		}
+	}
+	return ""
+}
+
+// ContextOf merges the context of every error in err's chain, outermost
+// first: a field set by an outer error wins over an inner one.
+func ContextOf(err error) Context {
+	var ctx Context
+	walk(err, func(e error) {
+		l, ok := e.(located)
+		if !ok {
+			return
+		}
+		inner := l.ErrorContext()
+		if ctx.Entry == "" {
+			ctx.Entry = inner.Entry
+		}
+		if ctx.Command == "" {
+			ctx.Command = inner.Command
+		}
+		if ctx.Phase == "" {
+			ctx.Phase = inner.Phase
+		}
+	})
+	return ctx
+}
+
+// walk calls visit for err and everything it wraps, depth first.
+func walk(err error, visit func(error)) {
+	if err == nil {
+		return
+	}
+	visit(err)
+	switch u := err.(type) {
+	case interface{ Unwrap() error }:
+		walk(u.Unwrap(), visit)
+	case interface{ Unwrap() []error }:
+		for _, inner := range u.Unwrap() {
+			walk(inner, visit)
+		}
+	}
+}
@@ -0,0 +1,261 @@
+// Package fleet runs maziq on several machines at once over ssh. Each host
+// needs maziq on its PATH; the remote runs are non-interactive, so their
+// one-line-per-event output is what progress is read from.
+package fleet
+
+import (
+	"bufio"
+	"context"
+	"errors"
+	"fmt"
+	"io"
+	"os"
+	"os/exec"
+	"sort"
+	"strings"
+	"sync"
+	"time"
+)
+
+// Host is one machine of an inventory.
+type Host struct {
+	// Name labels the host in progress and results.
+	Name   string
+	// Target is what ssh connects to, e.g. "dev@mac-mini.local".
+	Target string
+}
+
+// LoadInventory reads an inventory file: one host per line, either a bare
+// ssh target or "name target". Blank lines and # comments are ignored.
+func LoadInventory(path string) ([]Host, error) {
+	data, err := os.ReadFile(path)
+	if err != nil {
+		return nil, err
+	}
+	var hosts []Host
+	seen := map[string]bool{}
+	for i, line := range strings.Split(string(data), "\n") {
+		if j := strings.IndexByte(line, '#'); j >= 0 {
+			line = line[:j]
+		}
+		fields := strings.Fields(line)
+		var h Host
+		switch len(fields) {
+		case 0:
+			continue
+		case 1:
+			h = Host{Name: fields[0], Target: fields[0]}
+		case 2:
+			h = Host{Name: fields[0], Target: fields[1]}
+		default:
+			return nil, fmt.Errorf("%s:%d: expected \"target\" or \"name target\"", path, i+1)
+		}
+		if strings.HasPrefix(h.Target, "-") {
+			// ssh would read it as an option, e.g. -oProxyCommand=...
+			return nil, fmt.Errorf("%s:%d: target %q starts with -", path, i+1, h.Target)
+		}
+		if seen[h.Name] {
+			return nil, fmt.Errorf("%s:%d: host %q is listed twice", path, i+1, h.Name)
+		}
+		seen[h.Name] = true
+		hosts = append(hosts, h)
+	}
+	if len(hosts) == 0 {
+		return nil, fmt.Errorf("%s lists no hosts", path)
+	}
+	return hosts, nil
+}
+
+// Event is a progress line from one host.
+type Event struct {
+	Host    string
+	// Step is the catalog id the remote reported on; empty for lines that
+	// are not about a step.
+	Step    string
+	Message string
+	Time    time.Time
+	// Done marks the host's last event; Err is its failure, if any.
+	Done    bool
+	Err     error
+}
+
+// StepResult is one row of a remote run's summary.
+type StepResult struct {
+	ID     string
+	Result string
+	Reason string
+}
+
+// Result is the outcome of one host's run.
+type Result struct {
+	Host     string
+	Steps    []StepResult
+	Err      error
+	Duration time.Duration
+}
+
+// Run runs `maziq <args>` on every host, at most parallel at a time, and
+// reports progress through emit, which may be called concurrently.
+// Results come back in inventory order.
+func Run(ctx context.Context, hosts []Host, args []string, parallel int, emit func(Event)) []Result {
+	results := make([]Result, len(hosts))
+	sem := make(chan struct{}, max(parallel, 1))
+	var wg sync.WaitGroup
+	for i, h := range hosts {
+		wg.Add(1)
+		go func() {
+			defer wg.Done()
+			select {
+			case sem <- struct{}{}:
+				defer func() { <-sem }()
+			case <-ctx.Done():
+				results[i] = Result{Host: h.Name, Err: ctx.Err()}
+				emit(Event{Host: h.Name, Message: "cancelled", Time: time.Now(), Done: true, Err: ctx.Err()})
+				return
+			}
+			results[i] = runHost(ctx, h, args, emit)
+		}()
+	}
+	wg.Wait()
+	return results
+}
+
+// runHost runs maziq on one host and parses its output.
+func runHost(ctx context.Context, h Host, args []string, emit func(Event)) Result {
+	start := time.Now()
+	cmd := exec.CommandContext(ctx, "ssh", "-o", "BatchMode=yes", "--", h.Target, remoteCommand(args))
+	stdout, err := cmd.StdoutPipe()
+	if err != nil {
+		return finish(h, start, nil, err, emit)
+	}
+	var stderr strings.Builder
+	cmd.Stderr = &stderr
+	emit(Event{Host: h.Name, Message: "connecting to " + h.Target, Time: time.Now()})
+	if err := cmd.Start(); err != nil {
+		return finish(h, start, nil, err, emit)
+	}
+	steps := parse(stdout, func(step, message string) {
+		emit(Event{Host: h.Name, Step: step, Message: message, Time: time.Now()})
+	})
+	err = cmd.Wait()
+	if err != nil {
+		if last := lastLine(stderr.String()); last != "" {
+			err = fmt.Errorf("%w: %s", err, last)
+		}
+	}
+	return finish(h, start, steps, err, emit)
+}
+
+func finish(h Host, start time.Time, steps []StepResult, err error, emit func(Event)) Result {
+	message := "done"
+	if err != nil {
+		message = err.Error()
+	}
+	emit(Event{Host: h.Name, Message: message, Time: time.Now(), Done: true, Err: err})
+	return Result{Host: h.Name, Steps: steps, Err: err, Duration: time.Since(start)}
+}
+
+// remoteCommand is the command line ssh hands to the remote shell.
+func remoteCommand(args []string) string {
+	words := []string{"maziq", "--non-interactive", "--yes"}
+	for _, a := range args {
+		words = append(words, "'"+strings.ReplaceAll(a, "'", `'\''`)+"'")
+	}
+	return strings.Join(words, " ")
+}
+
+// parse reads a non-interactive run's output: "<RFC3339> <id> <message>"
+// lines while it runs, then the summary table.
+func parse(r io.Reader, progress func(step, message string)) []StepResult {
+	var steps []StepResult
+	summary := false
+	sc := bufio.NewScanner(r)
+	for sc.Scan() {
+		line := sc.Text()
+		switch {
+		case strings.HasPrefix(line, "Summary:"):
+			summary = true
+			progress("", line)
+		case summary:
+			fields := strings.Fields(line)
+			if len(fields) < 2 || fields[0] == "ID" {
+				continue
+			}
+			result, reason := fields[1], fields[2:]
+			if result == "not" && len(reason) > 0 && reason[0] == "run" {
+				result, reason = "not run", reason[1:]
+			}
+			steps = append(steps, StepResult{ID: fields[0], Result: result, Reason: strings.Join(reason, " ")})
+		default:
+			fields := strings.SplitN(line, " ", 3)
+			if len(fields) == 3 {
+				if _, err := time.Parse(time.RFC3339, fields[0]); err == nil {
+					progress(fields[1], fields[2])
+					continue
+				}
+			}
+			if strings.TrimSpace(line) != "" {
+				progress("", line)
+			}
+		}
+	}
+	return steps
+}
+
+func lastLine(s string) string {
+	lines := strings.Split(strings.TrimSpace(s), "\n")
+	return strings.TrimSpace(lines[len(lines)-1])
+}
+
+// Matrix lists every step id reported by any host, sorted, as the columns
+// of a per-host success/failure matrix.
+func Matrix(results []Result) []string {
+	seen := map[string]bool{}
+	var ids []string
+	for _, r := range results {
+		for _, s := range r.Steps {
+			if !seen[s.ID] {
+				seen[s.ID] = true
+				ids = append(ids, s.ID)
+			}
+		}
+	}
+	sort.Strings(ids)
+	return ids
+}
+
+// Cell is the matrix cell for step id on r: the step's result, or "-"
+// when the host never reported it.
+func (r Result) Cell(id string) string {
+	for _, s := range r.Steps {
+		if s.ID == id {
+			return s.Result
+		}
+	}
+	return "-"
+}
+
+// Failed counts the hosts whose run failed.
+func Failed(results []Result) int {
+	n := 0
+	for _, r := range results {
+		if r.Err != nil {
+			n++
+		}
+	}
+	return n
+}
+
+// Err summarizes results as an error naming the failed hosts.
+func Err(results []Result) error {
+	var failed []string
+	for _, r := range results {
+		if r.Err != nil {
+			failed = append(failed, r.Host)
+		}
+	}
+	if len(failed) == 0 {
+		return nil
+	}
+	return errors.New("failed on " + strings.Join(failed, ", "))
+}
@@ -0,0 +1,66 @@
+// Package guided keeps the progress of `maziq onboard guided`, so a new
+// engineer can stop halfway and resume on another day.
+package guided
+
+import (
+	"encoding/json"
+	"os"
+	"path/filepath"
+	"time"
+
+	"github.com/hmziqrs/maziq/internal/paths"
+)
+
+// Progress is what the guided mode finished so far.
+type Progress struct {
+	Template  string               `json:"template"`
+	Started   time.Time            `json:"started"`
+	// Done maps finished step ids to when they finished.
+	Done      map[string]time.Time `json:"done"`
+	// Checklist maps the checked-off manual items to when.
+	Checklist map[string]time.Time `json:"checklist"`
+}
+
+// Load reads the saved progress; without any it starts afresh.
+func Load() (Progress, error) {
+	p := Progress{Done: map[string]time.Time{}, Checklist: map[string]time.Time{}}
+	data, err := os.ReadFile(paths.GuidedFile())
+	if os.IsNotExist(err) {
+		p.Started = time.Now()
+		return p, nil
+	}
+	if err != nil {
+		return p, err
+	}
+	if err := json.Unmarshal(data, &p); err != nil {
+		return p, err
+	}
+	if p.Done == nil {
+		p.Done = map[string]time.Time{}
+	}
+	if p.Checklist == nil {
+		p.Checklist = map[string]time.Time{}
+	}
+	return p, nil
+}
+
+// Save writes p, creating the state directory.
+func Save(p Progress) error {
+	path := paths.GuidedFile()
+	if err := os.MkdirAll(filepath.Dir(path), 0o755); err != nil {
+		return err
+	}
+	data, err := json.MarshalIndent(p, "", "  ")
+	if err != nil {
+		return err
+	}
+	return os.WriteFile(path, append(data, '\n'), 0o644)
+}
+
+// Reset forgets all progress.
+func Reset() error {
+	if err := os.Remove(paths.GuidedFile()); err != nil && !os.IsNotExist(err) {
+		return err
+	}
+	return nil
+}
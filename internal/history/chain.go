@@ -0,0 +1,144 @@
+package history
+
+import (
+	"bufio"
+	"bytes"
+	"crypto/sha256"
+	"encoding/hex"
+	"encoding/json"
+	"io"
+	"os"
+
+	"github.com/hmziqrs/maziq/internal/paths"
+)
+
+// maxLine bounds a history line; records carry at most two output tails.
+const maxLine = 1 << 20
+
+// chainHash is the hash of a record whose JSON without the hash field is
+// body, following the record hashed prev.
+func chainHash(prev string, body []byte) string {
+	sum := sha256.Sum256(append([]byte(prev+"\n"), body...))
+	return hex.EncodeToString(sum[:])
+}
+
+// seal encodes rec chained to prev. The hash covers the encoded line
+// itself, so records written by newer versions with more fields still
+// verify.
+func seal(rec Record, prev string) ([]byte, error) {
+	rec.Prev, rec.Hash = prev, ""
+	body, err := json.Marshal(rec)
+	if err != nil {
+		return nil, err
+	}
+	rec.Hash = chainHash(prev, body)
+	return json.Marshal(rec)
+}
+
+// unseal returns the line as it was hashed: without its trailing hash
+// field. ok is false when the hash is not where seal put it.
+func unseal(line []byte, hash string) (body []byte, ok bool) {
+	suffix := []byte(`,"hash":"` + hash + `"}`)
+	if !bytes.HasSuffix(line, suffix) {
+		return nil, false
+	}
+	return append(bytes.Clone(line[:len(line)-len(suffix)]), '}'), true
+}
+
+// link is the part of a line the chain needs.
+type link struct {
+	Prev string `json:"prev"`
+	Hash string `json:"hash"`
+}
+
+// lastHash returns the hash of the last record in r, or "" when there is
+// none or it predates chaining.
+func lastHash(r io.Reader) (string, error) {
+	scanner := bufio.NewScanner(r)
+	scanner.Buffer(nil, maxLine)
+	last := ""
+	for scanner.Scan() {
+		var l link
+		if len(bytes.TrimSpace(scanner.Bytes())) > 0 && json.Unmarshal(scanner.Bytes(), &l) == nil {
+			last = l.Hash
+		}
+	}
+	return last, scanner.Err()
+}
+
+// Break is a place where the hash chain of the history file does not hold.
+type Break struct {
+	Line    int
+	Message string
+}
+
+// Verification is the outcome of Verify.
+type Verification struct {
+	// Chained counts the hashed records; Legacy the older ones written
+	// before records were chained, which cannot be checked.
+	Chained int
+	Legacy  int
+	// Head is the hash of the last record. Recording it elsewhere also
+	// catches records removed from the end, which the chain alone cannot.
+	Head    string
+	Breaks  []Break
+}
+
+// Verify walks the history file and reports every record that was edited,
+// inserted or removed since it was written. It detects tampering, not who
+// did it: someone able to rewrite the whole file can rebuild the chain.
+func Verify() (Verification, error) {
+	var v Verification
+	f, err := os.Open(paths.HistoryFile())
+	if os.IsNotExist(err) {
+		return v, nil
+	}
+	if err != nil {
+		return v, err
+	}
+	defer f.Close()
+
+	scanner := bufio.NewScanner(f)
+	scanner.Buffer(nil, maxLine)
+	n := 0
+	// torn is the break of the last line read when it was not a record.
+	torn := -1
+	for scanner.Scan() {
+		n++
+		line := bytes.TrimSpace(scanner.Bytes())
+		if len(line) == 0 {
+			continue
+		}
+		fail := func(message string) {
+			v.Breaks = append(v.Breaks, Break{Line: n, Message: message})
+		}
+		var l link
+		if err := json.Unmarshal(line, &l); err != nil {
+			fail("not a valid record")
+			torn = len(v.Breaks) - 1
+			continue
+		}
+		torn = -1
+		if l.Hash == "" {
+			if v.Chained > 0 {
+				fail("record has no hash; it was added outside maziq")
+			} else {
+				v.Legacy++
+			}
+			continue
+		}
+		if l.Prev != v.Head {
+			fail("the record before it was removed, reordered or edited")
+		}
+		body, ok := unseal(line, l.Hash)
+		if !ok || chainHash(l.Prev, body) != l.Hash {
+			fail("record was edited after it was written")
+		}
+		v.Chained++
+		v.Head = l.Hash
+	}
+	if torn >= 0 {
+		v.Breaks[torn].Message = "last record is incomplete; maziq was interrupted while writing it"
+	}
+	return v, scanner.Err()
+}
@@ -0,0 +1,113 @@
+package history
+
+import (
+	"bytes"
+	"os"
+	"slices"
+	"strings"
+	"testing"
+
+	"github.com/hmziqrs/maziq/internal/paths"
+	"github.com/hmziqrs/maziq/internal/testenv"
+)
+
+// appendRecords writes one install record per id to a fresh history.
+func appendRecords(t *testing.T, ids ...string) {
+	t.Helper()
+	testenv.New(t)
+	for i, id := range ids {
+		if err := Append(Record{Software: id, Action: "install", Source: "brew", Timestamp: int64(i + 1)}); err != nil {
+			t.Fatal(err)
+		}
+	}
+}
+
+// breakLines returns the lines Verify reports a break on.
+func breakLines(t *testing.T) (Verification, []int) {
+	t.Helper()
+	v, err := Verify()
+	if err != nil {
+		t.Fatal(err)
+	}
+	var lines []int
+	for _, b := range v.Breaks {
+		lines = append(lines, b.Line)
+	}
+	return v, lines
+}
+
+func TestVerify(t *testing.T) {
+	tests := []struct {
+		name   string
+		change func(lines [][]byte) [][]byte
+		breaks []int
+	}{
+		{"untouched", func(lines [][]byte) [][]byte { return lines }, nil},
+		{"tampered", func(lines [][]byte) [][]byte {
+			lines[1] = bytes.Replace(lines[1], []byte(`"software":"node"`), []byte(`"software":"evil"`), 1)
+			return lines
+		}, []int{2}},
+		{"reordered", func(lines [][]byte) [][]byte {
+			lines[1], lines[2] = lines[2], lines[1]
+			return lines
+		}, []int{2, 3}},
+		{"inserted", func(lines [][]byte) [][]byte {
+			return slices.Insert(lines, 1, []byte(`{"software":"evil","action":"install","source":"brew","timestamp":9}`))
+		}, []int{2}},
+		{"removed", func(lines [][]byte) [][]byte {
+			return slices.Delete(lines, 1, 2)
+		}, []int{2}},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			appendRecords(t, "git", "node", "bun")
+			data, err := os.ReadFile(paths.HistoryFile())
+			if err != nil {
+				t.Fatal(err)
+			}
+			lines := tt.change(bytes.Split(bytes.TrimSuffix(data, []byte("\n")), []byte("\n")))
+			if err := os.WriteFile(paths.HistoryFile(), append(bytes.Join(lines, []byte("\n")), '\n'), 0o644); err != nil {
+				t.Fatal(err)
+			}
+			v, got := breakLines(t)
+			if !slices.Equal(got, tt.breaks) {
+				t.Errorf("breaks on lines %v, want %v: %+v", got, tt.breaks, v.Breaks)
+			}
+		})
+	}
+}
+
+// A record cut short by an interrupted write is reported as such, and the
+// next record starts on a line of its own and chains to the last whole one.
+func TestVerifyTruncatedLastLine(t *testing.T) {
+	appendRecords(t, "git", "node")
+	data, err := os.ReadFile(paths.HistoryFile())
+	if err != nil {
+		t.Fatal(err)
+	}
+	if err := os.WriteFile(paths.HistoryFile(), data[:len(data)-20], 0o644); err != nil {
+		t.Fatal(err)
+	}
+	v, got := breakLines(t)
+	if !slices.Equal(got, []int{2}) || !strings.Contains(v.Breaks[0].Message, "incomplete") {
+		t.Fatalf("breaks %+v, want line 2 reported as incomplete", v.Breaks)
+	}
+	if v.Chained != 1 {
+		t.Errorf("chained = %d, want 1", v.Chained)
+	}
+
+	if err := Append(Record{Software: "bun", Action: "install", Source: "brew", Timestamp: 3}); err != nil {
+		t.Fatal(err)
+	}
+	v, got = breakLines(t)
+	if !slices.Equal(got, []int{2}) || v.Chained != 2 {
+		t.Errorf("after another append: breaks %+v and %d chained, want only line 2 broken and 2 chained", v.Breaks, v.Chained)
+	}
+	records, err := Load()
+	if err != nil {
+		t.Fatal(err)
+	}
+	if len(records) != 2 || records[1].Software != "bun" {
+		t.Errorf("loaded %+v, want git and bun", records)
+	}
+}
@@ -0,0 +1,238 @@
+// Package history appends completed install/update/uninstall runs to the
+// JSON-lines history file and derives duration estimates from it.
+package history
+
+import (
+	"bufio"
+	"encoding/json"
+	"os"
+	"path/filepath"
+	"sort"
+	"syscall"
+	"time"
+
+	"github.com/hmziqrs/maziq/internal/paths"
+)
+
+// window is how many recent runs the rolling average considers.
+const window = 5
+
+// Record is one line of the history file.
+type Record struct {
+	Software   string     `json:"software"`
+	Action     string     `json:"action"`
+	Version    string     `json:"version,omitempty"`
+	Source     string     `json:"source"`
+	Timestamp  int64      `json:"timestamp"`
+	DurationMs int64      `json:"duration_ms,omitempty"`
+	// Failed marks runs whose command failed; they don't count towards
+	// duration estimates.
+	Failed     bool       `json:"failed,omitempty"`
+	// Run is the start of the plan run the record belongs to, in Unix
+	// seconds; Template is set when that run applied a whole template.
+	Run        int64      `json:"run,omitempty"`
+	Template   string     `json:"template,omitempty"`
+	// Recovery is what the user chose after the failure: retry, skip or
+	// abort. Empty when the run decided on its own.
+	Recovery   string     `json:"recovery,omitempty"`
+	// Execution is the command's captured outcome, kept so failures can be
+	// investigated after the fact.
+	Execution  *Execution `json:"execution,omitempty"`
+	// Prev is the hash of the record before this one and Hash this
+	// record's own, chaining the file so edits show up (see Verify). Hash
+	// must stay the last field.
+	Prev       string     `json:"prev,omitempty"`
+	Hash       string     `json:"hash,omitempty"`
+}
+
+// Execution is the command behind a record and what it printed.
+type Execution struct {
+	Command     string `json:"command"`
+	Attempts    int    `json:"attempts,omitempty"`
+	ExitCode    int    `json:"exit_code"`
+	Error       string `json:"error,omitempty"`
+	Stdout      string `json:"stdout,omitempty"`
+	Stderr      string `json:"stderr,omitempty"`
+	// Fingerprint identifies a failure, so repeats of the same breakage
+	// can be told from new ones.
+	Fingerprint string `json:"fingerprint,omitempty"`
+}
+
+// Duration returns the recorded run time; zero for older records.
+func (r Record) Duration() time.Duration {
+	return time.Duration(r.DurationMs) * time.Millisecond
+}
+
+// Append adds rec to the history file, creating it if needed, chained to
+// the record before it.
+func Append(rec Record) error {
+	path := paths.HistoryFile()
+	if err := os.MkdirAll(filepath.Dir(path), 0o755); err != nil {
+		return err
+	}
+	f, err := os.OpenFile(path, os.O_CREATE|os.O_APPEND|os.O_RDWR, 0o644)
+	if err != nil {
+		return err
+	}
+	defer f.Close()
+	// Other maziq processes append too; hold the file from reading the
+	// last hash until the new line is written.
+	if err := syscall.Flock(int(f.Fd()), syscall.LOCK_EX); err != nil {
+		return err
+	}
+	defer syscall.Flock(int(f.Fd()), syscall.LOCK_UN)
+	prev, err := lastHash(f)
+	if err != nil {
+		return err
+	}
+	line, err := seal(rec, prev)
+	if err != nil {
+		return err
+	}
+	// A line cut short by an interrupted write must not swallow this one.
+	torn, err := endsMidLine(f)
+	if err != nil {
+		return err
+	}
+	if torn {
+		line = append([]byte{'\n'}, line...)
+	}
+	_, err = f.Write(append(line, '\n'))
+	return err
+}
+
+// endsMidLine reports that the last line of f has no newline, as after a
+// write that was cut short.
+func endsMidLine(f *os.File) (bool, error) {
+	info, err := f.Stat()
+	if err != nil || info.Size() == 0 {
+		return false, err
+	}
+	last := make([]byte, 1)
+	if _, err := f.ReadAt(last, info.Size()-1); err != nil {
+		return false, err
+	}
+	return last[0] != '\n', nil
+}
+
+// Load reads every record, oldest first. A missing file is an empty history
+// and malformed lines are skipped.
+func Load() ([]Record, error) {
+	f, err := os.Open(paths.HistoryFile())
+	if os.IsNotExist(err) {
+		return nil, nil
+	}
+	if err != nil {
+		return nil, err
+	}
+	defer f.Close()
+
+	var out []Record
+	scanner := bufio.NewScanner(f)
+	for scanner.Scan() {
+		var rec Record
+		if json.Unmarshal(scanner.Bytes(), &rec) == nil && rec.Software != "" {
+			out = append(out, rec)
+		}
+	}
+	return out, scanner.Err()
+}
+
+// AverageDurations returns, per software id, the mean duration of its last
+// few timed runs of action.
+func AverageDurations(action string) (map[string]time.Duration, error) {
+	records, err := Load()
+	if err != nil {
+		return nil, err
+	}
+	recent := map[string][]time.Duration{}
+	for i := len(records) - 1; i >= 0; i-- {
+		rec := records[i]
+		if rec.Action != action || rec.Failed || rec.DurationMs <= 0 || len(recent[rec.Software]) >= window {
+			continue
+		}
+		recent[rec.Software] = append(recent[rec.Software], rec.Duration())
+	}
+	out := make(map[string]time.Duration, len(recent))
+	for id, durations := range recent {
+		var sum time.Duration
+		for _, d := range durations {
+			sum += d
+		}
+		out[id] = sum / time.Duration(len(durations))
+	}
+	return out, nil
+}
+
+// Install is maziq's latest install of an entry that is still installed.
+type Install struct {
+	Time time.Time
+	// Run numbers the plan run that installed it, counting the runs in the
+	// history from 1; zero for records without a run.
+	Run  int
+}
+
+// Installed returns what maziq installed and has not uninstalled since:
+// the entries whose latest successful install or uninstall record is an
+// install.
+func Installed() (map[string]Install, error) {
+	records, err := Load()
+	if err != nil {
+		return nil, err
+	}
+	runs := map[int64]int{}
+	out := map[string]Install{}
+	for _, rec := range records {
+		if rec.Run != 0 && runs[rec.Run] == 0 {
+			runs[rec.Run] = len(runs) + 1
+		}
+		if rec.Failed {
+			continue
+		}
+		switch rec.Action {
+		case "install":
+			out[rec.Software] = Install{Time: time.Unix(rec.Timestamp, 0), Run: runs[rec.Run]}
+		case "uninstall":
+			delete(out, rec.Software)
+		}
+	}
+	return out, nil
+}
+
+// recentWindow is how far back RecentlyUsed looks.
+const recentWindow = 30 * 24 * time.Hour
+
+// RecentlyUsed returns up to limit software ids acted on one at a time
+// in the last month, most often used first and, among equals, most
+// recently used first. Template runs don't count: they act on everything.
+func RecentlyUsed(limit int) ([]string, error) {
+	records, err := Load()
+	if err != nil {
+		return nil, err
+	}
+	since := time.Now().Add(-recentWindow).Unix()
+	uses := map[string]int{}
+	last := map[string]int64{}
+	var ids []string
+	for _, rec := range records {
+		if rec.Template != "" || rec.Timestamp < since {
+			continue
+		}
+		if uses[rec.Software] == 0 {
+			ids = append(ids, rec.Software)
+		}
+		uses[rec.Software]++
+		last[rec.Software] = rec.Timestamp
+	}
+	sort.SliceStable(ids, func(i, j int) bool {
+		a, b := ids[i], ids[j]
+		if uses[a] != uses[b] {
+			return uses[a] > uses[b]
+		}
+		return last[a] > last[b]
+	})
+	if len(ids) > limit {
+		ids = ids[:limit]
+	}
+	return ids, nil
+}
@@ -0,0 +1,147 @@
+package history
+
+import (
+	"sort"
+	"time"
+)
+
+// mostFailedLimit bounds Stats.MostFailed.
+const mostFailedLimit = 5
+
+// Stats aggregates the history into a few numbers. It is computed locally
+// and never leaves the machine.
+type Stats struct {
+	Records    int
+	Installs   int
+	Updates    int
+	Uninstalls int
+	Failures   int
+	// Backends holds the successful runs of every source, busiest first.
+	Backends   []BackendStats
+	// MostFailed lists the entries with the most failed runs.
+	MostFailed []FailureStats
+	// LastOnboarding is the most recent run that applied a whole template
+	// for installing, if any.
+	LastOnboarding *Onboarding
+}
+
+// BackendStats is how a source performed.
+type BackendStats struct {
+	Source  string
+	Runs    int
+	Average time.Duration
+}
+
+// FailureStats counts the failed runs of one entry.
+type FailureStats struct {
+	Software string
+	Failures int
+	Runs     int
+}
+
+// Onboarding summarizes one template run.
+type Onboarding struct {
+	Template string
+	Started  time.Time
+	// Duration runs from the start of the plan to its last recorded step.
+	Duration time.Duration
+	Steps    int
+	Failed   int
+}
+
+// Summarize computes Stats over records.
+func Summarize(records []Record) Stats {
+	s := Stats{Records: len(records)}
+	type timing struct {
+		runs  int
+		total time.Duration
+		timed int
+	}
+	backends := map[string]*timing{}
+	runs := map[string]int{}
+	failures := map[string]int{}
+	for _, rec := range records {
+		runs[rec.Software]++
+		if rec.Failed {
+			s.Failures++
+			failures[rec.Software]++
+			continue
+		}
+		switch rec.Action {
+		case "install":
+			s.Installs++
+		case "update":
+			s.Updates++
+		case "uninstall":
+			s.Uninstalls++
+		}
+		b := backends[rec.Source]
+		if b == nil {
+			b = &timing{}
+			backends[rec.Source] = b
+		}
+		b.runs++
+		if rec.DurationMs > 0 {
+			b.total += rec.Duration()
+			b.timed++
+		}
+	}
+
+	for source, b := range backends {
+		stats := BackendStats{Source: source, Runs: b.runs}
+		if b.timed > 0 {
+			stats.Average = b.total / time.Duration(b.timed)
+		}
+		s.Backends = append(s.Backends, stats)
+	}
+	sort.Slice(s.Backends, func(i, j int) bool {
+		if s.Backends[i].Runs != s.Backends[j].Runs {
+			return s.Backends[i].Runs > s.Backends[j].Runs
+		}
+		return s.Backends[i].Source < s.Backends[j].Source
+	})
+
+	for id, n := range failures {
+		s.MostFailed = append(s.MostFailed, FailureStats{Software: id, Failures: n, Runs: runs[id]})
+	}
+	sort.Slice(s.MostFailed, func(i, j int) bool {
+		if s.MostFailed[i].Failures != s.MostFailed[j].Failures {
+			return s.MostFailed[i].Failures > s.MostFailed[j].Failures
+		}
+		return s.MostFailed[i].Software < s.MostFailed[j].Software
+	})
+	if len(s.MostFailed) > mostFailedLimit {
+		s.MostFailed = s.MostFailed[:mostFailedLimit]
+	}
+
+	s.LastOnboarding = lastOnboarding(records)
+	return s
+}
+
+// lastOnboarding gathers the records of the latest template install run.
+func lastOnboarding(records []Record) *Onboarding {
+	var last int64
+	for _, rec := range records {
+		if rec.Template != "" && rec.Action == "install" && rec.Run > last {
+			last = rec.Run
+		}
+	}
+	if last == 0 {
+		return nil
+	}
+	o := &Onboarding{Started: time.Unix(last, 0)}
+	var end int64
+	for _, rec := range records {
+		if rec.Run != last {
+			continue
+		}
+		o.Template = rec.Template
+		o.Steps++
+		if rec.Failed {
+			o.Failed++
+		}
+		end = max(end, rec.Timestamp)
+	}
+	o.Duration = time.Duration(end-last) * time.Second
+	return o
+}
@@ -0,0 +1,218 @@
+// Package installers downloads upstream install scripts and checks them
+// against pinned SHA256 hashes before anything runs them.
+package installers
+
+import (
+	"context"
+	"crypto/sha256"
+	_ "embed"
+	"encoding/hex"
+	"errors"
+	"fmt"
+	"io"
+	"net/http"
+	"os"
+	"path/filepath"
+	"sort"
+	"strings"
+
+	"github.com/hmziqrs/maziq/internal/paths"
+	"github.com/hmziqrs/maziq/internal/tomlite"
+)
+
+// builtinPins ships with maziq; the user's pins file overrides it.
+//
+//go:embed pins.toml
+var builtinPins []byte
+
+// maxScript bounds how much of an installer is downloaded.
+const maxScript = 8 << 20
+
+// ErrUnpinned is returned for an installer without a pinned hash.
+var ErrUnpinned = errors.New("no pinned SHA256")
+
+// MismatchError reports a downloaded installer whose hash differs from its
+// pin, e.g. because upstream published a new version.
+type MismatchError struct {
+	URL  string
+	Want string
+	Got  string
+}
+
+func (e *MismatchError) Error() string {
+	return fmt.Sprintf("%s: SHA256 %s does not match the pinned %s; review the script and run `maziq catalog refresh-hashes`", e.URL, e.Got, e.Want)
+}
+
+// Pins returns the pinned hash of every known installer URL, the user's
+// pins taking precedence over the built-in ones.
+func Pins() (map[string]string, error) {
+	out := map[string]string{}
+	for _, source := range []struct {
+		name string
+		read func() ([]byte, error)
+	}{
+		{"built-in pins", func() ([]byte, error) { return builtinPins, nil }},
+		{paths.InstallerPinsFile(), func() ([]byte, error) { return os.ReadFile(paths.InstallerPinsFile()) }},
+	} {
+		data, err := source.read()
+		if os.IsNotExist(err) {
+			continue
+		}
+		if err != nil {
+			return nil, err
+		}
+		doc, err := tomlite.Parse(data)
+		if err != nil {
+			return nil, fmt.Errorf("%s: %w", source.name, err)
+		}
+		hashes, _ := doc.Table("sha256")
+		for url := range hashes {
+			if hash, _ := hashes.Str(url); hash != "" {
+				out[url] = hash
+			}
+		}
+	}
+	return out, nil
+}
+
+// Fetch downloads url and returns its contents and SHA256.
+func Fetch(ctx context.Context, url string) ([]byte, string, error) {
+	req, err := http.NewRequestWithContext(ctx, http.MethodGet, url, nil)
+	if err != nil {
+		return nil, "", err
+	}
+	resp, err := http.DefaultClient.Do(req)
+	if err != nil {
+		return nil, "", err
+	}
+	defer resp.Body.Close()
+	if resp.StatusCode != http.StatusOK {
+		return nil, "", fmt.Errorf("%s: %s", url, resp.Status)
+	}
+	data, err := io.ReadAll(io.LimitReader(resp.Body, maxScript))
+	if err != nil {
+		return nil, "", err
+	}
+	sum := sha256.Sum256(data)
+	return data, hex.EncodeToString(sum[:]), nil
+}
+
+// ErrOffline is returned in offline mode for an installer that was never
+// cached by `maziq cache warm`.
+var ErrOffline = errors.New("not in the offline cache")
+
+// Download fetches url, checks it against its pin and writes it to a
+// private temporary file. A copy cached by Cache is used when its hash
+// still matches the pin; when offline is set nothing else is. The caller
+// removes the returned path.
+func Download(ctx context.Context, url string, offline bool) (string, error) {
+	data, err := verified(ctx, url, offline)
+	if err != nil {
+		return "", err
+	}
+	f, err := os.CreateTemp("", "maziq-installer-*")
+	if err != nil {
+		return "", err
+	}
+	defer f.Close()
+	if _, err := f.Write(data); err != nil {
+		os.Remove(f.Name())
+		return "", err
+	}
+	return f.Name(), nil
+}
+
+// Cache downloads url into the installer cache for offline runs, after
+// checking it against its pin.
+func Cache(ctx context.Context, url string) error {
+	data, err := verified(ctx, url, false)
+	if err != nil {
+		return err
+	}
+	pins, err := Pins()
+	if err != nil {
+		return err
+	}
+	if err := os.MkdirAll(paths.InstallerCacheDir(), 0o755); err != nil {
+		return err
+	}
+	return os.WriteFile(cachePath(pins[url]), data, 0o644)
+}
+
+// Cached reports whether url has a cached copy matching its pin.
+func Cached(url string) bool {
+	pins, err := Pins()
+	if err != nil || pins[url] == "" {
+		return false
+	}
+	_, ok := readCache(pins[url])
+	return ok
+}
+
+// verified returns the contents of url once they match its pin, from the
+// cache when possible and only from it when offline is set.
+func verified(ctx context.Context, url string, offline bool) ([]byte, error) {
+	pins, err := Pins()
+	if err != nil {
+		return nil, err
+	}
+	want := pins[url]
+	if want == "" {
+		return nil, fmt.Errorf("%s: %w; review the script and run `maziq catalog refresh-hashes`", url, ErrUnpinned)
+	}
+	if data, ok := readCache(want); ok {
+		return data, nil
+	}
+	if offline {
+		return nil, fmt.Errorf("%s: %w; run `maziq cache warm` while online", url, ErrOffline)
+	}
+	data, got, err := Fetch(ctx, url)
+	if err != nil {
+		return nil, err
+	}
+	if !strings.EqualFold(got, want) {
+		return nil, &MismatchError{URL: url, Want: want, Got: got}
+	}
+	return data, nil
+}
+
+func cachePath(sum string) string {
+	return filepath.Join(paths.InstallerCacheDir(), strings.ToLower(sum))
+}
+
+// readCache returns the cached script with the given SHA256, ignoring a
+// file whose contents no longer hash to its name.
+func readCache(want string) ([]byte, bool) {
+	data, err := os.ReadFile(cachePath(want))
+	if err != nil {
+		return nil, false
+	}
+	sum := sha256.Sum256(data)
+	return data, strings.EqualFold(hex.EncodeToString(sum[:]), want)
+}
+
+// Encode renders pins as a pins file.
+func Encode(pins map[string]string) []byte {
+	urls := make([]string, 0, len(pins))
+	for url := range pins {
+		urls = append(urls, url)
+	}
+	sort.Strings(urls)
+	var b strings.Builder
+	b.WriteString("# Written by `maziq catalog refresh-hashes`.\n\n[sha256]\n")
+	for _, url := range urls {
+		fmt.Fprintf(&b, "%s = %s\n", tomlite.QuoteKey(url), tomlite.Quote(pins[url]))
+	}
+	return []byte(b.String())
+}
+
+// Save writes pins to path, the user's pins file when empty.
+func Save(path string, pins map[string]string) error {
+	if path == "" {
+		path = paths.InstallerPinsFile()
+	}
+	if err := os.MkdirAll(filepath.Dir(path), 0o755); err != nil {
+		return err
+	}
+	return os.WriteFile(path, Encode(pins), 0o644)
+}
@@ -0,0 +1,40 @@
+// Package logging routes the process-wide slog logger to maziq.log in the
+// state directory, so log lines never interleave with CLI output or the TUI.
+package logging
+
+import (
+	"fmt"
+	"log/slog"
+	"os"
+	"path/filepath"
+
+	"github.com/hmziqrs/maziq/internal/paths"
+)
+
+// ParseLevel accepts debug, info, warn or error.
+func ParseLevel(name string) (slog.Level, error) {
+	var level slog.Level
+	if err := level.UnmarshalText([]byte(name)); err != nil {
+		return level, fmt.Errorf("unknown log level %q (expected debug, info, warn or error)", name)
+	}
+	return level, nil
+}
+
+// Setup makes slog's default logger append to the log file at the given
+// level. The returned function closes the file.
+func Setup(name string) (func(), error) {
+	level, err := ParseLevel(name)
+	if err != nil {
+		return nil, err
+	}
+	path := paths.LogFile()
+	if err := os.MkdirAll(filepath.Dir(path), 0o755); err != nil {
+		return nil, err
+	}
+	f, err := os.OpenFile(path, os.O_CREATE|os.O_APPEND|os.O_WRONLY, 0o644)
+	if err != nil {
+		return nil, err
+	}
+	slog.SetDefault(slog.New(slog.NewTextHandler(f, &slog.HandlerOptions{Level: level})))
+	return func() { f.Close() }, nil
+}
@@ -0,0 +1,223 @@
+package manager
+
+import (
+	"context"
+	"encoding/json"
+	"fmt"
+	"os"
+	"os/exec"
+	"path/filepath"
+	"strings"
+	"sync"
+
+	"github.com/hmziqrs/maziq/internal/catalog"
+)
+
+// Adapter owns how the entries naming it are detected and changed. The
+// catalog adapter, used for entries without one, runs the catalog's probe
+// and recipes as they are; specialized adapters ask their backend instead.
+type Adapter interface {
+	// Probe detects entry's state. The prober bounds ctx and fills in the
+	// status's id and check time.
+	Probe(ctx context.Context, p *Prober, entry catalog.Entry) Status
+	// Apply runs action for entry.
+	Apply(ctx context.Context, m *Manager, entry catalog.Entry, action Action) (ExecutionEvent, error)
+}
+
+var (
+	adaptersMu sync.RWMutex
+	adapters   = map[string]Adapter{
+		catalog.AdapterBrew:   brewAdapter{},
+		catalog.AdapterCargo:  cargoAdapter{},
+		catalog.AdapterNpm:    npmAdapter{},
+		catalog.AdapterScript: scriptAdapter{},
+	}
+)
+
+// Pinner is implemented by adapters that can install a specific version.
+// Plans resolved with pinned versions hand their entries to Pin.
+type Pinner interface {
+	Pin(entry catalog.Entry, version string) catalog.Entry
+}
+
+// RegisterAdapter makes a available to entries whose Adapter is name,
+// replacing any adapter registered under it.
+func RegisterAdapter(name string, a Adapter) {
+	adaptersMu.Lock()
+	defer adaptersMu.Unlock()
+	adapters[name] = a
+}
+
+// AdapterFor returns the adapter handling entry, falling back to the
+// catalog adapter for entries that name none or an unknown one.
+func AdapterFor(entry catalog.Entry) Adapter {
+	adaptersMu.RLock()
+	defer adaptersMu.RUnlock()
+	if a, ok := adapters[entry.Adapter]; ok {
+		return a
+	}
+	return catalogAdapter{}
+}
+
+// catalogAdapter runs the entry's catalog probe and its recipes, preferred
+// source first.
+type catalogAdapter struct{}
+
+func (catalogAdapter) Probe(ctx context.Context, p *Prober, entry catalog.Entry) Status {
+	return p.probeCatalog(ctx, entry)
+}
+
+func (catalogAdapter) Apply(ctx context.Context, m *Manager, entry catalog.Entry, action Action) (ExecutionEvent, error) {
+	return m.applyRecipes(ctx, entry, action, PreferredRecipes(entry, action, m.Options.Backend))
+}
+
+// packageName returns the Package of entry's first install recipe from
+// source.
+func packageName(entry catalog.Entry, sources ...catalog.Source) string {
+	for _, recipe := range entry.Install {
+		for _, source := range sources {
+			if recipe.Source == source && recipe.Package != "" {
+				return recipe.Package
+			}
+		}
+	}
+	return ""
+}
+
+// brewPrefixes are Homebrew's default prefixes on Apple Silicon and Intel.
+var brewPrefixes = []string{"/opt/homebrew", "/usr/local"}
+
+// brewAdapter reads which versions of a formula or cask Homebrew keeps in
+// its Cellar or Caskroom, without paying for brew's own startup. Software
+// installed some other way, e.g. an app dragged into /Applications, is
+// still found by the catalog probe.
+type brewAdapter struct {
+	catalogAdapter
+}
+
+func (a brewAdapter) Probe(ctx context.Context, p *Prober, entry catalog.Entry) Status {
+	dir := "Cellar"
+	token := packageName(entry, catalog.SourceBrew)
+	if token == "" {
+		dir, token = "Caskroom", packageName(entry, catalog.SourceBrewCask)
+	}
+	if token != "" {
+		for _, prefix := range brewPrefixes {
+			versions, err := os.ReadDir(filepath.Join(prefix, dir, token))
+			if err != nil {
+				continue
+			}
+			latest := ""
+			for _, v := range versions {
+				if v.IsDir() && !strings.HasPrefix(v.Name(), ".") && (latest == "" || compareVersions(v.Name(), latest) > 0) {
+					latest = v.Name()
+				}
+			}
+			if latest != "" {
+				return Status{State: StateInstalled, Version: latest}
+			}
+		}
+	}
+	return a.catalogAdapter.Probe(ctx, p, entry)
+}
+
+// cargoAdapter reads `cargo install --list`, which knows crates whose
+// binary is named differently from the crate or is not on PATH yet.
+type cargoAdapter struct {
+	catalogAdapter
+}
+
+func (a cargoAdapter) Probe(ctx context.Context, p *Prober, entry catalog.Entry) Status {
+	pkg := packageName(entry, catalog.SourceCargo)
+	out, err := exec.CommandContext(ctx, "cargo", "install", "--list").Output()
+	if pkg == "" || err != nil {
+		return a.catalogAdapter.Probe(ctx, p, entry)
+	}
+	// Crate lines look like "just v1.25.2:", followed by indented binaries.
+	for _, line := range strings.Split(string(out), "\n") {
+		name, rest, ok := strings.Cut(line, " ")
+		if ok && name == pkg {
+			version, _, _ := strings.Cut(rest, ":")
+			return Status{State: StateInstalled, Version: strings.TrimPrefix(version, "v")}
+		}
+	}
+	return Status{State: StateNotInstalled}
+}
+
+// npmAdapter asks npm for the globally installed version of a package
+// and the registry for the latest one. It can pin installs to a version.
+type npmAdapter struct {
+	catalogAdapter
+}
+
+func (a npmAdapter) Probe(ctx context.Context, p *Prober, entry catalog.Entry) Status {
+	pkg := packageName(entry, catalog.SourceNpm)
+	if pkg == "" {
+		return a.catalogAdapter.Probe(ctx, p, entry)
+	}
+	// npm ls exits non-zero over problems elsewhere in the global tree;
+	// its JSON is still usable, so only the output matters.
+	out, _ := exec.CommandContext(ctx, "sh", "-c", nvmEnv+"npm ls -g --depth=0 --json").Output()
+	var list struct {
+		Dependencies map[string]struct {
+			Version string `json:"version"`
+		} `json:"dependencies"`
+	}
+	if len(out) == 0 || json.Unmarshal(out, &list) != nil {
+		// No npm yet; the package may still be installed another way.
+		return a.catalogAdapter.Probe(ctx, p, entry)
+	}
+	dep, ok := list.Dependencies[pkg]
+	if !ok {
+		return Status{State: StateNotInstalled}
+	}
+	status := Status{State: StateInstalled, Version: dep.Version}
+	latest, err := exec.CommandContext(ctx, "sh", "-c", nvmEnv+"npm view "+shellQuote(pkg)+" version").Output()
+	if err == nil {
+		status.Latest = strings.TrimSpace(string(latest))
+		if compareVersions(status.Latest, status.Version) > 0 {
+			status.State = StateOutdated
+		}
+	}
+	return status
+}
+
+// Pin makes entry's npm recipes install exactly version.
+func (npmAdapter) Pin(entry catalog.Entry, version string) catalog.Entry {
+	pin := func(recipes []catalog.Recipe) []catalog.Recipe {
+		out := append([]catalog.Recipe(nil), recipes...)
+		for i, recipe := range out {
+			if recipe.Source == catalog.SourceNpm && recipe.Package != "" {
+				out[i].Command = fmt.Sprintf("npm install -g %s@%s", recipe.Package, version)
+			}
+		}
+		return out
+	}
+	entry.Install = pin(entry.Install)
+	entry.Update = pin(entry.Update)
+	return entry
+}
+
+// scriptAdapter handles entries installed by piping an upstream script
+// into a shell. Those installers are idempotent, so when the update
+// recipes fail the install script is run again.
+type scriptAdapter struct {
+	catalogAdapter
+}
+
+func (a scriptAdapter) Apply(ctx context.Context, m *Manager, entry catalog.Entry, action Action) (ExecutionEvent, error) {
+	event, err := a.catalogAdapter.Apply(ctx, m, entry, action)
+	if err == nil || action != ActionUpdate || ctx.Err() != nil {
+		return event, err
+	}
+	var scripts []catalog.Recipe
+	for _, recipe := range entry.Install {
+		if recipe.Source == catalog.SourceScript {
+			scripts = append(scripts, recipe)
+		}
+	}
+	if len(scripts) == 0 {
+		return event, err
+	}
+	return m.applyRecipes(ctx, entry, action, scripts)
+}
@@ -0,0 +1,80 @@
+package manager
+
+import (
+	"os"
+	"os/exec"
+	"path/filepath"
+	"regexp"
+)
+
+// macPortsBin is where MacPorts installs its binaries.
+const macPortsBin = "/opt/local/bin"
+
+func fileExists(path string) bool {
+	_, err := os.Stat(path)
+	return err == nil
+}
+
+// BrewPrefix returns where Homebrew is installed: HOMEBREW_PREFIX when
+// `brew shellenv` set it, otherwise the first standard prefix holding a
+// brew binary, /opt/homebrew on Apple Silicon before /usr/local on Intel.
+// It is empty when Homebrew is not installed at any of them.
+func BrewPrefix() string {
+	if prefix := os.Getenv("HOMEBREW_PREFIX"); prefix != "" && fileExists(filepath.Join(prefix, "bin", "brew")) {
+		return prefix
+	}
+	for _, prefix := range brewPrefixes {
+		if fileExists(filepath.Join(prefix, "bin", "brew")) {
+			return prefix
+		}
+	}
+	return ""
+}
+
+// brewBinary returns the absolute path of brew, so it runs even from a
+// shell whose PATH the user's profile has not set up yet; plain "brew"
+// when no prefix has it.
+func brewBinary() string {
+	if prefix := BrewPrefix(); prefix != "" {
+		return filepath.Join(prefix, "bin", "brew")
+	}
+	return "brew"
+}
+
+// brewInvocation matches brew where a shell command starts: at the start
+// of the script or after a separator.
+var brewInvocation = regexp.MustCompile(`(^|[;&|(]\s*)brew(\s|$)`)
+
+// BrewCommand rewrites every brew invocation in script to brew's absolute
+// path, leaving scripts alone while Homebrew is not installed.
+func BrewCommand(script string) string {
+	binary := brewBinary()
+	if binary == "brew" {
+		return script
+	}
+	return brewInvocation.ReplaceAllString(script, "${1}"+shellQuote(binary)+"${2}")
+}
+
+// homebrewPresent reports whether a brew binary exists, on PATH or at one
+// of its standard prefixes.
+func homebrewPresent() bool {
+	if _, err := exec.LookPath("brew"); err == nil {
+		return true
+	}
+	return BrewPrefix() != ""
+}
+
+// macPortsPresent reports whether the MacPorts port command is available.
+func macPortsPresent() bool {
+	if _, err := exec.LookPath("port"); err == nil {
+		return true
+	}
+	return fileExists(macPortsBin + "/port")
+}
+
+// preferMacPorts is true when Homebrew is absent but MacPorts is installed,
+// in which case port recipes win over brew ones. It is a variable so the
+// decision can be pinned by callers that already know the answer.
+var preferMacPorts = func() bool {
+	return !homebrewPresent() && macPortsPresent()
+}
@@ -0,0 +1,64 @@
+package manager
+
+import (
+	"context"
+	"slices"
+
+	"github.com/hmziqrs/maziq/internal/catalog"
+)
+
+// brewNoAutoUpdate is added to brew commands once the run has updated
+// Homebrew itself.
+const brewNoAutoUpdate = "HOMEBREW_NO_AUTO_UPDATE=1"
+
+// usesBrew reports whether step installs or updates through brew, which
+// refreshes every tap first unless told not to.
+func (m *Manager) usesBrew(step Step) bool {
+	if step.Entry.ID == "homebrew" || step.Action == ActionUninstall {
+		return false
+	}
+	recipes := PreferredRecipes(step.Entry, step.Action, m.Options.Backend)
+	return len(recipes) > 0 && isBrew(recipes[0].Source)
+}
+
+func isBrew(source catalog.Source) bool {
+	return source == catalog.SourceBrew || source == catalog.SourceBrewCask
+}
+
+// batchBrewUpdate reports whether plan has enough brew steps that one
+// `brew update` up front beats letting each of them auto-update.
+func (m *Manager) batchBrewUpdate(plan Plan) bool {
+	if m.Options.Offline {
+		return false
+	}
+	n := 0
+	for _, step := range plan.Steps {
+		if m.usesBrew(step) {
+			n++
+		}
+	}
+	return n > 1
+}
+
+// updateBrew runs `brew update` once for the run. On success the brew
+// steps that follow skip their own auto-update; on failure they keep it,
+// so a flaky update costs nothing but time.
+func (m *Manager) updateBrew(ctx context.Context) ExecutionEvent {
+	event := m.Executor.Run(ctx, Command{
+		SoftwareID: "homebrew",
+		Action:     string(ActionUpdate),
+		Source:     string(catalog.SourceBrew),
+		Script:     "brew update",
+	})
+	m.brewUpdated = event.Success
+	return event
+}
+
+// brewEnv adds HOMEBREW_NO_AUTO_UPDATE to env for brew recipes once the
+// run has updated Homebrew.
+func (m *Manager) brewEnv(recipe catalog.Recipe, env []string) []string {
+	if !m.brewUpdated || !isBrew(recipe.Source) || slices.Contains(env, brewNoAutoUpdate) {
+		return env
+	}
+	return append(env, brewNoAutoUpdate)
+}
@@ -0,0 +1,80 @@
+package manager
+
+import (
+	"context"
+	"fmt"
+	"strings"
+
+	"github.com/hmziqrs/maziq/internal/catalog"
+	"github.com/hmziqrs/maziq/internal/errs"
+)
+
+// Conflict pairs an entry with one it must not be installed alongside.
+type Conflict struct {
+	ID   string
+	With string
+}
+
+func (c Conflict) String() string {
+	return c.ID + " ↔ " + c.With
+}
+
+// ConflictError is returned when a plan would install conflicting entries.
+type ConflictError struct {
+	Conflicts []Conflict
+}
+
+func (e *ConflictError) Error() string {
+	parts := make([]string, len(e.Conflicts))
+	for i, c := range e.Conflicts {
+		parts[i] = c.String()
+	}
+	return fmt.Sprintf("plan installs conflicting software: %s", strings.Join(parts, ", "))
+}
+
+func (e *ConflictError) ErrorCode() errs.Code {
+	return errs.CodePlan
+}
+
+// Conflicts returns the conflicting pairs among an install plan's own
+// entries, each pair once.
+func (p Plan) Conflicts() []Conflict {
+	if p.Action != ActionInstall {
+		return nil
+	}
+	var out []Conflict
+	for i, a := range p.Steps {
+		for _, b := range p.Steps[i+1:] {
+			if a.Entry.ConflictsWith(b.Entry.ID) {
+				out = append(out, Conflict{ID: a.Entry.ID, With: b.Entry.ID})
+			}
+		}
+	}
+	return out
+}
+
+// InstalledConflicts probes the entries that conflict with the plan's install
+// steps but are not part of it, and returns those already installed.
+func InstalledConflicts(ctx context.Context, plan Plan) []Conflict {
+	if plan.Action != ActionInstall {
+		return nil
+	}
+	inPlan := map[string]bool{}
+	for _, step := range plan.Steps {
+		inPlan[step.Entry.ID] = true
+	}
+	prober := NewProber(0)
+	var out []Conflict
+	for _, step := range plan.Steps {
+		for _, id := range step.Entry.Conflicts {
+			other, ok := catalog.Lookup(id)
+			if !ok || inPlan[id] {
+				continue
+			}
+			if prober.Probe(ctx, other).State == StateInstalled {
+				out = append(out, Conflict{ID: step.Entry.ID, With: id})
+			}
+		}
+	}
+	return out
+}
@@ -0,0 +1,88 @@
+package manager
+
+import (
+	"context"
+	"os"
+	"os/exec"
+	"path/filepath"
+	"sort"
+	"strings"
+
+	"github.com/hmziqrs/maziq/internal/catalog"
+)
+
+// applicationsDir is where Discover looks for app bundles.
+const applicationsDir = "/Applications"
+
+// App is an application bundle found by Discover.
+type App struct {
+	Path       string
+	// Entry is the id of the catalog entry whose probe points at the
+	// bundle; empty when the catalog does not know the app.
+	Entry      string
+	// Provenance is who installed the entry; unset without one.
+	Provenance Provenance
+}
+
+// Discover lists the app bundles in /Applications through Spotlight and
+// matches them against the catalog, so apps installed by hand or by a
+// plain `brew install --cask` show up as known entries. Without Spotlight
+// (indexing off) it falls back to reading the directory.
+func Discover(ctx context.Context) ([]App, error) {
+	paths, err := spotlightApps(ctx)
+	if err != nil || len(paths) == 0 {
+		if paths, err = listApps(); err != nil {
+			return nil, err
+		}
+	}
+	byPath := map[string]catalog.Entry{}
+	for _, e := range catalog.All() {
+		if e.Probe.AppPath != "" {
+			byPath[e.Probe.AppPath] = e
+		}
+	}
+	provenances, err := LoadProvenances(ctx)
+	if err != nil {
+		return nil, err
+	}
+	apps := make([]App, 0, len(paths))
+	for _, path := range paths {
+		app := App{Path: path}
+		if entry, ok := byPath[path]; ok {
+			app.Entry, app.Provenance = entry.ID, provenances.Of(entry)
+		}
+		apps = append(apps, app)
+	}
+	sort.Slice(apps, func(i, j int) bool { return apps[i].Path < apps[j].Path })
+	return apps, nil
+}
+
+// spotlightApps asks Spotlight for the app bundles directly in
+// /Applications; nested ones (helpers, updaters) are left out.
+func spotlightApps(ctx context.Context) ([]string, error) {
+	out, err := exec.CommandContext(ctx, "mdfind", "-onlyin", applicationsDir, "kMDItemContentType == 'com.apple.application-bundle'").Output()
+	if err != nil {
+		return nil, err
+	}
+	var paths []string
+	for _, line := range strings.Split(strings.TrimSpace(string(out)), "\n") {
+		if filepath.Dir(line) == applicationsDir {
+			paths = append(paths, line)
+		}
+	}
+	return paths, nil
+}
+
+func listApps() ([]string, error) {
+	entries, err := os.ReadDir(applicationsDir)
+	if err != nil {
+		return nil, err
+	}
+	var paths []string
+	for _, e := range entries {
+		if strings.HasSuffix(e.Name(), ".app") {
+			paths = append(paths, filepath.Join(applicationsDir, e.Name()))
+		}
+	}
+	return paths, nil
+}
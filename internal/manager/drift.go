@@ -0,0 +1,118 @@
+package manager
+
+import (
+	"context"
+	"fmt"
+
+	"github.com/hmziqrs/maziq/internal/catalog"
+	"github.com/hmziqrs/maziq/internal/options"
+	"github.com/hmziqrs/maziq/internal/templates"
+)
+
+// DriftItem is one entry that differs from a template.
+type DriftItem struct {
+	ID     string
+	Detail string
+}
+
+// Drift compares the machine's detected state against a template.
+type Drift struct {
+	Template string
+	// Missing entries are in the template (or its dependencies) but not
+	// installed.
+	Missing  []DriftItem
+	// Outdated entries are installed but behind, or differ from the
+	// template's pinned version.
+	Outdated []DriftItem
+	// Extra entries are installed but not part of the template.
+	Extra    []DriftItem
+	// Unknown entries could not be probed.
+	Unknown  []DriftItem
+	opts     ResolveOptions
+}
+
+// Empty reports whether the machine matches the template.
+func (d Drift) Empty() bool {
+	return len(d.Missing) == 0 && len(d.Outdated) == 0 && len(d.Extra) == 0
+}
+
+// DetectDrift probes every catalog entry and sorts the differences from t
+// into missing, outdated and extra software. The remediation plans
+// follow run.
+func DetectDrift(ctx context.Context, t templates.Template, run options.Run) (Drift, error) {
+	opts := TemplateOptions(t)
+	opts.Run = run
+	plan, err := ResolveWith(t.Software, ActionInstall, opts)
+	if err != nil {
+		return Drift{}, err
+	}
+	expected := map[string]bool{}
+	for _, id := range plan.IDs() {
+		expected[id] = true
+	}
+
+	drift := Drift{Template: t.Name, opts: opts}
+	statuses := NewProber(run.Jobs).ProbeAll(ctx, catalog.All(), ProbeModeFull)
+	for _, entry := range catalog.All() {
+		st := statuses[entry.ID]
+		installed := st.State == StateInstalled || st.State == StateOutdated
+		switch {
+		case !expected[entry.ID]:
+			if installed {
+				drift.Extra = append(drift.Extra, DriftItem{ID: entry.ID, Detail: st.Version})
+			}
+		case st.State == StateNotInstalled:
+			drift.Missing = append(drift.Missing, DriftItem{ID: entry.ID})
+		case installed && t.Versions[entry.ID] != "":
+			// A pin wins over newer releases.
+			if st.Version != "" && st.Version != t.Versions[entry.ID] {
+				detail := fmt.Sprintf("%s, pinned %s", st.Version, t.Versions[entry.ID])
+				drift.Outdated = append(drift.Outdated, DriftItem{ID: entry.ID, Detail: detail})
+			}
+		case st.State == StateOutdated:
+			detail := st.Version + " (update available)"
+			if st.Latest != "" {
+				detail = fmt.Sprintf("%s (%s available)", st.Version, st.Latest)
+			}
+			drift.Outdated = append(drift.Outdated, DriftItem{ID: entry.ID, Detail: detail})
+		case !installed:
+			drift.Unknown = append(drift.Unknown, DriftItem{ID: entry.ID, Detail: st.Error})
+		}
+	}
+	return drift, nil
+}
+
+// Remediation returns the plans that bring the machine back in line:
+// installing what is missing, then updating what is outdated. Extra
+// software is only reported, never removed.
+func (d Drift) Remediation() ([]Plan, error) {
+	var plans []Plan
+	for _, group := range []struct {
+		action Action
+		items  []DriftItem
+	}{
+		{ActionInstall, d.Missing},
+		{ActionUpdate, d.Outdated},
+	} {
+		if len(group.items) == 0 {
+			continue
+		}
+		ids := make([]string, len(group.items))
+		for i, item := range group.items {
+			ids[i] = item.ID
+		}
+		// Ids are already substituted; only pins, hooks, profiles and run
+		// options still matter.
+		plan, err := ResolveWith(ids, group.action, ResolveOptions{
+			Versions:       d.opts.Versions,
+			PostInstall:    d.opts.PostInstall,
+			EditorProfiles: d.opts.EditorProfiles,
+			Run:            d.opts.Run,
+		})
+		if err != nil {
+			return nil, err
+		}
+		plans = append(plans, plan)
+	}
+	return plans, nil
+}
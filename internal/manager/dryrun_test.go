@@ -0,0 +1,55 @@
+package manager
+
+import (
+	"context"
+	"errors"
+	"testing"
+
+	"github.com/hmziqrs/maziq/internal/catalog"
+	"github.com/hmziqrs/maziq/internal/options"
+	"github.com/hmziqrs/maziq/internal/testenv"
+)
+
+// A dry run of every catalog entry, with hooks and an editor profile to
+// provision, may probe the machine but must not write to it or run
+// anything that changes it.
+func TestDryRunWritesNothing(t *testing.T) {
+	var ids []string
+	for _, entry := range catalog.All() {
+		ids = append(ids, entry.ID)
+	}
+	for _, action := range []Action{ActionInstall, ActionUpdate, ActionUninstall} {
+		t.Run(string(action), func(t *testing.T) {
+			env := testenv.New(t)
+			opts := options.Defaults()
+			opts.DryRun = true
+			opts.ForceAdopted = true
+			opts.ConfirmSDKRemoval = true
+			opts.NonInteractive = true
+			plan, err := ResolveWith(ids, action, ResolveOptions{
+				Run:            opts,
+				PostInstall:    map[string][]string{"visual_studio_code": {"git config --global core.editor 'code --wait'"}},
+				EditorProfiles: []string{"vscode"},
+			})
+			if err != nil {
+				t.Fatal(err)
+			}
+			started := 0
+			err = New(opts).RunPlan(context.Background(), plan, func(ev TaskEvent) {
+				if ev.Kind == EventStepStarted {
+					started++
+				}
+			})
+			// Entries without a recipe for action fail their step; the
+			// run as a whole must still get through.
+			var partial *RunError
+			if err != nil && !errors.As(err, &partial) {
+				t.Fatalf("dry run failed: %v", err)
+			}
+			if started == 0 {
+				t.Fatal("the dry run started no step")
+			}
+			env.AssertUntouched(t)
+		})
+	}
+}
@@ -0,0 +1,302 @@
+package manager
+
+import (
+	"context"
+	"errors"
+	"fmt"
+	"runtime/debug"
+	"slices"
+	"sync"
+	"time"
+
+	"github.com/hmziqrs/maziq/internal/crash"
+)
+
+// JobID identifies a job submitted to an Engine.
+type JobID int
+
+// JobEvent is either a value emitted by a running job or, when Done is set,
+// its completion.
+type JobEvent struct {
+	Job     JobID
+	Name    string
+	Payload any
+	Done    bool
+	Err     error
+}
+
+// JobStarted is emitted as a payload when a queued job leaves the queue
+// and starts running.
+type JobStarted struct{}
+
+// TaskState is where a job is in its life.
+type TaskState int
+
+const (
+	TaskPending TaskState = iota
+	TaskRunning
+	TaskDone
+	TaskFailed
+	TaskCancelled
+)
+
+func (s TaskState) String() string {
+	switch s {
+	case TaskPending:
+		return "pending"
+	case TaskRunning:
+		return "running"
+	case TaskDone:
+		return "done"
+	case TaskFailed:
+		return "failed"
+	default:
+		return "cancelled"
+	}
+}
+
+// Task describes a submitted or queued job for task lists.
+type Task struct {
+	ID      JobID
+	Name    string
+	State   TaskState
+	// Queued is set for jobs that waited their turn in the queue.
+	Queued  bool
+	Created time.Time
+	Started time.Time
+	Ended   time.Time
+	Err     error
+}
+
+// keepFinished bounds how many finished tasks Tasks still lists.
+const keepFinished = 100
+
+// Job is the body of a background job. It must return promptly once ctx is
+// cancelled and reports progress by calling emit.
+type Job func(ctx context.Context, emit func(any)) error
+
+// Engine multiplexes long-running jobs (plan runs, status probes) onto
+// goroutines, funnels everything they emit into a single event stream and
+// lets callers cancel jobs individually.
+type Engine struct {
+	events chan JobEvent
+	quit   chan struct{}
+	wg     sync.WaitGroup
+
+	mu      sync.Mutex
+	next    JobID
+	cancels map[JobID]context.CancelFunc
+	tasks   []*Task
+	// queue holds jobs waiting for the queued job running before them;
+	// busy is set while one runs.
+	queue   []queuedJob
+	busy    bool
+}
+
+type queuedJob struct {
+	id  JobID
+	job Job
+}
+
+// NewEngine returns an idle engine.
+func NewEngine() *Engine {
+	return &Engine{
+		events:  make(chan JobEvent, 256),
+		quit:    make(chan struct{}),
+		cancels: map[JobID]context.CancelFunc{},
+	}
+}
+
+// Events is the stream of every job's emitted values and completions.
+func (e *Engine) Events() <-chan JobEvent {
+	return e.events
+}
+
+// Submit starts job in the background and returns its id immediately.
+func (e *Engine) Submit(name string, job Job) JobID {
+	e.mu.Lock()
+	defer e.mu.Unlock()
+	task := e.add(name, false)
+	e.start(task, job)
+	return task.ID
+}
+
+// Enqueue adds job to the queue, whose jobs run one at a time in order,
+// and returns its id immediately. Use it for jobs that change the system,
+// so two runs never install at once.
+func (e *Engine) Enqueue(name string, job Job) JobID {
+	e.mu.Lock()
+	defer e.mu.Unlock()
+	task := e.add(name, true)
+	e.queue = append(e.queue, queuedJob{task.ID, job})
+	e.advance()
+	return task.ID
+}
+
+// add records a new task. e.mu must be held.
+func (e *Engine) add(name string, queued bool) *Task {
+	e.next++
+	task := &Task{ID: e.next, Name: name, Queued: queued, Created: time.Now()}
+	e.tasks = append(e.tasks, task)
+	// Forget the oldest finished task once there are too many.
+	finished := 0
+	for _, t := range e.tasks {
+		if t.State > TaskRunning {
+			finished++
+		}
+	}
+	if finished > keepFinished {
+		for i, t := range e.tasks {
+			if t.State > TaskRunning {
+				e.tasks = slices.Delete(e.tasks, i, i+1)
+				break
+			}
+		}
+	}
+	return task
+}
+
+// advance starts the next queued job unless one is running. e.mu must be
+// held.
+func (e *Engine) advance() {
+	if e.busy || len(e.queue) == 0 {
+		return
+	}
+	next := e.queue[0]
+	e.queue = e.queue[1:]
+	e.busy = true
+	e.start(e.task(next.id), next.job)
+}
+
+// start runs job for task. e.mu must be held.
+func (e *Engine) start(task *Task, job Job) {
+	ctx, cancel := context.WithCancel(context.Background())
+	e.cancels[task.ID] = cancel
+	task.State, task.Started = TaskRunning, time.Now()
+	id, name := task.ID, task.Name
+
+	e.wg.Add(1)
+	go func() {
+		defer e.wg.Done()
+		defer cancel()
+		if task.Queued {
+			e.send(JobEvent{Job: id, Name: name, Payload: JobStarted{}})
+		}
+		err := runJob(ctx, job, func(payload any) {
+			e.send(JobEvent{Job: id, Name: name, Payload: payload})
+		})
+		e.mu.Lock()
+		delete(e.cancels, id)
+		e.finish(task, err)
+		e.mu.Unlock()
+		e.send(JobEvent{Job: id, Name: name, Done: true, Err: err})
+		// Only now, so listeners see this job end before the next starts.
+		if task.Queued {
+			e.mu.Lock()
+			e.busy = false
+			e.advance()
+			e.mu.Unlock()
+		}
+	}()
+}
+
+// runJob runs job, turning a panic into the job's error so one broken job
+// neither kills the process nor leaves the TUI's terminal unusable. A crash
+// report is written all the same.
+func runJob(ctx context.Context, job Job, emit func(any)) (err error) {
+	defer func() {
+		if r := recover(); r != nil {
+			path, werr := crash.Write(r, debug.Stack())
+			if werr != nil {
+				path = "not written: " + werr.Error()
+			}
+			err = fmt.Errorf("internal error: %v (crash report %s)", r, path)
+		}
+	}()
+	return job(ctx, emit)
+}
+
+// finish records how task ended. e.mu must be held.
+func (e *Engine) finish(task *Task, err error) {
+	task.Ended, task.Err = time.Now(), err
+	switch {
+	case errors.Is(err, context.Canceled):
+		task.State = TaskCancelled
+	case err != nil:
+		task.State = TaskFailed
+	default:
+		task.State = TaskDone
+	}
+}
+
+// task finds a recorded task. e.mu must be held.
+func (e *Engine) task(id JobID) *Task {
+	for _, t := range e.tasks {
+		if t.ID == id {
+			return t
+		}
+	}
+	return nil
+}
+
+// Tasks lists pending, running and recently finished jobs, oldest first.
+func (e *Engine) Tasks() []Task {
+	e.mu.Lock()
+	defer e.mu.Unlock()
+	out := make([]Task, len(e.tasks))
+	for i, t := range e.tasks {
+		out[i] = *t
+	}
+	return out
+}
+
+// send delivers ev unless the engine has been shut down and nobody is
+// listening anymore.
+func (e *Engine) send(ev JobEvent) {
+	select {
+	case e.events <- ev:
+	case <-e.quit:
+	}
+}
+
+// Cancel stops a running job or drops a queued one before it starts. It
+// reports whether the job had not finished yet.
+func (e *Engine) Cancel(id JobID) bool {
+	e.mu.Lock()
+	for i, q := range e.queue {
+		if q.id == id {
+			e.queue = slices.Delete(e.queue, i, i+1)
+			task := e.task(id)
+			e.finish(task, context.Canceled)
+			e.mu.Unlock()
+			go e.send(JobEvent{Job: id, Name: task.Name, Done: true, Err: context.Canceled})
+			return true
+		}
+	}
+	cancel, ok := e.cancels[id]
+	e.mu.Unlock()
+	if ok {
+		cancel()
+	}
+	return ok
+}
+
+// Running returns the number of jobs that have not finished yet.
+func (e *Engine) Running() int {
+	e.mu.Lock()
+	defer e.mu.Unlock()
+	return len(e.cancels)
+}
+
+// Shutdown cancels every running job and waits for them to exit, so no
+// child process outlives the frontend. The engine must not be used after.
+func (e *Engine) Shutdown() {
+	e.mu.Lock()
+	e.queue = nil
+	for _, cancel := range e.cancels {
+		cancel()
+	}
+	e.mu.Unlock()
+	close(e.quit)
+	e.wg.Wait()
+}
@@ -0,0 +1,58 @@
+package manager
+
+import (
+	"os"
+	"path/filepath"
+	"strings"
+	"sync"
+
+	"github.com/hmziqrs/maziq/internal/paths"
+)
+
+// toolPaths are the bin directories installers create, highest priority
+// first. Once one exists it belongs on PATH for the rest of the process.
+func toolPaths() []string {
+	home := paths.Home()
+	return []string{
+		"/opt/homebrew/bin",
+		"/opt/homebrew/sbin",
+		"/usr/local/bin",
+		macPortsBin,
+		"/opt/local/sbin",
+		filepath.Join(home, ".nix-profile", "bin"),
+		"/nix/var/nix/profiles/default/bin",
+		filepath.Join(home, ".cargo", "bin"),
+		filepath.Join(home, ".bun", "bin"),
+		filepath.Join(home, "go", "bin"),
+		filepath.Join(home, ".local", "share", "mise", "shims"),
+		filepath.Join(home, ".asdf", "shims"),
+		filepath.Join(home, ".local", "bin"),
+	}
+}
+
+var pathMu sync.Mutex
+
+// RefreshPath prepends every tool directory that exists but is missing from
+// PATH, the equivalent of re-sourcing `brew shellenv` or ~/.cargo/env, so
+// binaries installed earlier in a run are found by later steps and probes.
+// It returns the directories it added.
+func RefreshPath() []string {
+	pathMu.Lock()
+	defer pathMu.Unlock()
+
+	current := filepath.SplitList(os.Getenv("PATH"))
+	present := map[string]bool{}
+	for _, dir := range current {
+		present[dir] = true
+	}
+	var added []string
+	for _, dir := range toolPaths() {
+		if !present[dir] && fileExists(dir) {
+			added = append(added, dir)
+		}
+	}
+	if len(added) > 0 {
+		os.Setenv("PATH", strings.Join(append(added, current...), string(os.PathListSeparator)))
+	}
+	return added
+}
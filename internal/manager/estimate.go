@@ -0,0 +1,35 @@
+package manager
+
+import (
+	"time"
+
+	"github.com/hmziqrs/maziq/internal/history"
+)
+
+// Estimate returns the expected duration of each plan step, keyed by
+// software id, from the rolling average of recorded runs. Entries that were
+// never timed are absent.
+func Estimate(plan Plan) map[string]time.Duration {
+	averages, err := history.AverageDurations(string(plan.Action))
+	if err != nil {
+		return nil
+	}
+	out := map[string]time.Duration{}
+	for _, step := range plan.Steps {
+		if d, ok := averages[step.Entry.ID]; ok {
+			out[step.Entry.ID] = d
+		}
+	}
+	return out
+}
+
+// FormatETA renders d rounded for humans, e.g. "~4m30s".
+func FormatETA(d time.Duration) string {
+	switch {
+	case d <= 0:
+		return "?"
+	case d < time.Minute:
+		return "~" + d.Round(time.Second).String()
+	}
+	return "~" + d.Round(10*time.Second).String()
+}
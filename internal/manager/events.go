@@ -0,0 +1,107 @@
+package manager
+
+import (
+	"encoding/json"
+	"errors"
+	"fmt"
+	"io"
+	"strings"
+	"time"
+
+	"github.com/hmziqrs/maziq/internal/crash"
+)
+
+// EventKind says what a TaskEvent reports.
+type EventKind string
+
+const (
+	// EventPlanComputed opens a run; Total is the number of steps.
+	EventPlanComputed EventKind = "plan_computed"
+	// EventStepStarted is a step's command about to run.
+	EventStepStarted  EventKind = "step_started"
+	// EventStepOutput is anything said along the way: preflight checks,
+	// retries, hints, hooks. It never changes a step's outcome.
+	EventStepOutput   EventKind = "step_output"
+	// EventStepFinished is a step's outcome: Skipped, or Execution's
+	// Success. A step whose test fails after it succeeded finishes again,
+	// failed; the last one counts.
+	EventStepFinished EventKind = "step_finished"
+	// EventRunFinished closes a run; Reason is set when it did not
+	// complete.
+	EventRunFinished  EventKind = "run_finished"
+)
+
+// noted passes events on to report after remembering them for a crash
+// report.
+func noted(report func(TaskEvent)) func(TaskEvent) {
+	return func(ev TaskEvent) {
+		crash.Note(strings.TrimSpace(string(ev.Kind) + " " + ev.SoftwareID + " " + ev.Message))
+		report(ev)
+	}
+}
+
+// runFinished is the event that closes a run of total steps ending with
+// err.
+func runFinished(total int, err error) TaskEvent {
+	ev := TaskEvent{Kind: EventRunFinished, Message: "run finished", Time: time.Now(), Total: total}
+	var failed *RunError
+	switch {
+	case errors.As(err, &failed):
+		ev.Message = fmt.Sprintf("run finished with %d failed step(s)", len(failed.Failed))
+		ev.Reason = err.Error()
+	case err != nil:
+		ev.Message = "run stopped: " + err.Error()
+		ev.Reason = err.Error()
+	}
+	return ev
+}
+
+// eventJSON is the exported form of a TaskEvent, one JSON object per line.
+type eventJSON struct {
+	Kind       EventKind `json:"kind"`
+	ID         string    `json:"id,omitempty"`
+	Time       time.Time `json:"time"`
+	Step       int       `json:"step,omitempty"`
+	Total      int       `json:"total,omitempty"`
+	Message    string    `json:"message"`
+	Skipped    bool      `json:"skipped,omitempty"`
+	Hook       bool      `json:"hook,omitempty"`
+	Reason     string    `json:"reason,omitempty"`
+	Command    string    `json:"command,omitempty"`
+	Source     string    `json:"source,omitempty"`
+	Success    *bool     `json:"success,omitempty"`
+	ExitCode   int       `json:"exit_code,omitempty"`
+	// DurationMs is the command's wall time.
+	DurationMs int64     `json:"duration_ms,omitempty"`
+}
+
+// EventWriter writes the events of a run to w as JSON lines, for tools
+// that follow a run without parsing its messages.
+type EventWriter struct {
+	enc *json.Encoder
+}
+
+func NewEventWriter(w io.Writer) *EventWriter {
+	return &EventWriter{enc: json.NewEncoder(w)}
+}
+
+// Write encodes ev as one line.
+func (w *EventWriter) Write(ev TaskEvent) error {
+	out := eventJSON{
+		Kind:    ev.Kind,
+		ID:      ev.SoftwareID,
+		Time:    ev.Time.UTC(),
+		Step:    ev.Step,
+		Total:   ev.Total,
+		Message: ev.Message,
+		Skipped: ev.Skipped,
+		Hook:    ev.Hook,
+		Reason:  ev.Reason,
+	}
+	if x := ev.Execution; x != nil {
+		success := x.Success
+		out.Command, out.Source, out.Success = x.Command, x.Source, &success
+		out.ExitCode, out.DurationMs = x.ExitCode, x.Duration.Milliseconds()
+	}
+	return w.enc.Encode(out)
+}
@@ -0,0 +1,282 @@
+// Package manager runs install, update and uninstall commands for catalog
+// entries and reports what happened as ExecutionEvents.
+package manager
+
+import (
+	"context"
+	"errors"
+	"fmt"
+	"log/slog"
+	"os"
+	"os/exec"
+	"path/filepath"
+	"strings"
+	"syscall"
+	"time"
+
+	"github.com/hmziqrs/maziq/internal/catalog"
+	"github.com/hmziqrs/maziq/internal/paths"
+)
+
+// Command is a single shell command run on behalf of a software entry.
+type Command struct {
+	SoftwareID string
+	Action     string
+	Source     string
+	Script     string
+	// Env holds KEY=VALUE pairs added to the inherited environment.
+	Env        []string
+	// Mode is the shell Script runs in; ShellSh uses the executor's.
+	Mode       catalog.ShellMode
+	// Dir and TempDir choose the working directory, as on catalog.Recipe.
+	Dir        string
+	TempDir    bool
+}
+
+// RetryAttempt records a failed attempt that the executor retried.
+type RetryAttempt struct {
+	Attempt int
+	Reason  string
+	Delay   time.Duration
+}
+
+// ExecutionEvent is the outcome of running a Command.
+type ExecutionEvent struct {
+	SoftwareID string
+	Action     string
+	Source     string
+	Command    string
+	Attempts   int
+	Retries    []RetryAttempt
+	Success    bool
+	Error      string
+	Timestamp  time.Time
+	// Duration is the wall time spent, retries and backoff included.
+	Duration   time.Duration
+	// ExitCode is the last attempt's exit status, or -1 when the command
+	// could not start or was killed by a signal.
+	ExitCode   int
+	// Stdout and Stderr hold the tail of the last attempt's output, at most
+	// outputLimit bytes each.
+	Stdout     string
+	Stderr     string
+	// Hint is advice for a recognized failure, shown next to Error.
+	Hint       string
+	// Reused marks a command that already succeeded earlier in the same
+	// run and was not run again.
+	Reused     bool
+}
+
+// outputLimit caps the output kept per stream. The tail is what matters:
+// that is where installers print their errors.
+const outputLimit = 16 << 10
+
+// tailBuffer keeps the last outputLimit bytes written to it.
+type tailBuffer struct {
+	buf       []byte
+	truncated bool
+}
+
+func (b *tailBuffer) Write(p []byte) (int, error) {
+	b.buf = append(b.buf, p...)
+	if over := len(b.buf) - outputLimit; over > 0 {
+		b.buf = append(b.buf[:0], b.buf[over:]...)
+		b.truncated = true
+	}
+	return len(p), nil
+}
+
+func (b *tailBuffer) String() string {
+	if b.truncated {
+		return "[…]" + string(b.buf)
+	}
+	return string(b.buf)
+}
+
+// CommandExecutor runs shell commands, retrying transient failures.
+type CommandExecutor struct {
+	Shell   string
+	Retry   RetryPolicy
+	DryRun  bool
+	// Askpass is the SUDO_ASKPASS helper used by sudo inside commands.
+	// Without one, sudo only succeeds on cached credentials.
+	Askpass string
+}
+
+// NewCommandExecutor returns an executor using sh and the default retry policy.
+func NewCommandExecutor() *CommandExecutor {
+	return &CommandExecutor{
+		Shell: "sh",
+		Retry: DefaultRetryPolicy(),
+	}
+}
+
+// Run executes cmd and returns the resulting event. Failures whose stderr
+// looks transient are retried according to the executor's RetryPolicy.
+func (e *CommandExecutor) Run(ctx context.Context, cmd Command) (event ExecutionEvent) {
+	event = ExecutionEvent{
+		SoftwareID: cmd.SoftwareID,
+		Action:     cmd.Action,
+		Source:     cmd.Source,
+		Command:    cmd.Script,
+		Timestamp:  time.Now(),
+	}
+	defer func() {
+		event.Duration = time.Since(event.Timestamp)
+	}()
+	if e.DryRun {
+		event.Success = true
+		return event
+	}
+
+	log := slog.With("software", cmd.SoftwareID, "action", cmd.Action, "source", cmd.Source)
+	log.Debug("running command", "command", cmd.Script, "env", cmd.Env)
+	policy := e.Retry.normalized()
+	for attempt := 1; ; attempt++ {
+		event.Attempts = attempt
+		dir, cleanup, err := workDir(cmd)
+		if err != nil {
+			event.Error = err.Error()
+			event.ExitCode = -1
+			log.Error("command failed", "error", event.Error)
+			return event
+		}
+		stdout, stderr, err := e.runOnce(ctx, cmd.Mode, dir, e.prepare(cmd), cmd.Env)
+		cleanup()
+		event.Stdout, event.Stderr, event.ExitCode = stdout, stderr, exitCode(err)
+		if err == nil {
+			event.Success = true
+			event.Error = ""
+			log.Info("command succeeded", "attempts", attempt)
+			return event
+		}
+		event.Error = errorText(err, stderr)
+
+		reason := transientReason(stderr)
+		if attempt >= policy.MaxAttempts || reason == "" || ctx.Err() != nil {
+			log.Error("command failed", "attempts", attempt, "exit_code", event.ExitCode, "error", event.Error)
+			event.Hint = Hint(stderr + "\n" + event.Error)
+			return event
+		}
+		delay := policy.delay(attempt)
+		log.Warn("retrying command", "attempt", attempt, "reason", reason, "delay", delay)
+		event.Retries = append(event.Retries, RetryAttempt{
+			Attempt: attempt,
+			Reason:  reason,
+			Delay:   delay,
+		})
+		select {
+		case <-ctx.Done():
+			event.Error = ctx.Err().Error()
+			return event
+		case <-time.After(delay):
+		}
+	}
+}
+
+// nixProfileScript puts nix on PATH for shells that were started before
+// the user's profile sourced it (fresh installs, launchd, the TUI).
+const nixProfileScript = "/nix/var/nix/profiles/default/etc/profile.d/nix-daemon.sh"
+
+// nvmEnv loads nvm: it is a shell function, so node only exists once it
+// is sourced.
+const nvmEnv = `export NVM_DIR="${NVM_DIR:-$HOME/.nvm}"; [ -s "$NVM_DIR/nvm.sh" ] && . "$NVM_DIR/nvm.sh"; `
+
+// prepare adapts a command's script to the environment its source needs and
+// makes any sudo in it non-interactive.
+func (e *CommandExecutor) prepare(cmd Command) string {
+	if cmd.Mode == catalog.ShellFish {
+		// The shims below are POSIX shell; fish gets its own for sudo.
+		if NeedsSudo(cmd.Script) {
+			cmd.Script = fishSudoShim(e.Askpass) + cmd.Script
+		}
+		return cmd.Script
+	}
+	if NeedsSudo(cmd.Script) {
+		cmd.Script = sudoShim(e.Askpass) + cmd.Script
+	}
+	switch cmd.Source {
+	case "brew", "brew-cask":
+		return BrewCommand(cmd.Script)
+	case "nix":
+		return fmt.Sprintf("[ -r %s ] && . %s; %s", nixProfileScript, nixProfileScript, cmd.Script)
+	case "port":
+		return fmt.Sprintf("PATH=%s:/opt/local/sbin:$PATH; %s", macPortsBin, cmd.Script)
+	case "npm":
+		return nvmEnv + cmd.Script
+	}
+	return cmd.Script
+}
+
+// workDir returns the directory cmd runs in, "" for maziq's own, and a
+// cleanup to call once it ran. Every attempt gets a fresh temporary
+// directory, so a retried clone does not trip over the last one.
+func workDir(cmd Command) (string, func(), error) {
+	switch {
+	case cmd.TempDir:
+		dir, err := os.MkdirTemp("", "maziq-"+cmd.SoftwareID+"-")
+		if err != nil {
+			return "", nil, fmt.Errorf("create working directory: %w", err)
+		}
+		return dir, func() { os.RemoveAll(dir) }, nil
+	case cmd.Dir != "":
+		dir := cmd.Dir
+		if rest, ok := strings.CutPrefix(dir, "~/"); ok {
+			dir = filepath.Join(paths.Home(), rest)
+		}
+		if err := os.MkdirAll(dir, 0o755); err != nil {
+			return "", nil, fmt.Errorf("create working directory: %w", err)
+		}
+		return dir, func() {}, nil
+	}
+	return "", func() {}, nil
+}
+
+// runOnce runs script once in mode's shell and dir with env added to the
+// inherited environment and returns the tail of its stdout and stderr.
+func (e *CommandExecutor) runOnce(ctx context.Context, mode catalog.ShellMode, dir, script string, env []string) (string, string, error) {
+	argv := mode.Argv()
+	if mode == catalog.ShellSh && e.Shell != "" {
+		argv[0] = e.Shell
+	}
+	var stdout, stderr tailBuffer
+	c := exec.CommandContext(ctx, argv[0], append(argv[1:], script)...)
+	c.Dir = dir
+	if len(env) > 0 {
+		c.Env = append(os.Environ(), env...)
+	}
+	c.Stdout = &stdout
+	c.Stderr = &stderr
+	// Run in its own process group so cancelling also stops whatever the
+	// script spawned (brew, curl, installers), not just the shell.
+	c.SysProcAttr = &syscall.SysProcAttr{Setpgid: true}
+	c.Cancel = func() error {
+		return syscall.Kill(-c.Process.Pid, syscall.SIGTERM)
+	}
+	c.WaitDelay = 5 * time.Second
+	err := c.Run()
+	return stdout.String(), stderr.String(), err
+}
+
+// exitCode extracts the exit status from a runOnce error.
+func exitCode(err error) int {
+	if err == nil {
+		return 0
+	}
+	var exit *exec.ExitError
+	if errors.As(err, &exit) {
+		return exit.ExitCode()
+	}
+	return -1
+}
+
+// errorText prefers the last meaningful stderr line over the bare exit status.
+func errorText(err error, stderr string) string {
+	lines := strings.Split(strings.TrimSpace(stderr), "\n")
+	for i := len(lines) - 1; i >= 0; i-- {
+		if line := strings.TrimSpace(lines[i]); line != "" {
+			return line
+		}
+	}
+	return err.Error()
+}
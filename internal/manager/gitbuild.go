@@ -0,0 +1,51 @@
+package manager
+
+import (
+	"fmt"
+	"path/filepath"
+	"strings"
+
+	"github.com/hmziqrs/maziq/internal/catalog"
+	"github.com/hmziqrs/maziq/internal/paths"
+)
+
+// gitCheckout is where the source of entry id is kept between updates.
+func gitCheckout(id string) string {
+	return filepath.Join(paths.SourcesDir(), id)
+}
+
+// gitScript performs action on the GitBuild of entry id. Installing clones
+// afresh, updating pulls into the checkout (cloning when it is gone); both
+// then build and copy the artifacts into ~/.local/bin. Uninstalling
+// removes the artifacts and the checkout.
+func gitScript(id string, action Action, git catalog.GitBuild) string {
+	src := shellQuote(gitCheckout(id))
+	bin := paths.LocalBinDir()
+	clone := "git clone --depth 1"
+	if git.Ref != "" {
+		clone += " --branch " + shellQuote(git.Ref)
+	}
+	clone += " " + shellQuote(git.Repo) + " " + src
+
+	var steps []string
+	switch action {
+	case ActionUninstall:
+		for _, artifact := range git.Artifacts {
+			steps = append(steps, "rm -f "+shellQuote(filepath.Join(bin, filepath.Base(artifact))))
+		}
+		return strings.Join(append(steps, "rm -rf "+src), " && ")
+	case ActionInstall:
+		steps = append(steps, "rm -rf "+src, clone)
+	default:
+		steps = append(steps, fmt.Sprintf("if [ -d %[1]s/.git ]; then git -C %[1]s pull --ff-only; else %[2]s; fi", src, clone))
+	}
+	steps = append(steps, "cd "+src)
+	if git.Build != "" {
+		steps = append(steps, "("+git.Build+")")
+	}
+	steps = append(steps, "mkdir -p "+shellQuote(bin))
+	for _, artifact := range git.Artifacts {
+		steps = append(steps, "install -m 755 "+shellQuote(artifact)+" "+shellQuote(bin+"/"))
+	}
+	return "set -e; " + strings.Join(steps, "; ")
+}
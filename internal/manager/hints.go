@@ -0,0 +1,47 @@
+package manager
+
+import "regexp"
+
+// hint maps a recognizable failure to what the user can do about it.
+type hint struct {
+	pattern *regexp.Regexp
+	advice  string
+}
+
+// hints are checked in order against a failed command's stderr and error;
+// the first match wins. Keep the advice to one actionable sentence.
+var hints = []hint{
+	{regexp.MustCompile(`(?i)no such keg`),
+		"Homebrew did not install this; remove it the way it was installed, or check `brew list`."},
+	{regexp.MustCompile(`(?i)it seems there is already an app at`),
+		"An app of that name is already in /Applications; delete it or let Homebrew take it over with `brew install --cask --adopt`."},
+	{regexp.MustCompile(`(?i)(brew|mas|cargo|npm|pipx|mise): (command )?not found|command not found: (brew|mas|cargo|npm|pipx|mise)`),
+		"The tool this recipe uses is not installed or not on PATH; install its catalog entry first or open a new shell."},
+	{regexp.MustCompile(`(?i)npm (err!|error) code eacces|eacces: permission denied`),
+		"npm cannot write to its global prefix; install Node through nvm or Homebrew instead of using sudo."},
+	{regexp.MustCompile("(?i)linker `?cc`? not found|linking with `?cc`? failed|xcrun: error: invalid active developer path"),
+		"The Xcode Command Line Tools are missing or broken; run `xcode-select --install` and retry."},
+	{regexp.MustCompile(`(?i)command line tools are already installed`),
+		"The Command Line Tools are already installed; update them through Software Update instead."},
+	{regexp.MustCompile(`(?i)(permission denied|not writable).*(/opt/homebrew|/usr/local)`),
+		"The Homebrew prefix is not writable by you; run `sudo chown -R $(whoami) $(brew --prefix)/*`."},
+	{regexp.MustCompile(`(?i)sha256 mismatch`),
+		"The download changed upstream; run `brew update` and retry, or wait for the formula to be fixed."},
+	{regexp.MustCompile(`(?i)not signed in|sign in to the app store`),
+		"Sign in to the App Store app first; mas cannot sign in by itself."},
+	{regexp.MustCompile(`(?i)no space left on device`),
+		"The disk is full; free some space (`brew cleanup` helps) and retry."},
+	{regexp.MustCompile(`(?i)a password is required|sudo: .*terminal is required`),
+		"This step needs sudo; run it interactively or pass --sudo-askpass."},
+}
+
+// Hint returns advice for a failed command's output, or "" when nothing
+// recognizable went wrong.
+func Hint(output string) string {
+	for _, h := range hints {
+		if h.pattern.MatchString(output) {
+			return h.advice
+		}
+	}
+	return ""
+}
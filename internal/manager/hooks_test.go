@@ -0,0 +1,47 @@
+package manager
+
+import (
+	"context"
+	"os"
+	"path/filepath"
+	"strings"
+	"testing"
+
+	"github.com/hmziqrs/maziq/internal/catalog"
+	"github.com/hmziqrs/maziq/internal/options"
+)
+
+// Two steps sharing a post-install hook, as RunPlan sees them, run it once;
+// a hook of their own still runs for each.
+func TestSharedHookRunsOnce(t *testing.T) {
+	log := filepath.Join(t.TempDir(), "hooks.log")
+	shared := catalog.Hook{Description: "wasm target", Command: "echo shared >> " + shellQuote(log)}
+	m := New(options.Defaults())
+	m.Executor.Shell = "/bin/sh"
+	m.ranHooks = map[string]bool{}
+
+	var events []ExecutionEvent
+	for _, id := range []string{"first", "second"} {
+		own := catalog.Hook{Description: id, Command: "echo " + id + " >> " + shellQuote(log)}
+		step := Step{Entry: catalog.Entry{ID: id}, Action: ActionInstall, Hooks: []catalog.Hook{shared, own}}
+		events = append(events, m.RunHooks(context.Background(), step)...)
+	}
+	for i, event := range events {
+		if !event.Success {
+			t.Fatalf("hook %d failed: %s", i, event.Error)
+		}
+	}
+	if events[0].Reused || !events[2].Reused || events[3].Reused {
+		t.Errorf("reused = %v %v %v %v, want false false true false", events[0].Reused, events[1].Reused, events[2].Reused, events[3].Reused)
+	}
+
+	data, err := os.ReadFile(log)
+	if err != nil {
+		t.Fatal(err)
+	}
+	got := strings.Fields(string(data))
+	want := []string{"shared", "first", "second"}
+	if strings.Join(got, " ") != strings.Join(want, " ") {
+		t.Errorf("hooks ran %q, want %q", got, want)
+	}
+}
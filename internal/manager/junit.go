@@ -0,0 +1,115 @@
+package manager
+
+import (
+	"encoding/xml"
+	"fmt"
+	"io"
+	"strings"
+	"time"
+)
+
+// junitSuites is the root of a JUnit XML report.
+type junitSuites struct {
+	XMLName xml.Name     `xml:"testsuites"`
+	Suites  []junitSuite `xml:"testsuite"`
+}
+
+type junitSuite struct {
+	Name      string      `xml:"name,attr"`
+	Tests     int         `xml:"tests,attr"`
+	Failures  int         `xml:"failures,attr"`
+	Skipped   int         `xml:"skipped,attr"`
+	Time      string      `xml:"time,attr"`
+	Timestamp string      `xml:"timestamp,attr"`
+	Cases     []junitCase `xml:"testcase"`
+}
+
+type junitCase struct {
+	ClassName string        `xml:"classname,attr"`
+	Name      string        `xml:"name,attr"`
+	Time      string        `xml:"time,attr"`
+	Failure   *junitMessage `xml:"failure,omitempty"`
+	Skipped   *junitMessage `xml:"skipped,omitempty"`
+	SystemOut string        `xml:"system-out,omitempty"`
+	SystemErr string        `xml:"system-err,omitempty"`
+}
+
+type junitMessage struct {
+	Message string `xml:"message,attr"`
+	Text    string `xml:",chardata"`
+}
+
+// WriteJUnit writes the outcome of a run of plan as a JUnit XML report:
+// one test case per step, named after the entry and classed by action,
+// so CI dashboards can show provisioning like a test run. Steps the run
+// never reached are reported as skipped.
+func WriteJUnit(w io.Writer, name string, plan Plan, events []TaskEvent) error {
+	finished := map[string]TaskEvent{}
+	started := map[string]time.Time{}
+	var first, last time.Time
+	for _, ev := range events {
+		if first.IsZero() {
+			first = ev.Time
+		}
+		last = ev.Time
+		switch ev.Kind {
+		case EventStepStarted:
+			if _, ok := started[ev.SoftwareID]; !ok {
+				started[ev.SoftwareID] = ev.Time
+			}
+		case EventStepFinished:
+			finished[ev.SoftwareID] = ev
+		}
+	}
+	suite := junitSuite{
+		Name:      name,
+		Time:      seconds(last.Sub(first)),
+		Timestamp: first.UTC().Format(time.RFC3339),
+	}
+	for i, o := range Summarize(plan.IDs(), events) {
+		step := plan.Steps[i]
+		c := junitCase{ClassName: "maziq." + string(step.Action), Name: o.ID, Time: seconds(0)}
+		ev, ok := finished[o.ID]
+		if at, ran := started[o.ID]; ran && ok {
+			// Retries and the post-step test count towards the step.
+			c.Time = seconds(ev.Time.Sub(at))
+		}
+		if ok && ev.Execution != nil {
+			c.SystemOut = strings.TrimSpace(ev.Execution.Stdout)
+			c.SystemErr = strings.TrimSpace(ev.Execution.Stderr)
+		}
+		switch o.Result {
+		case ResultFailed:
+			suite.Failures++
+			text := ""
+			if ok && ev.Execution != nil {
+				text = ev.Execution.Command
+			}
+			c.Failure = &junitMessage{Message: o.Reason, Text: text}
+		case ResultSkipped, ResultNotRun:
+			suite.Skipped++
+			reason := o.Reason
+			if o.Result == ResultNotRun {
+				reason = "not run"
+			}
+			c.Skipped = &junitMessage{Message: reason}
+		}
+		suite.Cases = append(suite.Cases, c)
+	}
+	suite.Tests = len(suite.Cases)
+	if _, err := io.WriteString(w, xml.Header); err != nil {
+		return err
+	}
+	enc := xml.NewEncoder(w)
+	enc.Indent("", "  ")
+	if err := enc.Encode(junitSuites{Suites: []junitSuite{suite}}); err != nil {
+		return err
+	}
+	_, err := io.WriteString(w, "\n")
+	return err
+}
+
+// seconds formats d the way JUnit reports expect.
+func seconds(d time.Duration) string {
+	return fmt.Sprintf("%.3f", d.Seconds())
+}
@@ -0,0 +1,41 @@
+package manager
+
+import (
+	"context"
+	"fmt"
+	"os/exec"
+	"strings"
+
+	"github.com/hmziqrs/maziq/internal/catalog"
+)
+
+// LaunchApp opens the GUI app of entry, by its bundle path when the probe
+// knows it and by name otherwise.
+func LaunchApp(ctx context.Context, entry catalog.Entry) error {
+	if entry.Kind != catalog.KindGUI {
+		return fmt.Errorf("%s is not an app", entry.Name)
+	}
+	app := entry.Probe.AppPath
+	if app == "" {
+		app = entry.Name
+	}
+	if out, err := exec.CommandContext(ctx, "open", "-a", app).CombinedOutput(); err != nil {
+		return fmt.Errorf("open -a %s: %s", app, strings.TrimSpace(string(out)))
+	}
+	return nil
+}
+
+// BinaryPath returns the path of the binary entry's probe runs, as `which`
+// would print it.
+func BinaryPath(ctx context.Context, entry catalog.Entry) (string, error) {
+	binary := entry.Probe.Binary()
+	if binary == "" {
+		return "", fmt.Errorf("%s has no binary to look up", entry.Name)
+	}
+	out, err := exec.CommandContext(ctx, "sh", "-c", "command -v "+shellQuote(binary)).Output()
+	path := strings.TrimSpace(string(out))
+	if err != nil || path == "" {
+		return "", fmt.Errorf("%s is not on PATH", binary)
+	}
+	return path, nil
+}
@@ -0,0 +1,138 @@
+package manager
+
+import (
+	"context"
+	"fmt"
+	"time"
+
+	"github.com/hmziqrs/maziq/internal/catalog"
+)
+
+// LifecycleActions are the stages of a lifecycle test, in order: install,
+// test, update and, unless keep is set, uninstall.
+func LifecycleActions(keep bool) []Action {
+	actions := []Action{ActionInstall, ActionTest, ActionUpdate, ActionUninstall}
+	if keep {
+		return actions[:len(actions)-1]
+	}
+	return actions
+}
+
+// LifecycleStage is the outcome of one stage of a lifecycle test.
+type LifecycleStage struct {
+	Action   Action
+	Success  bool
+	Error    string
+	// Version is the version probed after the stage, if any.
+	Version  string
+	Duration time.Duration
+	Event    ExecutionEvent
+}
+
+// Lifecycle exercises entry end to end, running the stages of
+// LifecycleActions one after the other. Each stage is followed by a probe,
+// so a recipe that exits zero without installing or removing anything
+// still fails its stage. Install, update and uninstall stages are recorded
+// in the history as one run, so the uninstall stage sees the entry as
+// installed by maziq. An SDK the install stage put there is removed again
+// without ConfirmSDKRemoval; one that was there before still needs it. It
+// stops at the first failing stage and returns the stages that ran.
+func (m *Manager) Lifecycle(ctx context.Context, entry catalog.Entry, keep bool, report func(TaskEvent)) ([]LifecycleStage, error) {
+	report = noted(report)
+	emit := func(format string, args ...any) {
+		report(TaskEvent{Kind: EventStepOutput, SoftwareID: entry.ID, Message: fmt.Sprintf(format, args...), Time: time.Now()})
+	}
+	RefreshPath()
+	prober := NewProber(m.Options.Jobs)
+	run := runTag{started: time.Now().Unix()}
+	installedHere := true
+	if st := prober.Probe(ctx, entry); st.State == StateInstalled || st.State == StateOutdated {
+		installedHere = false
+		emit("%s is already installed (%s); the install stage runs over it", entry.Name, st.Version)
+	}
+	actions := LifecycleActions(keep)
+	var stages []LifecycleStage
+	for i, action := range actions {
+		if err := ctx.Err(); err != nil {
+			return stages, err
+		}
+		report(TaskEvent{
+			Kind:       EventStepStarted,
+			SoftwareID: entry.ID,
+			Message:    fmt.Sprintf("[%d/%d] %s %s…", i+1, len(actions), action, entry.Name),
+			Time:       time.Now(),
+			Step:       i + 1,
+			Total:      len(actions),
+		})
+		started := time.Now()
+		var event ExecutionEvent
+		var err error
+		if action == ActionTest {
+			event, err = m.Test(ctx, entry)
+		} else {
+			event, err = m.lifecycleApply(ctx, entry, action, installedHere)
+			RefreshPath()
+		}
+		stage := LifecycleStage{Action: action, Duration: time.Since(started)}
+		if err == nil && !m.Executor.DryRun && action != ActionTest {
+			st := prober.Probe(ctx, entry)
+			stage.Version = st.Version
+			if want := lifecycleWants(action); want != st.State && !(want == StateInstalled && st.State == StateOutdated) {
+				event.Success = false
+				event.Error = fmt.Sprintf("exited zero but %s is %s afterwards", entry.ID, st.State)
+				err = &StepError{Action: action, ID: entry.ID, Event: event}
+			}
+		}
+		stage.Event = event
+		stage.Success = err == nil
+		if err != nil {
+			stage.Error = err.Error()
+		}
+		stages = append(stages, stage)
+		if action != ActionTest && event.Command != "" {
+			if recErr := m.record(ctx, prober, run, Step{Entry: entry, Action: action}, event, stage.Duration, ""); recErr != nil {
+				emit("could not record history: %v", recErr)
+			}
+		}
+		finished := event
+		if err != nil {
+			report(TaskEvent{Kind: EventStepFinished, SoftwareID: entry.ID, Message: "✗ " + err.Error(), Time: time.Now(), Step: i + 1, Total: len(actions), Execution: &finished, Reason: stage.Error})
+			if event.Hint != "" {
+				emit("hint: %s", event.Hint)
+			}
+			if ctx.Err() != nil {
+				return stages, ctx.Err()
+			}
+			return stages, err
+		}
+		message := fmt.Sprintf("✓ %s %s", action, entry.ID)
+		if event.Source != "" {
+			message += " via " + event.Source
+		}
+		if stage.Version != "" {
+			message += " (" + stage.Version + ")"
+		}
+		report(TaskEvent{Kind: EventStepFinished, SoftwareID: entry.ID, Message: message, Time: time.Now(), Step: i + 1, Total: len(actions), Execution: &finished})
+	}
+	return stages, nil
+}
+
+// lifecycleApply applies a lifecycle stage. Uninstalling an SDK the
+// lifecycle installed itself only deletes the toolchains and caches its
+// install stage created, so the SDK guard is lifted for that stage.
+func (m *Manager) lifecycleApply(ctx context.Context, entry catalog.Entry, action Action, installedHere bool) (ExecutionEvent, error) {
+	if action != ActionUninstall || !installedHere || m.Options.ConfirmSDKRemoval {
+		return m.Apply(ctx, entry, action)
+	}
+	m.Options.ConfirmSDKRemoval = true
+	defer func() { m.Options.ConfirmSDKRemoval = false }()
+	return m.Apply(ctx, entry, action)
+}
+
+// lifecycleWants is the state an entry must be probed in after action.
+func lifecycleWants(action Action) State {
+	if action == ActionUninstall {
+		return StateNotInstalled
+	}
+	return StateInstalled
+}
@@ -0,0 +1,112 @@
+package manager
+
+import (
+	"context"
+	"errors"
+	"os"
+	"path/filepath"
+	"testing"
+
+	"github.com/hmziqrs/maziq/internal/catalog"
+	"github.com/hmziqrs/maziq/internal/history"
+	"github.com/hmziqrs/maziq/internal/options"
+	"github.com/hmziqrs/maziq/internal/testenv"
+)
+
+// lifecycleEntry is a CLI whose "binary" is a file in the sandbox's home
+// holding its version.
+func lifecycleEntry(home string) catalog.Entry {
+	tool := shellQuote(filepath.Join(home, "tool"))
+	return catalog.Entry{
+		ID:        "lifecycle_tool",
+		Name:      "Lifecycle tool",
+		Kind:      catalog.KindCLI,
+		Probe:     catalog.Probe{Command: "cat " + tool},
+		Test:      "test -f " + tool,
+		Install:   []catalog.Recipe{{Source: catalog.SourceScript, Command: "echo 1.0.0 > " + tool}},
+		Update:    []catalog.Recipe{{Source: catalog.SourceScript, Command: "echo 1.1.0 > " + tool}},
+		Uninstall: []catalog.Recipe{{Source: catalog.SourceScript, Command: "rm " + tool}},
+	}
+}
+
+// A full lifecycle installs, tests, updates and uninstalls the entry: the
+// uninstall stage must not take what the install stage put there for
+// adopted software.
+func TestLifecycleInstallsAndUninstalls(t *testing.T) {
+	env := testenv.New(t)
+	// The recipes and probes need a real shell.
+	t.Setenv("PATH", "/usr/bin:/bin")
+	entry := lifecycleEntry(env.Home)
+
+	var events []TaskEvent
+	stages, err := New(options.Defaults()).Lifecycle(context.Background(), entry, false, func(ev TaskEvent) {
+		events = append(events, ev)
+	})
+	if err != nil {
+		for _, ev := range events {
+			t.Log(ev.Message)
+		}
+		t.Fatalf("lifecycle failed: %v", err)
+	}
+	want := LifecycleActions(false)
+	if len(stages) != len(want) {
+		t.Fatalf("ran %d stages, want %d", len(stages), len(want))
+	}
+	for i, stage := range stages {
+		if stage.Action != want[i] || !stage.Success {
+			t.Errorf("stage %d = %s (success %v), want a successful %s", i, stage.Action, stage.Success, want[i])
+		}
+	}
+	if got := stages[2].Version; got != "1.1.0" {
+		t.Errorf("version after update = %q, want 1.1.0", got)
+	}
+	if _, err := os.Stat(filepath.Join(env.Home, "tool")); !os.IsNotExist(err) {
+		t.Errorf("the uninstall stage left the tool behind: %v", err)
+	}
+
+	records, err := history.Load()
+	if err != nil {
+		t.Fatal(err)
+	}
+	var actions []string
+	for _, rec := range records {
+		actions = append(actions, rec.Action)
+		if rec.Run != records[0].Run {
+			t.Errorf("%s recorded as run %d, want every stage in run %d", rec.Action, rec.Run, records[0].Run)
+		}
+	}
+	if len(actions) != 3 || actions[0] != "install" || actions[1] != "update" || actions[2] != "uninstall" {
+		t.Errorf("recorded %q, want install, update and uninstall", actions)
+	}
+}
+
+// The uninstall stage may remove an SDK the install stage put there, but
+// not one that was installed before the lifecycle started.
+func TestLifecycleSDKRemoval(t *testing.T) {
+	for _, preinstalled := range []bool{false, true} {
+		env := testenv.New(t)
+		t.Setenv("PATH", "/usr/bin:/bin")
+		entry := lifecycleEntry(env.Home)
+		entry.Kind = catalog.KindSDK
+		tool := filepath.Join(env.Home, "tool")
+		if preinstalled {
+			if err := os.WriteFile(tool, []byte("0.9.0\n"), 0o644); err != nil {
+				t.Fatal(err)
+			}
+		}
+
+		stages, err := New(options.Defaults()).Lifecycle(context.Background(), entry, false, func(TaskEvent) {})
+		var refused *SDKRemovalError
+		switch {
+		case !preinstalled && err != nil:
+			t.Errorf("lifecycle of an SDK it installed failed: %v", err)
+		case preinstalled && !errors.As(err, &refused):
+			t.Errorf("lifecycle of a pre-installed SDK returned %v, want an SDKRemovalError", err)
+		case preinstalled && len(stages) != len(LifecycleActions(false)):
+			t.Errorf("ran %d stages, want the uninstall stage to run and be refused", len(stages))
+		}
+		if _, err := os.Stat(tool); preinstalled != (err == nil) {
+			t.Errorf("preinstalled %v: the tool exists afterwards = %v", preinstalled, err == nil)
+		}
+	}
+}
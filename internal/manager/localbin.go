@@ -0,0 +1,74 @@
+package manager
+
+import (
+	"os"
+	"path/filepath"
+	"strings"
+
+	"github.com/hmziqrs/maziq/internal/paths"
+	"github.com/hmziqrs/maziq/internal/shellprofile"
+)
+
+// localBinBlock names the managed block that puts ~/.local/bin on PATH.
+const localBinBlock = "local-bin"
+
+// localBinShell is a shell profile and the line that prepends ~/.local/bin
+// to PATH in it, unless it is already there.
+type localBinShell struct {
+	path   string
+	line   string
+	// always is set for the login shell's profile, which is created if missing.
+	always bool
+}
+
+func localBinShells() []localBinShell {
+	home := paths.Home()
+	sh := `case ":$PATH:" in *":$HOME/.local/bin:"*) ;; *) export PATH="$HOME/.local/bin:$PATH" ;; esac`
+	fish := `contains -- $HOME/.local/bin $PATH; or set -gx PATH $HOME/.local/bin $PATH`
+	return []localBinShell{
+		{filepath.Join(home, ".zshrc"), sh, true},
+		{filepath.Join(home, ".bashrc"), sh, false},
+		{filepath.Join(home, ".config", "fish", "config.fish"), fish, false},
+	}
+}
+
+// EnsureLocalBin prepares ~/.local/bin for binaries maziq installs itself:
+// it creates the directory, puts it on PATH in the shell profiles through
+// a managed block, and on this process's PATH for later steps.
+func EnsureLocalBin() error {
+	if err := os.MkdirAll(paths.LocalBinDir(), 0o755); err != nil {
+		return err
+	}
+	for _, shell := range localBinShells() {
+		if _, err := os.Stat(shell.path); err != nil && !shell.always {
+			continue
+		}
+		if err := shellprofile.Upsert(shell.path, localBinBlock, shell.line); err != nil {
+			return err
+		}
+	}
+	RefreshPath()
+	return nil
+}
+
+// localBinFirst returns env with ~/.local/bin at the front of PATH, so a
+// probe finds what maziq installed there before a copy elsewhere on PATH.
+func localBinFirst(env []string) []string {
+	bin := paths.LocalBinDir()
+	out := make([]string, 0, len(env)+1)
+	path := ""
+	for _, kv := range env {
+		if value, ok := strings.CutPrefix(kv, "PATH="); ok {
+			path = value
+			continue
+		}
+		out = append(out, kv)
+	}
+	dirs := []string{bin}
+	for _, dir := range filepath.SplitList(path) {
+		if dir != bin {
+			dirs = append(dirs, dir)
+		}
+	}
+	return append(out, "PATH="+strings.Join(dirs, string(os.PathListSeparator)))
+}
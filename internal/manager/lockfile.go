@@ -0,0 +1,151 @@
+package manager
+
+import (
+	"context"
+	"encoding/json"
+	"fmt"
+	"os"
+	"path/filepath"
+	"regexp"
+	"time"
+
+	"github.com/hmziqrs/maziq/internal/catalog"
+	"github.com/hmziqrs/maziq/internal/options"
+)
+
+// LockfileVersion is bumped whenever Lockfile changes incompatibly.
+const LockfileVersion = 1
+
+// Lockfile records the version of every entry a template run left
+// installed, so `onboard fresh --locked` can reproduce it elsewhere.
+// Field names are part of the file format.
+type Lockfile struct {
+	Version  int           `json:"version"`
+	Template string        `json:"template,omitempty"`
+	Created  time.Time     `json:"created"`
+	Entries  []LockedEntry `json:"entries"`
+}
+
+// LockedEntry is the version of one entry and the backend it came from.
+type LockedEntry struct {
+	ID      string `json:"id"`
+	Source  string `json:"source,omitempty"`
+	Version string `json:"version"`
+}
+
+// LockPlan probes the entries of a finished plan and records their
+// versions. Entries without a detectable version are left out.
+func LockPlan(ctx context.Context, plan Plan, opts options.Run) Lockfile {
+	lf := Lockfile{Version: LockfileVersion, Template: plan.Template, Created: time.Now().UTC()}
+	prober := NewProber(opts.Jobs)
+	for _, step := range plan.Steps {
+		st := prober.Probe(ctx, step.Entry)
+		if st.Version == "" || st.State != StateInstalled && st.State != StateOutdated {
+			continue
+		}
+		locked := LockedEntry{ID: step.Entry.ID, Version: st.Version}
+		if recipes := PreferredRecipes(step.Entry, ActionInstall, opts.Backend); len(recipes) > 0 {
+			locked.Source = string(recipes[0].Source)
+		}
+		lf.Entries = append(lf.Entries, locked)
+	}
+	return lf
+}
+
+// pinnableVersion keeps versions that end up in shell commands plain.
+var pinnableVersion = regexp.MustCompile(`^[0-9A-Za-z][0-9A-Za-z.+_-]*$`)
+
+// pinRecipe returns r rewritten to install exactly version, for the
+// backends that can.
+func pinRecipe(r catalog.Recipe, version string) (catalog.Recipe, bool) {
+	if r.Package == "" || !pinnableVersion.MatchString(version) {
+		return r, false
+	}
+	pkg := r.Package
+	switch r.Source {
+	case catalog.SourceCargo:
+		r.Command = fmt.Sprintf("{ command -v cargo-binstall >/dev/null && cargo binstall --no-confirm %[1]s@%[2]s; } || cargo install --locked %[1]s@%[2]s", pkg, version)
+	case catalog.SourceNpm:
+		r.Command = fmt.Sprintf("npm install -g %s@%s", pkg, version)
+	case catalog.SourcePipx:
+		r.Command = fmt.Sprintf("pipx install '%s==%s'", pkg, version)
+	case catalog.SourceMise:
+		r.Command = fmt.Sprintf("mise use --global %s@%s", pkg, version)
+	case catalog.SourceAsdf:
+		r.Command = fmt.Sprintf("asdf plugin add %[1]s; asdf install %[1]s %[2]s && asdf set --home %[1]s %[2]s", pkg, version)
+	default:
+		return r, false
+	}
+	r.Installer = nil
+	return r, true
+}
+
+// Lock pins the install steps of plan to the versions in lf. Entries whose
+// backends cannot install an exact version (Homebrew, installer scripts)
+// keep installing the latest; notes lists them and entries lf lacks.
+func (p Plan) Lock(lf Lockfile, backend options.Backend) (plan Plan, notes []string, err error) {
+	if lf.Version != LockfileVersion {
+		return p, nil, fmt.Errorf("lockfile version %d is not supported (expected %d)", lf.Version, LockfileVersion)
+	}
+	versions := make(map[string]LockedEntry, len(lf.Entries))
+	for _, e := range lf.Entries {
+		versions[e.ID] = e
+	}
+	steps := make([]Step, len(p.Steps))
+	for i, step := range p.Steps {
+		steps[i] = step
+		if step.Action != ActionInstall {
+			continue
+		}
+		locked, ok := versions[step.Entry.ID]
+		if !ok {
+			notes = append(notes, step.Entry.ID+": not in the lockfile, installs the latest")
+			continue
+		}
+		var pinned []catalog.Recipe
+		for _, r := range PreferredRecipes(step.Entry, ActionInstall, backend) {
+			if r, ok := pinRecipe(r, locked.Version); ok {
+				pinned = append(pinned, r)
+			}
+		}
+		if len(pinned) == 0 {
+			notes = append(notes, fmt.Sprintf("%s: %s cannot install %s exactly, installs the latest", step.Entry.ID, orSource(locked.Source), locked.Version))
+			continue
+		}
+		steps[i].Entry.Install = pinned
+	}
+	p.Steps = steps
+	return p, notes, nil
+}
+
+func orSource(source string) string {
+	if source == "" {
+		return "its backend"
+	}
+	return source
+}
+
+// WriteLockfile saves lf as indented JSON, creating its directory.
+func WriteLockfile(path string, lf Lockfile) error {
+	if err := os.MkdirAll(filepath.Dir(path), 0o755); err != nil {
+		return err
+	}
+	data, err := json.MarshalIndent(lf, "", "  ")
+	if err != nil {
+		return err
+	}
+	return os.WriteFile(path, append(data, '\n'), 0o644)
+}
+
+// ReadLockfile loads a lockfile written by WriteLockfile.
+func ReadLockfile(path string) (Lockfile, error) {
+	data, err := os.ReadFile(path)
+	if err != nil {
+		return Lockfile{}, err
+	}
+	var lf Lockfile
+	if err := json.Unmarshal(data, &lf); err != nil {
+		return Lockfile{}, fmt.Errorf("%s: %w", path, err)
+	}
+	return lf, nil
+}
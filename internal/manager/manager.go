@@ -0,0 +1,319 @@
+package manager
+
+import (
+	"context"
+	"fmt"
+	"os"
+	"sort"
+	"strings"
+	"time"
+
+	"github.com/hmziqrs/maziq/internal/aliases"
+	"github.com/hmziqrs/maziq/internal/catalog"
+	"github.com/hmziqrs/maziq/internal/errs"
+	"github.com/hmziqrs/maziq/internal/installers"
+	"github.com/hmziqrs/maziq/internal/options"
+	"github.com/hmziqrs/maziq/internal/paths"
+	"github.com/hmziqrs/maziq/internal/trash"
+)
+
+// Action is an operation the manager can perform on an entry.
+type Action string
+
+const (
+	ActionInstall   Action = "install"
+	ActionUpdate    Action = "update"
+	ActionUninstall Action = "uninstall"
+	// ActionTest runs an entry's test command. It only labels test runs
+	// and is not a plan action.
+	ActionTest      Action = "test"
+)
+
+// Recipes returns the entry's recipes for action, in fallback order.
+func Recipes(entry catalog.Entry, action Action) []catalog.Recipe {
+	switch action {
+	case ActionInstall:
+		return entry.Install
+	case ActionUpdate:
+		return entry.Update
+	case ActionUninstall:
+		return entry.Uninstall
+	}
+	return nil
+}
+
+// PreferredRecipes orders an entry's recipes for the given backend. Nix
+// recipes are opt-in: they come first under the nix backend and are
+// dropped otherwise. MacPorts recipes come first when Homebrew is absent
+// but MacPorts is installed, and last otherwise.
+func PreferredRecipes(entry catalog.Entry, action Action, backend options.Backend) []catalog.Recipe {
+	ports := preferMacPorts()
+	var preferred, rest, fallback []catalog.Recipe
+	for _, recipe := range Recipes(entry, action) {
+		switch {
+		case recipe.Source == catalog.SourceNix && backend == options.BackendNix:
+			preferred = append(preferred, recipe)
+		case recipe.Source == catalog.SourceNix:
+			// Not opted in.
+		case recipe.Source == catalog.SourcePort && ports:
+			preferred = append(preferred, recipe)
+		case recipe.Source == catalog.SourcePort:
+			fallback = append(fallback, recipe)
+		default:
+			rest = append(rest, recipe)
+		}
+	}
+	return append(append(preferred, rest...), fallback...)
+}
+
+// Destructive reports whether running recipe for action removes software or
+// files, which frontends confirm before running.
+func Destructive(action Action, recipe catalog.Recipe) bool {
+	return action == ActionUninstall || strings.Contains(recipe.Command, "sudo rm")
+}
+
+// ManualCommand returns the command a user can paste into a shell to
+// perform action on entry themselves: the first recipe the backend
+// prefers, with the environment it sets. ok is false when entry has no
+// recipe for action.
+func ManualCommand(entry catalog.Entry, action Action, backend options.Backend) (command string, ok bool) {
+	recipes := PreferredRecipes(entry, action, backend)
+	if len(recipes) == 0 {
+		return "", false
+	}
+	recipe := recipes[0]
+	if recipe.Mode != catalog.ShellSh {
+		argv := recipe.Mode.Argv()
+		recipe.Command = strings.Join(argv, " ") + " " + shellQuote(recipe.Command)
+	}
+	keys := make([]string, 0, len(recipe.Env))
+	for key := range recipe.Env {
+		keys = append(keys, key)
+	}
+	sort.Strings(keys)
+	var prefix []string
+	for _, key := range keys {
+		prefix = append(prefix, key+"="+shellQuote(recipe.Env[key]))
+	}
+	if len(prefix) == 0 {
+		return recipe.Command, true
+	}
+	if strings.ContainsAny(recipe.Command, ";&|") {
+		// Assignments in front of a compound command only reach its first
+		// part; a subshell keeps them out of the user's own shell.
+		return "(export " + strings.Join(prefix, " ") + "; " + recipe.Command + ")", true
+	}
+	return strings.Join(prefix, " ") + " " + recipe.Command, true
+}
+
+// Manager performs actions on catalog entries through a CommandExecutor.
+type Manager struct {
+	Executor      *CommandExecutor
+	// Options are the run options the manager follows. ContinueOnError
+	// keeps RunPlan going past failed steps, skipping only the steps that
+	// depend on them; ForceAdopted lets Apply uninstall entries maziq did
+	// not install, which otherwise fail with an AdoptedError, and
+	// ConfirmSDKRemoval SDKs, which otherwise fail with an SDKRemovalError.
+	Options       options.Run
+	// SkipPreflight stops RunPlan from running the preflight checks, for
+	// callers that ran them already.
+	SkipPreflight bool
+	// Recover, when set, is asked what to do about every failed step
+	// instead of following ContinueOnError; its answer is recorded in the
+	// history.
+	Recover       func(ctx context.Context, step Step, event ExecutionEvent, err error) Recovery
+	// brewUpdated is set once RunPlan ran `brew update` for the run.
+	brewUpdated   bool
+	// ranHooks holds the post-install hook commands that succeeded during
+	// the current RunPlan, so a hook shared by several steps runs once.
+	// Only hooks are deduplicated: install recipes always run.
+	ranHooks      map[string]bool
+}
+
+// New returns a manager following opts with a default executor.
+func New(opts options.Run) *Manager {
+	executor := NewCommandExecutor()
+	executor.Askpass = opts.Askpass
+	executor.DryRun = opts.DryRun
+	return &Manager{Executor: executor, Options: opts}
+}
+
+// StepError reports an action whose every recipe failed.
+type StepError struct {
+	Action Action
+	ID     string
+	Event  ExecutionEvent
+}
+
+func (e *StepError) Error() string {
+	return fmt.Sprintf("%s %s failed: %s", e.Action, e.ID, e.Event.Error)
+}
+
+func (e *StepError) ErrorCode() errs.Code {
+	return errs.CodeStep
+}
+
+func (e *StepError) ErrorContext() errs.Context {
+	return errs.Context{Entry: e.ID, Command: e.Event.Command, Phase: string(e.Action)}
+}
+
+// Apply runs action for entry through its adapter. The returned event
+// records the source used. GUI apps are snapshotted before they are
+// uninstalled, and uninstalling an SDK or what maziq did not install is
+// refused unless ConfirmSDKRemoval or ForceAdopted is set.
+func (m *Manager) Apply(ctx context.Context, entry catalog.Entry, action Action) (ExecutionEvent, error) {
+	if action == ActionUninstall {
+		if err := m.checkSDKRemoval(entry); err != nil {
+			return ExecutionEvent{SoftwareID: entry.ID, Action: string(action)}, err
+		}
+		if err := m.checkAdopted(ctx, entry); err != nil {
+			return ExecutionEvent{SoftwareID: entry.ID, Action: string(action)}, err
+		}
+	}
+	if action == ActionUninstall && entry.Kind == catalog.KindGUI && !m.Executor.DryRun {
+		// Recorded first so the app can be restored at the same version.
+		if err := m.takeSnapshot(ctx, entry); err != nil {
+			return ExecutionEvent{SoftwareID: entry.ID, Action: string(action)},
+				fmt.Errorf("%s: could not record a snapshot before uninstalling: %w", entry.ID, err)
+		}
+	}
+	event, err := AdapterFor(entry).Apply(ctx, m, entry, action)
+	if err != nil {
+		return event, err
+	}
+	if action == ActionUninstall && !m.Executor.DryRun && len(entry.Aliases) > 0 {
+		// Aliases pointing at a removed binary would break the user's shell.
+		if err := aliases.Disable(entry.ID); err != nil {
+			return event, fmt.Errorf("%s uninstalled but its aliases could not be removed: %w", entry.ID, err)
+		}
+	}
+	return event, nil
+}
+
+// applyRecipes runs recipes for action, falling back through them in
+// order until one succeeds.
+func (m *Manager) applyRecipes(ctx context.Context, entry catalog.Entry, action Action, recipes []catalog.Recipe) (ExecutionEvent, error) {
+	if len(recipes) == 0 {
+		return ExecutionEvent{SoftwareID: entry.ID, Action: string(action)},
+			fmt.Errorf("%s has no %s recipe", entry.ID, action)
+	}
+	if m.Options.Offline && action != ActionUninstall {
+		ready, reasons := offlineRecipes(recipes)
+		if len(ready) == 0 {
+			event := ExecutionEvent{
+				SoftwareID: entry.ID,
+				Action:     string(action),
+				Source:     string(recipes[0].Source),
+				Command:    recipes[0].Command,
+				Error:      "needs the network in offline mode: " + strings.Join(reasons, ", "),
+				ExitCode:   -1,
+				Timestamp:  time.Now(),
+			}
+			return event, &StepError{Action: action, ID: entry.ID, Event: event}
+		}
+		recipes = ready
+	}
+
+	var event ExecutionEvent
+	for _, recipe := range recipes {
+		script, cleanup, err := m.script(ctx, entry.ID, action, recipe)
+		if err != nil {
+			event = ExecutionEvent{
+				SoftwareID: entry.ID,
+				Action:     string(action),
+				Source:     string(recipe.Source),
+				Command:    recipe.Command,
+				Error:      err.Error(),
+				ExitCode:   -1,
+				Timestamp:  time.Now(),
+			}
+			continue
+		}
+		event = m.Executor.Run(ctx, Command{
+			SoftwareID: entry.ID,
+			Action:     string(action),
+			Source:     string(recipe.Source),
+			Script:     script,
+			Env:        m.brewEnv(recipe, recipeEnv(recipe)),
+			Mode:       recipe.Mode,
+			Dir:        recipe.Dir,
+			TempDir:    recipe.TempDir,
+		})
+		cleanup()
+		if event.Success || ctx.Err() != nil {
+			break
+		}
+	}
+	if !event.Success {
+		return event, &StepError{Action: action, ID: entry.ID, Event: event}
+	}
+	return event, nil
+}
+
+// recipeEnv returns recipe's Env as sorted KEY=VALUE pairs.
+func recipeEnv(recipe catalog.Recipe) []string {
+	env := make([]string, 0, len(recipe.Env))
+	for key, value := range recipe.Env {
+		env = append(env, key+"="+value)
+	}
+	sort.Strings(env)
+	return env
+}
+
+// script returns the shell string that runs recipe for action on entry id.
+// Installer scripts are downloaded and verified against their pinned
+// SHA256 first; cleanup removes the download once it has run. Git builds
+// run the manager's own script, and GitHub release assets are downloaded
+// and checked against their published SHA256; both first make sure
+// ~/.local/bin is on PATH. Unless PermanentDelete is set, what a recipe
+// removes with rm -rf goes to the Trash. Dry runs download nothing.
+func (m *Manager) script(ctx context.Context, id string, action Action, recipe catalog.Recipe) (script string, cleanup func(), err error) {
+	if (recipe.Git != nil || recipe.Release != nil) && action != ActionUninstall && !m.Executor.DryRun {
+		if err := EnsureLocalBin(); err != nil {
+			return "", nil, fmt.Errorf("preparing %s: %w", paths.LocalBinDir(), err)
+		}
+	}
+	if recipe.Git != nil && !m.Executor.DryRun {
+		return gitScript(id, action, *recipe.Git), func() {}, nil
+	}
+	if recipe.Release != nil && !m.Executor.DryRun {
+		return releaseScript(ctx, action, *recipe.Release)
+	}
+	if recipe.Installer == nil || m.Executor.DryRun {
+		if m.Options.Offline && (recipe.Source == catalog.SourceBrew || recipe.Source == catalog.SourceBrewCask) {
+			return brewOffline + recipe.Command, func() {}, nil
+		}
+		if !m.Options.PermanentDelete && recipe.Mode != catalog.ShellFish {
+			return trash.Script(recipe.Command), func() {}, nil
+		}
+		return recipe.Command, func() {}, nil
+	}
+	path, err := installers.Download(ctx, recipe.Installer.URL, m.Options.Offline)
+	if err != nil {
+		return "", nil, err
+	}
+	words := []string{shellQuote(recipe.Installer.Shell), shellQuote(path)}
+	for _, arg := range recipe.Installer.Args {
+		words = append(words, shellQuote(arg))
+	}
+	return strings.Join(words, " "), func() { os.Remove(path) }, nil
+}
+
+// Test runs entry's test command, which exits zero when the installed
+// software actually works.
+func (m *Manager) Test(ctx context.Context, entry catalog.Entry) (ExecutionEvent, error) {
+	if entry.Test == "" {
+		return ExecutionEvent{SoftwareID: entry.ID, Action: string(ActionTest)},
+			fmt.Errorf("%s has no test command", entry.ID)
+	}
+	event := m.Executor.Run(ctx, Command{
+		SoftwareID: entry.ID,
+		Action:     string(ActionTest),
+		Source:     "test",
+		Script:     entry.Test,
+	})
+	if !event.Success {
+		return event, &StepError{Action: ActionTest, ID: entry.ID, Event: event}
+	}
+	return event, nil
+}
@@ -0,0 +1,145 @@
+package manager
+
+import (
+	"context"
+	"fmt"
+	"strings"
+	"time"
+
+	"github.com/hmziqrs/maziq/internal/cache"
+	"github.com/hmziqrs/maziq/internal/catalog"
+	"github.com/hmziqrs/maziq/internal/installers"
+	"github.com/hmziqrs/maziq/internal/options"
+)
+
+// brewOffline keeps brew from updating itself, which needs the network,
+// before installing from its download cache.
+const brewOffline = "export HOMEBREW_NO_AUTO_UPDATE=1; "
+
+// WarmResult is the outcome of caching one entry for offline installs.
+type WarmResult struct {
+	ID     string
+	Source string
+	Cached bool
+	// Error says why nothing was cached, e.g. because every recipe
+	// downloads at install time.
+	Error  string
+}
+
+// Warm fetches what installing entry needs into the offline cache: the
+// verified installer script or the bottle or cask download of the first
+// recipe that can be cached, in preference order.
+func (m *Manager) Warm(ctx context.Context, entry catalog.Entry) WarmResult {
+	result := WarmResult{ID: entry.ID}
+	for _, recipe := range PreferredRecipes(entry, ActionInstall, m.Options.Backend) {
+		switch {
+		case recipe.Installer != nil:
+			if !m.Executor.DryRun {
+				if err := installers.Cache(ctx, recipe.Installer.URL); err != nil {
+					result.Error = err.Error()
+					continue
+				}
+			}
+		case fetchScript(recipe) != "":
+			event := m.Executor.Run(ctx, Command{
+				SoftwareID: entry.ID,
+				Action:     "fetch",
+				Source:     string(recipe.Source),
+				Script:     fetchScript(recipe),
+				Env:        recipeEnv(recipe),
+			})
+			if !event.Success {
+				result.Error = event.Error
+				continue
+			}
+			if !m.Executor.DryRun {
+				item := cache.Item{Software: entry.ID, Source: string(recipe.Source), Package: recipe.Package, Timestamp: time.Now().Unix()}
+				if err := cache.Append(item); err != nil {
+					result.Error = err.Error()
+					return result
+				}
+			}
+		default:
+			continue
+		}
+		result.Source = string(recipe.Source)
+		result.Cached = true
+		result.Error = ""
+		return result
+	}
+	if result.Error == "" {
+		result.Error = "needs the network at install time: none of its recipes can be cached"
+	}
+	return result
+}
+
+// fetchScript downloads recipe's package into its package manager's cache
+// without installing it, or is empty when the source has no such command.
+func fetchScript(recipe catalog.Recipe) string {
+	if recipe.Package == "" {
+		return ""
+	}
+	switch recipe.Source {
+	case catalog.SourceBrew:
+		return "brew fetch " + shellQuote(recipe.Package)
+	case catalog.SourceBrewCask:
+		return "brew fetch --cask " + shellQuote(recipe.Package)
+	}
+	return ""
+}
+
+// offlineRecipes returns the recipes that can run from the cache, and why
+// the others cannot.
+func offlineRecipes(recipes []catalog.Recipe) ([]catalog.Recipe, []string) {
+	items, _ := cache.Load()
+	var ready []catalog.Recipe
+	var reasons []string
+	for _, recipe := range recipes {
+		switch {
+		case recipe.Installer != nil && installers.Cached(recipe.Installer.URL):
+			ready = append(ready, recipe)
+		case recipe.Installer != nil:
+			reasons = append(reasons, string(recipe.Source)+" installer not cached")
+		case fetchScript(recipe) != "" && cache.Has(items, string(recipe.Source), recipe.Package):
+			ready = append(ready, recipe)
+		case fetchScript(recipe) != "":
+			reasons = append(reasons, string(recipe.Source)+" download not cached")
+		default:
+			reasons = append(reasons, string(recipe.Source)+" always downloads")
+		}
+	}
+	return ready, reasons
+}
+
+// OfflineReason returns why action on entry needs the network even after
+// `maziq cache warm`, or "" when it can run from the cache. Uninstalls
+// never need it. Recipes are ordered for backend.
+func OfflineReason(entry catalog.Entry, action Action, backend options.Backend) string {
+	if action == ActionUninstall {
+		return ""
+	}
+	ready, reasons := offlineRecipes(PreferredRecipes(entry, action, backend))
+	if len(ready) > 0 {
+		return ""
+	}
+	if len(reasons) == 0 {
+		return fmt.Sprintf("no %s recipe", action)
+	}
+	return strings.Join(reasons, ", ")
+}
+
+// checkOffline replaces the network check in offline mode, reporting the
+// steps that will fail without a connection.
+func checkOffline(plan Plan, backend options.Backend) Check {
+	c := Check{Name: "Offline cache", OK: true, Detail: "every step runs from the cache"}
+	var online []string
+	for _, step := range plan.Steps {
+		if reason := OfflineReason(step.Entry, step.Action, backend); reason != "" {
+			online = append(online, fmt.Sprintf("%s (%s)", step.Entry.ID, reason))
+		}
+	}
+	if len(online) > 0 {
+		c.Detail = fmt.Sprintf("%d of %d steps need the network: %s", len(online), len(plan.Steps), strings.Join(online, "; "))
+	}
+	return c
+}
@@ -0,0 +1,224 @@
+package manager
+
+import (
+	"fmt"
+	"strings"
+
+	"github.com/hmziqrs/maziq/internal/catalog"
+	"github.com/hmziqrs/maziq/internal/configurator"
+	"github.com/hmziqrs/maziq/internal/errs"
+	"github.com/hmziqrs/maziq/internal/options"
+	"github.com/hmziqrs/maziq/internal/templates"
+)
+
+// ErrDependencyCycle is returned when entries depend on each other.
+var ErrDependencyCycle = errs.New(errs.CodePlan, "dependency cycle")
+
+// Step is one entry/action pair in a plan.
+type Step struct {
+	Entry    catalog.Entry
+	Action   Action
+	// Hooks run after a successful install: the entry's own, then the
+	// template's.
+	Hooks    []catalog.Hook
+	// Profiles are editor profiles applied after a successful install.
+	Profiles []string
+}
+
+// Plan is an ordered list of steps.
+type Plan struct {
+	Action      Action
+	Steps       []Step
+	// Template names the template the plan applies, if any.
+	Template    string
+	// Recommended lists recommended entries the plan leaves out.
+	Recommended []Recommendation
+}
+
+// Recommendation is an entry recommended by some of a plan's entries.
+type Recommendation struct {
+	ID  string
+	For []string
+}
+
+// IDs returns the software ids of the plan's steps, in order.
+func (p Plan) IDs() []string {
+	ids := make([]string, len(p.Steps))
+	for i, step := range p.Steps {
+		ids[i] = step.Entry.ID
+	}
+	return ids
+}
+
+// ResolveOptions tweaks plan resolution.
+type ResolveOptions struct {
+	// Substitutes replaces catalog ids, both requested and dependencies,
+	// e.g. nvm → mise_node under the mise runtime strategy.
+	Substitutes     map[string]string
+	// WithRecommended installs recommended entries like dependencies.
+	WithRecommended bool
+	// Versions pins software ids to a version, for adapters that can
+	// install one.
+	Versions        map[string]string
+	// PostInstall adds hook commands per software id.
+	PostInstall     map[string][]string
+	// EditorProfiles are attached to the install steps of their editors.
+	EditorProfiles  []string
+	// Run supplies the backend, which decides whose dependencies apply,
+	// and the entries whose install steps clear quarantine.
+	Run             options.Run
+}
+
+// Resolve orders ids so that dependencies come first. Installs also pull
+// in missing dependencies; uninstalls run in reverse dependency order.
+func Resolve(ids []string, action Action, run options.Run) (Plan, error) {
+	return ResolveWith(ids, action, ResolveOptions{Run: run})
+}
+
+// ResolveTemplate resolves the plan for every entry of t, honoring its
+// runtime strategy.
+func ResolveTemplate(t templates.Template, action Action, run options.Run) (Plan, error) {
+	opts := TemplateOptions(t)
+	opts.Run = run
+	plan, err := ResolveWith(t.Software, action, opts)
+	plan.Template = t.Name
+	return plan, err
+}
+
+// TemplateOptions returns the resolve options implied by t.
+func TemplateOptions(t templates.Template) ResolveOptions {
+	return ResolveOptions{
+		Substitutes:    catalog.RuntimeSubstitutes(t.Runtimes),
+		Versions:       t.Versions,
+		PostInstall:    t.PostInstall,
+		EditorProfiles: t.EditorProfiles,
+	}
+}
+
+// ResolveWith is Resolve with explicit options.
+func ResolveWith(ids []string, action Action, opts ResolveOptions) (Plan, error) {
+	substitute := func(id string) string {
+		if replacement, ok := opts.Substitutes[id]; ok {
+			return replacement
+		}
+		return id
+	}
+	ids = append([]string(nil), ids...)
+	for i := range ids {
+		ids[i] = substitute(ids[i])
+	}
+
+	requested := map[string]bool{}
+	for _, id := range ids {
+		if _, ok := catalog.Lookup(id); !ok {
+			return Plan{}, &catalog.UnknownError{ID: id}
+		}
+		requested[id] = true
+	}
+
+	const (
+		unvisited = iota
+		visiting
+		done
+	)
+	ports := preferMacPorts()
+	nix := opts.Run.Backend == options.BackendNix
+	state := map[string]int{}
+	var order []catalog.Entry
+	var visit func(id string, path []string) error
+	visit = func(id string, path []string) error {
+		switch state[id] {
+		case done:
+			return nil
+		case visiting:
+			return fmt.Errorf("%w: %s", ErrDependencyCycle, strings.Join(append(path, id), " → "))
+		}
+		entry, ok := catalog.Lookup(id)
+		if !ok {
+			return fmt.Errorf("%s depends on %w", path[len(path)-1], &catalog.UnknownError{ID: id})
+		}
+		state[id] = visiting
+		deps := entry.Dependencies
+		if opts.WithRecommended && action == ActionInstall {
+			deps = append(append([]string(nil), deps...), entry.Recommended...)
+		}
+		for _, dep := range deps {
+			if dep == "homebrew" && (ports && entry.HasSource(catalog.SourcePort) || nix && entry.HasSource(catalog.SourceNix)) {
+				// Installed through MacPorts or Nix instead.
+				continue
+			}
+			if err := visit(substitute(dep), append(path, id)); err != nil {
+				return err
+			}
+		}
+		state[id] = done
+		if action == ActionInstall || requested[id] {
+			order = append(order, entry)
+		}
+		return nil
+	}
+	for _, id := range ids {
+		if err := visit(id, nil); err != nil {
+			return Plan{}, err
+		}
+	}
+
+	if action == ActionUninstall {
+		for i, j := 0, len(order)-1; i < j; i, j = i+1, j-1 {
+			order[i], order[j] = order[j], order[i]
+		}
+	}
+	plan := Plan{Action: action}
+	included := map[string]bool{}
+	for _, entry := range order {
+		if version := opts.Versions[entry.ID]; version != "" && action != ActionUninstall {
+			if pinner, ok := AdapterFor(entry).(Pinner); ok {
+				entry = pinner.Pin(entry, version)
+			}
+		}
+		step := Step{Entry: entry, Action: action}
+		if action == ActionInstall {
+			step.Hooks = append(step.Hooks, InstallHooks(entry, opts.Run.ClearQuarantine)...)
+			for _, command := range opts.PostInstall[entry.ID] {
+				step.Hooks = append(step.Hooks, catalog.Hook{Description: "template hook", Command: command})
+			}
+			profiles, err := configurator.EditorProfiles(opts.EditorProfiles, entry.ID)
+			if err != nil {
+				return Plan{}, err
+			}
+			step.Profiles = profiles
+		}
+		plan.Steps = append(plan.Steps, step)
+		included[entry.ID] = true
+	}
+	if action == ActionInstall {
+		plan.Recommended = recommendations(order, included, substitute)
+	}
+	if conflicts := plan.Conflicts(); len(conflicts) > 0 {
+		return Plan{}, &ConflictError{Conflicts: conflicts}
+	}
+	return plan, nil
+}
+
+// recommendations collects the recommended entries missing from a plan,
+// in first-mention order.
+func recommendations(order []catalog.Entry, included map[string]bool, substitute func(string) string) []Recommendation {
+	var out []Recommendation
+	index := map[string]int{}
+	for _, entry := range order {
+		for _, id := range entry.Recommended {
+			id = substitute(id)
+			if included[id] {
+				continue
+			}
+			i, ok := index[id]
+			if !ok {
+				i = len(out)
+				index[id] = i
+				out = append(out, Recommendation{ID: id})
+			}
+			out[i].For = append(out[i].For, entry.ID)
+		}
+	}
+	return out
+}
@@ -0,0 +1,218 @@
+package manager
+
+import (
+	"encoding/json"
+	"fmt"
+	"os"
+	"time"
+
+	"github.com/hmziqrs/maziq/internal/catalog"
+	"github.com/hmziqrs/maziq/internal/options"
+)
+
+// PlanFileVersion is bumped whenever PlanFile changes incompatibly.
+const PlanFileVersion = 1
+
+// PlanFile is an exported plan: every step with the exact command it
+// runs, so it can be reviewed and then replayed unchanged on another
+// machine. Field names are part of the file format.
+type PlanFile struct {
+	Version  int            `json:"version"`
+	Template string         `json:"template,omitempty"`
+	Action   string         `json:"action"`
+	Backend  string         `json:"backend,omitempty"`
+	Created  time.Time      `json:"created"`
+	Steps    []PlanFileStep `json:"steps"`
+}
+
+// PlanFileStep is one step of a PlanFile.
+type PlanFileStep struct {
+	ID       string         `json:"id"`
+	Action   string         `json:"action"`
+	Recipe   PlanFileRecipe `json:"recipe"`
+	Hooks    []PlanFileHook `json:"hooks,omitempty"`
+	Profiles []string       `json:"profiles,omitempty"`
+}
+
+// PlanFileHook is a post-install hook of a step.
+type PlanFileHook struct {
+	Description string `json:"description"`
+	Command     string `json:"command"`
+}
+
+// PlanFileRecipe is the single recipe a step runs.
+type PlanFileRecipe struct {
+	Source    string             `json:"source"`
+	Command   string             `json:"command"`
+	Package   string             `json:"package,omitempty"`
+	AppID     string             `json:"app_id,omitempty"`
+	Env       map[string]string  `json:"env,omitempty"`
+	Mode      string             `json:"mode,omitempty"`
+	Dir       string             `json:"dir,omitempty"`
+	TempDir   bool               `json:"temp_dir,omitempty"`
+	Installer *PlanFileInstaller `json:"installer,omitempty"`
+	Git       *PlanFileGit       `json:"git,omitempty"`
+	Release   *PlanFileRelease   `json:"release,omitempty"`
+}
+
+// PlanFileInstaller is a pinned installer script; see catalog.Installer.
+type PlanFileInstaller struct {
+	URL   string   `json:"url"`
+	Shell string   `json:"shell"`
+	Args  []string `json:"args,omitempty"`
+}
+
+// PlanFileGit is a build from a git repository; see catalog.GitBuild.
+type PlanFileGit struct {
+	Repo      string   `json:"repo"`
+	Ref       string   `json:"ref,omitempty"`
+	Build     string   `json:"build,omitempty"`
+	Artifacts []string `json:"artifacts"`
+}
+
+// PlanFileRelease is a GitHub release asset; see catalog.GitHubRelease.
+type PlanFileRelease struct {
+	Repo     string   `json:"repo"`
+	Asset    string   `json:"asset"`
+	Binaries []string `json:"binaries"`
+}
+
+// ExportPlan freezes plan into a PlanFile, choosing for every step the
+// recipe a run under backend would try first.
+func ExportPlan(plan Plan, backend options.Backend) (PlanFile, error) {
+	pf := PlanFile{
+		Version:  PlanFileVersion,
+		Template: plan.Template,
+		Action:   string(plan.Action),
+		Backend:  string(backend),
+		Created:  time.Now().UTC(),
+	}
+	for _, step := range plan.Steps {
+		recipes := PreferredRecipes(step.Entry, step.Action, backend)
+		if len(recipes) == 0 {
+			return PlanFile{}, fmt.Errorf("%s has no %s recipe", step.Entry.ID, step.Action)
+		}
+		r := recipes[0]
+		fs := PlanFileStep{
+			ID:       step.Entry.ID,
+			Action:   string(step.Action),
+			Recipe:   PlanFileRecipe{Source: string(r.Source), Command: r.Command, Package: r.Package, AppID: r.AppID, Env: r.Env, Mode: string(r.Mode), Dir: r.Dir, TempDir: r.TempDir},
+			Profiles: step.Profiles,
+		}
+		if in := r.Installer; in != nil {
+			fs.Recipe.Installer = &PlanFileInstaller{URL: in.URL, Shell: in.Shell, Args: in.Args}
+		}
+		if g := r.Git; g != nil {
+			fs.Recipe.Git = &PlanFileGit{Repo: g.Repo, Ref: g.Ref, Build: g.Build, Artifacts: g.Artifacts}
+		}
+		if rel := r.Release; rel != nil {
+			fs.Recipe.Release = &PlanFileRelease{Repo: rel.Repo, Asset: rel.Asset, Binaries: rel.Binaries}
+		}
+		for _, h := range step.Hooks {
+			fs.Hooks = append(fs.Hooks, PlanFileHook{Description: h.Description, Command: h.Command})
+		}
+		pf.Steps = append(pf.Steps, fs)
+	}
+	return pf, nil
+}
+
+// ImportPlan rebuilds the plan a PlanFile describes. Entries come from the
+// catalog for probing, but each step runs only the recipe in the file.
+func ImportPlan(pf PlanFile) (Plan, error) {
+	if pf.Version != PlanFileVersion {
+		return Plan{}, fmt.Errorf("plan file version %d is not supported (expected %d)", pf.Version, PlanFileVersion)
+	}
+	plan := Plan{Action: Action(pf.Action), Template: pf.Template}
+	for i, s := range pf.Steps {
+		entry, ok := catalog.Lookup(s.ID)
+		if !ok {
+			return Plan{}, fmt.Errorf("step %d: %w", i+1, &catalog.UnknownError{ID: s.ID})
+		}
+		if s.Recipe.Command == "" {
+			return Plan{}, fmt.Errorf("step %d: %s has no command", i+1, s.ID)
+		}
+		mode, err := catalog.ParseShellMode(s.Recipe.Mode)
+		if err != nil {
+			return Plan{}, fmt.Errorf("step %d: %w", i+1, err)
+		}
+		action := Action(s.Action)
+		recipe := []catalog.Recipe{{
+			Source:  catalog.Source(s.Recipe.Source),
+			Command: s.Recipe.Command,
+			Package: s.Recipe.Package,
+			AppID:   s.Recipe.AppID,
+			Env:     s.Recipe.Env,
+			Mode:    mode,
+			Dir:     s.Recipe.Dir,
+			TempDir: s.Recipe.TempDir,
+		}}
+		if in := s.Recipe.Installer; in != nil {
+			recipe[0].Installer = &catalog.Installer{URL: in.URL, Shell: in.Shell, Args: in.Args}
+		}
+		if g := s.Recipe.Git; g != nil {
+			recipe[0].Git = &catalog.GitBuild{Repo: g.Repo, Ref: g.Ref, Build: g.Build, Artifacts: g.Artifacts}
+		}
+		if rel := s.Recipe.Release; rel != nil {
+			recipe[0].Release = &catalog.GitHubRelease{Repo: rel.Repo, Asset: rel.Asset, Binaries: rel.Binaries}
+		}
+		switch action {
+		case ActionInstall:
+			entry.Install = recipe
+		case ActionUpdate:
+			entry.Update = recipe
+		case ActionUninstall:
+			entry.Uninstall = recipe
+		default:
+			return Plan{}, fmt.Errorf("step %d: unknown action %q", i+1, s.Action)
+		}
+		var hooks []catalog.Hook
+		for _, h := range s.Hooks {
+			hooks = append(hooks, catalog.Hook{Description: h.Description, Command: h.Command})
+		}
+		plan.Steps = append(plan.Steps, Step{Entry: entry, Action: action, Hooks: hooks, Profiles: s.Profiles})
+	}
+	return plan, nil
+}
+
+// Drifted lists the steps of an imported plan whose command differs from
+// what the local catalog would run now, as "id: exported (catalog now: …)".
+func (pf PlanFile) Drifted() []string {
+	var out []string
+	for _, s := range pf.Steps {
+		entry, ok := catalog.Lookup(s.ID)
+		if !ok {
+			continue
+		}
+		recipes := PreferredRecipes(entry, Action(s.Action), options.Backend(pf.Backend))
+		if len(recipes) == 0 || recipes[0].Command != s.Recipe.Command {
+			now := "none"
+			if len(recipes) > 0 {
+				now = recipes[0].Command
+			}
+			out = append(out, fmt.Sprintf("%s: %s (catalog now: %s)", s.ID, s.Recipe.Command, now))
+		}
+	}
+	return out
+}
+
+// WritePlanFile saves pf as indented JSON.
+func WritePlanFile(path string, pf PlanFile) error {
+	data, err := json.MarshalIndent(pf, "", "  ")
+	if err != nil {
+		return err
+	}
+	return os.WriteFile(path, append(data, '\n'), 0o644)
+}
+
+// ReadPlanFile loads a plan exported by WritePlanFile.
+func ReadPlanFile(path string) (PlanFile, error) {
+	data, err := os.ReadFile(path)
+	if err != nil {
+		return PlanFile{}, err
+	}
+	var pf PlanFile
+	if err := json.Unmarshal(data, &pf); err != nil {
+		return PlanFile{}, fmt.Errorf("%s: %w", path, err)
+	}
+	return pf, nil
+}
@@ -0,0 +1,63 @@
+package manager
+
+import (
+	"context"
+	"sync"
+
+	"github.com/hmziqrs/maziq/internal/options"
+)
+
+// PrefetchSteps is how many steps are downloaded ahead while a plan waits
+// for confirmation.
+const PrefetchSteps = 3
+
+// Prefetch downloads the first steps of a plan in the background.
+type Prefetch struct {
+	stop chan struct{}
+	done chan struct{}
+	once sync.Once
+}
+
+// StartPrefetch warms the download caches (brew fetch, verified installer
+// scripts) of the first PrefetchSteps installs in plan that are not
+// installed yet, one at a time, so the run starts warm once the user
+// confirms. Nothing is fetched for offline or dry runs. Failures are
+// ignored: the run downloads whatever is missing.
+func StartPrefetch(plan Plan, opts options.Run) *Prefetch {
+	p := &Prefetch{stop: make(chan struct{}), done: make(chan struct{})}
+	go func() {
+		defer close(p.done)
+		if opts.Offline || opts.DryRun {
+			return
+		}
+		ctx := context.Background()
+		m, prober := New(opts), NewProber(opts.Jobs)
+		fetched := 0
+		for _, step := range plan.Steps {
+			if fetched == PrefetchSteps {
+				return
+			}
+			select {
+			case <-p.stop:
+				return
+			default:
+			}
+			if step.Action != ActionInstall || prober.Probe(ctx, step.Entry).State == StateInstalled {
+				continue
+			}
+			m.Warm(ctx, step.Entry)
+			fetched++
+		}
+	}()
+	return p
+}
+
+// Stop starts no more downloads and waits for the one in flight, so it
+// cannot race the run for Homebrew's lock. Stop on nil does nothing.
+func (p *Prefetch) Stop() {
+	if p == nil {
+		return
+	}
+	p.once.Do(func() { close(p.stop) })
+	<-p.done
+}
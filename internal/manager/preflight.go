@@ -0,0 +1,191 @@
+package manager
+
+import (
+	"context"
+	"fmt"
+	"net"
+	"os/exec"
+	"runtime"
+	"strconv"
+	"strings"
+	"syscall"
+	"time"
+
+	"github.com/hmziqrs/maziq/internal/errs"
+	"github.com/hmziqrs/maziq/internal/options"
+)
+
+// minFreeDisk is the free space a plan needs at the very least; downloads
+// and caches of even small installs add up quickly.
+const minFreeDisk = 5 << 30
+
+// networkProbe is dialed to check network access; nearly every recipe
+// downloads from GitHub or its CDN.
+const networkProbe = "github.com:443"
+
+// Check is one preflight result.
+type Check struct {
+	Name   string
+	OK     bool
+	Detail string
+}
+
+func (c Check) String() string {
+	mark := "✓"
+	if !c.OK {
+		mark = "✗"
+	}
+	return fmt.Sprintf("%s %s: %s", mark, c.Name, c.Detail)
+}
+
+// PreflightError lists the checks that failed.
+type PreflightError struct {
+	Failed []Check
+}
+
+func (e *PreflightError) Error() string {
+	parts := make([]string, len(e.Failed))
+	for i, c := range e.Failed {
+		parts[i] = c.Name + ": " + c.Detail
+	}
+	return "preflight failed: " + strings.Join(parts, "; ")
+}
+
+func (e *PreflightError) ErrorCode() errs.Code {
+	return errs.CodePreflight
+}
+
+func (e *PreflightError) ErrorContext() errs.Context {
+	return errs.Context{Phase: "preflight"}
+}
+
+// Preflight validates that the machine can run plan: macOS version,
+// free disk space, Rosetta for Intel-only entries and network access, or
+// in offline mode which steps the cache does not cover.
+// Uninstall plans need none of that and always pass.
+func Preflight(ctx context.Context, plan Plan, opts options.Run) []Check {
+	if plan.Action == ActionUninstall || len(plan.Steps) == 0 {
+		return nil
+	}
+	var checks []Check
+	if plan.Action == ActionInstall {
+		checks = append(checks, checkMacOS(ctx, plan))
+		if c, needed := checkRosetta(ctx, plan); needed {
+			checks = append(checks, c)
+		}
+	}
+	if opts.Offline {
+		return append(checks, checkDisk(plan), checkOffline(plan, opts.Backend))
+	}
+	return append(checks, checkDisk(plan), checkNetwork(ctx))
+}
+
+// PreflightErr returns a *PreflightError for the failed checks, or nil.
+func PreflightErr(checks []Check) error {
+	var failed []Check
+	for _, c := range checks {
+		if !c.OK {
+			failed = append(failed, c)
+		}
+	}
+	if len(failed) == 0 {
+		return nil
+	}
+	return &PreflightError{Failed: failed}
+}
+
+func checkMacOS(ctx context.Context, plan Plan) Check {
+	c := Check{Name: "macOS version", OK: true}
+	out, err := exec.CommandContext(ctx, "sw_vers", "-productVersion").Output()
+	if err != nil {
+		c.Detail = "unknown (sw_vers unavailable), skipped"
+		return c
+	}
+	current := strings.TrimSpace(string(out))
+	var blocked []string
+	for _, step := range plan.Steps {
+		if need := step.Entry.Requirements.MinMacOS; need != "" && compareVersions(current, need) < 0 {
+			blocked = append(blocked, fmt.Sprintf("%s needs %s", step.Entry.ID, need))
+		}
+	}
+	c.Detail = current
+	if len(blocked) > 0 {
+		c.OK = false
+		c.Detail = current + ", but " + strings.Join(blocked, ", ")
+	}
+	return c
+}
+
+// checkRosetta reports whether Rosetta is installed when the plan holds
+// Intel-only entries and runs on Apple Silicon.
+func checkRosetta(ctx context.Context, plan Plan) (Check, bool) {
+	var intel []string
+	for _, step := range plan.Steps {
+		if step.Entry.Requirements.Rosetta {
+			intel = append(intel, step.Entry.ID)
+		}
+	}
+	if len(intel) == 0 || runtime.GOARCH != "arm64" {
+		return Check{}, false
+	}
+	c := Check{Name: "Rosetta", OK: true, Detail: "installed"}
+	if exec.CommandContext(ctx, "/usr/bin/arch", "-x86_64", "/usr/bin/true").Run() != nil {
+		c.OK = false
+		c.Detail = fmt.Sprintf("missing, needed by %s; run `softwareupdate --install-rosetta --agree-to-license`", strings.Join(intel, ", "))
+	}
+	return c, true
+}
+
+// checkDisk wants room for the plan's estimated installed size, and never
+// less than minFreeDisk.
+func checkDisk(plan Plan) Check {
+	c := Check{Name: "Disk space", OK: true}
+	var st syscall.Statfs_t
+	if err := syscall.Statfs("/", &st); err != nil {
+		c.Detail = "unknown, skipped"
+		return c
+	}
+	free := uint64(st.Bavail) * uint64(st.Bsize)
+	c.Detail = FormatSize(int64(free)) + " free"
+	needed := max(minFreeDisk, Usage(plan, PlanSizes(plan)).Installed)
+	if int64(free) < needed {
+		c.OK = false
+		c.Detail += ", at least " + FormatSize(needed) + " needed"
+	}
+	return c
+}
+
+func checkNetwork(ctx context.Context) Check {
+	c := Check{Name: "Network", OK: true, Detail: "reachable"}
+	dialer := net.Dialer{Timeout: 5 * time.Second}
+	conn, err := dialer.DialContext(ctx, "tcp", networkProbe)
+	if err != nil {
+		c.OK = false
+		c.Detail = fmt.Sprintf("cannot reach %s: %v", networkProbe, err)
+		return c
+	}
+	conn.Close()
+	return c
+}
+
+// compareVersions compares dotted numeric versions, treating missing
+// components as zero.
+func compareVersions(a, b string) int {
+	as, bs := strings.Split(a, "."), strings.Split(b, ".")
+	for i := 0; i < max(len(as), len(bs)); i++ {
+		var x, y int
+		if i < len(as) {
+			x, _ = strconv.Atoi(as[i])
+		}
+		if i < len(bs) {
+			y, _ = strconv.Atoi(bs[i])
+		}
+		if x != y {
+			if x < y {
+				return -1
+			}
+			return 1
+		}
+	}
+	return 0
+}
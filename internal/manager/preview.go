@@ -0,0 +1,86 @@
+package manager
+
+import (
+	"context"
+
+	"github.com/hmziqrs/maziq/internal/catalog"
+	"github.com/hmziqrs/maziq/internal/options"
+)
+
+// Preview is what a run would do with one step, judged from the entry's
+// current status.
+type Preview struct {
+	Step    Step
+	Status  Status
+	// Skip is set when RunPlan would skip the step.
+	Skip    bool
+	// Verdict reads like "already installed at 0.10.2 → would skip".
+	Verdict string
+}
+
+// PreviewPlan probes every entry of plan and describes what running each
+// step with opts would do, for dry runs.
+func PreviewPlan(ctx context.Context, plan Plan, opts options.Run) []Preview {
+	entries := make([]catalog.Entry, len(plan.Steps))
+	for i, step := range plan.Steps {
+		entries[i] = step.Entry
+	}
+	statuses := NewProber(opts.Jobs).ProbeAll(ctx, entries, ProbeModeFull)
+	previews := make([]Preview, len(plan.Steps))
+	for i, step := range plan.Steps {
+		st := statuses[step.Entry.ID]
+		verdict, skip := describe(step.Action, st, opts.Force)
+		previews[i] = Preview{Step: step, Status: st, Skip: skip, Verdict: verdict}
+	}
+	return previews
+}
+
+// describe mirrors what RunPlan and the recipes do in each state; force
+// is Options.Force.
+func describe(action Action, st Status, force bool) (string, bool) {
+	current := st.State.String()
+	if st.Version != "" {
+		current += " at " + st.Version
+	}
+	switch st.State {
+	case StateInstalled:
+		switch action {
+		case ActionInstall:
+			if force {
+				return "already installed at " + orUnknown(st.Version) + " → would reinstall (--force)", false
+			}
+			return "already installed at " + orUnknown(st.Version) + " → would skip", true
+		case ActionUpdate:
+			return "up to date at " + orUnknown(st.Version) + " → would check for updates", false
+		}
+		return current + " → would uninstall", false
+	case StateOutdated:
+		target := ""
+		if st.Latest != "" {
+			target = " to " + st.Latest
+		}
+		switch action {
+		case ActionInstall:
+			return current + " → would install over it", false
+		case ActionUpdate:
+			return current + " → would upgrade" + target, false
+		}
+		return current + " → would uninstall", false
+	case StateNotInstalled:
+		switch action {
+		case ActionInstall:
+			return "missing → would install", false
+		case ActionUpdate:
+			return "missing → update would fail; install it first", false
+		}
+		return "missing → nothing to uninstall", false
+	}
+	return "status unknown → would " + string(action), false
+}
+
+func orUnknown(version string) string {
+	if version == "" {
+		return "an unknown version"
+	}
+	return version
+}
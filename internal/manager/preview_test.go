@@ -0,0 +1,50 @@
+package manager
+
+import (
+	"testing"
+
+	"github.com/hmziqrs/maziq/internal/options"
+)
+
+func TestDescribe(t *testing.T) {
+	installed := Status{State: StateInstalled, Version: "1.2.3"}
+	for _, tc := range []struct {
+		action   Action
+		st       Status
+		force    bool
+		wantSkip bool
+	}{
+		{ActionInstall, installed, false, true},
+		{ActionInstall, installed, true, false},
+		{ActionUpdate, installed, false, false},
+		{ActionUninstall, installed, false, false},
+		{ActionInstall, Status{State: StateNotInstalled}, false, false},
+		{ActionInstall, Status{State: StateOutdated, Version: "1.0"}, false, false},
+		{ActionInstall, Status{State: StateUnknown}, false, false},
+	} {
+		verdict, skip := describe(tc.action, tc.st, tc.force)
+		if skip != tc.wantSkip {
+			t.Errorf("describe(%s, %s, force=%v) skip = %v (%q), want %v", tc.action, tc.st.State, tc.force, skip, verdict, tc.wantSkip)
+		}
+	}
+}
+
+// Managers built from different options must not share them, so tasks
+// can run side by side with their own overrides.
+func TestNewKeepsOptionsPerManager(t *testing.T) {
+	base := options.Defaults()
+	dry := base
+	dry.DryRun = true
+	dry.Askpass = "/usr/local/bin/askpass"
+
+	a, b := New(base), New(dry)
+	if a.Executor.DryRun || a.Executor.Askpass != "" {
+		t.Errorf("the first manager picked up the second one's options: %+v", a.Executor)
+	}
+	if !b.Executor.DryRun || b.Executor.Askpass != dry.Askpass || !b.Options.DryRun {
+		t.Errorf("the second manager ignored its options: %+v", b.Executor)
+	}
+	if base.DryRun {
+		t.Error("building a manager changed the caller's options")
+	}
+}
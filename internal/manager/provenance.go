@@ -0,0 +1,128 @@
+package manager
+
+import (
+	"context"
+	"fmt"
+	"os/exec"
+	"strings"
+
+	"github.com/hmziqrs/maziq/internal/catalog"
+	"github.com/hmziqrs/maziq/internal/errs"
+	"github.com/hmziqrs/maziq/internal/history"
+)
+
+// Origin says who installed an entry that is present.
+type Origin int
+
+const (
+	// OriginMaziq is an entry a maziq run installed.
+	OriginMaziq Origin = iota
+	// OriginBrew is a formula or cask installed with brew directly.
+	OriginBrew
+	// OriginPreExisting is anything else: there before maziq, or put there
+	// by hand.
+	OriginPreExisting
+)
+
+// Provenance is who installed an entry and, for maziq, in which run.
+type Provenance struct {
+	Origin Origin
+	// Install is maziq's install record, for OriginMaziq.
+	Install history.Install
+}
+
+func (p Provenance) String() string {
+	switch p.Origin {
+	case OriginMaziq:
+		when := p.Install.Time.Format("2006-01-02")
+		if p.Install.Run == 0 {
+			return "installed by maziq on " + when
+		}
+		return fmt.Sprintf("installed by maziq run #%d on %s", p.Install.Run, when)
+	case OriginBrew:
+		return "installed with brew outside maziq"
+	}
+	return "pre-existing, installed outside maziq"
+}
+
+// Provenances answers Of for many entries from one read of the history
+// and one listing of Homebrew.
+type Provenances struct {
+	installed map[string]history.Install
+	brew      map[string]bool
+	casks     map[string]bool
+}
+
+// LoadProvenances reads what maziq installed and what brew has installed.
+// Without brew, nothing counts as brew-direct.
+func LoadProvenances(ctx context.Context) (*Provenances, error) {
+	installed, err := history.Installed()
+	if err != nil {
+		return nil, err
+	}
+	return &Provenances{
+		installed: installed,
+		brew:      brewList(ctx, "--formula"),
+		casks:     brewList(ctx, "--cask"),
+	}, nil
+}
+
+func brewList(ctx context.Context, kind string) map[string]bool {
+	out, err := exec.CommandContext(ctx, brewBinary(), "list", "-1", kind).Output()
+	if err != nil {
+		return nil
+	}
+	names := map[string]bool{}
+	for _, name := range strings.Fields(string(out)) {
+		names[name] = true
+	}
+	return names
+}
+
+// Of returns who installed entry, assuming it is present.
+func (p *Provenances) Of(entry catalog.Entry) Provenance {
+	if install, ok := p.installed[entry.ID]; ok {
+		return Provenance{Origin: OriginMaziq, Install: install}
+	}
+	for _, r := range entry.Install {
+		switch {
+		case r.Source == catalog.SourceBrew && p.brew[r.Package],
+			r.Source == catalog.SourceBrewCask && p.casks[r.Package]:
+			return Provenance{Origin: OriginBrew}
+		}
+	}
+	return Provenance{Origin: OriginPreExisting}
+}
+
+// AdoptedError refuses to uninstall an entry maziq did not install.
+type AdoptedError struct {
+	ID         string
+	Provenance Provenance
+}
+
+func (e *AdoptedError) Error() string {
+	return fmt.Sprintf("%s is %s; pass --force-adopted to uninstall it anyway", e.ID, e.Provenance)
+}
+
+func (e *AdoptedError) ErrorContext() errs.Context {
+	return errs.Context{Entry: e.ID, Phase: string(ActionUninstall)}
+}
+
+// checkAdopted refuses uninstalling an installed entry that maziq did not
+// install, unless ForceAdopted is set.
+func (m *Manager) checkAdopted(ctx context.Context, entry catalog.Entry) error {
+	if m.Options.ForceAdopted {
+		return nil
+	}
+	if st := NewProber(m.Options.Jobs).Probe(ctx, entry); st.State != StateInstalled && st.State != StateOutdated {
+		return nil
+	}
+	provenances, err := LoadProvenances(ctx)
+	if err != nil {
+		return err
+	}
+	if p := provenances.Of(entry); p.Origin != OriginMaziq {
+		return &AdoptedError{ID: entry.ID, Provenance: p}
+	}
+	return nil
+}
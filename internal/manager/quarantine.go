@@ -0,0 +1,47 @@
+package manager
+
+import (
+	"context"
+	"os/exec"
+	"slices"
+	"strings"
+
+	"github.com/hmziqrs/maziq/internal/catalog"
+)
+
+// quarantineAttr is the extended attribute macOS puts on downloaded files,
+// which makes Gatekeeper check an app before its first launch.
+const quarantineAttr = "com.apple.quarantine"
+
+// InstallHooks returns the hooks to run after entry installs: its own
+// post-install hooks, then, for GUI entries listed in trusted (see
+// options.Run.ClearQuarantine), one clearing the quarantine attribute from
+// the app bundle.
+func InstallHooks(entry catalog.Entry, trusted []string) []catalog.Hook {
+	hooks := append([]catalog.Hook(nil), entry.PostInstall...)
+	if entry.Probe.AppPath != "" && slices.Contains(trusted, entry.ID) {
+		hooks = append(hooks, catalog.Hook{
+			Description: "clear quarantine",
+			Command:     "xattr -dr " + quarantineAttr + " " + shellQuote(entry.Probe.AppPath),
+		})
+	}
+	return hooks
+}
+
+// gatekeeperWarning reports an app bundle that still carries the
+// quarantine attribute, and whether Gatekeeper would refuse to launch it.
+// It returns "" for apps that launch without a prompt.
+func gatekeeperWarning(ctx context.Context, app string) string {
+	if exec.CommandContext(ctx, "xattr", "-p", quarantineAttr, app).Run() != nil {
+		return ""
+	}
+	out, err := exec.CommandContext(ctx, "spctl", "--assess", "--type", "execute", app).CombinedOutput()
+	if err != nil && ctx.Err() == nil {
+		reason := strings.TrimSpace(string(out))
+		if reason == "" {
+			reason = err.Error()
+		}
+		return "blocked by Gatekeeper: " + reason
+	}
+	return "quarantined; macOS asks before the first launch"
+}
@@ -0,0 +1,48 @@
+package manager
+
+import "context"
+
+// Recovery is what to do about a step that failed.
+type Recovery string
+
+const (
+	RecoveryRetry Recovery = "retry"
+	RecoverySkip  Recovery = "skip"
+	RecoveryAbort Recovery = "abort"
+)
+
+// RecoveryRequest asks a frontend what to do about a failed step. The run
+// waits until exactly one Recovery is sent on Reply.
+type RecoveryRequest struct {
+	Step  Step
+	Event ExecutionEvent
+	Err   error
+	Reply chan<- Recovery
+}
+
+// AskRecovery returns a Manager.Recover that sends a RecoveryRequest
+// through emit and waits for the answer; cancelling ctx aborts.
+func AskRecovery(emit func(any)) func(context.Context, Step, ExecutionEvent, error) Recovery {
+	return func(ctx context.Context, step Step, event ExecutionEvent, err error) Recovery {
+		reply := make(chan Recovery, 1)
+		emit(RecoveryRequest{Step: step, Event: event, Err: err, Reply: reply})
+		select {
+		case r := <-reply:
+			return r
+		case <-ctx.Done():
+			return RecoveryAbort
+		}
+	}
+}
+
+// recoverFrom decides what follows a failed step: Recover's answer when
+// set, otherwise skipping or aborting as ContinueOnError says.
+func (m *Manager) recoverFrom(ctx context.Context, step Step, event ExecutionEvent, err error) Recovery {
+	if m.Recover == nil || m.Executor.DryRun {
+		if m.Options.ContinueOnError {
+			return RecoverySkip
+		}
+		return RecoveryAbort
+	}
+	return m.Recover(ctx, step, event, err)
+}
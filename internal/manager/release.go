@@ -0,0 +1,250 @@
+package manager
+
+import (
+	"bufio"
+	"context"
+	"crypto/sha256"
+	"encoding/hex"
+	"encoding/json"
+	"fmt"
+	"io"
+	"net/http"
+	"os"
+	"path"
+	"path/filepath"
+	"runtime"
+	"strings"
+
+	"github.com/hmziqrs/maziq/internal/catalog"
+	"github.com/hmziqrs/maziq/internal/paths"
+)
+
+// maxAsset bounds how much of a release asset is downloaded.
+const maxAsset = 512 << 20
+
+// hostNames are the spellings release assets use for the host's OS and
+// architecture, most common first.
+var hostNames = map[string][]string{
+	"darwin": {"darwin", "macos", "macOS", "apple-darwin", "osx"},
+	"linux":  {"linux", "unknown-linux-gnu", "unknown-linux-musl"},
+	"arm64":  {"arm64", "aarch64"},
+	"amd64":  {"amd64", "x86_64", "x64"},
+}
+
+// archives are the asset suffixes extracted rather than installed as is.
+var archives = []string{".tar.gz", ".tgz", ".tar.xz", ".tar.bz2", ".zip"}
+
+type githubRelease struct {
+	TagName string        `json:"tag_name"`
+	Assets  []githubAsset `json:"assets"`
+}
+
+type githubAsset struct {
+	Name   string `json:"name"`
+	URL    string `json:"browser_download_url"`
+	// Digest is "sha256:<hex>" for assets uploaded since GitHub started
+	// recording it.
+	Digest string `json:"digest"`
+}
+
+// releaseScript performs action on release. Installing and updating
+// download the latest release's asset for the host, check its SHA256 and
+// copy its binaries into ~/.local/bin; uninstalling removes them. cleanup
+// removes the download once the script has run.
+func releaseScript(ctx context.Context, action Action, release catalog.GitHubRelease) (script string, cleanup func(), err error) {
+	bin := paths.LocalBinDir()
+	if action == ActionUninstall {
+		var steps []string
+		for _, name := range release.Binaries {
+			steps = append(steps, "rm -f "+shellQuote(filepath.Join(bin, name)))
+		}
+		return strings.Join(steps, " && "), func() {}, nil
+	}
+	if len(release.Binaries) == 0 {
+		return "", nil, fmt.Errorf("%s: the release recipe names no binaries", release.Repo)
+	}
+	latest, err := latestRelease(ctx, release.Repo)
+	if err != nil {
+		return "", nil, err
+	}
+	asset, ok := hostAsset(latest.Assets, release.Asset)
+	if !ok {
+		return "", nil, fmt.Errorf("%s %s has no asset matching %q for %s/%s", release.Repo, latest.TagName, release.Asset, runtime.GOOS, runtime.GOARCH)
+	}
+	want, err := assetChecksum(ctx, latest.Assets, asset)
+	if err != nil {
+		return "", nil, fmt.Errorf("%s %s: %w", release.Repo, latest.TagName, err)
+	}
+	dir, err := os.MkdirTemp("", "maziq-release-*")
+	if err != nil {
+		return "", nil, err
+	}
+	cleanup = func() { os.RemoveAll(dir) }
+	file := filepath.Join(dir, asset.Name)
+	got, err := download(ctx, asset.URL, file)
+	if err != nil {
+		cleanup()
+		return "", nil, err
+	}
+	if got != want {
+		cleanup()
+		return "", nil, fmt.Errorf("%s: SHA256 %s does not match the published %s", asset.URL, got, want)
+	}
+
+	steps := []string{"set -e", "mkdir -p " + shellQuote(bin), "cd " + shellQuote(dir)}
+	if !isArchive(asset.Name) {
+		steps = append(steps, "install -m 755 "+shellQuote(asset.Name)+" "+shellQuote(filepath.Join(bin, release.Binaries[0])))
+		return strings.Join(steps, "; "), cleanup, nil
+	}
+	// bsdtar, macOS's tar, also unpacks zip files.
+	steps = append(steps, "mkdir out", "tar -xf "+shellQuote(asset.Name)+" -C out")
+	for _, name := range release.Binaries {
+		found := fmt.Sprintf(`"$(find out -type f -name %s | head -n 1)"`, shellQuote(name))
+		steps = append(steps, fmt.Sprintf("[ -n %[1]s ] || { echo %[2]s >&2; exit 1; }", found, shellQuote(name+" is not in "+asset.Name)),
+			"install -m 755 "+found+" "+shellQuote(filepath.Join(bin, name)))
+	}
+	return strings.Join(steps, "; "), cleanup, nil
+}
+
+// latestRelease asks the GitHub API for repo's latest release. GITHUB_TOKEN
+// is sent when set, to lift the anonymous rate limit.
+func latestRelease(ctx context.Context, repo string) (githubRelease, error) {
+	var release githubRelease
+	url := "https://api.github.com/repos/" + repo + "/releases/latest"
+	req, err := http.NewRequestWithContext(ctx, http.MethodGet, url, nil)
+	if err != nil {
+		return release, err
+	}
+	req.Header.Set("Accept", "application/vnd.github+json")
+	if token := os.Getenv("GITHUB_TOKEN"); token != "" {
+		req.Header.Set("Authorization", "Bearer "+token)
+	}
+	resp, err := http.DefaultClient.Do(req)
+	if err != nil {
+		return release, err
+	}
+	defer resp.Body.Close()
+	if resp.StatusCode != http.StatusOK {
+		return release, fmt.Errorf("%s: %s", url, resp.Status)
+	}
+	if err := json.NewDecoder(resp.Body).Decode(&release); err != nil {
+		return release, fmt.Errorf("%s: %w", url, err)
+	}
+	return release, nil
+}
+
+// hostAsset returns the asset matching pattern for the host, trying every
+// spelling of its OS and architecture.
+func hostAsset(assets []githubAsset, pattern string) (githubAsset, bool) {
+	oses := hostNames[runtime.GOOS]
+	if oses == nil {
+		oses = []string{runtime.GOOS}
+	}
+	arches := hostNames[runtime.GOARCH]
+	if arches == nil {
+		arches = []string{runtime.GOARCH}
+	}
+	for _, goos := range oses {
+		for _, arch := range arches {
+			name := strings.NewReplacer("{os}", goos, "{arch}", arch).Replace(pattern)
+			for _, asset := range assets {
+				if ok, _ := path.Match(name, asset.Name); ok {
+					return asset, true
+				}
+			}
+		}
+	}
+	return githubAsset{}, false
+}
+
+// assetChecksum returns the SHA256 asset should have: the digest GitHub
+// recorded for it, or else the one listed in a checksums file of the same
+// release. A release that publishes neither is refused.
+func assetChecksum(ctx context.Context, assets []githubAsset, asset githubAsset) (string, error) {
+	if sum, ok := strings.CutPrefix(asset.Digest, "sha256:"); ok {
+		return strings.ToLower(sum), nil
+	}
+	for _, a := range assets {
+		lower := strings.ToLower(a.Name)
+		if a.Name != asset.Name+".sha256" && !strings.Contains(lower, "checksums") && !strings.Contains(lower, "sha256sums") {
+			continue
+		}
+		sums, err := fetchText(ctx, a.URL)
+		if err != nil {
+			return "", err
+		}
+		if sum, ok := listedChecksum(sums, asset.Name); ok {
+			return sum, nil
+		}
+	}
+	return "", fmt.Errorf("no SHA256 published for %s", asset.Name)
+}
+
+// listedChecksum finds name in a checksums file: "<hex>  name" lines, or a
+// lone hash for a file covering a single asset.
+func listedChecksum(sums, name string) (string, bool) {
+	scanner := bufio.NewScanner(strings.NewReader(sums))
+	for scanner.Scan() {
+		fields := strings.Fields(scanner.Text())
+		switch {
+		case len(fields) == 1 && len(fields[0]) == 64:
+			return strings.ToLower(fields[0]), true
+		case len(fields) >= 2 && len(fields[0]) == 64 && strings.TrimPrefix(fields[len(fields)-1], "*") == name:
+			return strings.ToLower(fields[0]), true
+		}
+	}
+	return "", false
+}
+
+// fetchText downloads a small text file such as a checksums list.
+func fetchText(ctx context.Context, url string) (string, error) {
+	req, err := http.NewRequestWithContext(ctx, http.MethodGet, url, nil)
+	if err != nil {
+		return "", err
+	}
+	resp, err := http.DefaultClient.Do(req)
+	if err != nil {
+		return "", err
+	}
+	defer resp.Body.Close()
+	if resp.StatusCode != http.StatusOK {
+		return "", fmt.Errorf("%s: %s", url, resp.Status)
+	}
+	data, err := io.ReadAll(io.LimitReader(resp.Body, 1<<20))
+	return string(data), err
+}
+
+// download writes url to file and returns its SHA256.
+func download(ctx context.Context, url, file string) (string, error) {
+	req, err := http.NewRequestWithContext(ctx, http.MethodGet, url, nil)
+	if err != nil {
+		return "", err
+	}
+	resp, err := http.DefaultClient.Do(req)
+	if err != nil {
+		return "", err
+	}
+	defer resp.Body.Close()
+	if resp.StatusCode != http.StatusOK {
+		return "", fmt.Errorf("%s: %s", url, resp.Status)
+	}
+	f, err := os.Create(file)
+	if err != nil {
+		return "", err
+	}
+	defer f.Close()
+	hash := sha256.New()
+	if _, err := io.Copy(io.MultiWriter(f, hash), io.LimitReader(resp.Body, maxAsset)); err != nil {
+		return "", err
+	}
+	return hex.EncodeToString(hash.Sum(nil)), f.Close()
+}
+
+func isArchive(name string) bool {
+	for _, suffix := range archives {
+		if strings.HasSuffix(name, suffix) {
+			return true
+		}
+	}
+	return false
+}
@@ -0,0 +1,61 @@
+package manager
+
+import "slices"
+
+// mustPrecede reports whether a has to run before b: installs and updates
+// need their dependencies first, uninstalls remove dependents first.
+func mustPrecede(a, b Step) bool {
+	if b.Action == ActionUninstall {
+		return slices.Contains(a.Entry.Dependencies, b.Entry.ID)
+	}
+	return slices.Contains(b.Entry.Dependencies, a.Entry.ID)
+}
+
+// MoveStep swaps step i with its neighbour above (delta -1) or below
+// (delta +1). It reports false, leaving the plan as is, when that would
+// run a step before something it needs.
+func (p Plan) MoveStep(i, delta int) (Plan, bool) {
+	j := i + delta
+	if delta != -1 && delta != 1 || i < 0 || j < 0 || i >= len(p.Steps) || j >= len(p.Steps) {
+		return p, false
+	}
+	first, second := min(i, j), max(i, j)
+	if mustPrecede(p.Steps[first], p.Steps[second]) {
+		return p, false
+	}
+	p.Steps = slices.Clone(p.Steps)
+	p.Steps[i], p.Steps[j] = p.Steps[j], p.Steps[i]
+	return p, true
+}
+
+// RunLast moves the steps of last to the end of the plan, keeping their
+// relative order. Steps that must follow one of them move along, so the
+// dependency order still holds.
+func (p Plan) RunLast(last map[string]bool) Plan {
+	if len(last) == 0 {
+		return p
+	}
+	deferred := make([]bool, len(p.Steps))
+	for i, step := range p.Steps {
+		if last[step.Entry.ID] {
+			deferred[i] = true
+			continue
+		}
+		for j := 0; j < i; j++ {
+			if deferred[j] && mustPrecede(p.Steps[j], step) {
+				deferred[i] = true
+				break
+			}
+		}
+	}
+	var head, tail []Step
+	for i, step := range p.Steps {
+		if deferred[i] {
+			tail = append(tail, step)
+		} else {
+			head = append(head, step)
+		}
+	}
+	p.Steps = append(head, tail...)
+	return p
+}
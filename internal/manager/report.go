@@ -0,0 +1,129 @@
+package manager
+
+import (
+	"context"
+	"crypto/sha256"
+	"encoding/hex"
+	"fmt"
+	"net/url"
+	"os/exec"
+	"regexp"
+	"strings"
+
+	"github.com/hmziqrs/maziq/internal/catalog"
+	"github.com/hmziqrs/maziq/internal/history"
+)
+
+// IssuesURL is where `maziq report-issue` files broken recipes.
+const IssuesURL = "https://github.com/hmziqrs/maziq/issues/new"
+
+// reportStderrLimit keeps issue URLs within what browsers accept.
+const reportStderrLimit = 3000
+
+// digits are blanked out of the error before fingerprinting, so timestamps,
+// pids and versions in it don't make every failure look new.
+var digits = regexp.MustCompile(`\d+`)
+
+// Fingerprint identifies a failure of id's action: the same command
+// ending with the same last line of stderr (or error) gets the same one.
+func Fingerprint(id, action string, event ExecutionEvent) string {
+	last := event.Error
+	if lines := strings.Split(strings.TrimSpace(event.Stderr), "\n"); lines[len(lines)-1] != "" {
+		last = lines[len(lines)-1]
+	}
+	sum := sha256.Sum256([]byte(strings.Join([]string{id, action, event.Command, digits.ReplaceAllString(last, "#")}, "\x00")))
+	return hex.EncodeToString(sum[:6])
+}
+
+// Breakage is the latest failure of an entry and how many of its runs in a
+// row ended with the same fingerprint.
+type Breakage struct {
+	Record history.Record
+	Count  int
+}
+
+// LastBreakage returns id's latest recorded failure; ok is false when its
+// latest run succeeded or it never ran.
+func LastBreakage(id string) (b Breakage, ok bool, err error) {
+	records, err := history.Load()
+	if err != nil {
+		return b, false, err
+	}
+	for i := len(records) - 1; i >= 0; i-- {
+		rec := records[i]
+		if rec.Software != id || rec.Execution == nil {
+			continue
+		}
+		if !rec.Failed || b.Count > 0 && rec.Execution.Fingerprint != b.Record.Execution.Fingerprint {
+			break
+		}
+		if b.Count == 0 {
+			b.Record = rec
+		}
+		b.Count++
+	}
+	return b, b.Count > 0, nil
+}
+
+// IssueReport pre-fills a GitHub issue about b: the failing command, its
+// stderr and the macOS and Homebrew versions it failed on.
+func IssueReport(ctx context.Context, b Breakage) (title, body string) {
+	rec, run := b.Record, b.Record.Execution
+	title = fmt.Sprintf("%s: %s via %s fails", rec.Software, rec.Action, rec.Source)
+	stderr := run.Stderr
+	if len(stderr) > reportStderrLimit {
+		stderr = "…" + stderr[len(stderr)-reportStderrLimit:]
+	}
+	var sb strings.Builder
+	fmt.Fprintf(&sb, "`%s %s` failed %d time(s) in a row (fingerprint `%s`).\n\n", rec.Action, rec.Software, b.Count, run.Fingerprint)
+	fmt.Fprintf(&sb, "Command (exit %d):\n\n```sh\n%s\n```\n\n", run.ExitCode, run.Command)
+	if run.Error != "" {
+		fmt.Fprintf(&sb, "Error: %s\n\n", run.Error)
+	}
+	if stderr != "" {
+		fmt.Fprintf(&sb, "Stderr:\n\n```\n%s\n```\n\n", strings.TrimRight(stderr, "\n"))
+	}
+	fmt.Fprintf(&sb, "- macOS: %s\n", firstLine(ctx, "sw_vers", "-productVersion"))
+	fmt.Fprintf(&sb, "- Homebrew: %s\n", firstLine(ctx, brewBinary(), "--version"))
+	if link := upstreamIssues(rec); link != "" {
+		fmt.Fprintf(&sb, "\nUpstream issues: %s\n", link)
+	}
+	return title, sb.String()
+}
+
+// upstreamIssues links a search of the upstream tracker for recipes whose
+// failure is most likely upstream, such as a renamed cask.
+func upstreamIssues(rec history.Record) string {
+	var repo string
+	switch catalog.Source(rec.Source) {
+	case catalog.SourceBrew:
+		repo = "Homebrew/homebrew-core"
+	case catalog.SourceBrewCask:
+		repo = "Homebrew/homebrew-cask"
+	default:
+		return ""
+	}
+	name := rec.Software
+	if entry, ok := catalog.Lookup(rec.Software); ok {
+		for _, r := range Recipes(entry, Action(rec.Action)) {
+			if string(r.Source) == rec.Source && r.Package != "" {
+				name = r.Package
+			}
+		}
+	}
+	return fmt.Sprintf("https://github.com/%s/issues?q=%s", repo, url.QueryEscape(name))
+}
+
+// IssueURL returns the new-issue link with title and body filled in.
+func IssueURL(title, body string) string {
+	return IssuesURL + "?" + url.Values{"title": {title}, "body": {body}}.Encode()
+}
+
+func firstLine(ctx context.Context, name string, args ...string) string {
+	out, err := exec.CommandContext(ctx, name, args...).Output()
+	if err != nil {
+		return "unknown"
+	}
+	line, _, _ := strings.Cut(strings.TrimSpace(string(out)), "\n")
+	return line
+}
@@ -0,0 +1,101 @@
+package manager
+
+import (
+	"strings"
+	"time"
+)
+
+// RetryPolicy controls how the executor retries commands that fail for
+// transient reasons (network hiccups during downloads, flaky mirrors).
+type RetryPolicy struct {
+	// MaxAttempts is the total number of attempts, including the first one.
+	MaxAttempts    int
+	// InitialBackoff is the delay before the first retry.
+	InitialBackoff time.Duration
+	// MaxBackoff caps the delay between attempts.
+	MaxBackoff     time.Duration
+	// Multiplier grows the delay after every failed attempt.
+	Multiplier     float64
+}
+
+// DefaultRetryPolicy retries transient failures twice with exponential backoff.
+func DefaultRetryPolicy() RetryPolicy {
+	return RetryPolicy{
+		MaxAttempts:    3,
+		InitialBackoff: 2 * time.Second,
+		MaxBackoff:     30 * time.Second,
+		Multiplier:     2,
+	}
+}
+
+// NoRetry runs every command exactly once.
+func NoRetry() RetryPolicy {
+	return RetryPolicy{MaxAttempts: 1}
+}
+
+func (p RetryPolicy) normalized() RetryPolicy {
+	if p.MaxAttempts < 1 {
+		p.MaxAttempts = 1
+	}
+	if p.Multiplier < 1 {
+		p.Multiplier = 1
+	}
+	return p
+}
+
+// delay returns how long to wait after the given (1-based) failed attempt.
+func (p RetryPolicy) delay(attempt int) time.Duration {
+	d := float64(p.InitialBackoff)
+	for i := 1; i < attempt; i++ {
+		d *= p.Multiplier
+	}
+	if p.MaxBackoff > 0 && time.Duration(d) > p.MaxBackoff {
+		return p.MaxBackoff
+	}
+	return time.Duration(d)
+}
+
+// transientPatterns are lowercase stderr fragments that indicate a network
+// or mirror problem worth retrying, as opposed to a broken recipe.
+var transientPatterns = []string{
+	"could not resolve host",
+	"temporary failure in name resolution",
+	"failed to connect",
+	"connection reset",
+	"connection refused",
+	"connection timed out",
+	"operation timed out",
+	"network is unreachable",
+	"ssl_error",
+	"tls handshake timeout",
+	"unexpected eof",
+	"curl: (6)",
+	"curl: (7)",
+	"curl: (18)",
+	"curl: (28)",
+	"curl: (35)",
+	"curl: (56)",
+	"error: download failed",
+	"502 bad gateway",
+	"503 service unavailable",
+	"504 gateway timeout",
+	"etimedout",
+	"econnreset",
+	"eai_again",
+	"spurious network error",
+}
+
+// IsTransient reports whether stderr output looks like a network-ish failure.
+func IsTransient(stderr string) bool {
+	return transientReason(stderr) != ""
+}
+
+func transientReason(stderr string) string {
+	lower := strings.ToLower(stderr)
+	for _, pattern := range transientPatterns {
+		if strings.Contains(lower, pattern) {
+			return pattern
+		}
+	}
+	return ""
+}
@@ -0,0 +1,57 @@
+package manager
+
+import (
+	"context"
+	"os"
+	"testing"
+)
+
+func TestTransientReason(t *testing.T) {
+	for stderr, want := range map[string]string{
+		"curl: (6) Could not resolve host: github.com":            "could not resolve host",
+		"fatal: unable to access: Connection reset by peer":       "connection reset",
+		"Error: Download failed: https://example.com/tool.tar.gz": "error: download failed",
+		"npm ERR! code EAI_AGAIN":                                 "eai_again",
+		"error: failed to download: 503 Service Unavailable":      "503 service unavailable",
+		"warning: spurious network error (2 tries remaining)":     "spurious network error",
+		"Error: No available formula with the name \"tool\"":      "",
+		"npm ERR! 404 Not Found - GET https://registry/tool":      "",
+		"error: could not compile `tool` due to previous error":   "",
+		"sudo: a password is required":                            "",
+	} {
+		if got := transientReason(stderr); got != want {
+			t.Errorf("transientReason(%q) = %q, want %q", stderr, got, want)
+		}
+		if got := IsTransient(stderr); got != (want != "") {
+			t.Errorf("IsTransient(%q) = %v, want %v", stderr, got, want != "")
+		}
+	}
+}
+
+// A transient failure is retried up to the policy's attempts; a permanent
+// one fails on the first.
+func TestRunRetriesTransientFailures(t *testing.T) {
+	if _, err := os.Stat("/bin/sh"); err != nil {
+		t.Skip("no /bin/sh")
+	}
+	tests := []struct {
+		name     string
+		stderr   string
+		attempts int
+	}{
+		{"transient", "curl: (28) Operation timed out after 30000 milliseconds", 3},
+		{"permanent", "Error: No available formula with the name tool", 1},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			e := &CommandExecutor{Shell: "/bin/sh", Retry: RetryPolicy{MaxAttempts: 3}}
+			event := e.Run(context.Background(), Command{Script: "echo " + shellQuote(tt.stderr) + " >&2; exit 1"})
+			if event.Success {
+				t.Fatal("a failing command succeeded")
+			}
+			if event.Attempts != tt.attempts || len(event.Retries) != tt.attempts-1 {
+				t.Errorf("ran %d attempts with %d retries, want %d attempts", event.Attempts, len(event.Retries), tt.attempts)
+			}
+		})
+	}
+}
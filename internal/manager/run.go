@@ -0,0 +1,387 @@
+package manager
+
+import (
+	"context"
+	"fmt"
+	"slices"
+	"strings"
+	"time"
+
+	"github.com/hmziqrs/maziq/internal/configurator"
+	"github.com/hmziqrs/maziq/internal/errs"
+	"github.com/hmziqrs/maziq/internal/history"
+)
+
+// TaskEvent is a progress event emitted while a plan runs, tagged with the
+// software id it concerns so frontends can filter the stream per entry.
+// Kind says what happened; Message is a ready-made description for
+// frontends that just print the stream.
+type TaskEvent struct {
+	Kind       EventKind
+	SoftwareID string
+	Message    string
+	Time       time.Time
+	// Step and Total place step events in the plan, counting from 1.
+	Step       int
+	Total      int
+	// Skipped is set when an install step was skipped as already present.
+	Skipped    bool
+	// Execution is set when the event reports a finished command.
+	Execution  *ExecutionEvent
+	// Hook marks events from post-install hooks, which do not change the
+	// step's outcome.
+	Hook       bool
+	// Reason says why a step was skipped or failed.
+	Reason     string
+}
+
+// RunError reports every step that failed in a run that continued past
+// failures.
+type RunError struct {
+	Failed []error
+}
+
+func (e *RunError) Error() string {
+	if len(e.Failed) == 1 {
+		return e.Failed[0].Error()
+	}
+	return fmt.Sprintf("%d steps failed", len(e.Failed))
+}
+
+func (e *RunError) Unwrap() []error {
+	return e.Failed
+}
+
+func (e *RunError) ErrorCode() errs.Code {
+	return errs.CodePartial
+}
+
+// RunPlan executes plan steps in order and reports progress through report.
+// Install steps for entries that are already present are skipped unless
+// Options.Force is set. With
+// ContinueOnError, a failing step only skips the steps that depend on it
+// and the run ends with a *RunError listing every failure; otherwise it
+// stops at the first failing step. When Recover is set it decides
+// instead: retry runs the step again, skip goes on as ContinueOnError does
+// and abort stops. Preflight checks run first and a failing one stops the
+// plan before any step; a dry run only reports it and plans on.
+func (m *Manager) RunPlan(ctx context.Context, plan Plan, report func(TaskEvent)) (err error) {
+	report = noted(report)
+	emit := func(id, format string, args ...any) {
+		report(TaskEvent{Kind: EventStepOutput, SoftwareID: id, Message: fmt.Sprintf(format, args...), Time: time.Now()})
+	}
+	total := len(plan.Steps)
+	report(TaskEvent{Kind: EventPlanComputed, Message: fmt.Sprintf("%d steps", total), Time: time.Now(), Total: total})
+	defer func() { report(runFinished(total, err)) }()
+	if !m.SkipPreflight {
+		checks := Preflight(ctx, plan, m.Options)
+		for _, c := range checks {
+			emit("preflight", "%s", c)
+		}
+		if err := PreflightErr(checks); err != nil && !m.Executor.DryRun {
+			return err
+		}
+	}
+	RefreshPath()
+	prober := NewProber(m.Options.Jobs)
+	run := runTag{started: time.Now().Unix(), template: plan.Template}
+	failed := map[string]bool{}
+	var failures []error
+	// Homebrew is updated once, before the first brew step rather than up
+	// front, so it also works for plans that install Homebrew first.
+	m.brewUpdated = false
+	m.ranHooks = map[string]bool{}
+	defer func() { m.ranHooks = nil }()
+	batchBrew := m.batchBrewUpdate(plan)
+	for i := 0; i < len(plan.Steps); i++ {
+		step := plan.Steps[i]
+		id := step.Entry.ID
+		if err := ctx.Err(); err != nil {
+			return err
+		}
+		if dep := blockedBy(plan, step, failed); dep != "" {
+			failed[id] = true
+			report(TaskEvent{
+				Kind:       EventStepFinished,
+				SoftwareID: id,
+				Message:    fmt.Sprintf("[%d/%d] %s skipped: %s failed", i+1, total, step.Entry.Name, dep),
+				Time:       time.Now(),
+				Step:       i + 1,
+				Total:      total,
+				Skipped:    true,
+				Reason:     dep + " failed",
+			})
+			continue
+		}
+		if step.Action == ActionInstall && !m.Options.Force {
+			if st := prober.Probe(ctx, step.Entry); st.State == StateInstalled {
+				report(TaskEvent{
+					Kind:       EventStepFinished,
+					SoftwareID: id,
+					Message:    fmt.Sprintf("[%d/%d] %s already installed (%s), skipping", i+1, total, step.Entry.Name, st.Version),
+					Time:       time.Now(),
+					Step:       i + 1,
+					Total:      total,
+					Skipped:    true,
+					Reason:     "already installed",
+				})
+				continue
+			}
+		}
+		if batchBrew && m.usesBrew(step) {
+			batchBrew = false
+			emit("homebrew", "updating Homebrew once for this run…")
+			if event := m.updateBrew(ctx); event.Success {
+				emit("homebrew", "✓ Homebrew updated; brew steps skip their own auto-update")
+			} else if ctx.Err() == nil {
+				emit("homebrew", "brew update failed (%s); brew steps will update themselves", event.Error)
+			}
+		}
+		report(TaskEvent{
+			Kind:       EventStepStarted,
+			SoftwareID: id,
+			Message:    fmt.Sprintf("[%d/%d] %s %s…", i+1, total, step.Action, step.Entry.Name),
+			Time:       time.Now(),
+			Step:       i + 1,
+			Total:      total,
+		})
+		started := time.Now()
+		event, err := m.Apply(ctx, step.Entry, step.Action)
+		for _, retry := range event.Retries {
+			emit(id, "attempt %d failed (%s), retrying in %s", retry.Attempt, retry.Reason, retry.Delay)
+		}
+		finished := event
+		if err != nil {
+			if ctx.Err() != nil {
+				report(TaskEvent{Kind: EventStepFinished, SoftwareID: id, Message: "✗ cancelled", Time: time.Now(), Step: i + 1, Total: total, Execution: &finished})
+				return ctx.Err()
+			}
+			reason := event.Error
+			if reason == "" {
+				reason = err.Error()
+			}
+			report(TaskEvent{Kind: EventStepFinished, SoftwareID: id, Message: "✗ " + err.Error(), Time: time.Now(), Step: i + 1, Total: total, Execution: &finished, Reason: reason})
+			if event.Hint != "" {
+				emit(id, "hint: %s", event.Hint)
+			}
+			took := time.Since(started)
+			recovery := m.recoverFrom(ctx, step, event, err)
+			if !m.Executor.DryRun && event.Command != "" {
+				chosen := recovery
+				if m.Recover == nil {
+					chosen = ""
+				}
+				if err := m.record(ctx, prober, run, step, event, took, chosen); err != nil {
+					emit(id, "could not record history: %v", err)
+				} else if b, ok, _ := LastBreakage(id); ok && b.Count > 1 {
+					emit(id, "%s failed the same way %d times in a row; `maziq report-issue %s` drafts a bug report", id, b.Count, id)
+				}
+			}
+			switch recovery {
+			case RecoveryRetry:
+				emit(id, "retrying %s", step.Entry.Name)
+				i--
+				continue
+			case RecoveryAbort:
+				if ctx.Err() != nil {
+					return ctx.Err()
+				}
+				return err
+			}
+			failed[id] = true
+			failures = append(failures, err)
+			continue
+		}
+		report(TaskEvent{Kind: EventStepFinished, SoftwareID: id, Message: fmt.Sprintf("✓ %s %s via %s", step.Action, id, event.Source), Time: time.Now(), Step: i + 1, Total: total, Execution: &finished})
+		if added := RefreshPath(); len(added) > 0 {
+			emit(id, "added %s to PATH for the remaining steps", strings.Join(added, ", "))
+		}
+		for i, hook := range m.RunHooks(ctx, step) {
+			message := "✓ post-install: " + step.Hooks[i].Description
+			if hook.Reused {
+				message += " (already done this run)"
+			} else if !hook.Success {
+				message = fmt.Sprintf("✗ post-install %q failed: %s", step.Hooks[i].Description, hook.Error)
+			}
+			report(TaskEvent{Kind: EventStepOutput, SoftwareID: id, Message: message, Time: time.Now(), Execution: &hook, Hook: true})
+		}
+		for _, name := range step.Profiles {
+			report(TaskEvent{Kind: EventStepOutput, SoftwareID: id, Message: m.provision(ctx, name), Time: time.Now(), Hook: true})
+		}
+		if !m.Executor.DryRun {
+			if err := m.record(ctx, prober, run, step, event, time.Since(started), ""); err != nil {
+				emit(id, "could not record history: %v", err)
+			}
+		}
+		if err := m.verify(ctx, step, report); err != nil {
+			if !m.Options.ContinueOnError {
+				return err
+			}
+			failed[id] = true
+			failures = append(failures, err)
+		}
+	}
+	if len(failures) > 0 {
+		return &RunError{Failed: failures}
+	}
+	return nil
+}
+
+// verify runs the test command of a step that just installed or updated
+// its entry, so installs that report success but left something broken
+// still fail the step.
+func (m *Manager) verify(ctx context.Context, step Step, report func(TaskEvent)) error {
+	if m.Executor.DryRun || step.Action == ActionUninstall || step.Entry.Test == "" {
+		return nil
+	}
+	event, err := m.Test(ctx, step.Entry)
+	if err == nil {
+		report(TaskEvent{Kind: EventStepOutput, SoftwareID: step.Entry.ID, Message: "✓ verified: " + step.Entry.Test, Time: time.Now(), Hook: true})
+		return nil
+	}
+	report(TaskEvent{
+		Kind:       EventStepFinished,
+		SoftwareID: step.Entry.ID,
+		Message:    fmt.Sprintf("✗ %s %s succeeded but its test failed: %s", step.Action, step.Entry.ID, event.Error),
+		Time:       time.Now(),
+		Execution:  &event,
+		Reason:     "test failed: " + step.Entry.Test,
+	})
+	return err
+}
+
+// blockedBy returns the failed plan entry that step can't run without: a
+// failed dependency for installs and updates, a failed dependent for
+// uninstalls, which would break it. It returns "" when step can run.
+func blockedBy(plan Plan, step Step, failed map[string]bool) string {
+	if len(failed) == 0 {
+		return ""
+	}
+	if step.Action != ActionUninstall {
+		for _, dep := range step.Entry.Dependencies {
+			if failed[dep] {
+				return dep
+			}
+		}
+		return ""
+	}
+	for _, other := range plan.Steps {
+		if failed[other.Entry.ID] && slices.Contains(other.Entry.Dependencies, step.Entry.ID) {
+			return other.Entry.ID
+		}
+	}
+	return ""
+}
+
+// RunHooks runs step's post-install hooks, each as its own command, and
+// returns one event per hook. A failing hook does not stop the others.
+// Within RunPlan, a hook command that already succeeded for an earlier
+// step (say, the same `rustup target add`) is not run again; its event is
+// marked Reused. This is the only deduplication: the steps' own recipes
+// run regardless of what ran before them.
+func (m *Manager) RunHooks(ctx context.Context, step Step) []ExecutionEvent {
+	var out []ExecutionEvent
+	for _, hook := range step.Hooks {
+		if m.ranHooks[hook.Command] {
+			out = append(out, ExecutionEvent{
+				SoftwareID: step.Entry.ID,
+				Action:     "post-install",
+				Source:     "hook",
+				Command:    hook.Command,
+				Success:    true,
+				Timestamp:  time.Now(),
+				Reused:     true,
+			})
+			continue
+		}
+		event := m.Executor.Run(ctx, Command{
+			SoftwareID: step.Entry.ID,
+			Action:     "post-install",
+			Source:     "hook",
+			Script:     hook.Command,
+		})
+		if event.Success && m.ranHooks != nil {
+			m.ranHooks[hook.Command] = true
+		}
+		out = append(out, event)
+	}
+	return out
+}
+
+// provision applies an editor profile after its editor installed and
+// describes the outcome. Like hooks, a failure does not fail the step. A
+// dry run only diffs the profile against the machine.
+func (m *Manager) provision(ctx context.Context, name string) string {
+	p, err := configurator.Load(name)
+	if err == nil && m.Executor.DryRun {
+		var changes []configurator.Change
+		if changes, err = configurator.Diff(ctx, p, configurator.Options{}); err == nil {
+			var pending []string
+			for _, c := range changes {
+				if c.Pending {
+					pending = append(pending, c.Key)
+				}
+			}
+			if len(pending) == 0 {
+				return "[dry-run] editor profile " + name + " already matches"
+			}
+			return fmt.Sprintf("[dry-run] editor profile %s would change %s", name, strings.Join(pending, ", "))
+		}
+	}
+	if err == nil {
+		var written []configurator.Change
+		if written, err = configurator.Apply(ctx, p, configurator.Options{}); err == nil {
+			return fmt.Sprintf("✓ editor profile %s: %d change(s)", name, len(written))
+		}
+	}
+	return fmt.Sprintf("✗ editor profile %s failed: %v", name, err)
+}
+
+// RecordStep adds a step run outside RunPlan (a single install from the
+// catalog, say) to the history as a run of its own, so it counts towards
+// estimates and provenance like plan steps do.
+func (m *Manager) RecordStep(ctx context.Context, step Step, event ExecutionEvent, took time.Duration) error {
+	return m.record(ctx, NewProber(m.Options.Jobs), runTag{started: time.Now().Add(-took).Unix()}, step, event, took, "")
+}
+
+// runTag ties the history records of one plan run together.
+type runTag struct {
+	started  int64
+	template string
+}
+
+// record appends a finished or failed step to the history, with the
+// version now installed, how long the step took, which feeds later
+// estimates, the command's exit code and output and, for failures, the
+// recovery the user chose. Dry runs record nothing.
+func (m *Manager) record(ctx context.Context, prober *Prober, run runTag, step Step, event ExecutionEvent, took time.Duration, recovery Recovery) error {
+	if m.Executor.DryRun {
+		return nil
+	}
+	rec := history.Record{
+		Software:   step.Entry.ID,
+		Action:     string(step.Action),
+		Source:     event.Source,
+		Timestamp:  time.Now().Unix(),
+		DurationMs: took.Milliseconds(),
+		Failed:     !event.Success,
+		Run:        run.started,
+		Template:   run.template,
+		Recovery:   string(recovery),
+		Execution: &history.Execution{
+			Command:  event.Command,
+			Attempts: event.Attempts,
+			ExitCode: event.ExitCode,
+			Error:    event.Error,
+			Stdout:   event.Stdout,
+			Stderr:   event.Stderr,
+		},
+	}
+	if !event.Success {
+		rec.Execution.Fingerprint = Fingerprint(step.Entry.ID, string(step.Action), event)
+	}
+	if step.Action != ActionUninstall && event.Success {
+		rec.Version = prober.Probe(ctx, step.Entry).Version
+	}
+	return history.Append(rec)
+}
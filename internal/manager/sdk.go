@@ -0,0 +1,41 @@
+package manager
+
+import (
+	"fmt"
+	"strings"
+
+	"github.com/hmziqrs/maziq/internal/catalog"
+	"github.com/hmziqrs/maziq/internal/errs"
+)
+
+// SDKRemovalError refuses to uninstall an SDK without ConfirmSDKRemoval:
+// its toolchains and caches go with it.
+type SDKRemovalError struct {
+	ID      string
+	Removes []string
+}
+
+func (e *SDKRemovalError) Error() string {
+	what := "its toolchains and caches"
+	if len(e.Removes) > 0 {
+		what = strings.Join(e.Removes, ", ")
+	}
+	return fmt.Sprintf("uninstalling %s deletes %s; pass --confirm-sdk-removal to go ahead", e.ID, what)
+}
+
+func (e *SDKRemovalError) ErrorCode() errs.Code {
+	return errs.CodeAborted
+}
+
+func (e *SDKRemovalError) ErrorContext() errs.Context {
+	return errs.Context{Entry: e.ID, Phase: string(ActionUninstall)}
+}
+
+// checkSDKRemoval refuses uninstalling an SDK unless ConfirmSDKRemoval is
+// set. Dry runs are refused too, so they show what a real run would do.
+func (m *Manager) checkSDKRemoval(entry catalog.Entry) error {
+	if entry.Kind != catalog.KindSDK || m.Options.ConfirmSDKRemoval {
+		return nil
+	}
+	return &SDKRemovalError{ID: entry.ID, Removes: entry.Removes}
+}
@@ -0,0 +1,195 @@
+package manager
+
+import (
+	"context"
+	"encoding/json"
+	"fmt"
+	"net/http"
+	"os/exec"
+	"sort"
+	"strings"
+	"sync"
+	"time"
+
+	"github.com/hmziqrs/maziq/internal/catalog"
+)
+
+// DiskUsage is the approximate disk footprint of a plan.
+type DiskUsage struct {
+	Download  int64
+	Installed int64
+	// Unknown counts the install steps without any size information.
+	Unknown   int
+}
+
+// PlanSizes returns the catalog size of each install step of plan, keyed by
+// software id. Other actions don't add to disk usage and yield nil.
+func PlanSizes(plan Plan) map[string]catalog.Size {
+	if plan.Action != ActionInstall {
+		return nil
+	}
+	out := map[string]catalog.Size{}
+	for _, step := range plan.Steps {
+		out[step.Entry.ID] = step.Entry.Size
+	}
+	return out
+}
+
+// FetchSizes is PlanSizes with the download size of unsized Homebrew
+// entries filled in from `brew info --json`: the length of the bottle or
+// cask archive it points to. Lookups that fail leave the size unknown.
+func FetchSizes(ctx context.Context, plan Plan) map[string]catalog.Size {
+	sizes := PlanSizes(plan)
+	tokens := map[string]string{}
+	for _, step := range plan.Steps {
+		if sizes[step.Entry.ID] != (catalog.Size{}) {
+			continue
+		}
+		if token, ok := brewToken(step.Entry); ok {
+			tokens[token] = step.Entry.ID
+		}
+	}
+	if len(tokens) == 0 {
+		return sizes
+	}
+	ctx, cancel := context.WithTimeout(ctx, 15*time.Second)
+	defer cancel()
+	urls, err := brewArchiveURLs(ctx, tokens)
+	if err != nil {
+		return sizes
+	}
+	var mu sync.Mutex
+	var wg sync.WaitGroup
+	sem := make(chan struct{}, 8)
+	for token, url := range urls {
+		wg.Add(1)
+		go func() {
+			defer wg.Done()
+			sem <- struct{}{}
+			defer func() { <-sem }()
+			if n := contentLength(ctx, url); n > 0 {
+				mu.Lock()
+				sizes[tokens[token]] = catalog.Size{Download: n}
+				mu.Unlock()
+			}
+		}()
+	}
+	wg.Wait()
+	return sizes
+}
+
+// Usage totals sizes over plan. Entries with only a download size count it
+// as their installed size too, which underestimates unpacked apps.
+func Usage(plan Plan, sizes map[string]catalog.Size) DiskUsage {
+	var u DiskUsage
+	if sizes == nil {
+		return u
+	}
+	for _, step := range plan.Steps {
+		size := sizes[step.Entry.ID]
+		if size == (catalog.Size{}) {
+			u.Unknown++
+			continue
+		}
+		u.Download += size.Download
+		u.Installed += max(size.Installed, size.Download)
+	}
+	return u
+}
+
+// brewToken returns the formula or cask name of e's Homebrew install
+// recipe.
+func brewToken(e catalog.Entry) (string, bool) {
+	for _, recipe := range e.Install {
+		if recipe.Source != catalog.SourceBrew && recipe.Source != catalog.SourceBrewCask {
+			continue
+		}
+		if recipe.Package != "" {
+			return recipe.Package, true
+		}
+	}
+	return "", false
+}
+
+// brewArchiveURLs maps each token to the archive Homebrew would download
+// for it.
+func brewArchiveURLs(ctx context.Context, tokens map[string]string) (map[string]string, error) {
+	args := []string{"info", "--json=v2"}
+	for token := range tokens {
+		args = append(args, token)
+	}
+	out, err := exec.CommandContext(ctx, brewBinary(), args...).Output()
+	if err != nil {
+		return nil, err
+	}
+	var info struct {
+		Formulae []struct {
+			Name   string `json:"name"`
+			Bottle struct {
+				Stable struct {
+					Files map[string]struct {
+						URL string `json:"url"`
+					} `json:"files"`
+				} `json:"stable"`
+			} `json:"bottle"`
+		} `json:"formulae"`
+		Casks []struct {
+			Token string `json:"token"`
+			URL   string `json:"url"`
+		} `json:"casks"`
+	}
+	if err := json.Unmarshal(out, &info); err != nil {
+		return nil, fmt.Errorf("parse brew info: %w", err)
+	}
+	urls := map[string]string{}
+	for _, f := range info.Formulae {
+		// Bottles for different macOS versions are close enough in size;
+		// pick one deterministically.
+		tags := make([]string, 0, len(f.Bottle.Stable.Files))
+		for tag := range f.Bottle.Stable.Files {
+			tags = append(tags, tag)
+		}
+		if len(tags) > 0 {
+			sort.Strings(tags)
+			urls[f.Name] = f.Bottle.Stable.Files[tags[0]].URL
+		}
+	}
+	for _, c := range info.Casks {
+		if c.URL != "" {
+			urls[c.Token] = c.URL
+		}
+	}
+	return urls, nil
+}
+
+// contentLength returns the size of the resource at url from a HEAD
+// request, or 0 when it is unknown.
+func contentLength(ctx context.Context, url string) int64 {
+	req, err := http.NewRequestWithContext(ctx, http.MethodHead, url, nil)
+	if err != nil {
+		return 0
+	}
+	if strings.HasPrefix(url, "https://ghcr.io/") {
+		// Homebrew's bottles are public; GitHub's registry still wants the
+		// anonymous token Homebrew itself sends.
+		req.Header.Set("Authorization", "Bearer QQ==")
+	}
+	resp, err := http.DefaultClient.Do(req)
+	if err != nil {
+		return 0
+	}
+	resp.Body.Close()
+	if resp.StatusCode != http.StatusOK {
+		return 0
+	}
+	return max(resp.ContentLength, 0)
+}
+
+// FormatSize renders n bytes for humans, e.g. "1.4 GB" or "230 MB".
+func FormatSize(n int64) string {
+	const gib = 1 << 30
+	if n >= gib {
+		return fmt.Sprintf("%.1f GB", float64(n)/gib)
+	}
+	return fmt.Sprintf("%d MB", n>>20)
+}
@@ -0,0 +1,52 @@
+package manager
+
+import (
+	"context"
+	"time"
+
+	"github.com/hmziqrs/maziq/internal/catalog"
+	"github.com/hmziqrs/maziq/internal/snapshot"
+)
+
+// takeSnapshot records a GUI entry that is about to be uninstalled: its
+// app bundle, cask and version. Entries that aren't installed have nothing
+// to record.
+func (m *Manager) takeSnapshot(ctx context.Context, entry catalog.Entry) error {
+	st := NewProber(m.Options.Jobs).Probe(ctx, entry)
+	if st.State != StateInstalled && st.State != StateOutdated {
+		return nil
+	}
+	s := snapshot.Snapshot{
+		Software:  entry.ID,
+		Name:      entry.Name,
+		AppPath:   entry.Probe.AppPath,
+		Cask:      packageName(entry, catalog.SourceBrewCask),
+		MasAppID:  entry.Probe.MasAppID,
+		Version:   st.Version,
+		Timestamp: time.Now().Unix(),
+	}
+	if recipes := PreferredRecipes(entry, ActionUninstall, m.Options.Backend); len(recipes) > 0 {
+		s.Command = recipes[0].Command
+	}
+	return snapshot.Append(s)
+}
+
+// Restore reinstalls the entry recorded in s, pinned to the recorded
+// version when its adapter can pin. Casks and App Store apps only install
+// their current version, so callers should compare the returned status
+// with the snapshot.
+func (m *Manager) Restore(ctx context.Context, s snapshot.Snapshot) (ExecutionEvent, Status, error) {
+	entry, ok := catalog.Lookup(s.Software)
+	if !ok {
+		return ExecutionEvent{}, Status{}, &catalog.UnknownError{ID: s.Software}
+	}
+	if pinner, ok := AdapterFor(entry).(Pinner); ok && s.Version != "" {
+		entry = pinner.Pin(entry, s.Version)
+	}
+	event, err := m.Apply(ctx, entry, ActionInstall)
+	if err != nil || m.Executor.DryRun {
+		return event, Status{}, err
+	}
+	RefreshPath()
+	return event, NewProber(m.Options.Jobs).Probe(ctx, entry), nil
+}
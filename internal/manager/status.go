@@ -0,0 +1,268 @@
+package manager
+
+import (
+	"context"
+	"encoding/json"
+	"os"
+	"os/exec"
+	"regexp"
+	"strings"
+	"sync"
+	"time"
+
+	"github.com/hmziqrs/maziq/internal/catalog"
+)
+
+// State is the detected installation state of an entry.
+type State int
+
+const (
+	StateUnknown State = iota
+	StateNotInstalled
+	StateInstalled
+	StateOutdated
+	StateError
+)
+
+func (s State) String() string {
+	switch s {
+	case StateNotInstalled:
+		return "missing"
+	case StateInstalled:
+		return "installed"
+	case StateOutdated:
+		return "outdated"
+	case StateError:
+		return "error"
+	default:
+		return "unknown"
+	}
+}
+
+// Status is the result of probing a single entry.
+type Status struct {
+	ID          string
+	State       State
+	Version     string
+	// Latest is the newest available version, when the adapter knows it.
+	Latest      string
+	Error       string
+	// Warning flags an installed entry that may not work as expected,
+	// e.g. an app Gatekeeper blocks.
+	Warning     string
+	// DeepPending is set when the entry has a deep probe that has not run yet.
+	DeepPending bool
+	CheckedAt   time.Time
+}
+
+// ProbeMode selects which probes a refresh runs.
+type ProbeMode int
+
+const (
+	// ProbeModeQuick runs quick probes and marks deep ones as pending.
+	ProbeModeQuick ProbeMode = iota
+	// ProbeModeDeep runs only the deep probes.
+	ProbeModeDeep
+	// ProbeModeFull runs every probe regardless of depth.
+	ProbeModeFull
+)
+
+// Prober detects installed versions, time-boxing each probe by its depth.
+type Prober struct {
+	Shell        string
+	QuickTimeout time.Duration
+	DeepTimeout  time.Duration
+	// Workers bounds how many probes ProbeAll runs at once.
+	Workers      int
+}
+
+// NewProber returns a prober with defaults tuned to keep refreshes snappy,
+// running at most jobs probes at once; zero picks the default.
+func NewProber(jobs int) *Prober {
+	workers := jobs
+	if workers <= 0 {
+		workers = 8
+	}
+	return &Prober{
+		Shell:        "sh",
+		QuickTimeout: 2 * time.Second,
+		DeepTimeout:  30 * time.Second,
+		Workers:      workers,
+	}
+}
+
+// ProbeAll probes the entries selected by mode. In quick mode, entries with
+// deep probes are returned with DeepPending set and are not executed.
+// Probes run concurrently, at most Workers at a time.
+func (p *Prober) ProbeAll(ctx context.Context, entries []catalog.Entry, mode ProbeMode) map[string]Status {
+	RefreshPath()
+	out := make(map[string]Status, len(entries))
+	var (
+		mu  sync.Mutex
+		wg  sync.WaitGroup
+		sem = make(chan struct{}, max(p.Workers, 1))
+	)
+	for _, entry := range entries {
+		deep := entry.Probe.Depth == catalog.ProbeDeep
+		switch {
+		case mode == ProbeModeQuick && deep:
+			mu.Lock()
+			out[entry.ID] = Status{ID: entry.ID, State: StateUnknown, DeepPending: true}
+			mu.Unlock()
+		case mode == ProbeModeDeep && !deep:
+			continue
+		default:
+			wg.Add(1)
+			sem <- struct{}{}
+			go func(entry catalog.Entry) {
+				defer wg.Done()
+				status := p.Probe(ctx, entry)
+				<-sem
+				mu.Lock()
+				out[entry.ID] = status
+				mu.Unlock()
+			}(entry)
+		}
+	}
+	wg.Wait()
+	return out
+}
+
+// Probe detects a single entry's state through its adapter. When ctx is
+// cancelled the state is unknown: commands killed by it say nothing about
+// whether the entry is installed.
+func (p *Prober) Probe(ctx context.Context, entry catalog.Entry) Status {
+	timeout := p.QuickTimeout
+	if entry.Probe.Depth == catalog.ProbeDeep {
+		timeout = p.DeepTimeout
+	}
+	probeCtx, cancel := context.WithTimeout(ctx, timeout)
+	defer cancel()
+
+	checked := time.Now()
+	var status Status
+	if ctx.Err() == nil {
+		status = AdapterFor(entry).Probe(probeCtx, p, entry)
+		if entry.Probe.AppPath != "" && (status.State == StateInstalled || status.State == StateOutdated) {
+			status.Warning = gatekeeperWarning(probeCtx, entry.Probe.AppPath)
+		}
+	}
+	switch {
+	case ctx.Err() != nil:
+		status = Status{State: StateUnknown, Error: "probe cancelled: " + ctx.Err().Error()}
+	case probeCtx.Err() == context.DeadlineExceeded:
+		status = Status{State: StateError, Error: "probe timed out after " + timeout.String()}
+	}
+	status.ID = entry.ID
+	status.CheckedAt = checked
+	return status
+}
+
+// probeCatalog runs entry's catalog probe: its app bundle, mas app, pipx
+// package or version command, in that order. Version commands look in
+// ~/.local/bin before the rest of PATH.
+func (p *Prober) probeCatalog(ctx context.Context, entry catalog.Entry) Status {
+	var status Status
+	if entry.Probe.AppPath != "" {
+		if _, err := os.Stat(entry.Probe.AppPath); err != nil {
+			status.State = StateNotInstalled
+			return status
+		}
+		status.State = StateInstalled
+		out, err := exec.CommandContext(ctx, "mdls", "-name", "kMDItemVersion", "-raw", entry.Probe.AppPath).Output()
+		if v := strings.TrimSpace(string(out)); err == nil && v != "(null)" {
+			status.Version = v
+		}
+		return status
+	}
+	if entry.Probe.MasAppID != "" {
+		return p.probeMas(ctx, status, entry.Probe.MasAppID)
+	}
+	if entry.Probe.PipxPackage != "" {
+		if version, ok := pipxVersion(ctx, entry.Probe.PipxPackage); ok {
+			status.State = StateInstalled
+			status.Version = version
+			return status
+		}
+		// Fall through to the command probe for installs made outside pipx.
+	}
+	if entry.Probe.Command == "" {
+		status.State = StateNotInstalled
+		return status
+	}
+
+	shell := p.Shell
+	if shell == "" {
+		shell = "sh"
+	}
+	cmd := exec.CommandContext(ctx, shell, "-c", BrewCommand(entry.Probe.Command))
+	cmd.Env = localBinFirst(os.Environ())
+	out, err := cmd.Output()
+	if err != nil {
+		status.State = StateNotInstalled
+		return status
+	}
+	status.State = StateInstalled
+	status.Version = extractVersion(string(out))
+	return status
+}
+
+// probeMas looks the app up in `mas list`, whose lines look like
+// "497799835  Xcode  (15.4)".
+func (p *Prober) probeMas(ctx context.Context, status Status, appID string) Status {
+	out, err := exec.CommandContext(ctx, "mas", "list").Output()
+	if err != nil {
+		status.State = StateError
+		status.Error = "mas list failed (is mas installed?)"
+		return status
+	}
+	status.State = StateNotInstalled
+	for _, line := range strings.Split(string(out), "\n") {
+		fields := strings.Fields(line)
+		if len(fields) > 0 && fields[0] == appID {
+			status.State = StateInstalled
+			status.Version = strings.Trim(fields[len(fields)-1], "()")
+			break
+		}
+	}
+	return status
+}
+
+// pipxList mirrors the parts of `pipx list --json` maziq reads.
+type pipxList struct {
+	Venvs map[string]struct {
+		Metadata struct {
+			MainPackage struct {
+				PackageVersion string `json:"package_version"`
+			} `json:"main_package"`
+		} `json:"metadata"`
+	} `json:"venvs"`
+}
+
+// pipxVersion returns the version pipx reports for pkg, if pipx manages it.
+func pipxVersion(ctx context.Context, pkg string) (string, bool) {
+	out, err := exec.CommandContext(ctx, "pipx", "list", "--json").Output()
+	if err != nil {
+		return "", false
+	}
+	var list pipxList
+	if err := json.Unmarshal(out, &list); err != nil {
+		return "", false
+	}
+	venv, ok := list.Venvs[pkg]
+	if !ok {
+		return "", false
+	}
+	return venv.Metadata.MainPackage.PackageVersion, true
+}
+
+var versionPattern = regexp.MustCompile(`\d+(\.\d+)+([-+.][0-9A-Za-z.]+)?`)
+
+// extractVersion pulls the first version-looking token out of probe output.
+func extractVersion(output string) string {
+	if v := versionPattern.FindString(output); v != "" {
+		return v
+	}
+	line, _, _ := strings.Cut(strings.TrimSpace(output), "\n")
+	return line
+}
@@ -0,0 +1,26 @@
+package manager
+
+import (
+	"context"
+	"testing"
+
+	"github.com/hmziqrs/maziq/internal/testenv"
+)
+
+// A probe the caller cancelled reports an unknown state, not a missing
+// entry, and a probe left alone still classifies the entry.
+func TestProbeCancelled(t *testing.T) {
+	env := testenv.New(t)
+	t.Setenv("PATH", "/usr/bin:/bin")
+	entry := lifecycleEntry(env.Home)
+	prober := NewProber(0)
+
+	if got := prober.Probe(context.Background(), entry); got.State != StateNotInstalled {
+		t.Errorf("probe of a missing entry = %s, want %s", got.State, StateNotInstalled)
+	}
+	ctx, cancel := context.WithCancel(context.Background())
+	cancel()
+	if got := prober.Probe(ctx, entry); got.State != StateUnknown || got.Error == "" {
+		t.Errorf("cancelled probe = %s (%q), want %s with the reason", got.State, got.Error, StateUnknown)
+	}
+}
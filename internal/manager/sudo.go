@@ -0,0 +1,115 @@
+package manager
+
+import (
+	"context"
+	"fmt"
+	"os"
+	"os/exec"
+	"regexp"
+	"strings"
+	"time"
+
+	"github.com/hmziqrs/maziq/internal/errs"
+	"github.com/hmziqrs/maziq/internal/options"
+)
+
+// sudoPattern matches sudo used as a command, not as part of a word.
+var sudoPattern = regexp.MustCompile(`(^|[;&|(\s])sudo\s`)
+
+// sudoKeepAliveInterval is well inside sudo's default 5 minute timeout.
+const sudoKeepAliveInterval = time.Minute
+
+// ErrSudoUnavailable is returned when a run needs administrator rights that
+// could not be obtained.
+var ErrSudoUnavailable = errs.New(errs.CodePermission, "sudo credentials unavailable")
+
+// NeedsSudo reports whether script invokes sudo.
+func NeedsSudo(script string) bool {
+	return sudoPattern.MatchString(script)
+}
+
+// PlanNeedsSudo reports whether any command the plan would run first for
+// its steps, including post-install hooks, invokes sudo.
+func PlanNeedsSudo(plan Plan, backend options.Backend) bool {
+	for _, step := range plan.Steps {
+		if recipes := PreferredRecipes(step.Entry, step.Action, backend); len(recipes) > 0 && NeedsSudo(recipes[0].Command) {
+			return true
+		}
+		for _, hook := range step.Hooks {
+			if NeedsSudo(hook.Command) {
+				return true
+			}
+		}
+	}
+	return false
+}
+
+// sudoShim makes every sudo inside a script non-interactive: it uses the
+// askpass helper when there is one and otherwise fails fast instead of
+// waiting for a password on a terminal nobody is looking at.
+func sudoShim(askpass string) string {
+	if askpass != "" {
+		return fmt.Sprintf("export SUDO_ASKPASS=%s; sudo() { command sudo -A \"$@\"; }; ", shellQuote(askpass))
+	}
+	return `sudo() { command sudo -n "$@"; }; `
+}
+
+// fishSudoShim is sudoShim for recipes run by fish.
+func fishSudoShim(askpass string) string {
+	if askpass != "" {
+		return fmt.Sprintf("set -x SUDO_ASKPASS %s; function sudo; command sudo -A $argv; end; ", fishQuote(askpass))
+	}
+	return "function sudo; command sudo -n $argv; end; "
+}
+
+func shellQuote(s string) string {
+	return "'" + strings.ReplaceAll(s, "'", `'\''`) + "'"
+}
+
+// fishQuote quotes s for fish, whose single quotes escape with a
+// backslash.
+func fishQuote(s string) string {
+	return "'" + strings.NewReplacer(`\`, `\\`, "'", `\'`).Replace(s) + "'"
+}
+
+// SudoCached reports whether sudo credentials are already cached.
+func SudoCached(ctx context.Context) bool {
+	return exec.CommandContext(ctx, "sudo", "-n", "true").Run() == nil
+}
+
+// SudoAuthenticate caches sudo credentials: from password when given (the
+// TUI's modal), through askpass when configured, or by prompting on the
+// terminal.
+func SudoAuthenticate(ctx context.Context, password, askpass string) error {
+	var c *exec.Cmd
+	switch {
+	case password != "":
+		c = exec.CommandContext(ctx, "sudo", "-S", "-p", "", "-v")
+		c.Stdin = strings.NewReader(password + "\n")
+	case askpass != "":
+		c = exec.CommandContext(ctx, "sudo", "-A", "-v")
+		c.Env = append(os.Environ(), "SUDO_ASKPASS="+askpass)
+	default:
+		c = exec.CommandContext(ctx, "sudo", "-v")
+		c.Stdin, c.Stdout, c.Stderr = os.Stdin, os.Stdout, os.Stderr
+	}
+	if err := c.Run(); err != nil {
+		return fmt.Errorf("%w: authentication failed: %w", ErrSudoUnavailable, err)
+	}
+	return nil
+}
+
+// SudoKeepAlive refreshes the cached credentials until ctx is done, so
+// long runs don't hit sudo's timeout halfway through.
+func SudoKeepAlive(ctx context.Context) {
+	ticker := time.NewTicker(sudoKeepAliveInterval)
+	defer ticker.Stop()
+	for {
+		select {
+		case <-ctx.Done():
+			return
+		case <-ticker.C:
+			exec.CommandContext(ctx, "sudo", "-n", "-v").Run()
+		}
+	}
+}
@@ -0,0 +1,69 @@
+package manager
+
+import (
+	"os"
+	"os/exec"
+	"path/filepath"
+	"strings"
+	"testing"
+)
+
+func TestNeedsSudo(t *testing.T) {
+	for script, want := range map[string]bool{
+		"sudo softwareupdate -i -a":               true,
+		"echo ok\nsudo xcodebuild -license":       true,
+		"brew update && sudo xcodebuild -license": true,
+		"brew update || sudo true":                true,
+		"true;sudo rm /etc/tool":                  true,
+		"true; sudo rm /etc/tool":                 true,
+		"echo $(sudo id -u)":                      true,
+		"pseudo install":                          false,
+		"pseudo-sudo install":                     false,
+		"visudo -c":                               false,
+		"cat /etc/sudoers":                        false,
+		"brew install sudo-prompt":                false,
+	} {
+		if got := NeedsSudo(script); got != want {
+			t.Errorf("NeedsSudo(%q) = %v, want %v", script, got, want)
+		}
+	}
+}
+
+// Every sudo the script runs goes through the shim: -A with an askpass
+// helper, -n without one. A word containing sudo is left alone.
+func TestSudoShim(t *testing.T) {
+	if _, err := os.Stat("/bin/sh"); err != nil {
+		t.Skip("no /bin/sh")
+	}
+	script := "sudo first\ntrue && sudo second; sudo third\necho pseudo fourth"
+	for askpass, flag := range map[string]string{"": "-n", "/opt/askpass": "-A"} {
+		bin := t.TempDir()
+		log := filepath.Join(bin, "sudo.log")
+		fake := "#!/bin/sh\necho \"$* ${SUDO_ASKPASS:-none}\" >> '" + log + "'\n"
+		if err := os.WriteFile(filepath.Join(bin, "sudo"), []byte(fake), 0o755); err != nil {
+			t.Fatal(err)
+		}
+		e := &CommandExecutor{Shell: "/bin/sh", Askpass: askpass}
+		cmd := exec.Command("/bin/sh", "-c", e.prepare(Command{Script: script}))
+		cmd.Env = append(os.Environ(), "PATH="+bin+":/usr/bin:/bin", "SUDO_ASKPASS=")
+		out, err := cmd.CombinedOutput()
+		if err != nil {
+			t.Fatalf("askpass %q: %v: %s", askpass, err, out)
+		}
+		if strings.TrimSpace(string(out)) != "pseudo fourth" {
+			t.Errorf("askpass %q: printed %q, want only the pseudo line", askpass, out)
+		}
+		data, err := os.ReadFile(log)
+		if err != nil {
+			t.Fatal(err)
+		}
+		env := askpass
+		if env == "" {
+			env = "none"
+		}
+		want := []string{flag + " first " + env, flag + " second " + env, flag + " third " + env}
+		if got := strings.Split(strings.TrimSpace(string(data)), "\n"); strings.Join(got, "|") != strings.Join(want, "|") {
+			t.Errorf("askpass %q: sudo ran as %q, want %q", askpass, got, want)
+		}
+	}
+}
@@ -0,0 +1,60 @@
+package manager
+
+// Result is how a plan step ended.
+type Result string
+
+const (
+	ResultSucceeded Result = "succeeded"
+	ResultSkipped   Result = "skipped"
+	ResultFailed    Result = "failed"
+	ResultNotRun    Result = "not run"
+)
+
+// Outcome is the result of one plan step and, unless it succeeded, why.
+type Outcome struct {
+	ID     string
+	Result Result
+	Reason string
+}
+
+// Summarize derives the outcome of each of ids, in order, from the events
+// a run reported. Steps without a final event were never reached.
+func Summarize(ids []string, events []TaskEvent) []Outcome {
+	byID := make(map[string]Outcome, len(ids))
+	for _, ev := range events {
+		if ev.Kind != EventStepFinished {
+			continue
+		}
+		switch {
+		case ev.Skipped:
+			byID[ev.SoftwareID] = Outcome{Result: ResultSkipped, Reason: ev.Reason}
+		case ev.Execution != nil && ev.Execution.Success:
+			byID[ev.SoftwareID] = Outcome{Result: ResultSucceeded}
+		case ev.Execution != nil:
+			reason := ev.Reason
+			if reason == "" {
+				reason = ev.Execution.Error
+			}
+			byID[ev.SoftwareID] = Outcome{Result: ResultFailed, Reason: reason}
+		}
+	}
+	out := make([]Outcome, len(ids))
+	for i, id := range ids {
+		o, ok := byID[id]
+		if !ok {
+			o.Result = ResultNotRun
+		}
+		o.ID = id
+		out[i] = o
+	}
+	return out
+}
+
+// Tally counts outcomes per result.
+func Tally(outcomes []Outcome) map[Result]int {
+	counts := map[Result]int{}
+	for _, o := range outcomes {
+		counts[o.Result]++
+	}
+	return counts
+}
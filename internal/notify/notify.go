@@ -0,0 +1,96 @@
+// Package notify posts a summary of finished runs to a webhook, so runs
+// nobody watches (CI, MDM, fleets) still report back.
+package notify
+
+import (
+	"bytes"
+	"context"
+	"encoding/json"
+	"fmt"
+	"io"
+	"net/http"
+	"strings"
+	"time"
+
+	"github.com/hmziqrs/maziq/internal/config"
+)
+
+// Summary describes a finished run.
+type Summary struct {
+	Action    string    `json:"action"`
+	Template  string    `json:"template,omitempty"`
+	Host      string    `json:"host"`
+	Started   time.Time `json:"started"`
+	Duration  float64   `json:"duration_seconds"`
+	Succeeded int       `json:"succeeded"`
+	Skipped   int       `json:"skipped"`
+	Failed    int       `json:"failed"`
+	NotRun    int       `json:"not_run"`
+	// Failures is "id: reason" for every failed step.
+	Failures  []string  `json:"failures,omitempty"`
+	// Error is why the run ended early, if it did.
+	Error     string    `json:"error,omitempty"`
+}
+
+// OK reports whether every step succeeded or was skipped.
+func (s Summary) OK() bool {
+	return s.Failed == 0 && s.Error == ""
+}
+
+// Text renders s as a short chat message.
+func (s Summary) Text() string {
+	mark, outcome := "✅", "succeeded"
+	if !s.OK() {
+		mark, outcome = "❌", "failed"
+	}
+	what := "maziq " + s.Action
+	if s.Template != "" {
+		what += " " + s.Template
+	}
+	var b strings.Builder
+	fmt.Fprintf(&b, "%s %s %s on %s after %s: %d succeeded, %d skipped, %d failed",
+		mark, what, outcome, s.Host, time.Duration(s.Duration*float64(time.Second)).Round(time.Second), s.Succeeded, s.Skipped, s.Failed)
+	if s.NotRun > 0 {
+		fmt.Fprintf(&b, ", %d not run", s.NotRun)
+	}
+	for _, f := range s.Failures {
+		b.WriteString("\n• " + f)
+	}
+	if s.Error != "" && s.Failed == 0 {
+		b.WriteString("\n" + s.Error)
+	}
+	return b.String()
+}
+
+// Post sends s to hook in the hook's format.
+func Post(ctx context.Context, hook config.Webhook, s Summary) error {
+	hook = hook.Resolved()
+	var payload any = s
+	switch hook.Format {
+	case "slack":
+		payload = map[string]string{"text": s.Text()}
+	case "discord":
+		payload = map[string]string{"content": s.Text()}
+	}
+	body, err := json.Marshal(payload)
+	if err != nil {
+		return err
+	}
+	ctx, cancel := context.WithTimeout(ctx, 15*time.Second)
+	defer cancel()
+	req, err := http.NewRequestWithContext(ctx, http.MethodPost, hook.URL, bytes.NewReader(body))
+	if err != nil {
+		return err
+	}
+	req.Header.Set("Content-Type", "application/json")
+	resp, err := http.DefaultClient.Do(req)
+	if err != nil {
+		return err
+	}
+	defer resp.Body.Close()
+	if resp.StatusCode >= 300 {
+		detail, _ := io.ReadAll(io.LimitReader(resp.Body, 512))
+		return fmt.Errorf("webhook answered %s: %s", resp.Status, strings.TrimSpace(string(detail)))
+	}
+	return nil
+}
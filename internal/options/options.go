@@ -0,0 +1,83 @@
+// Package options holds the preferences runs follow, set from global CLI
+// flags and config.toml.
+package options
+
+import "fmt"
+
+// Backend is the preferred install source for entries that offer several.
+type Backend string
+
+const (
+	// BackendDefault uses each entry's own source order (Homebrew first).
+	BackendDefault Backend = ""
+	// BackendNix prefers `nix profile install` wherever an entry supports it.
+	BackendNix Backend = "nix"
+)
+
+// ParseBackend validates a --backend value.
+func ParseBackend(value string) (Backend, error) {
+	switch value {
+	case "", "brew", "default":
+		return BackendDefault, nil
+	case "nix":
+		return BackendNix, nil
+	}
+	return BackendDefault, fmt.Errorf("unknown backend %q (expected brew or nix)", value)
+}
+
+// Run holds the preferences a run follows, as set by global flags and
+// config.toml. Frontends build one and hand it down explicitly instead of
+// setting process-wide state, so a single task can override any of them.
+type Run struct {
+	// DryRun prints commands instead of executing them. Probes,
+	// preflight checks, plans and configurator diffs still run, since
+	// they only read the machine; nothing is written: no history,
+	// snapshots, lockfiles, guided progress, downloads, shell profile or
+	// config changes. Only the diagnostic log keeps being written.
+	DryRun            bool
+	Backend           Backend
+	// Jobs bounds how many status probes run at once; zero keeps the
+	// prober's default.
+	Jobs              int
+	// ContinueOnError makes plan runs go past failed steps instead of
+	// stopping at the first one.
+	ContinueOnError   bool
+	// Force runs install steps for entries that are already installed
+	// instead of skipping them.
+	Force             bool
+	// ForceAdopted lets uninstalls remove software maziq did not install.
+	ForceAdopted      bool
+	// ConfirmSDKRemoval lets uninstalls remove SDKs, which delete their
+	// toolchains and caches with them.
+	ConfirmSDKRemoval bool
+	// Offline restricts runs to what `maziq cache warm` downloaded
+	// earlier; recipes that need the network fail instead of trying it.
+	Offline           bool
+	// PermanentDelete makes recipes delete data with rm -rf as written;
+	// otherwise what they remove as the user is moved to the Trash.
+	PermanentDelete   bool
+	// ClearQuarantine lists the GUI entries trusted enough to have the
+	// quarantine attribute cleared from their app after installing.
+	ClearQuarantine   []string
+	// Askpass is the SUDO_ASKPASS helper used for commands that need
+	// root; empty means credentials are requested up front.
+	Askpass           string
+	// NonInteractive disables every prompt; questions are answered from
+	// flags instead.
+	NonInteractive    bool
+	// AssumeYes answers yes to every confirmation.
+	AssumeYes         bool
+	// EventLog is the file plan runs append their progress events to as
+	// JSON lines, or "".
+	EventLog          string
+	// JUnitReport is the file plan runs write a JUnit XML report to when
+	// they end, or "".
+	JUnitReport       string
+	// Verbosity is the diagnostic log level: debug, info, warn or error.
+	Verbosity         string
+}
+
+// Defaults returns the options of a run without flags or config.toml.
+func Defaults() Run {
+	return Run{ContinueOnError: true, Verbosity: "info"}
+}
@@ -0,0 +1,134 @@
+// Package paths centralizes every location maziq reads from or writes to.
+package paths
+
+import (
+	"os"
+	"path/filepath"
+)
+
+// LaunchAgentPrefix prefixes the label of every launchd agent maziq installs.
+const LaunchAgentPrefix = "dev.hmziq.maziq"
+
+// Home returns the user's home directory, falling back to the working directory.
+func Home() string {
+	if home, err := os.UserHomeDir(); err == nil {
+		return home
+	}
+	return "."
+}
+
+// ConfigDir holds user-edited configuration (config.toml, templates, profiles).
+func ConfigDir() string {
+	if dir := os.Getenv("XDG_CONFIG_HOME"); dir != "" {
+		return filepath.Join(dir, "maziq")
+	}
+	return filepath.Join(Home(), ".config", "maziq")
+}
+
+// ConfigFile holds the machine's persistent preferences.
+func ConfigFile() string {
+	return filepath.Join(ConfigDir(), "config.toml")
+}
+
+// InstallerPinsFile holds the user's SHA256 pins for installer scripts,
+// written by `maziq catalog refresh-hashes`.
+func InstallerPinsFile() string {
+	return filepath.Join(ConfigDir(), "installer-pins.toml")
+}
+
+// StateDir holds data maziq generates itself (history, logs, caches).
+func StateDir() string {
+	if dir := os.Getenv("XDG_STATE_HOME"); dir != "" {
+		return filepath.Join(dir, "maziq")
+	}
+	return filepath.Join(Home(), ".local", "state", "maziq")
+}
+
+// HistoryFile is the JSON-lines install history.
+func HistoryFile() string {
+	return filepath.Join(StateDir(), "install_history.jsonl")
+}
+
+// SnapshotFile is the JSON-lines manifest of GUI apps recorded right before
+// they were uninstalled.
+func SnapshotFile() string {
+	return filepath.Join(StateDir(), "snapshots.jsonl")
+}
+
+// CacheDir holds what `maziq cache warm` downloaded for offline installs.
+func CacheDir() string {
+	return filepath.Join(StateDir(), "cache")
+}
+
+// InstallerCacheDir holds verified installer scripts, named by SHA256.
+func InstallerCacheDir() string {
+	return filepath.Join(CacheDir(), "installers")
+}
+
+// CacheManifestFile is the JSON-lines record of the packages whose
+// downloads were fetched into a package manager's own cache.
+func CacheManifestFile() string {
+	return filepath.Join(CacheDir(), "warmed.jsonl")
+}
+
+// LogDir holds per-run logs.
+func LogDir() string {
+	return filepath.Join(StateDir(), "logs")
+}
+
+// LogFile is the diagnostic log, written at the configured log level.
+func LogFile() string {
+	return filepath.Join(LogDir(), "maziq.log")
+}
+
+// SourcesDir holds the checkouts of software built from git, one per
+// catalog id.
+func SourcesDir() string {
+	return filepath.Join(StateDir(), "src")
+}
+
+// LocalBinDir is where maziq places binaries it installs itself.
+func LocalBinDir() string {
+	return filepath.Join(Home(), ".local", "bin")
+}
+
+// GitConfig is the user's global git configuration.
+func GitConfig() string {
+	return filepath.Join(Home(), ".gitconfig")
+}
+
+// NpmRC is the user's npm configuration.
+func NpmRC() string {
+	return filepath.Join(Home(), ".npmrc")
+}
+
+// LaunchAgentsDir is the per-user launchd agent directory.
+func LaunchAgentsDir() string {
+	return filepath.Join(Home(), "Library", "LaunchAgents")
+}
+
+// CrashDir holds the reports written when maziq panics.
+func CrashDir() string {
+	return filepath.Join(StateDir(), "crashes")
+}
+
+// SocketFile is the unix socket `maziq serve` listens on by default.
+func SocketFile() string {
+	return filepath.Join(StateDir(), "maziq.sock")
+}
+
+// ServeTokenFile holds the bearer token of the running `maziq serve`.
+func ServeTokenFile() string {
+	return filepath.Join(StateDir(), "serve.token")
+}
+
+// LockFile is where `maziq onboard fresh` records the versions a template
+// run installed, unless --lockfile names another file.
+func LockFile(template string) string {
+	return filepath.Join(StateDir(), "locks", template+".lock.json")
+}
+
+// GuidedFile keeps the progress of `maziq onboard guided` between sessions.
+func GuidedFile() string {
+	return filepath.Join(StateDir(), "guided.json")
+}
@@ -0,0 +1,216 @@
+// Package prompt describes a question once so either frontend can ask it:
+// Ask here reads the answer line by line on a terminal, and the TUI renders
+// the same Prompt as a component (see internal/tui/prompt.go).
+package prompt
+
+import (
+	"bufio"
+	"errors"
+	"fmt"
+	"os"
+	"slices"
+	"strconv"
+	"strings"
+
+	"github.com/charmbracelet/x/term"
+
+	"github.com/hmziqrs/maziq/internal/options"
+)
+
+// Kind selects how a prompt is answered.
+type Kind int
+
+const (
+	// KindText is a single line of text.
+	KindText Kind = iota
+	// KindPassword is a line of text that is never echoed.
+	KindPassword
+	// KindSelect picks one of Options.
+	KindSelect
+	// KindMultiSelect picks any number of Options.
+	KindMultiSelect
+	// KindConfirm is a yes/no question.
+	KindConfirm
+)
+
+// Prompt is one question.
+type Prompt struct {
+	Kind     Kind
+	Label    string
+	// Default answers an empty reply: text for text prompts, "y" for a
+	// confirm that defaults to yes, an option for selects.
+	Default  string
+	Options  []string
+	// Validate, when set, rejects a text or password answer; the prompt
+	// shows its error and asks again.
+	Validate func(string) error
+}
+
+// Answer is the reply to a Prompt. Text is set for text and password
+// prompts, Selected holds option indexes for selects, Yes for confirms.
+type Answer struct {
+	Text     string
+	Selected []int
+	Yes      bool
+}
+
+// ErrCancelled is returned when the user backs out of a prompt.
+var ErrCancelled = errors.New("cancelled")
+
+// ErrNonInteractive is returned for prompts that have no default when
+// nothing may prompt.
+var ErrNonInteractive = errors.New("an answer is required but prompting is disabled (--non-interactive)")
+
+// stdin is shared by every Ask so buffered input is not lost between
+// questions.
+var stdin = bufio.NewReader(os.Stdin)
+
+// Ask asks p on the terminal. When run is non-interactive it takes the
+// default, or fails with ErrNonInteractive without one; confirms then
+// answer no unless run assumes yes.
+func Ask(run options.Run, p Prompt) (Answer, error) {
+	if run.NonInteractive {
+		return nonInteractive(p, run.AssumeYes)
+	}
+	for {
+		answer, err := askOnce(p)
+		if err == nil || errors.Is(err, ErrCancelled) {
+			return answer, err
+		}
+		fmt.Fprintf(os.Stderr, "  %v\n", err)
+	}
+}
+
+func nonInteractive(p Prompt, yes bool) (Answer, error) {
+	switch p.Kind {
+	case KindConfirm:
+		return Answer{Yes: yes}, nil
+	case KindSelect, KindMultiSelect:
+		if i := slices.Index(p.Options, p.Default); i >= 0 {
+			return Answer{Selected: []int{i}}, nil
+		}
+	default:
+		if p.Default != "" {
+			return Answer{Text: p.Default}, nil
+		}
+	}
+	return Answer{}, fmt.Errorf("%s: %w", p.Label, ErrNonInteractive)
+}
+
+func askOnce(p Prompt) (Answer, error) {
+	switch p.Kind {
+	case KindConfirm:
+		hint := "[y/N]"
+		if p.Default == "y" {
+			hint = "[Y/n]"
+		}
+		reply, err := line(fmt.Sprintf("%s %s ", p.Label, hint))
+		if err != nil {
+			return Answer{}, err
+		}
+		switch strings.ToLower(reply) {
+		case "":
+			return Answer{Yes: p.Default == "y"}, nil
+		case "y", "yes":
+			return Answer{Yes: true}, nil
+		case "n", "no":
+			return Answer{}, nil
+		}
+		return Answer{}, errors.New("answer y or n")
+	case KindSelect, KindMultiSelect:
+		return askSelect(p)
+	}
+	label := p.Label
+	if p.Default != "" && p.Kind == KindText {
+		label += " [" + p.Default + "]"
+	}
+	var reply string
+	var err error
+	if p.Kind == KindPassword {
+		fmt.Print(label + " ")
+		var raw []byte
+		raw, err = term.ReadPassword(os.Stdin.Fd())
+		fmt.Println()
+		reply = string(raw)
+	} else {
+		reply, err = line(label + " ")
+	}
+	if err != nil {
+		return Answer{}, err
+	}
+	if reply == "" {
+		reply = p.Default
+	}
+	if p.Validate != nil {
+		if err := p.Validate(reply); err != nil {
+			return Answer{}, err
+		}
+	}
+	return Answer{Text: reply}, nil
+}
+
+// askSelect lists the options by number and reads the chosen numbers,
+// separated by spaces or commas for a multi-select.
+func askSelect(p Prompt) (Answer, error) {
+	fmt.Println(p.Label)
+	for i, option := range p.Options {
+		marker := " "
+		if option == p.Default {
+			marker = "*"
+		}
+		fmt.Printf(" %s%2d) %s\n", marker, i+1, option)
+	}
+	question := "Choose a number:"
+	if p.Kind == KindMultiSelect {
+		question = "Choose numbers (e.g. 1 3):"
+	}
+	reply, err := line(question + " ")
+	if err != nil {
+		return Answer{}, err
+	}
+	if reply == "" {
+		if i := slices.Index(p.Options, p.Default); i >= 0 {
+			return Answer{Selected: []int{i}}, nil
+		}
+		if p.Kind == KindMultiSelect {
+			return Answer{}, nil
+		}
+		return Answer{}, errors.New("choose one of the numbers")
+	}
+	var selected []int
+	for _, field := range strings.FieldsFunc(reply, func(r rune) bool { return r == ',' || r == ' ' }) {
+		n, err := strconv.Atoi(field)
+		if err != nil || n < 1 || n > len(p.Options) {
+			return Answer{}, fmt.Errorf("%q is not one of the numbers", field)
+		}
+		if !slices.Contains(selected, n-1) {
+			selected = append(selected, n-1)
+		}
+	}
+	if p.Kind == KindSelect && len(selected) != 1 {
+		return Answer{}, errors.New("choose exactly one number")
+	}
+	return Answer{Selected: selected}, nil
+}
+
+// line prints question and reads one trimmed line. End of input cancels.
+func line(question string) (string, error) {
+	fmt.Print(question)
+	reply, err := stdin.ReadString('\n')
+	if err != nil && reply == "" {
+		return "", ErrCancelled
+	}
+	return strings.TrimSpace(reply), nil
+}
+
+// Confirm asks a yes/no question that defaults to no.
+func Confirm(run options.Run, question string) bool {
+	answer, err := Ask(run, Prompt{Kind: KindConfirm, Label: question})
+	return err == nil && answer.Yes
+}
+
+// Text asks for a line of text; def answers an empty reply.
+func Text(run options.Run, label, def string) (string, error) {
+	answer, err := Ask(run, Prompt{Kind: KindText, Label: label, Default: def})
+	return answer.Text, err
+}
@@ -0,0 +1,108 @@
+// Package purge finds and removes everything maziq itself created on the
+// machine, leaving the software it installed untouched.
+package purge
+
+import (
+	"fmt"
+	"os"
+	"os/exec"
+	"path/filepath"
+	"strings"
+
+	"github.com/hmziqrs/maziq/internal/paths"
+	"github.com/hmziqrs/maziq/internal/shellprofile"
+	"github.com/hmziqrs/maziq/internal/trash"
+)
+
+// ArtifactKind classifies something maziq generated.
+type ArtifactKind string
+
+const (
+	KindDirectory    ArtifactKind = "directory"
+	KindLaunchAgent  ArtifactKind = "launchd agent"
+	KindProfileBlock ArtifactKind = "shell profile block"
+	KindSymlink      ArtifactKind = "symlink"
+)
+
+// Artifact is a single removable item.
+type Artifact struct {
+	Kind   ArtifactKind
+	Path   string
+	Detail string
+}
+
+func (a Artifact) String() string {
+	if a.Detail != "" {
+		return fmt.Sprintf("%-20s %s (%s)", a.Kind, a.Path, a.Detail)
+	}
+	return fmt.Sprintf("%-20s %s", a.Kind, a.Path)
+}
+
+// Discover lists every maziq artifact present on disk.
+func Discover() ([]Artifact, error) {
+	var out []Artifact
+
+	for _, dir := range []string{paths.ConfigDir(), paths.StateDir()} {
+		if info, err := os.Stat(dir); err == nil && info.IsDir() {
+			out = append(out, Artifact{Kind: KindDirectory, Path: dir})
+		}
+	}
+
+	agents, _ := filepath.Glob(filepath.Join(paths.LaunchAgentsDir(), paths.LaunchAgentPrefix+"*.plist"))
+	for _, agent := range agents {
+		out = append(out, Artifact{Kind: KindLaunchAgent, Path: agent})
+	}
+
+	// The git and proxy configurators reuse the shell profile markers in
+	// ~/.gitconfig and ~/.npmrc.
+	for _, profile := range append(shellprofile.Profiles(), paths.GitConfig(), paths.NpmRC()) {
+		blocks, err := shellprofile.Blocks(profile)
+		if err != nil {
+			return nil, err
+		}
+		if len(blocks) > 0 {
+			out = append(out, Artifact{Kind: KindProfileBlock, Path: profile, Detail: strings.Join(blocks, ", ")})
+		}
+	}
+
+	links, err := os.ReadDir(paths.LocalBinDir())
+	if err != nil && !os.IsNotExist(err) {
+		return nil, err
+	}
+	for _, link := range links {
+		if link.Type()&os.ModeSymlink == 0 {
+			continue
+		}
+		path := filepath.Join(paths.LocalBinDir(), link.Name())
+		target, err := os.Readlink(path)
+		if err != nil {
+			continue
+		}
+		if strings.HasPrefix(target, paths.StateDir()+string(filepath.Separator)) {
+			out = append(out, Artifact{Kind: KindSymlink, Path: path, Detail: "→ " + target})
+		}
+	}
+	return out, nil
+}
+
+// Remove deletes a single artifact. Directories are moved to the Trash
+// unless permanent is set.
+func Remove(a Artifact, permanent bool) error {
+	switch a.Kind {
+	case KindDirectory:
+		if !permanent {
+			return trash.Move(a.Path)
+		}
+		return os.RemoveAll(a.Path)
+	case KindLaunchAgent:
+		// Unloading fails when the agent is not loaded; that's fine.
+		_ = exec.Command("launchctl", "unload", a.Path).Run()
+		return os.Remove(a.Path)
+	case KindProfileBlock:
+		_, err := shellprofile.RemoveAll(a.Path)
+		return err
+	case KindSymlink:
+		return os.Remove(a.Path)
+	}
+	return fmt.Errorf("unknown artifact kind %q", a.Kind)
+}
@@ -0,0 +1,275 @@
+// Package safety checks shell strings against a reviewable allowlist of
+// command shapes: package manager invocations, version queries and
+// installer scripts downloaded from known hosts. `maziq audit` reports
+// every catalog and template command that falls outside it.
+package safety
+
+import (
+	"net/url"
+	"path/filepath"
+	"regexp"
+	"slices"
+	"strings"
+)
+
+// Rule allows one program, optionally only with arguments matching Args.
+type Rule struct {
+	Program string
+	// Args is matched against the arguments joined by spaces; nil allows
+	// any arguments.
+	Args    *regexp.Regexp
+}
+
+// Rules is the allowlist. Keep it short and reviewable: anything not
+// covered here shows up in `maziq audit`.
+var Rules = []Rule{
+	{"brew", regexp.MustCompile(`^(install|upgrade|uninstall|reinstall|update|list|info|tap|--version|--prefix)\b`)},
+	{"cargo", regexp.MustCompile(`^(install|uninstall|binstall)\b`)},
+	{"npm", regexp.MustCompile(`^(install|uninstall|ls|view)\b`)},
+	{"rustup", nil},
+	{"pipx", regexp.MustCompile(`^(install|upgrade|uninstall|list)\b`)},
+	{"mas", regexp.MustCompile(`^(install|upgrade|uninstall|list)\b`)},
+	{"mise", regexp.MustCompile(`^(use|upgrade|uninstall|current|ls)\b`)},
+	{"asdf", regexp.MustCompile(`^(plugin|install|uninstall|current|set|exec)\b`)},
+	{"nix", regexp.MustCompile(`^profile (install|upgrade|remove|list)\b`)},
+	{"port", regexp.MustCompile(`^(install|upgrade|uninstall|installed)\b`)},
+	{"xcode-select", regexp.MustCompile(`^--install$`)},
+	{"xattr", regexp.MustCompile(`^-dr com\.apple\.quarantine `)},
+	{"command", regexp.MustCompile(`^-v \S+$`)},
+}
+
+// KnownHosts are the only hosts curl may download from, always over https.
+var KnownHosts = []string{
+	"raw.githubusercontent.com",
+	"sh.rustup.rs",
+	"bun.sh",
+	"install.python-poetry.org",
+	"astral.sh",
+}
+
+// SudoPrograms are the only programs catalog commands may run with sudo.
+var SudoPrograms = []string{"port", "mas", "rm", "tee", "softwareupdate"}
+
+// interpreters may only run scripts downloaded from a known host.
+var interpreters = []string{"sh", "bash", "zsh", "python3"}
+
+// versionQuery matches read-only probes such as `rustc --version`.
+var versionQuery = regexp.MustCompile(`^(--version|-V|version)( --machine)?$`)
+
+// homeDotDir matches removing a tool's own directory under $HOME.
+var homeDotDir = regexp.MustCompile(`^-rf "\$HOME/\.[A-Za-z0-9_-]+"$`)
+
+// download stands in for a $(…) substitution that fetched a script from a
+// known host, so `bash -c "$(curl …)"` passes.
+const download = "$DOWNLOAD"
+
+// Finding is a part of a shell string outside the allowlist.
+type Finding struct {
+	Segment string
+	Reason  string
+}
+
+// Check returns the segments of script that no rule allows. A script is
+// split at ;, &&, || and pipes, and $(…) substitutions are checked on
+// their own.
+func Check(script string) []Finding {
+	var findings []Finding
+	script, findings = substitutions(script)
+	var pipedDownload bool
+	for _, p := range split(script) {
+		segment := strings.Trim(p.text, " \t\n{}()")
+		if segment == "" {
+			continue
+		}
+		reason := checkSegment(segment, pipedDownload)
+		if reason != "" {
+			findings = append(findings, Finding{Segment: segment, Reason: reason})
+		}
+		pipedDownload = p.pipe && reason == "" && program(segment) == "curl"
+	}
+	return findings
+}
+
+// substitutions checks every $(…) in script and replaces the ones that
+// download from a known host with a marker.
+func substitutions(script string) (string, []Finding) {
+	var findings []Finding
+	for {
+		start := strings.Index(script, "$(")
+		if start < 0 {
+			return script, findings
+		}
+		end := closing(script, start+1)
+		if end < 0 {
+			return script, append(findings, Finding{Segment: script[start:], Reason: "unbalanced $("})
+		}
+		inner := script[start+2 : end]
+		innerFindings := Check(inner)
+		findings = append(findings, innerFindings...)
+		marker := "$SUBST"
+		if len(innerFindings) == 0 && program(inner) == "curl" {
+			marker = download
+		}
+		script = script[:start] + marker + script[end+1:]
+	}
+}
+
+// closing returns the index of the parenthesis closing the one at open,
+// or -1.
+func closing(script string, open int) int {
+	depth := 0
+	for i := open; i < len(script); i++ {
+		switch script[i] {
+		case '(':
+			depth++
+		case ')':
+			depth--
+			if depth == 0 {
+				return i
+			}
+		}
+	}
+	return -1
+}
+
+// Command is one simple command of a shell string.
+type Command struct {
+	Text    string
+	Program string
+	// Args are the words after the program, quotes kept.
+	Args    []string
+	Sudo    bool
+}
+
+// Commands splits script into its simple commands, the ones inside $(…)
+// substitutions included. Like Check, it ignores quoting when splitting.
+func Commands(script string) []Command {
+	var out []Command
+	for {
+		start := strings.Index(script, "$(")
+		if start < 0 {
+			break
+		}
+		end := closing(script, start+1)
+		if end < 0 {
+			break
+		}
+		out = append(out, Commands(script[start+2:end])...)
+		script = script[:start] + "$SUBST" + script[end+1:]
+	}
+	for _, p := range split(script) {
+		segment := strings.Trim(p.text, " \t\n{}()")
+		words, all := fields(segment), strings.Fields(segment)
+		if len(words) == 0 {
+			continue
+		}
+		out = append(out, Command{
+			Text:    segment,
+			Program: program(segment),
+			Args:    words[1:],
+			// fields dropped the assignments and sudo in front of words.
+			Sudo:    slices.Contains(all[:len(all)-len(words)], "sudo"),
+		})
+	}
+	return out
+}
+
+type part struct {
+	text string
+	// pipe is set when the part's output is piped into the next one.
+	pipe bool
+}
+
+// split cuts script at ;, &&, || and |, ignoring quoting.
+func split(script string) []part {
+	var parts []part
+	last := 0
+	for i := 0; i < len(script); i++ {
+		switch {
+		case strings.HasPrefix(script[i:], "&&"), strings.HasPrefix(script[i:], "||"):
+			parts = append(parts, part{text: script[last:i]})
+			i++
+			last = i + 1
+		case script[i] == ';', script[i] == '|':
+			parts = append(parts, part{text: script[last:i], pipe: script[i] == '|'})
+			last = i + 1
+		}
+	}
+	return append(parts, part{text: script[last:]})
+}
+
+// program returns the base name of the program a segment runs, past any
+// environment assignments and sudo.
+func program(segment string) string {
+	words := fields(segment)
+	if len(words) == 0 {
+		return ""
+	}
+	return filepath.Base(strings.Trim(words[0], `"'`))
+}
+
+// fields splits a segment into words, dropping leading VAR=value
+// assignments and sudo.
+func fields(segment string) []string {
+	words := strings.Fields(segment)
+	for len(words) > 0 && (words[0] == "sudo" || strings.Contains(words[0], "=") && !strings.HasPrefix(words[0], "-")) {
+		words = words[1:]
+	}
+	return words
+}
+
+// checkSegment returns why segment is outside the allowlist, or "".
+// pipedDownload is set when the previous segment piped a download into it.
+func checkSegment(segment string, pipedDownload bool) string {
+	words := fields(segment)
+	if len(words) == 0 {
+		return ""
+	}
+	name := program(segment)
+	args := strings.Join(words[1:], " ")
+	switch {
+	case name == "curl":
+		return checkCurl(words[1:])
+	case slices.Contains(interpreters, name):
+		if pipedDownload || strings.Contains(args, download) {
+			return ""
+		}
+		return name + " only runs installers downloaded from a known host"
+	case name == "rm":
+		if homeDotDir.MatchString(args) && !strings.HasPrefix(segment, "sudo") {
+			return ""
+		}
+		return "rm outside a tool's own directory under $HOME"
+	case versionQuery.MatchString(args):
+		return ""
+	}
+	for _, rule := range Rules {
+		if rule.Program == name && (rule.Args == nil || rule.Args.MatchString(args)) {
+			return ""
+		}
+	}
+	return "no allowlist rule for " + name
+}
+
+// checkCurl requires every URL curl fetches to be https on a known host.
+func checkCurl(args []string) string {
+	found := false
+	for _, arg := range args {
+		arg = strings.Trim(arg, `"'`)
+		if !strings.Contains(arg, "://") {
+			continue
+		}
+		found = true
+		u, err := url.Parse(arg)
+		if err != nil || u.Scheme != "https" {
+			return "curl over plain http: " + arg
+		}
+		if !slices.Contains(KnownHosts, u.Hostname()) {
+			return "curl from unknown host " + u.Hostname()
+		}
+	}
+	if !found {
+		return "curl without a URL"
+	}
+	return ""
+}
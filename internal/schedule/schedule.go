@@ -0,0 +1,133 @@
+// Package schedule manages the launchd agent that keeps the toolchain
+// current by running `maziq upgrade --all --non-interactive` periodically.
+package schedule
+
+import (
+	"bytes"
+	"encoding/xml"
+	"fmt"
+	"os"
+	"os/exec"
+	"path/filepath"
+	"strings"
+	"time"
+
+	"github.com/hmziqrs/maziq/internal/paths"
+)
+
+// Label is the launchd label of the maintenance agent.
+const Label = paths.LaunchAgentPrefix + ".upgrade"
+
+// hour is the local hour maintenance runs start at.
+const hour = 10
+
+// Frequency is how often the maintenance run happens.
+type Frequency string
+
+const (
+	Daily  Frequency = "daily"
+	Weekly Frequency = "weekly"
+)
+
+// Status describes the installed agent, if any.
+type Status struct {
+	Installed bool
+	Loaded    bool
+	Frequency Frequency
+	// LastRun is when the agent last wrote to its log; zero if never.
+	LastRun   time.Time
+}
+
+// PlistPath is where the agent definition lives.
+func PlistPath() string {
+	return filepath.Join(paths.LaunchAgentsDir(), Label+".plist")
+}
+
+// LogPath receives the output of every scheduled run.
+func LogPath() string {
+	return filepath.Join(paths.LogDir(), "schedule.log")
+}
+
+// Enable writes the agent running executable at freq and (re)loads it.
+func Enable(executable string, freq Frequency) error {
+	if err := os.MkdirAll(paths.LaunchAgentsDir(), 0o755); err != nil {
+		return err
+	}
+	if err := os.MkdirAll(paths.LogDir(), 0o755); err != nil {
+		return err
+	}
+	path := PlistPath()
+	// Loading an agent that is already loaded fails, so reload from scratch.
+	_ = exec.Command("launchctl", "unload", path).Run()
+	if err := os.WriteFile(path, plist(executable, freq), 0o644); err != nil {
+		return err
+	}
+	if out, err := exec.Command("launchctl", "load", "-w", path).CombinedOutput(); err != nil {
+		return fmt.Errorf("launchctl load: %v: %s", err, strings.TrimSpace(string(out)))
+	}
+	return nil
+}
+
+// Disable unloads and removes the agent. It is not an error if none exists.
+func Disable() error {
+	path := PlistPath()
+	if _, err := os.Stat(path); os.IsNotExist(err) {
+		return nil
+	}
+	// Unloading fails when the agent is not loaded; that's fine.
+	_ = exec.Command("launchctl", "unload", "-w", path).Run()
+	return os.Remove(path)
+}
+
+// Current reports the state of the agent.
+func Current() Status {
+	data, err := os.ReadFile(PlistPath())
+	if err != nil {
+		return Status{}
+	}
+	st := Status{Installed: true, Frequency: Daily}
+	if bytes.Contains(data, []byte("<key>Weekday</key>")) {
+		st.Frequency = Weekly
+	}
+	st.Loaded = exec.Command("launchctl", "list", Label).Run() == nil
+	if info, err := os.Stat(LogPath()); err == nil {
+		st.LastRun = info.ModTime()
+	}
+	return st
+}
+
+func plist(executable string, freq Frequency) []byte {
+	var b strings.Builder
+	b.WriteString(`<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+`)
+	fmt.Fprintf(&b, "  <key>Label</key>\n  <string>%s</string>\n", escape(Label))
+	b.WriteString("  <key>ProgramArguments</key>\n  <array>\n")
+	for _, arg := range []string{executable, "upgrade", "--all", "--non-interactive"} {
+		fmt.Fprintf(&b, "    <string>%s</string>\n", escape(arg))
+	}
+	b.WriteString("  </array>\n")
+	b.WriteString("  <key>StartCalendarInterval</key>\n  <dict>\n")
+	if freq == Weekly {
+		// Sunday, when the machine is least likely to be mid-work.
+		b.WriteString("    <key>Weekday</key>\n    <integer>0</integer>\n")
+	}
+	fmt.Fprintf(&b, "    <key>Hour</key>\n    <integer>%d</integer>\n", hour)
+	b.WriteString("    <key>Minute</key>\n    <integer>0</integer>\n  </dict>\n")
+	// launchd starts agents with a minimal PATH; maziq adds tool
+	// directories itself but needs a shell and coreutils to begin with.
+	b.WriteString("  <key>EnvironmentVariables</key>\n  <dict>\n")
+	b.WriteString("    <key>PATH</key>\n    <string>/usr/local/bin:/usr/bin:/bin:/usr/sbin:/sbin</string>\n  </dict>\n")
+	fmt.Fprintf(&b, "  <key>StandardOutPath</key>\n  <string>%s</string>\n", escape(LogPath()))
+	fmt.Fprintf(&b, "  <key>StandardErrorPath</key>\n  <string>%s</string>\n", escape(LogPath()))
+	b.WriteString("</dict>\n</plist>\n")
+	return []byte(b.String())
+}
+
+func escape(s string) string {
+	var b strings.Builder
+	_ = xml.EscapeText(&b, []byte(s))
+	return b.String()
+}
@@ -0,0 +1,380 @@
+// Package server exposes maziq over a local HTTP API so other frontends
+// (a menu-bar app, a Raycast extension) can list the catalog, read
+// statuses, trigger actions and follow their progress. It listens on a
+// unix socket by default; nothing is reachable from other machines. Every
+// request must carry the session's bearer token and come from a loopback
+// host without an Origin, so web pages cannot drive it.
+package server
+
+import (
+	"context"
+	"crypto/subtle"
+	"encoding/json"
+	"errors"
+	"fmt"
+	"mime"
+	"net"
+	"net/http"
+	"strconv"
+	"strings"
+	"sync"
+	"time"
+
+	"github.com/hmziqrs/maziq/internal/catalog"
+	"github.com/hmziqrs/maziq/internal/manager"
+	"github.com/hmziqrs/maziq/internal/options"
+)
+
+// APIVersion prefixes every route; bump it when a response changes
+// incompatibly.
+const APIVersion = "v1"
+
+// Server serves the API on top of an engine it owns.
+type Server struct {
+	engine *manager.Engine
+	prober *manager.Prober
+	// opts are the run options of every job; requests override DryRun.
+	opts   options.Run
+	// token is the bearer token every request must present.
+	token  string
+
+	mu          sync.Mutex
+	subscribers map[chan Event]bool
+	jobs        map[manager.JobID]*Job
+}
+
+// Job is a run triggered through the API.
+type Job struct {
+	ID      manager.JobID `json:"id"`
+	Action  string        `json:"action"`
+	IDs     []string      `json:"ids"`
+	DryRun  bool          `json:"dry_run"`
+	Started time.Time     `json:"started"`
+	Done    bool          `json:"done"`
+	Error   string        `json:"error,omitempty"`
+}
+
+// Event is one server-sent event on /v1/events.
+type Event struct {
+	Job        manager.JobID     `json:"job"`
+	Kind       manager.EventKind `json:"kind,omitempty"`
+	SoftwareID string            `json:"id,omitempty"`
+	Step       int               `json:"step,omitempty"`
+	Total      int               `json:"total,omitempty"`
+	Message    string            `json:"message,omitempty"`
+	Reason     string            `json:"reason,omitempty"`
+	Time       time.Time         `json:"time"`
+	Done       bool              `json:"done,omitempty"`
+	Error      string            `json:"error,omitempty"`
+}
+
+// New returns a server with its own engine whose jobs follow opts and
+// which only answers requests bearing token. Close it to stop running jobs.
+func New(opts options.Run, token string) *Server {
+	s := &Server{
+		engine:      manager.NewEngine(),
+		prober:      manager.NewProber(opts.Jobs),
+		opts:        opts,
+		token:       token,
+		subscribers: map[chan Event]bool{},
+		jobs:        map[manager.JobID]*Job{},
+	}
+	go s.pump()
+	return s
+}
+
+// Close cancels every running job and waits for them to exit.
+func (s *Server) Close() {
+	s.engine.Shutdown()
+}
+
+// Handler routes the API.
+func (s *Server) Handler() http.Handler {
+	mux := http.NewServeMux()
+	mux.HandleFunc("GET /"+APIVersion+"/catalog", s.handleCatalog)
+	mux.HandleFunc("GET /"+APIVersion+"/status", s.handleStatus)
+	mux.HandleFunc("GET /"+APIVersion+"/jobs", s.handleJobs)
+	mux.HandleFunc("POST /"+APIVersion+"/jobs", s.handleSubmit)
+	mux.HandleFunc("DELETE /"+APIVersion+"/jobs/{id}", s.handleCancel)
+	mux.HandleFunc("GET /"+APIVersion+"/events", s.handleEvents)
+	return s.guard(mux)
+}
+
+// guard refuses requests a browser could have sent on a page's behalf:
+// any with an Origin header or a Host that is not loopback (DNS
+// rebinding), and any without the session's bearer token.
+func (s *Server) guard(next http.Handler) http.Handler {
+	return http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		if r.Header.Get("Origin") != "" {
+			writeError(w, http.StatusForbidden, errors.New("cross-origin requests are refused"))
+			return
+		}
+		if host, _, err := net.SplitHostPort(r.Host); err == nil {
+			r.Host = host
+		}
+		if !IsLoopback(r.Host) {
+			writeError(w, http.StatusForbidden, fmt.Errorf("host %q is not a loopback address", r.Host))
+			return
+		}
+		token, ok := strings.CutPrefix(r.Header.Get("Authorization"), "Bearer ")
+		if !ok || subtle.ConstantTimeCompare([]byte(token), []byte(s.token)) != 1 {
+			w.Header().Set("WWW-Authenticate", "Bearer")
+			writeError(w, http.StatusUnauthorized, errors.New("missing or wrong bearer token"))
+			return
+		}
+		next.ServeHTTP(w, r)
+	})
+}
+
+// IsLoopback reports whether host, without a port, is localhost or a
+// loopback IP address.
+func IsLoopback(host string) bool {
+	host = strings.TrimSuffix(strings.TrimPrefix(host, "["), "]")
+	if host == "localhost" {
+		return true
+	}
+	ip := net.ParseIP(host)
+	return ip != nil && ip.IsLoopback()
+}
+
+// pump fans the engine's single event stream out to every subscriber and
+// keeps the job list current.
+func (s *Server) pump() {
+	for ev := range s.engine.Events() {
+		out := Event{Job: ev.Job, Time: time.Now(), Done: ev.Done}
+		if ev.Err != nil {
+			out.Error = ev.Err.Error()
+		}
+		if te, ok := ev.Payload.(manager.TaskEvent); ok {
+			out.Kind, out.SoftwareID, out.Message, out.Reason, out.Time = te.Kind, te.SoftwareID, te.Message, te.Reason, te.Time
+			out.Step, out.Total = te.Step, te.Total
+		} else if !ev.Done {
+			continue
+		}
+		s.mu.Lock()
+		if job := s.jobs[ev.Job]; job != nil && ev.Done {
+			job.Done, job.Error = true, out.Error
+		}
+		for ch := range s.subscribers {
+			select {
+			case ch <- out:
+			default:
+				// A stalled client misses events rather than stalling runs.
+			}
+		}
+		s.mu.Unlock()
+	}
+}
+
+type entryJSON struct {
+	ID       string   `json:"id"`
+	Name     string   `json:"name"`
+	Category string   `json:"category"`
+	Kind     string   `json:"kind"`
+	Tags     []string `json:"tags"`
+	Summary  string   `json:"summary"`
+	Homepage string   `json:"homepage,omitempty"`
+	Docs     string   `json:"docs,omitempty"`
+	License  string   `json:"license,omitempty"`
+}
+
+func (s *Server) handleCatalog(w http.ResponseWriter, r *http.Request) {
+	var out []entryJSON
+	for _, e := range catalog.All() {
+		out = append(out, entryJSON{
+			ID: e.ID, Name: e.Name, Category: string(e.Category), Kind: e.Kind.String(), Tags: e.Tags, Summary: e.Summary,
+			Homepage: e.Homepage, Docs: e.Docs, License: e.License,
+		})
+	}
+	writeJSON(w, http.StatusOK, out)
+}
+
+type statusJSON struct {
+	ID      string    `json:"id"`
+	State   string    `json:"state"`
+	Version string    `json:"version,omitempty"`
+	Latest  string    `json:"latest,omitempty"`
+	Error   string    `json:"error,omitempty"`
+	Warning string    `json:"warning,omitempty"`
+	Checked time.Time `json:"checked"`
+}
+
+// handleStatus probes ?id= entries, or the whole catalog without any.
+// ?deep=1 also runs the slow probes.
+func (s *Server) handleStatus(w http.ResponseWriter, r *http.Request) {
+	entries := catalog.All()
+	if ids := r.URL.Query()["id"]; len(ids) > 0 {
+		entries = entries[:0:0]
+		for _, id := range ids {
+			e, ok := catalog.Lookup(id)
+			if !ok {
+				writeError(w, http.StatusNotFound, &catalog.UnknownError{ID: id})
+				return
+			}
+			entries = append(entries, e)
+		}
+	}
+	mode := manager.ProbeModeQuick
+	if r.URL.Query().Get("deep") == "1" {
+		mode = manager.ProbeModeFull
+	}
+	statuses := s.prober.ProbeAll(r.Context(), entries, mode)
+	var out []statusJSON
+	for _, e := range entries {
+		st := statuses[e.ID]
+		out = append(out, statusJSON{ID: e.ID, State: st.State.String(), Version: st.Version, Latest: st.Latest, Error: st.Error, Warning: st.Warning, Checked: st.CheckedAt})
+	}
+	writeJSON(w, http.StatusOK, out)
+}
+
+func (s *Server) handleJobs(w http.ResponseWriter, r *http.Request) {
+	s.mu.Lock()
+	out := make([]Job, 0, len(s.jobs))
+	for _, job := range s.jobs {
+		out = append(out, *job)
+	}
+	s.mu.Unlock()
+	writeJSON(w, http.StatusOK, out)
+}
+
+// submitRequest is the body of POST /v1/jobs.
+type submitRequest struct {
+	Action string   `json:"action"`
+	IDs    []string `json:"ids"`
+	DryRun bool     `json:"dry_run"`
+	// Yes confirms destructive commands; nobody can be asked.
+	Yes    bool     `json:"yes"`
+}
+
+func (s *Server) handleSubmit(w http.ResponseWriter, r *http.Request) {
+	// A JSON content type cannot be sent cross-site without a preflight.
+	if media, _, _ := mime.ParseMediaType(r.Header.Get("Content-Type")); media != "application/json" {
+		writeError(w, http.StatusUnsupportedMediaType, errors.New("the body must be sent as application/json"))
+		return
+	}
+	var req submitRequest
+	if err := json.NewDecoder(r.Body).Decode(&req); err != nil {
+		writeError(w, http.StatusBadRequest, err)
+		return
+	}
+	action := manager.Action(req.Action)
+	if action != manager.ActionInstall && action != manager.ActionUpdate && action != manager.ActionUninstall {
+		writeError(w, http.StatusBadRequest, fmt.Errorf("unknown action %q", req.Action))
+		return
+	}
+	if len(req.IDs) == 0 {
+		writeError(w, http.StatusBadRequest, errors.New("ids is empty"))
+		return
+	}
+	plan, err := manager.Resolve(req.IDs, action, s.opts)
+	if err != nil {
+		writeError(w, http.StatusBadRequest, err)
+		return
+	}
+	if conflicts := plan.Conflicts(); len(conflicts) > 0 {
+		writeError(w, http.StatusConflict, &manager.ConflictError{Conflicts: conflicts})
+		return
+	}
+	if !req.DryRun && !req.Yes && destructive(plan, s.opts.Backend) {
+		writeError(w, http.StatusPreconditionRequired, errors.New("the plan runs destructive commands; resend with \"yes\": true"))
+		return
+	}
+	if !req.DryRun && manager.PlanNeedsSudo(plan, s.opts.Backend) && !manager.SudoCached(r.Context()) && s.opts.Askpass == "" {
+		writeError(w, http.StatusPreconditionFailed, fmt.Errorf("%w: run `sudo -v` first or start maziq serve with --sudo-askpass", manager.ErrSudoUnavailable))
+		return
+	}
+
+	job := &Job{Action: req.Action, IDs: plan.IDs(), DryRun: req.DryRun, Started: time.Now()}
+	s.mu.Lock()
+	// Jobs change the system, so they queue behind each other.
+	job.ID = s.engine.Enqueue(req.Action, func(ctx context.Context, emit func(any)) error {
+		if !req.DryRun && manager.PlanNeedsSudo(plan, s.opts.Backend) {
+			if !manager.SudoCached(ctx) {
+				if err := manager.SudoAuthenticate(ctx, "", s.opts.Askpass); err != nil {
+					return err
+				}
+			}
+			go manager.SudoKeepAlive(ctx)
+		}
+		opts := s.opts
+		opts.DryRun = req.DryRun
+		m := manager.New(opts)
+		return m.RunPlan(ctx, plan, func(ev manager.TaskEvent) { emit(ev) })
+	})
+	s.jobs[job.ID] = job
+	s.mu.Unlock()
+	writeJSON(w, http.StatusAccepted, job)
+}
+
+func (s *Server) handleCancel(w http.ResponseWriter, r *http.Request) {
+	id, err := strconv.Atoi(r.PathValue("id"))
+	if err != nil {
+		writeError(w, http.StatusBadRequest, err)
+		return
+	}
+	if !s.engine.Cancel(manager.JobID(id)) {
+		writeError(w, http.StatusNotFound, fmt.Errorf("job %d is not running", id))
+		return
+	}
+	w.WriteHeader(http.StatusNoContent)
+}
+
+// handleEvents streams every job's progress as server-sent events until
+// the client disconnects.
+func (s *Server) handleEvents(w http.ResponseWriter, r *http.Request) {
+	flusher, ok := w.(http.Flusher)
+	if !ok {
+		writeError(w, http.StatusInternalServerError, errors.New("streaming is not supported"))
+		return
+	}
+	ch := make(chan Event, 64)
+	s.mu.Lock()
+	s.subscribers[ch] = true
+	s.mu.Unlock()
+	defer func() {
+		s.mu.Lock()
+		delete(s.subscribers, ch)
+		s.mu.Unlock()
+	}()
+
+	w.Header().Set("Content-Type", "text/event-stream")
+	w.Header().Set("Cache-Control", "no-cache")
+	w.WriteHeader(http.StatusOK)
+	flusher.Flush()
+	for {
+		select {
+		case <-r.Context().Done():
+			return
+		case ev := <-ch:
+			data, err := json.Marshal(ev)
+			if err != nil {
+				continue
+			}
+			fmt.Fprintf(w, "data: %s\n\n", data)
+			flusher.Flush()
+		}
+	}
+}
+
+// destructive reports whether any step may run a destructive command
+// under backend.
+func destructive(plan manager.Plan, backend options.Backend) bool {
+	for _, step := range plan.Steps {
+		for _, recipe := range manager.PreferredRecipes(step.Entry, step.Action, backend) {
+			if manager.Destructive(step.Action, recipe) {
+				return true
+			}
+		}
+	}
+	return false
+}
+
+func writeJSON(w http.ResponseWriter, code int, v any) {
+	w.Header().Set("Content-Type", "application/json")
+	w.WriteHeader(code)
+	json.NewEncoder(w).Encode(v)
+}
+
+func writeError(w http.ResponseWriter, code int, err error) {
+	writeJSON(w, code, map[string]string{"error": err.Error()})
+}
@@ -0,0 +1,69 @@
+package server
+
+import (
+	"net/http"
+	"net/http/httptest"
+	"strings"
+	"testing"
+
+	"github.com/hmziqrs/maziq/internal/options"
+)
+
+func TestGuard(t *testing.T) {
+	s := New(options.Defaults(), "secret")
+	defer s.Close()
+	handler := s.Handler()
+
+	tests := []struct {
+		name   string
+		method string
+		host   string
+		header map[string]string
+		body   string
+		want   int
+	}{
+		{"authorized", http.MethodGet, "localhost", nil, "", http.StatusOK},
+		{"loopback ip with port", http.MethodGet, "127.0.0.1:7777", nil, "", http.StatusOK},
+		{"ipv6 loopback", http.MethodGet, "[::1]:7777", nil, "", http.StatusOK},
+		{"no token", http.MethodGet, "localhost", map[string]string{"Authorization": ""}, "", http.StatusUnauthorized},
+		{"wrong token", http.MethodGet, "localhost", map[string]string{"Authorization": "Bearer guess"}, "", http.StatusUnauthorized},
+		{"not bearer", http.MethodGet, "localhost", map[string]string{"Authorization": "secret"}, "", http.StatusUnauthorized},
+		{"rebound host", http.MethodGet, "attacker.example:7777", nil, "", http.StatusForbidden},
+		{"origin", http.MethodGet, "localhost", map[string]string{"Origin": "http://localhost"}, "", http.StatusForbidden},
+		{"form submit", http.MethodPost, "localhost", map[string]string{"Content-Type": "text/plain"}, `{"action": "install", "ids": ["git"]}`, http.StatusUnsupportedMediaType},
+		{"json submit", http.MethodPost, "localhost", map[string]string{"Content-Type": "application/json; charset=utf-8"}, `{"action": "nuke", "ids": ["git"]}`, http.StatusBadRequest},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			req := httptest.NewRequest(tt.method, "/"+APIVersion+"/jobs", strings.NewReader(tt.body))
+			req.Host = tt.host
+			req.Header.Set("Authorization", "Bearer secret")
+			for key, value := range tt.header {
+				req.Header.Set(key, value)
+			}
+			rec := httptest.NewRecorder()
+			handler.ServeHTTP(rec, req)
+			if rec.Code != tt.want {
+				t.Errorf("status = %d, want %d: %s", rec.Code, tt.want, rec.Body)
+			}
+		})
+	}
+}
+
+func TestIsLoopback(t *testing.T) {
+	for host, want := range map[string]bool{
+		"localhost":     true,
+		"127.0.0.1":     true,
+		"127.1.2.3":     true,
+		"::1":           true,
+		"[::1]":         true,
+		"0.0.0.0":       false,
+		"192.168.1.10":  false,
+		"localhost.com": false,
+		"":              false,
+	} {
+		if got := IsLoopback(host); got != want {
+			t.Errorf("IsLoopback(%q) = %v, want %v", host, got, want)
+		}
+	}
+}
@@ -0,0 +1,147 @@
+// Package shellprofile manages the marker-delimited blocks maziq writes into
+// shell startup files, so every line it adds can be found and removed again.
+package shellprofile
+
+import (
+	"os"
+	"path/filepath"
+	"strings"
+
+	"github.com/hmziqrs/maziq/internal/paths"
+)
+
+const (
+	beginPrefix = "# >>> maziq:"
+	endPrefix   = "# <<< maziq:"
+	beginSuffix = " >>>"
+	endSuffix   = " <<<"
+)
+
+// Profiles returns the shell startup files maziq may edit.
+func Profiles() []string {
+	home := paths.Home()
+	return []string{
+		filepath.Join(home, ".zshrc"),
+		filepath.Join(home, ".zprofile"),
+		filepath.Join(home, ".bashrc"),
+		filepath.Join(home, ".bash_profile"),
+		filepath.Join(home, ".config", "fish", "config.fish"),
+	}
+}
+
+func beginMarker(name string) string { return beginPrefix + name + beginSuffix }
+func endMarker(name string) string   { return endPrefix + name + endSuffix }
+
+// Blocks lists the names of the managed blocks found in path.
+func Blocks(path string) ([]string, error) {
+	data, err := os.ReadFile(path)
+	if err != nil {
+		if os.IsNotExist(err) {
+			return nil, nil
+		}
+		return nil, err
+	}
+	var names []string
+	for _, line := range strings.Split(string(data), "\n") {
+		line = strings.TrimSpace(line)
+		if strings.HasPrefix(line, beginPrefix) && strings.HasSuffix(line, beginSuffix) {
+			names = append(names, strings.TrimSuffix(strings.TrimPrefix(line, beginPrefix), beginSuffix))
+		}
+	}
+	return names, nil
+}
+
+// Block returns the body of the named block in path, and whether it exists.
+func Block(path, name string) (string, bool, error) {
+	data, err := os.ReadFile(path)
+	if err != nil {
+		if os.IsNotExist(err) {
+			return "", false, nil
+		}
+		return "", false, err
+	}
+	var body []string
+	inside, found := false, false
+	for _, line := range strings.Split(string(data), "\n") {
+		trimmed := strings.TrimSpace(line)
+		switch {
+		case !inside && trimmed == beginMarker(name):
+			inside, found = true, true
+		case inside && trimmed == endMarker(name):
+			inside = false
+		case inside:
+			body = append(body, line)
+		}
+	}
+	return strings.Join(body, "\n"), found, nil
+}
+
+// Upsert writes body into path as the named block, replacing any previous
+// version of that block. The file is created if it does not exist.
+func Upsert(path, name, body string) error {
+	data, err := os.ReadFile(path)
+	if err != nil && !os.IsNotExist(err) {
+		return err
+	}
+	content, _ := strip(string(data), func(block string) bool { return block == name })
+	content = strings.TrimRight(content, "\n")
+	if content != "" {
+		content += "\n\n"
+	}
+	content += beginMarker(name) + "\n" + strings.TrimRight(body, "\n") + "\n" + endMarker(name) + "\n"
+	if err := os.MkdirAll(filepath.Dir(path), 0o755); err != nil {
+		return err
+	}
+	return os.WriteFile(path, []byte(content), 0o644)
+}
+
+// Remove deletes the named block from path. It reports whether the file changed.
+func Remove(path, name string) (bool, error) {
+	return rewrite(path, func(block string) bool { return block == name })
+}
+
+// RemoveAll deletes every managed block from path.
+func RemoveAll(path string) (bool, error) {
+	return rewrite(path, func(string) bool { return true })
+}
+
+func rewrite(path string, drop func(name string) bool) (bool, error) {
+	data, err := os.ReadFile(path)
+	if err != nil {
+		if os.IsNotExist(err) {
+			return false, nil
+		}
+		return false, err
+	}
+	stripped, changed := strip(string(data), drop)
+	if !changed {
+		return false, nil
+	}
+	return true, os.WriteFile(path, []byte(stripped), 0o644)
+}
+
+// strip removes matching blocks (markers included) from content.
+func strip(content string, drop func(name string) bool) (string, bool) {
+	var kept []string
+	skipping := ""
+	changed := false
+	for _, line := range strings.Split(content, "\n") {
+		trimmed := strings.TrimSpace(line)
+		if skipping != "" {
+			if trimmed == endMarker(skipping) {
+				skipping = ""
+			}
+			continue
+		}
+		if strings.HasPrefix(trimmed, beginPrefix) && strings.HasSuffix(trimmed, beginSuffix) {
+			name := strings.TrimSuffix(strings.TrimPrefix(trimmed, beginPrefix), beginSuffix)
+			if drop(name) {
+				skipping = name
+				changed = true
+				continue
+			}
+		}
+		kept = append(kept, line)
+	}
+	return strings.Join(kept, "\n"), changed
+}
@@ -0,0 +1,83 @@
+// Package snapshot records what GUI apps looked like right before maziq
+// removed them, so they can be reinstalled at the same version later.
+package snapshot
+
+import (
+	"bufio"
+	"encoding/json"
+	"os"
+	"path/filepath"
+
+	"github.com/hmziqrs/maziq/internal/paths"
+)
+
+// Snapshot is one line of the snapshot manifest.
+type Snapshot struct {
+	Software  string `json:"software"`
+	Name      string `json:"name"`
+	// AppPath is the .app bundle that was removed.
+	AppPath   string `json:"app_path,omitempty"`
+	// Cask is the Homebrew cask token the app was installed from, if any.
+	Cask      string `json:"cask,omitempty"`
+	MasAppID  string `json:"mas_app_id,omitempty"`
+	Version   string `json:"version,omitempty"`
+	// Command is the uninstall command that followed the snapshot.
+	Command   string `json:"command,omitempty"`
+	Timestamp int64  `json:"timestamp"`
+}
+
+// Append adds s to the manifest, creating it if needed.
+func Append(s Snapshot) error {
+	path := paths.SnapshotFile()
+	if err := os.MkdirAll(filepath.Dir(path), 0o755); err != nil {
+		return err
+	}
+	line, err := json.Marshal(s)
+	if err != nil {
+		return err
+	}
+	f, err := os.OpenFile(path, os.O_CREATE|os.O_APPEND|os.O_WRONLY, 0o644)
+	if err != nil {
+		return err
+	}
+	defer f.Close()
+	_, err = f.Write(append(line, '\n'))
+	return err
+}
+
+// Load reads every snapshot, oldest first. A missing manifest is empty and
+// malformed lines are skipped.
+func Load() ([]Snapshot, error) {
+	f, err := os.Open(paths.SnapshotFile())
+	if os.IsNotExist(err) {
+		return nil, nil
+	}
+	if err != nil {
+		return nil, err
+	}
+	defer f.Close()
+
+	var out []Snapshot
+	scanner := bufio.NewScanner(f)
+	for scanner.Scan() {
+		var s Snapshot
+		if json.Unmarshal(scanner.Bytes(), &s) == nil && s.Software != "" {
+			out = append(out, s)
+		}
+	}
+	return out, scanner.Err()
+}
+
+// Latest returns the most recent snapshot of the given software id.
+func Latest(id string) (Snapshot, bool, error) {
+	all, err := Load()
+	if err != nil {
+		return Snapshot{}, false, err
+	}
+	for i := len(all) - 1; i >= 0; i-- {
+		if all[i].Software == id {
+			return all[i], true, nil
+		}
+	}
+	return Snapshot{}, false, nil
+}
@@ -0,0 +1,51 @@
+package templates
+
+import "fmt"
+
+// Role is a kind of machine the first-run wizard can set up.
+type Role struct {
+	ID      string
+	Name    string
+	Summary string
+	// Presets are the keys of the category presets the role combines.
+	Presets []string
+}
+
+// presets are curated slices of the catalog, one per area of work.
+var presets = map[string][]string{
+	"core":     {"homebrew", "xcode_clt", "mas"},
+	"browsers": {"firefox", "chrome"},
+	"editors":  {"visual_studio_code", "zed_stable"},
+	"shell":    {"bat", "eza", "fzf"},
+	"rust":     {"rustup", "rust_stable", "cargo_binstall", "cargo_just", "cargo_watch"},
+	"web":      {"nvm", "bun", "docker_desktop", "postman"},
+	"mobile":   {"xcode", "flutter", "android_studio", "nvm", "react_native_cli"},
+	"design":   {"keynote", "slack", "raycast"},
+}
+
+// Roles lists the wizard's choices in display order.
+var Roles = []Role{
+	{"rust", "Rust developer", "rustup, cargo tools, editors and shell utilities", []string{"core", "browsers", "editors", "shell", "rust"}},
+	{"web", "Web developer", "Node, Bun, Docker, Postman, editors and browsers", []string{"core", "browsers", "editors", "shell", "web"}},
+	{"mobile", "Mobile developer", "Xcode, Flutter, Android Studio and React Native", []string{"core", "browsers", "editors", "shell", "mobile"}},
+	{"designer", "Designer", "Browsers, Keynote, Slack and Raycast", []string{"core", "browsers", "design"}},
+}
+
+// RoleTemplate assembles the template for role from its presets, keeping
+// the first occurrence of entries shared between presets.
+func RoleTemplate(role Role) Template {
+	t := Template{
+		Name:        role.ID,
+		Description: fmt.Sprintf("%s machine, assembled by the setup wizard.", role.Name),
+	}
+	seen := map[string]bool{}
+	for _, preset := range role.Presets {
+		for _, id := range presets[preset] {
+			if !seen[id] {
+				seen[id] = true
+				t.Software = append(t.Software, id)
+			}
+		}
+	}
+	return t
+}
@@ -0,0 +1,193 @@
+// Package templates loads and saves onboarding templates: named lists of
+// catalog entries, optionally pinned to specific versions.
+package templates
+
+import (
+	"fmt"
+	"io/fs"
+	"os"
+	"path/filepath"
+	"sort"
+	"strings"
+
+	"github.com/hmziqrs/maziq/internal/catalog"
+	"github.com/hmziqrs/maziq/internal/errs"
+	"github.com/hmziqrs/maziq/internal/paths"
+	"github.com/hmziqrs/maziq/internal/tomlite"
+	builtin "github.com/hmziqrs/maziq/templates"
+)
+
+// DefaultName is the recommended template.
+const DefaultName = "hmziq"
+
+// Template is a named selection of catalog entries.
+type Template struct {
+	Name           string
+	Description    string
+	Software       []string
+	// Versions pins entries to the version recorded when the template was made.
+	Versions       map[string]string
+	// Runtimes selects whether language runtimes come from their native
+	// installers or from mise/asdf.
+	Runtimes       catalog.RuntimeStrategy
+	// PostInstall maps software ids to extra commands run after they
+	// install, on top of the catalog's own hooks.
+	PostInstall    map[string][]string
+	// EditorProfiles names configurator profiles applied after the editor
+	// they provision is installed.
+	EditorProfiles []string
+	// Checklist lists the manual steps `onboard guided` walks a new
+	// engineer through after the automated ones.
+	Checklist      []string
+}
+
+// Dir is where user templates live; they shadow built-ins of the same name.
+func Dir() string {
+	return filepath.Join(paths.ConfigDir(), "templates")
+}
+
+// ErrNotFound is returned by Load for a template that doesn't exist.
+var ErrNotFound = errs.New(errs.CodeUnknown, "template not found")
+
+// Load finds a template by name in the user directory, then the built-ins.
+func Load(name string) (Template, error) {
+	data, err := os.ReadFile(filepath.Join(Dir(), name+".toml"))
+	if os.IsNotExist(err) {
+		data, err = fs.ReadFile(builtin.FS, name+".toml")
+		if err != nil {
+			return Template{}, fmt.Errorf("%w: %q", ErrNotFound, name)
+		}
+	} else if err != nil {
+		return Template{}, err
+	}
+	t, err := Parse(data)
+	return t, errs.Wrap(errs.CodeConfig, err)
+}
+
+// Parse decodes a template and validates its software ids.
+func Parse(data []byte) (Template, error) {
+	doc, err := tomlite.Parse(data)
+	if err != nil {
+		return Template{}, err
+	}
+	t := Template{Versions: map[string]string{}, PostInstall: map[string][]string{}}
+	t.Name, _ = doc.Str("name")
+	t.Description, _ = doc.Str("description")
+	t.Software = doc.Strings("software")
+	t.EditorProfiles = doc.Strings("editor_profiles")
+	t.Checklist = doc.Strings("checklist")
+	strategy, _ := doc.Str("runtime_strategy")
+	if t.Runtimes, err = catalog.ParseRuntimeStrategy(strategy); err != nil {
+		return Template{}, err
+	}
+	if versions, ok := doc.Table("versions"); ok {
+		for id := range versions {
+			if v, ok := versions.Str(id); ok {
+				t.Versions[id] = v
+			}
+		}
+	}
+	if hooks, ok := doc.Table("post_install"); ok {
+		for id := range hooks {
+			if _, ok := catalog.Lookup(id); !ok {
+				return Template{}, fmt.Errorf("post_install hook for %w", &catalog.UnknownError{ID: id})
+			}
+			t.PostInstall[id] = hooks.Strings(id)
+		}
+	}
+	if t.Name == "" {
+		return Template{}, fmt.Errorf("template is missing a name")
+	}
+	for _, id := range t.Software {
+		if _, ok := catalog.Lookup(id); !ok {
+			return Template{}, fmt.Errorf("template %q references %w", t.Name, &catalog.UnknownError{ID: id})
+		}
+	}
+	return t, nil
+}
+
+// List returns the names of every available template, user and built-in.
+func List() []string {
+	seen := map[string]bool{}
+	builtins, _ := fs.Glob(builtin.FS, "*.toml")
+	users, _ := filepath.Glob(filepath.Join(Dir(), "*.toml"))
+	for _, path := range append(builtins, users...) {
+		seen[strings.TrimSuffix(filepath.Base(path), ".toml")] = true
+	}
+	names := make([]string, 0, len(seen))
+	for name := range seen {
+		names = append(names, name)
+	}
+	sort.Strings(names)
+	return names
+}
+
+// Encode renders a template as TOML.
+func Encode(t Template) []byte {
+	var b strings.Builder
+	fmt.Fprintf(&b, "name = %s\n", tomlite.Quote(t.Name))
+	fmt.Fprintf(&b, "description = %s\n", tomlite.Quote(t.Description))
+	if t.Runtimes != "" && t.Runtimes != catalog.RuntimeNative {
+		fmt.Fprintf(&b, "runtime_strategy = %s\n", tomlite.Quote(string(t.Runtimes)))
+	}
+	b.WriteString("\n")
+	b.WriteString("software = [\n")
+	for _, id := range t.Software {
+		fmt.Fprintf(&b, "  %s,\n", tomlite.Quote(id))
+	}
+	b.WriteString("]\n")
+	if len(t.EditorProfiles) > 0 {
+		quoted := make([]string, len(t.EditorProfiles))
+		for i, name := range t.EditorProfiles {
+			quoted[i] = tomlite.Quote(name)
+		}
+		fmt.Fprintf(&b, "editor_profiles = [%s]\n", strings.Join(quoted, ", "))
+	}
+	if len(t.Checklist) > 0 {
+		b.WriteString("\nchecklist = [\n")
+		for _, item := range t.Checklist {
+			fmt.Fprintf(&b, "  %s,\n", tomlite.Quote(item))
+		}
+		b.WriteString("]\n")
+	}
+	if len(t.Versions) > 0 {
+		ids := make([]string, 0, len(t.Versions))
+		for id := range t.Versions {
+			ids = append(ids, id)
+		}
+		sort.Strings(ids)
+		b.WriteString("\n[versions]\n")
+		for _, id := range ids {
+			fmt.Fprintf(&b, "%s = %s\n", tomlite.QuoteKey(id), tomlite.Quote(t.Versions[id]))
+		}
+	}
+	if len(t.PostInstall) > 0 {
+		ids := make([]string, 0, len(t.PostInstall))
+		for id := range t.PostInstall {
+			ids = append(ids, id)
+		}
+		sort.Strings(ids)
+		b.WriteString("\n[post_install]\n")
+		for _, id := range ids {
+			quoted := make([]string, len(t.PostInstall[id]))
+			for i, command := range t.PostInstall[id] {
+				quoted[i] = tomlite.Quote(command)
+			}
+			fmt.Fprintf(&b, "%s = [%s]\n", tomlite.QuoteKey(id), strings.Join(quoted, ", "))
+		}
+	}
+	return []byte(b.String())
+}
+
+// Save writes t into the user template directory. Existing templates are
+// only replaced when overwrite is set.
+func Save(t Template, overwrite bool) (string, error) {
+	path := filepath.Join(Dir(), t.Name+".toml")
+	if _, err := os.Stat(path); err == nil && !overwrite {
+		return "", fmt.Errorf("template %q already exists at %s", t.Name, path)
+	}
+	if err := os.MkdirAll(Dir(), 0o755); err != nil {
+		return "", err
+	}
+	return path, os.WriteFile(path, Encode(t), 0o644)
+}
@@ -0,0 +1,116 @@
+// Package testenv gives tests a machine maziq cannot hurt: an empty home
+// directory and a PATH of fake tools that only log how they were called.
+package testenv
+
+import (
+	"io/fs"
+	"os"
+	"path/filepath"
+	"strings"
+	"testing"
+)
+
+// tools are the commands maziq runs, directly or from recipes and probes.
+var tools = []string{
+	"sh", "bash", "zsh", "fish", "env", "sudo",
+	"brew", "port", "nix", "mas", "npm", "npx", "bun", "pnpm", "cargo", "rustup",
+	"go", "pipx", "pip", "pip3", "python3", "uv", "mise", "asdf", "curl", "wget",
+	"git", "gh", "ssh-keygen", "ssh-add", "code", "cursor", "zed", "defaults",
+	"killall", "launchctl", "open", "xattr", "spctl", "mdls", "mdfind",
+	"sw_vers", "softwareupdate", "xcode-select", "tar", "install", "rm", "mv",
+}
+
+// Env is a sandbox set up by New.
+type Env struct {
+	// Home is HOME; the XDG directories point inside it.
+	Home string
+	log  string
+}
+
+// New points HOME and the XDG base directories at an empty directory and
+// PATH at fake tools, for the rest of t. Every fake logs its name and
+// arguments and exits 1, as if nothing were installed.
+func New(t testing.TB) *Env {
+	t.Helper()
+	root := t.TempDir()
+	e := &Env{Home: filepath.Join(root, "home"), log: filepath.Join(root, "calls.log")}
+	bin := filepath.Join(root, "bin")
+	for _, dir := range []string{e.Home, bin} {
+		if err := os.Mkdir(dir, 0o755); err != nil {
+			t.Fatal(err)
+		}
+	}
+	script := "#!/bin/sh\necho \"${0##*/} $*\" >> '" + e.log + "'\nexit 1\n"
+	for _, tool := range tools {
+		if err := os.WriteFile(filepath.Join(bin, tool), []byte(script), 0o755); err != nil {
+			t.Fatal(err)
+		}
+	}
+	t.Setenv("HOME", e.Home)
+	t.Setenv("XDG_CONFIG_HOME", filepath.Join(e.Home, ".config"))
+	t.Setenv("XDG_STATE_HOME", filepath.Join(e.Home, ".local", "state"))
+	t.Setenv("XDG_CACHE_HOME", filepath.Join(e.Home, ".cache"))
+	t.Setenv("XDG_DATA_HOME", filepath.Join(e.Home, ".local", "share"))
+	t.Setenv("PATH", bin)
+	return e
+}
+
+// Calls returns every logged tool invocation, one "name args" per line.
+func (e *Env) Calls(t testing.TB) []string {
+	t.Helper()
+	data, err := os.ReadFile(e.log)
+	if os.IsNotExist(err) {
+		return nil
+	}
+	if err != nil {
+		t.Fatal(err)
+	}
+	return strings.Split(strings.TrimRight(string(data), "\n"), "\n")
+}
+
+// AssertUntouched fails t if anything was written under Home or a fake
+// tool was asked to change the machine: install, update or remove
+// something, or write a setting.
+func (e *Env) AssertUntouched(t testing.TB) {
+	t.Helper()
+	filepath.WalkDir(e.Home, func(path string, d fs.DirEntry, err error) error {
+		if err != nil {
+			t.Error(err)
+		} else if path != e.Home {
+			t.Errorf("wrote %s", path)
+		}
+		return nil
+	})
+	for _, call := range e.Calls(t) {
+		if changes(call) {
+			t.Errorf("ran %q", call)
+		}
+	}
+}
+
+// changes reports whether a logged call changes the machine rather than
+// reads it, e.g. `brew install` but not `cargo install --list`.
+func changes(call string) bool {
+	fields := strings.Fields(call)
+	if len(fields) == 0 {
+		return false
+	}
+	switch fields[0] {
+	case "ssh-keygen", "ssh-add", "rm", "mv", "install", "killall", "launchctl":
+		return true
+	}
+	for i, word := range fields {
+		switch word {
+		case "install", "uninstall", "reinstall", "upgrade", "update", "remove", "add", "write", "delete", "--install-extension":
+			if i+1 == len(fields) || !strings.HasPrefix(fields[i+1], "--list") {
+				return true
+			}
+		case "--global":
+			// git config --global KEY VALUE sets; with only KEY it reads.
+			if len(fields) > i+2 {
+				return true
+			}
+		}
+	}
+	return false
+}
@@ -0,0 +1,463 @@
+// Package tomlite reads the subset of TOML used by maziq's templates, config
+// and profiles: key/value pairs with bare, quoted or dotted keys, [tables],
+// [[arrays of tables]], single-line strings, integers, booleans, arrays and
+// inline tables. Floats, dates and multi-line strings are not supported.
+package tomlite
+
+import (
+	"fmt"
+	"strconv"
+	"strings"
+	"unicode/utf8"
+)
+
+// Table is a parsed TOML table. Values are string, int64, bool, []any,
+// Table or []Table.
+type Table map[string]any
+
+// Str returns the string value stored at key.
+func (t Table) Str(key string) (string, bool) {
+	v, ok := t[key].(string)
+	return v, ok
+}
+
+// Bool returns the boolean value stored at key.
+func (t Table) Bool(key string) (bool, bool) {
+	v, ok := t[key].(bool)
+	return v, ok
+}
+
+// Int returns the integer value stored at key.
+func (t Table) Int(key string) (int64, bool) {
+	v, ok := t[key].(int64)
+	return v, ok
+}
+
+// Strings returns the string elements of the array stored at key.
+func (t Table) Strings(key string) []string {
+	arr, _ := t[key].([]any)
+	out := make([]string, 0, len(arr))
+	for _, v := range arr {
+		if s, ok := v.(string); ok {
+			out = append(out, s)
+		}
+	}
+	return out
+}
+
+// Table returns the sub-table stored at key.
+func (t Table) Table(key string) (Table, bool) {
+	v, ok := t[key].(Table)
+	return v, ok
+}
+
+// Tables returns the array of tables stored at key.
+func (t Table) Tables(key string) []Table {
+	v, _ := t[key].([]Table)
+	return v
+}
+
+// Parse decodes a TOML document.
+func Parse(data []byte) (Table, error) {
+	p := &parser{src: string(data), line: 1}
+	root := Table{}
+	current := root
+	for {
+		p.skipSpaceAndComments()
+		if p.eof() {
+			return root, nil
+		}
+		var err error
+		switch {
+		case strings.HasPrefix(p.rest(), "[["):
+			current, err = p.arrayTableHeader(root)
+		case p.peek() == '[':
+			current, err = p.tableHeader(root)
+		default:
+			err = p.keyValue(current)
+		}
+		if err != nil {
+			return nil, fmt.Errorf("line %d: %w", p.line, err)
+		}
+	}
+}
+
+type parser struct {
+	src  string
+	pos  int
+	line int
+}
+
+func (p *parser) eof() bool    { return p.pos >= len(p.src) }
+func (p *parser) rest() string { return p.src[p.pos:] }
+func (p *parser) peek() byte   { return p.src[p.pos] }
+
+func (p *parser) advance() byte {
+	c := p.src[p.pos]
+	p.pos++
+	return c
+}
+
+// skipInline skips spaces and tabs on the current line.
+func (p *parser) skipInline() {
+	for !p.eof() && (p.peek() == ' ' || p.peek() == '\t') {
+		p.pos++
+	}
+}
+
+// skipSpaceAndComments skips whitespace, newlines and comments.
+func (p *parser) skipSpaceAndComments() {
+	for !p.eof() {
+		switch c := p.peek(); {
+		case c == '\n':
+			p.line++
+			p.pos++
+		case c == ' ' || c == '\t' || c == '\r':
+			p.pos++
+		case c == '#':
+			for !p.eof() && p.peek() != '\n' {
+				p.pos++
+			}
+		default:
+			return
+		}
+	}
+}
+
+// endOfLine requires that only whitespace or a comment remains on the line.
+func (p *parser) endOfLine() error {
+	p.skipInline()
+	if p.eof() {
+		return nil
+	}
+	switch p.peek() {
+	case '\n', '\r', '#':
+		return nil
+	}
+	return fmt.Errorf("unexpected %q after value", p.peek())
+}
+
+func (p *parser) headerPath(open, close string) ([]string, error) {
+	p.pos += len(open)
+	keys, err := p.keyPath()
+	if err != nil {
+		return nil, err
+	}
+	p.skipInline()
+	if !strings.HasPrefix(p.rest(), close) {
+		return nil, fmt.Errorf("unterminated table header")
+	}
+	p.pos += len(close)
+	return keys, p.endOfLine()
+}
+
+func descend(root Table, keys []string) (Table, error) {
+	t := root
+	for _, key := range keys {
+		switch v := t[key].(type) {
+		case nil:
+			next := Table{}
+			t[key] = next
+			t = next
+		case Table:
+			t = v
+		case []Table:
+			t = v[len(v)-1]
+		default:
+			return nil, fmt.Errorf("key %q is not a table", key)
+		}
+	}
+	return t, nil
+}
+
+// assign stores value at keys in t, creating the tables a dotted key
+// passes through.
+func assign(t Table, keys []string, value any) error {
+	parent, err := descend(t, keys[:len(keys)-1])
+	if err != nil {
+		return err
+	}
+	last := keys[len(keys)-1]
+	if _, dup := parent[last]; dup {
+		return fmt.Errorf("duplicate key %q", strings.Join(keys, "."))
+	}
+	parent[last] = value
+	return nil
+}
+
+func (p *parser) tableHeader(root Table) (Table, error) {
+	keys, err := p.headerPath("[", "]")
+	if err != nil {
+		return nil, err
+	}
+	return descend(root, keys)
+}
+
+func (p *parser) arrayTableHeader(root Table) (Table, error) {
+	keys, err := p.headerPath("[[", "]]")
+	if err != nil {
+		return nil, err
+	}
+	parent, err := descend(root, keys[:len(keys)-1])
+	if err != nil {
+		return nil, err
+	}
+	last := keys[len(keys)-1]
+	existing, ok := parent[last].([]Table)
+	if !ok && parent[last] != nil {
+		return nil, fmt.Errorf("key %q is not an array of tables", last)
+	}
+	next := Table{}
+	parent[last] = append(existing, next)
+	return next, nil
+}
+
+func (p *parser) key() (string, error) {
+	p.skipInline()
+	if p.eof() {
+		return "", fmt.Errorf("expected key")
+	}
+	if c := p.peek(); c == '"' || c == '\'' {
+		return p.stringValue()
+	}
+	start := p.pos
+	for !p.eof() {
+		c := p.peek()
+		if c == '_' || c == '-' || (c >= 'a' && c <= 'z') || (c >= 'A' && c <= 'Z') || (c >= '0' && c <= '9') {
+			p.pos++
+			continue
+		}
+		break
+	}
+	if start == p.pos {
+		return "", fmt.Errorf("expected key, found %q", p.peek())
+	}
+	return p.src[start:p.pos], nil
+}
+
+// keyPath reads a key, which may be dotted: a."b.c".d is three keys.
+func (p *parser) keyPath() ([]string, error) {
+	var keys []string
+	for {
+		key, err := p.key()
+		if err != nil {
+			return nil, err
+		}
+		keys = append(keys, key)
+		p.skipInline()
+		if p.eof() || p.peek() != '.' {
+			return keys, nil
+		}
+		p.pos++
+	}
+}
+
+// assignment reads "key = value" into t.
+func (p *parser) assignment(t Table) error {
+	keys, err := p.keyPath()
+	if err != nil {
+		return err
+	}
+	if p.eof() || p.advance() != '=' {
+		return fmt.Errorf("expected '=' after key %q", strings.Join(keys, "."))
+	}
+	p.skipInline()
+	value, err := p.value()
+	if err != nil {
+		return err
+	}
+	return assign(t, keys, value)
+}
+
+func (p *parser) keyValue(t Table) error {
+	if err := p.assignment(t); err != nil {
+		return err
+	}
+	return p.endOfLine()
+}
+
+func (p *parser) value() (any, error) {
+	if p.eof() {
+		return nil, fmt.Errorf("expected value")
+	}
+	switch c := p.peek(); {
+	case c == '"' || c == '\'':
+		return p.stringValue()
+	case c == '[':
+		return p.array()
+	case c == '{':
+		return p.inlineTable()
+	}
+	// Booleans and integers run to the next delimiter, so "trueish" is
+	// one unsupported value rather than true followed by junk.
+	start := p.pos
+	for !p.eof() && strings.IndexByte(" \t\r\n,]}#", p.peek()) < 0 {
+		p.pos++
+	}
+	raw := p.src[start:p.pos]
+	switch raw {
+	case "":
+		return nil, fmt.Errorf("expected value")
+	case "true":
+		return true, nil
+	case "false":
+		return false, nil
+	}
+	n, err := strconv.ParseInt(strings.ReplaceAll(raw, "_", ""), 10, 64)
+	if err != nil {
+		return nil, fmt.Errorf("unsupported value %q", raw)
+	}
+	return n, nil
+}
+
+func (p *parser) stringValue() (string, error) {
+	quote := p.advance()
+	var b strings.Builder
+	for !p.eof() {
+		c := p.advance()
+		switch {
+		case c == quote:
+			return b.String(), nil
+		case c == '\n':
+			return "", fmt.Errorf("newline in string")
+		case c == '\\' && quote == '"':
+			if p.eof() {
+				return "", fmt.Errorf("unterminated escape")
+			}
+			switch e := p.advance(); e {
+			case 'n':
+				b.WriteByte('\n')
+			case 't':
+				b.WriteByte('\t')
+			case 'r':
+				b.WriteByte('\r')
+			case 'b':
+				b.WriteByte('\b')
+			case 'f':
+				b.WriteByte('\f')
+			case '"', '\\':
+				b.WriteByte(e)
+			case 'u', 'U':
+				r, err := p.unicodeEscape(e)
+				if err != nil {
+					return "", err
+				}
+				b.WriteRune(r)
+			default:
+				return "", fmt.Errorf("unsupported escape \\%c", e)
+			}
+		default:
+			b.WriteByte(c)
+		}
+	}
+	return "", fmt.Errorf("unterminated string")
+}
+
+// unicodeEscape reads the hex digits of a \uXXXX or \UXXXXXXXX escape.
+func (p *parser) unicodeEscape(e byte) (rune, error) {
+	n := 4
+	if e == 'U' {
+		n = 8
+	}
+	if len(p.rest()) < n {
+		return 0, fmt.Errorf("unterminated escape \\%c", e)
+	}
+	digits := p.rest()[:n]
+	code, err := strconv.ParseUint(digits, 16, 32)
+	if err != nil || !utf8.ValidRune(rune(code)) {
+		return 0, fmt.Errorf("invalid escape \\%c%s", e, digits)
+	}
+	p.pos += n
+	return rune(code), nil
+}
+
+func (p *parser) array() ([]any, error) {
+	p.pos++ // [
+	out := []any{}
+	for {
+		p.skipSpaceAndComments()
+		if p.eof() {
+			return nil, fmt.Errorf("unterminated array")
+		}
+		if p.peek() == ']' {
+			p.pos++
+			return out, nil
+		}
+		v, err := p.value()
+		if err != nil {
+			return nil, err
+		}
+		out = append(out, v)
+		p.skipSpaceAndComments()
+		if !p.eof() && p.peek() == ',' {
+			p.pos++
+		} else if !p.eof() && p.peek() != ']' {
+			return nil, fmt.Errorf("expected ',' or ']' in array, found %q", p.peek())
+		}
+	}
+}
+
+func (p *parser) inlineTable() (Table, error) {
+	p.pos++ // {
+	t := Table{}
+	for {
+		p.skipInline()
+		if p.eof() {
+			return nil, fmt.Errorf("unterminated inline table")
+		}
+		if p.peek() == '}' {
+			p.pos++
+			return t, nil
+		}
+		if err := p.assignment(t); err != nil {
+			return nil, err
+		}
+		p.skipInline()
+		if !p.eof() && p.peek() == ',' {
+			p.pos++
+		} else if !p.eof() && p.peek() != '}' {
+			return nil, fmt.Errorf("expected ',' or '}' in inline table, found %q", p.peek())
+		}
+	}
+}
+
+// Quote renders s as a TOML basic string.
+func Quote(s string) string {
+	var b strings.Builder
+	b.WriteByte('"')
+	for _, r := range s {
+		switch r {
+		case '\\':
+			b.WriteString(`\\`)
+		case '"':
+			b.WriteString(`\"`)
+		case '\n':
+			b.WriteString(`\n`)
+		case '\t':
+			b.WriteString(`\t`)
+		case '\r':
+			b.WriteString(`\r`)
+		default:
+			if r < 0x20 || r == 0x7f {
+				fmt.Fprintf(&b, `\u%04X`, r)
+			} else {
+				b.WriteRune(r)
+			}
+		}
+	}
+	b.WriteByte('"')
+	return b.String()
+}
+
+// QuoteKey renders key bare when possible and quoted otherwise.
+func QuoteKey(key string) string {
+	for _, c := range key {
+		if !(c == '_' || c == '-' || (c >= 'a' && c <= 'z') || (c >= 'A' && c <= 'Z') || (c >= '0' && c <= '9')) {
+			return Quote(key)
+		}
+	}
+	if key == "" {
+		return `""`
+	}
+	return key
+}
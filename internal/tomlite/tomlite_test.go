@@ -0,0 +1,106 @@
+package tomlite
+
+import (
+	"reflect"
+	"strings"
+	"testing"
+)
+
+func TestParse(t *testing.T) {
+	tests := []struct {
+		name string
+		src  string
+		want Table
+	}{
+		{"empty", "", Table{}},
+		{"comments only", "# nothing\n\n  # here\n", Table{}},
+		{"scalars", "name = \"maziq\"\ncount = 1_000\nneg = -3\non = true\noff = false\n",
+			Table{"name": "maziq", "count": int64(1000), "neg": int64(-3), "on": true, "off": false}},
+		{"literal string", `path = 'C:\tools\bin'`, Table{"path": `C:\tools\bin`}},
+		{"escapes", `s = "tab\tquote\"slash\\nl\n"`, Table{"s": "tab\tquote\"slash\\nl\n"}},
+		{"unicode escapes", `s = "caf\u00e9 \U0001F600"`, Table{"s": "café 😀"}},
+		{"trailing comment", "on = true # yes\n", Table{"on": true}},
+		{"quoted key", `"node.js" = 1`, Table{"node.js": int64(1)}},
+		{"dotted key", "a.b = 1\na.c = 2\n", Table{"a": Table{"b": int64(1), "c": int64(2)}}},
+		{"dotted quoted key", `a."b.c" = 1`, Table{"a": Table{"b.c": int64(1)}}},
+		{"array", "ids = [\"git\", \"node\",]\n", Table{"ids": []any{"git", "node"}}},
+		{"multiline array", "ids = [\n  \"git\", # first\n  \"node\"\n]\n", Table{"ids": []any{"git", "node"}}},
+		{"inline table", `t = { a = 1, b.c = "x" }`, Table{"t": Table{"a": int64(1), "b": Table{"c": "x"}}}},
+		{"tables", "[a]\nx = 1\n[a.b]\ny = 2\n[\"c.d\"]\nz = 3\n",
+			Table{"a": Table{"x": int64(1), "b": Table{"y": int64(2)}}, "c.d": Table{"z": int64(3)}}},
+		{"array of tables", "[[step]]\nid = \"git\"\n[[step]]\nid = \"node\"\n",
+			Table{"step": []Table{{"id": "git"}, {"id": "node"}}}},
+		{"crlf", "a = 1\r\nb = 2\r\n", Table{"a": int64(1), "b": int64(2)}},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			got, err := Parse([]byte(tt.src))
+			if err != nil {
+				t.Fatalf("Parse: %v", err)
+			}
+			if !reflect.DeepEqual(got, tt.want) {
+				t.Errorf("Parse = %#v, want %#v", got, tt.want)
+			}
+		})
+	}
+}
+
+func TestParseRejects(t *testing.T) {
+	tests := []struct {
+		name string
+		src  string
+	}{
+		{"prefixed true", "on = trueish"},
+		{"prefixed false", "off = falsey"},
+		{"float", "x = 1.5"},
+		{"missing value", "x =\n"},
+		{"missing equals", "x 1"},
+		{"duplicate key", "x = 1\nx = 2"},
+		{"duplicate dotted key", "a.b = 1\na.b = 2"},
+		{"dotted key through a value", "a = 1\na.b = 2"},
+		{"header through a value", "a = 1\n[a.b]"},
+		{"unterminated string", `s = "open`},
+		{"newline in string", "s = \"a\nb\""},
+		{"unknown escape", `s = "\q"`},
+		{"short unicode escape", `s = "\u12"`},
+		{"bad unicode escape", `s = "\uZZZZ"`},
+		{"surrogate escape", `s = "\uD800"`},
+		{"unterminated header", "[a"},
+		{"empty header", "[]"},
+		{"junk after value", "x = 1 2"},
+		{"array without commas", "ids = [1 2]"},
+		{"unterminated array", "ids = [1, 2"},
+		{"inline table without commas", "t = { a = 1 b = 2 }"},
+		{"unterminated inline table", "t = { a = 1"},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			if got, err := Parse([]byte(tt.src)); err == nil {
+				t.Errorf("Parse(%q) = %#v, want an error", tt.src, got)
+			}
+		})
+	}
+}
+
+// Quote and QuoteKey write what Parse reads back unchanged.
+func TestQuoteRoundTrip(t *testing.T) {
+	for _, s := range []string{
+		"",
+		"plain",
+		`back\slash "quoted"`,
+		"tab\tnewline\ncarriage\r",
+		"bell\a nul\x00 del\x7f",
+		"café 😀",
+		"a.b = c # not a comment",
+	} {
+		src := QuoteKey(s) + " = " + Quote(s) + "\n"
+		got, err := Parse([]byte(src))
+		if err != nil {
+			t.Errorf("Parse(%q): %v", src, err)
+			continue
+		}
+		if v, ok := got[s]; !ok || v != s {
+			t.Errorf("round trip of %q through %q = %#v", s, strings.TrimSpace(src), got)
+		}
+	}
+}
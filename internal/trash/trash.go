@@ -0,0 +1,59 @@
+// Package trash moves what maziq deletes into the user's Trash, so removed
+// data can still be put back from Finder. Config's permanent_delete turns
+// it off.
+package trash
+
+import (
+	"fmt"
+	"os"
+	"os/exec"
+	"path/filepath"
+	"regexp"
+	"strings"
+)
+
+// Move moves path to the Trash with the trash command (shipped with macOS
+// 14 and later, or from Homebrew) and through Finder without one. A
+// missing path is not an error.
+func Move(path string) error {
+	abs, err := filepath.Abs(path)
+	if err != nil {
+		return err
+	}
+	if _, err := os.Lstat(abs); os.IsNotExist(err) {
+		return nil
+	}
+	cmd := exec.Command("osascript", "-e", "on run argv", "-e", finderDelete, "-e", "end run", abs)
+	if bin, err := exec.LookPath("trash"); err == nil {
+		cmd = exec.Command(bin, abs)
+	}
+	if out, err := cmd.CombinedOutput(); err != nil {
+		return fmt.Errorf("move %s to the Trash: %v: %s", abs, err, strings.TrimSpace(string(out)))
+	}
+	return nil
+}
+
+// finderDelete asks Finder to trash the first argument of a run handler.
+const finderDelete = `tell application "Finder" to delete POSIX file (item 1 of argv)`
+
+// shim defines maziq_trash, the shell counterpart of Move, for scripts
+// whose rm -rf Script rewrites.
+const shim = `maziq_trash() { for p in "$@"; do ` +
+	`[ -e "$p" ] || [ -L "$p" ] || continue; ` +
+	`if command -v trash >/dev/null 2>&1; then trash "$p"; ` +
+	`else osascript -e 'on run argv' -e '` + finderDelete + `' -e 'end run' "$(cd "$(dirname "$p")" && pwd)/$(basename "$p")" >/dev/null; ` +
+	`fi || return 1; done; }; `
+
+// removal matches an rm -rf run as the user, at the start of a line or
+// after a command separator. One run through sudo removes system files the
+// Trash has no place for and is left alone.
+var removal = regexp.MustCompile(`(?m)(^\s*|[;&|(]\s*)rm -(?:rf|fr) `)
+
+// Script rewrites every unprivileged rm -rf in script to move its
+// arguments to the Trash instead. Scripts without one are returned as is.
+func Script(script string) string {
+	if !removal.MatchString(script) {
+		return script
+	}
+	return shim + removal.ReplaceAllString(script, "${1}maziq_trash ")
+}
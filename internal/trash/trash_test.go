@@ -0,0 +1,89 @@
+package trash
+
+import (
+	"os"
+	"os/exec"
+	"path/filepath"
+	"strings"
+	"testing"
+)
+
+func TestScript(t *testing.T) {
+	tests := []struct {
+		name   string
+		script string
+		want   string // without the shim; "" when left as is
+	}{
+		{"plain", "rm -rf ~/.cache/tool", "maziq_trash ~/.cache/tool"},
+		{"fr", "rm -fr ~/.cache/tool", "maziq_trash ~/.cache/tool"},
+		{"quoted path", `rm -rf "$HOME/.tool"`, `maziq_trash "$HOME/.tool"`},
+		{"path with spaces", `rm -rf "$HOME/Library/Application Support/Tool" '/Applications/My Tool.app'`,
+			`maziq_trash "$HOME/Library/Application Support/Tool" '/Applications/My Tool.app'`},
+		{"and", "brew uninstall tool && rm -rf ~/.tool", "brew uninstall tool && maziq_trash ~/.tool"},
+		{"or and semicolon", "rm -rf a || true; rm -fr b", "maziq_trash a || true; maziq_trash b"},
+		{"subshell", "(cd ~ && rm -rf .tool)", "(cd ~ && maziq_trash .tool)"},
+		{"later line", "echo removing\n  rm -rf ~/.tool", "echo removing\n  maziq_trash ~/.tool"},
+		{"sudo", "sudo rm -rf /Library/Tool", ""},
+		{"sudo then user", "sudo rm -rf /Library/Tool && rm -rf ~/.tool", "sudo rm -rf /Library/Tool && maziq_trash ~/.tool"},
+		{"not recursive", "rm -f ~/.toolrc", ""},
+		{"inside a word", "confirm -rf x", ""},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			got := Script(tt.script)
+			if tt.want == "" {
+				if got != tt.script {
+					t.Errorf("Script(%q) = %q, want it unchanged", tt.script, got)
+				}
+				return
+			}
+			if got != shim+tt.want {
+				t.Errorf("Script(%q) = %q, want the shim and %q", tt.script, strings.TrimPrefix(got, shim), tt.want)
+			}
+		})
+	}
+}
+
+// The rewritten script hands every existing path to trash as one argument,
+// spaces and all, and skips missing ones.
+func TestScriptRuns(t *testing.T) {
+	if _, err := os.Stat("/bin/sh"); err != nil {
+		t.Skip("no /bin/sh")
+	}
+	dir := t.TempDir()
+	bin := filepath.Join(dir, "bin")
+	log := filepath.Join(dir, "trash.log")
+	if err := os.Mkdir(bin, 0o755); err != nil {
+		t.Fatal(err)
+	}
+	fake := "#!/bin/sh\nfor p in \"$@\"; do echo \"$p\" >> '" + log + "'; done\n"
+	if err := os.WriteFile(filepath.Join(bin, "trash"), []byte(fake), 0o755); err != nil {
+		t.Fatal(err)
+	}
+	for _, name := range []string{"with space", "plain"} {
+		if err := os.WriteFile(filepath.Join(dir, name), nil, 0o644); err != nil {
+			t.Fatal(err)
+		}
+	}
+
+	script := `cd "$DIR" && rm -rf "$DIR/with space" missing; rm -fr plain`
+	cmd := exec.Command("/bin/sh", "-c", Script(script))
+	cmd.Env = append(os.Environ(), "PATH="+bin+":/usr/bin:/bin", "DIR="+dir)
+	if out, err := cmd.CombinedOutput(); err != nil {
+		t.Fatalf("script failed: %v: %s", err, out)
+	}
+	data, err := os.ReadFile(log)
+	if err != nil {
+		t.Fatal(err)
+	}
+	got := strings.Split(strings.TrimSpace(string(data)), "\n")
+	want := []string{filepath.Join(dir, "with space"), "plain"}
+	if strings.Join(got, "|") != strings.Join(want, "|") {
+		t.Errorf("trashed %q, want %q", got, want)
+	}
+	for _, name := range []string{"with space", "plain"} {
+		if _, err := os.Stat(filepath.Join(dir, name)); err != nil {
+			t.Errorf("%s was deleted rather than handed to trash: %v", name, err)
+		}
+	}
+}
@@ -0,0 +1,448 @@
+// Package tui implements the interactive Bubbletea frontend.
+package tui
+
+import (
+	"fmt"
+	"os"
+	"runtime/debug"
+	"slices"
+	"strings"
+	"time"
+
+	tea "github.com/charmbracelet/bubbletea"
+	"github.com/charmbracelet/lipgloss"
+
+	"github.com/hmziqrs/maziq/internal/config"
+	"github.com/hmziqrs/maziq/internal/crash"
+	"github.com/hmziqrs/maziq/internal/manager"
+	"github.com/hmziqrs/maziq/internal/options"
+	"github.com/hmziqrs/maziq/internal/templates"
+)
+
+type screen int
+
+const (
+	screenMenu screen = iota
+	screenCatalog
+	screenTasks
+	screenWizard
+	screenE2E
+	screenStats
+	screenQueue
+)
+
+type model struct {
+	width        int
+	height       int
+	selectedMenu int
+	menuItems    []string
+	ready        bool
+	// stack holds the screens opened from the menu, innermost last; Esc
+	// pops one level. An empty stack is the menu.
+	stack        []screen
+	help         bool
+	engine       *manager.Engine
+	cfg          config.Config
+	// opts are the run options from config.toml and the global flags.
+	opts         options.Run
+	wizard       wizardScreen
+	catalog      catalogScreen
+	tasks        tasksScreen
+	e2e          e2eScreen
+	stats        statsScreen
+	queue        queueScreen
+	// toastTicking is set while a toastTick is scheduled.
+	toastTicking bool
+}
+
+// engineMsg wraps an event from a background job.
+type engineMsg manager.JobEvent
+
+// listen waits for the next engine event. Update re-arms it after every
+// delivery, so one subscription serves all jobs.
+func listen(engine *manager.Engine) tea.Cmd {
+	return func() tea.Msg {
+		return engineMsg(<-engine.Events())
+	}
+}
+
+func initialModel(engine *manager.Engine, cfg config.Config, opts options.Run) model {
+	m := model{
+		menuItems: []string{
+			"Software Catalog",
+			"Templates",
+			"Task Queue",
+			"E2E Testing",
+			"Statistics",
+		},
+		ready:   true,
+		engine:  engine,
+		catalog: newCatalogScreen(engine, opts, cfg.Favorites),
+		cfg:     cfg,
+		opts:    opts,
+		tasks:   newTasksScreen(engine, cfg.Template, opts),
+		e2e:     newE2EScreen(engine, opts),
+		queue:   newQueueScreen(engine),
+	}
+	if cfg.Role == "" {
+		m = m.push(screenWizard)
+	}
+	return m
+}
+
+// Run starts the TUI and blocks until the user quits. Preferences come
+// from cfg and runs follow opts, which the caller built from cfg and the
+// global flags.
+func Run(cfg config.Config, opts options.Run) error {
+	applyTheme(cfg.Theme)
+
+	engine := manager.NewEngine()
+	defer engine.Shutdown()
+	// Panics are handled here rather than by Bubbletea so the report file
+	// is written and pointed at after the terminal is back to normal.
+	p := tea.NewProgram(
+		initialModel(engine, cfg, opts),
+		tea.WithAltScreen(),
+		tea.WithMouseCellMotion(),
+		tea.WithoutCatchPanics(),
+	)
+	defer func() {
+		if r := recover(); r != nil {
+			_ = p.ReleaseTerminal()
+			crash.Report(r, debug.Stack())
+			os.Exit(crash.ExitCode)
+		}
+	}()
+	_, err := p.Run()
+	return err
+}
+
+func (m model) Init() tea.Cmd {
+	return listen(m.engine)
+}
+
+func (m model) Update(msg tea.Msg) (tea.Model, tea.Cmd) {
+	switch msg := msg.(type) {
+	case tea.WindowSizeMsg:
+		m.width = msg.Width
+		m.height = msg.Height
+		return m, nil
+
+	case engineMsg:
+		ev := manager.JobEvent(msg)
+		m.queue = m.queue.onJobEvent(ev)
+		switch payload := ev.Payload.(type) {
+		case statusMsg:
+			m.catalog = m.catalog.applyStatuses(payload)
+		case actionMsg:
+			m.catalog = m.catalog.applyAction(payload)
+			return m.expireToasts(listen(m.engine))
+		case sudoMsg:
+			var cmd tea.Cmd
+			m.catalog = m.catalog.onSudo(payload)
+			m.tasks, cmd = m.tasks.onSudo(payload)
+			m.e2e = m.e2e.onSudo(payload)
+			return m.expireToasts(tea.Batch(listen(m.engine), cmd))
+		default:
+			m.tasks = m.tasks.onJobEvent(ev)
+			m.e2e = m.e2e.onJobEvent(ev)
+		}
+		return m, listen(m.engine)
+
+	case toastMsg:
+		m.catalog.toasts = m.catalog.toasts.push(msg.severity, msg.text)
+		return m.expireToasts(nil)
+
+	case toastTickMsg:
+		m.catalog.toasts = m.catalog.toasts.prune(time.Time(msg))
+		m.toastTicking = false
+		return m.expireToasts(nil)
+
+	case tickMsg:
+		if m.current() == screenQueue {
+			m.queue.open()
+		}
+		if m.tasks.phase.busy() {
+			return m, tick()
+		}
+		return m, nil
+
+	case tea.KeyMsg:
+		if msg.String() == "ctrl+c" {
+			return m, tea.Quit
+		}
+		if m.help {
+			switch msg.String() {
+			case "?", "esc", "q":
+				m.help = false
+			}
+			return m, nil
+		}
+		if msg.String() == "?" && !m.typing() {
+			m.help = true
+			return m, nil
+		}
+		if m.current() == screenWizard {
+			var role *templates.Role
+			var done bool
+			m.wizard, role, done = m.wizard.update(msg)
+			if !done {
+				return m, nil
+			}
+			cfg, err := finishWizard(m.cfg, role)
+			if err != nil && role != nil {
+				m.wizard.err = err
+				return m, nil
+			}
+			m.cfg = cfg
+			m.tasks = newTasksScreen(m.engine, cfg.Template, m.opts)
+			m = m.pop()
+			return m, nil
+		}
+		if m.current() == screenTasks {
+			var cmd tea.Cmd
+			var back bool
+			m.tasks, cmd, back = m.tasks.update(msg)
+			if back {
+				m = m.pop()
+			}
+			return m, cmd
+		}
+		if m.current() == screenCatalog {
+			var cmd tea.Cmd
+			var back bool
+			m.catalog, cmd, back = m.catalog.update(msg)
+			if back {
+				m = m.pop()
+			}
+			return m.expireToasts(cmd)
+		}
+		if m.current() == screenE2E {
+			var cmd tea.Cmd
+			var back bool
+			m.e2e, cmd, back = m.e2e.update(msg)
+			if back {
+				m = m.pop()
+			}
+			return m, cmd
+		}
+		if m.current() == screenQueue {
+			var cmd tea.Cmd
+			var back bool
+			m.queue, cmd, back = m.queue.update(msg)
+			if back {
+				m = m.pop()
+			}
+			return m, cmd
+		}
+		if m.current() == screenStats {
+			var cmd tea.Cmd
+			var back bool
+			m.stats, cmd, back = m.stats.update(msg)
+			if back {
+				m = m.pop()
+			}
+			return m, cmd
+		}
+
+		switch msg.String() {
+		case "q":
+			return m, tea.Quit
+
+		case "up", "k":
+			if m.selectedMenu > 0 {
+				m.selectedMenu--
+			}
+
+		case "down", "j":
+			if m.selectedMenu < len(m.menuItems)-1 {
+				m.selectedMenu++
+			}
+
+		case "enter", " ":
+			switch m.selectedMenu {
+			case 0:
+				m = m.push(screenCatalog)
+				m.catalog.open()
+				return m, nil
+			case 1:
+				m = m.push(screenTasks)
+				return m, nil
+			case 2:
+				m = m.push(screenQueue)
+				m.queue.open()
+				return m, nil
+			case 3:
+				m = m.push(screenE2E)
+				m.e2e.open()
+				return m, nil
+			case 4:
+				m = m.push(screenStats)
+				m.stats.open()
+				return m, nil
+			}
+		}
+	}
+	return m, nil
+}
+
+// screenTitles names each screen in the breadcrumb.
+var screenTitles = map[screen]string{
+	screenCatalog: "Software Catalog",
+	screenTasks:   "Templates",
+	screenWizard:  "Setup",
+	screenE2E:     "E2E Testing",
+	screenStats:   "Statistics",
+	screenQueue:   "Task Queue",
+}
+
+// current is the screen on top of the stack.
+func (m model) current() screen {
+	if len(m.stack) == 0 {
+		return screenMenu
+	}
+	return m.stack[len(m.stack)-1]
+}
+
+// push opens s on top of the current screen, if the current screen may
+// open it.
+func (m model) push(s screen) model {
+	if !slices.Contains(screenMoves[m.current()], s) {
+		return m
+	}
+	m.stack = append(slices.Clone(m.stack), s)
+	return m
+}
+
+// pop returns to the screen below the current one.
+func (m model) pop() model {
+	if len(m.stack) > 0 {
+		m.stack = m.stack[:len(m.stack)-1]
+	}
+	return m
+}
+
+// breadcrumb renders the path from the menu to whatever is open, including
+// the levels a screen opens inside itself such as details and dialogs.
+func (m model) breadcrumb() string {
+	crumbs := []string{"MazIQ"}
+	for _, s := range m.stack {
+		crumbs = append(crumbs, screenTitles[s])
+	}
+	switch m.current() {
+	case screenCatalog:
+		crumbs = append(crumbs, m.catalog.trail()...)
+	case screenTasks:
+		crumbs = append(crumbs, m.tasks.trail()...)
+	}
+	last := len(crumbs) - 1
+	return mutedStyle.Render(strings.Join(crumbs[:last], " › ")+" › ") + selectedMenuItemStyle.Render(crumbs[last])
+}
+
+// expireToasts schedules a toastTick alongside cmd while toasts are shown
+// and none is pending.
+func (m model) expireToasts(cmd tea.Cmd) (model, tea.Cmd) {
+	if m.toastTicking || len(m.catalog.toasts) == 0 {
+		return m, cmd
+	}
+	m.toastTicking = true
+	return m, tea.Batch(cmd, toastTick())
+}
+
+// typing reports whether a text input has focus, so printable keys must not
+// trigger global shortcuts.
+func (m model) typing() bool {
+	return m.catalog.password != nil || m.tasks.password != nil ||
+		m.current() == screenE2E && m.e2e.typing()
+}
+
+func (m model) View() string {
+	if m.width == 0 {
+		return "Loading..."
+	}
+	if m.help {
+		return helpView(m.width, m.height)
+	}
+	height := m.height - 1
+	var view string
+	switch m.current() {
+	case screenWizard:
+		return m.wizard.view(m.width, m.height)
+	case screenCatalog:
+		view = m.catalog.view(m.width, height)
+	case screenTasks:
+		view = m.tasks.view(m.width, height)
+	case screenE2E:
+		view = m.e2e.view(m.width, height)
+	case screenStats:
+		view = m.stats.view(m.width, height)
+	case screenQueue:
+		view = m.queue.view(m.width, height)
+	}
+	if view != "" {
+		return lipgloss.JoinVertical(lipgloss.Left, m.breadcrumb(), view)
+	}
+
+	var sections []string
+
+	// Logo and title
+	logo := logoStyle.Render(`
+ ███╗   ███╗ █████╗ ███████╗██╗ ██████╗
+ ████╗ ████║██╔══██╗╚══███╔╝██║██╔═══██╗
+ ██╔████╔██║███████║  ███╔╝ ██║██║   ██║
+ ██║╚██╔╝██║██╔══██║ ███╔╝  ██║██║▄▄ ██║
+ ██║ ╚═╝ ██║██║  ██║███████╗██║╚██████╔╝
+ ╚═╝     ╚═╝╚═╝  ╚═╝╚══════╝╚═╝ ╚══▀▀═╝ `)
+
+	subtitle := subtitleStyle.Render("macOS Provisioning & Automation Tool")
+
+	header := lipgloss.JoinVertical(lipgloss.Center, logo, subtitle)
+	sections = append(sections, header)
+
+	// Status indicator
+	var status string
+	running, pending := m.queue.counts()
+	switch {
+	case m.ready && running+pending > 0:
+		status = warningStyle.Render(fmt.Sprintf("● %d running, %d queued", running, pending))
+	case m.ready:
+		status = readyStyle.Render("● Ready")
+	default:
+		status = errorStyle.Render("● Not Ready")
+	}
+	statusBox := boxStyle.Width(m.width - 4).Render(status)
+	sections = append(sections, statusBox)
+
+	// Menu
+	var menuItems []string
+	for i, item := range m.menuItems {
+		var renderedItem string
+		if i == m.selectedMenu {
+			renderedItem = selectedMenuItemStyle.Render("❯ " + item)
+		} else {
+			renderedItem = menuItemStyle.Render("  " + item)
+		}
+		menuItems = append(menuItems, renderedItem)
+	}
+
+	menu := strings.Join(menuItems, "\n")
+	menuBox := boxStyle.
+		Width(m.width - 4).
+		Render(menu)
+	sections = append(sections, menuBox)
+
+	// Help text
+	sections = append(sections, menuKeys.footer())
+
+	// Join all sections
+	content := lipgloss.JoinVertical(lipgloss.Left, sections...)
+
+	// Center the content
+	return lipgloss.Place(
+		m.width,
+		m.height,
+		lipgloss.Center,
+		lipgloss.Center,
+		content,
+	)
+}
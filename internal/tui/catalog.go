@@ -0,0 +1,830 @@
+package tui
+
+import (
+	"context"
+	"fmt"
+	"os/exec"
+	"slices"
+	"strings"
+	"time"
+
+	tea "github.com/charmbracelet/bubbletea"
+	"github.com/charmbracelet/lipgloss"
+
+	"github.com/hmziqrs/maziq/internal/catalog"
+	"github.com/hmziqrs/maziq/internal/clipboard"
+	"github.com/hmziqrs/maziq/internal/config"
+	"github.com/hmziqrs/maziq/internal/errs"
+	"github.com/hmziqrs/maziq/internal/history"
+	"github.com/hmziqrs/maziq/internal/manager"
+	"github.com/hmziqrs/maziq/internal/options"
+)
+
+// statusMsg delivers the result of a probe run to the catalog screen.
+type statusMsg struct {
+	statuses    map[string]manager.Status
+	mode        manager.ProbeMode
+	// provenances says who installed each entry; nil when unreadable.
+	provenances *manager.Provenances
+}
+
+// probeJob probes entries on the engine, at most jobs at once, and emits a
+// single statusMsg.
+func probeJob(entries []catalog.Entry, mode manager.ProbeMode, jobs int) manager.Job {
+	return func(ctx context.Context, emit func(any)) error {
+		statuses := manager.NewProber(jobs).ProbeAll(ctx, entries, mode)
+		provenances, _ := manager.LoadProvenances(ctx)
+		emit(statusMsg{statuses: statuses, mode: mode, provenances: provenances})
+		return nil
+	}
+}
+
+// actionMsg reports an action run from the catalog and the entry's status
+// afterwards.
+type actionMsg struct {
+	entry  catalog.Entry
+	action manager.Action
+	event  manager.ExecutionEvent
+	err    error
+	status manager.Status
+}
+
+// actionJob applies action to entry on the engine following opts, then
+// re-probes it.
+func actionJob(entry catalog.Entry, action manager.Action, opts options.Run) manager.Job {
+	return func(ctx context.Context, emit func(any)) error {
+		m := manager.New(opts)
+		started := time.Now()
+		event, err := m.Apply(ctx, entry, action)
+		if event.Command != "" && !m.Executor.DryRun {
+			// Best effort: the history only feeds estimates and provenance.
+			_ = m.RecordStep(ctx, manager.Step{Entry: entry, Action: action}, event, time.Since(started))
+		}
+		emit(actionMsg{
+			entry:  entry,
+			action: action,
+			event:  event,
+			err:    err,
+			status: manager.NewProber(opts.Jobs).Probe(ctx, entry),
+		})
+		return err
+	}
+}
+
+// recentLimit is how many recently used entries the catalog pins.
+const recentLimit = 5
+
+type catalogScreen struct {
+	engine      *manager.Engine
+	opts        options.Run
+	// entries lists the pinned favorites and recent entries, then every
+	// entry again; the first pinned of them are the pinned ones.
+	entries     []catalog.Entry
+	pinned      int
+	favorites   []string
+	// recent are the ids acted on most lately, most used first.
+	recent      []string
+	// tag narrows entries to one tag; "" lists the whole catalog.
+	tag         string
+	// state narrows entries to those probed in that state; StateUnknown
+	// lists every state.
+	state       manager.State
+	statuses    map[string]manager.Status
+	// provenances backs the provenance shown in the detail pane.
+	provenances *manager.Provenances
+	cursor      int
+	// probing is which status refreshes are outstanding.
+	probing     probePhase
+	// confirm is the open confirmation dialog, if any, for uninstalling
+	// the entry it holds.
+	confirm     *confirmDialog
+	// typedID is the SDK whose id was typed out in confirm; only that
+	// entry's uninstall may remove an SDK.
+	typedID     string
+	password    *passwordDialog
+	// toasts are the recent outcomes and warnings, newest last.
+	toasts      toasts
+	// detail shows the selected entry's full definition beside the list.
+	detail      bool
+}
+
+func newCatalogScreen(engine *manager.Engine, opts options.Run, favorites []string) catalogScreen {
+	// Best effort: without a readable history nothing is recent.
+	recent, _ := history.RecentlyUsed(recentLimit)
+	c := catalogScreen{
+		engine:    engine,
+		opts:      opts,
+		favorites: favorites,
+		recent:    recent,
+		statuses:  map[string]manager.Status{},
+	}
+	c.list()
+	return c
+}
+
+// list rebuilds entries from the tag filter, favorites and recent ids,
+// keeping the cursor on the entry it was on.
+func (c *catalogScreen) list() {
+	var current string
+	inPinned := c.cursor < c.pinned
+	if c.cursor < len(c.entries) {
+		current = c.entries[c.cursor].ID
+	}
+	var all []catalog.Entry
+	for _, entry := range c.tagged() {
+		if st, known := c.statuses[entry.ID]; c.state == manager.StateUnknown || known && st.State == c.state {
+			all = append(all, entry)
+		}
+	}
+	var pinned []catalog.Entry
+	for _, id := range append(slices.Clone(c.favorites), c.recent...) {
+		i := slices.IndexFunc(all, func(e catalog.Entry) bool { return e.ID == id })
+		if i >= 0 && !slices.ContainsFunc(pinned, func(e catalog.Entry) bool { return e.ID == id }) {
+			pinned = append(pinned, all[i])
+		}
+	}
+	c.entries = append(pinned, all...)
+	c.pinned = len(pinned)
+	c.cursor = 0
+	section := c.entries[c.pinned:]
+	offset := c.pinned
+	if inPinned {
+		section, offset = c.entries, 0
+	}
+	if i := slices.IndexFunc(section, func(e catalog.Entry) bool { return e.ID == current }); i >= 0 {
+		c.cursor = offset + i
+	}
+}
+
+// tagged returns the entries the tag filter lets through, whatever their
+// state.
+func (c catalogScreen) tagged() []catalog.Entry {
+	var out []catalog.Entry
+	for _, entry := range catalog.All() {
+		if c.tag == "" || entry.HasTag(c.tag) {
+			out = append(out, entry)
+		}
+	}
+	return out
+}
+
+// filterState narrows the list to entries in state, or lists every state
+// again when that filter is already on.
+func (c catalogScreen) filterState(state manager.State) catalogScreen {
+	if c.state == state {
+		c.state = manager.StateUnknown
+	} else {
+		c.state = state
+	}
+	c.list()
+	return c
+}
+
+// isFavorite reports whether id is pinned as a favorite.
+func (c catalogScreen) isFavorite(id string) bool {
+	return slices.Contains(c.favorites, id)
+}
+
+// toggleFavorite pins or unpins the entry at the cursor and saves the
+// favorites to config.toml.
+func (c catalogScreen) toggleFavorite() catalogScreen {
+	entry := c.entries[c.cursor]
+	favorites := slices.Clone(c.favorites)
+	message := "Pinned " + entry.Name + " to favorites"
+	if i := slices.Index(favorites, entry.ID); i >= 0 {
+		favorites = slices.Delete(favorites, i, i+1)
+		message = "Removed " + entry.Name + " from favorites"
+	} else {
+		favorites = append(favorites, entry.ID)
+	}
+	if err := config.SaveFavorites(favorites); err != nil {
+		c.toasts = c.toasts.push(severityError, "could not save favorites: "+err.Error())
+		return c
+	}
+	c.favorites = favorites
+	c.list()
+	c.toasts = c.toasts.push(severityInfo, message)
+	return c
+}
+
+// used moves id to the front of the recent entries after acting on it.
+func (c catalogScreen) used(id string) catalogScreen {
+	recent := []string{id}
+	for _, other := range c.recent {
+		if other != id && len(recent) < recentLimit {
+			recent = append(recent, other)
+		}
+	}
+	c.recent = recent
+	c.list()
+	return c
+}
+
+// nextTag cycles the tag filter through every tag, then back to all
+// entries.
+func (c catalogScreen) nextTag() catalogScreen {
+	all := catalog.Tags()
+	i := slices.Index(all, c.tag)
+	if i+1 < len(all) {
+		c.tag = all[i+1]
+	} else {
+		c.tag = ""
+	}
+	c.list()
+	c.cursor = 0
+	return c
+}
+
+// open kicks off a quick refresh the first time the screen is shown.
+func (c *catalogScreen) open() {
+	if len(c.statuses) > 0 || c.probing.quick() {
+		return
+	}
+	c.refresh(manager.ProbeModeQuick)
+}
+
+func (c *catalogScreen) refresh(mode manager.ProbeMode) {
+	ev := quickRequested
+	if mode == manager.ProbeModeDeep {
+		ev = deepRequested
+	}
+	next := c.probing.next(ev)
+	if next == c.probing {
+		// That pass is already running.
+		return
+	}
+	c.probing = next
+	if mode == manager.ProbeModeDeep {
+		for id, st := range c.statuses {
+			if entry, ok := catalog.Lookup(id); ok && entry.Probe.Depth == catalog.ProbeDeep {
+				st.DeepPending = true
+				c.statuses[id] = st
+			}
+		}
+	}
+	// Probe what the state filter hides too: its state may have changed.
+	c.engine.Submit("probe", probeJob(c.tagged(), mode, c.opts.Jobs))
+}
+
+// refreshOnly re-probes entries fully, quick and deep probes alike, and
+// merges the results into the statuses without a refresh of the rest.
+func (c catalogScreen) refreshOnly(entries []catalog.Entry, what string) catalogScreen {
+	c.toasts = c.toasts.push(severityInfo, "Refreshing "+what+"…")
+	c.engine.Submit("probe", probeJob(entries, manager.ProbeModeFull, c.opts.Jobs))
+	return c
+}
+
+// refreshCategory re-probes the entries in the selected entry's category.
+func (c catalogScreen) refreshCategory() catalogScreen {
+	category := c.entries[c.cursor].Category
+	var entries []catalog.Entry
+	for _, entry := range c.tagged() {
+		if entry.Category == category {
+			entries = append(entries, entry)
+		}
+	}
+	return c.refreshOnly(entries, fmt.Sprintf("%s (%d entries)", category, len(entries)))
+}
+
+// applyStatuses merges probe results. A quick refresh never clobbers a
+// deep result that already arrived, and schedules the deep probes in the
+// background once the quick pass is done.
+func (c catalogScreen) applyStatuses(msg statusMsg) catalogScreen {
+	if msg.provenances != nil {
+		c.provenances = msg.provenances
+	}
+	for id, st := range msg.statuses {
+		if prev, ok := c.statuses[id]; ok && st.DeepPending && !prev.CheckedAt.IsZero() {
+			prev.DeepPending = c.probing.deep()
+			c.statuses[id] = prev
+			continue
+		}
+		c.statuses[id] = st
+	}
+	if c.state != manager.StateUnknown {
+		c.list()
+	}
+	switch msg.mode {
+	case manager.ProbeModeFull:
+		// Only refreshOnly probes fully; the other refreshes go on.
+		return c
+	case manager.ProbeModeQuick:
+		if next := c.probing.next(quickFinished); next != c.probing {
+			c.probing = next
+			c.refresh(manager.ProbeModeDeep)
+		}
+		return c
+	}
+	c.probing = c.probing.next(deepFinished)
+	return c
+}
+
+// uninstall removes entry, asking for the sudo password first when its
+// recipe needs one. SDKs are only removed once their id was typed out in
+// the confirmation dialog; otherwise the manager refuses. entry comes from
+// the dialog rather than the cursor, since the list may be rebuilt while
+// a dialog is open.
+func (c catalogScreen) uninstall(entry catalog.Entry) catalogScreen {
+	plan := manager.Plan{Action: manager.ActionUninstall, Steps: []manager.Step{{Entry: entry, Action: manager.ActionUninstall}}}
+	if c.password == nil && needsPassword(plan, c.opts) {
+		c.password = newPasswordDialog("Uninstalling " + entry.Name + " runs a command with sudo.")
+		c.password.entry = entry
+		return c
+	}
+	c.password = nil
+	c.toasts = c.toasts.push(severityInfo, "Uninstalling "+entry.Name+"…")
+	opts := c.opts
+	if entry.Kind == catalog.KindSDK && c.typedID == entry.ID {
+		opts.ConfirmSDKRemoval = true
+	}
+	c.typedID = ""
+	c.engine.Enqueue("uninstall "+entry.ID, actionJob(entry, manager.ActionUninstall, opts))
+	return c
+}
+
+// openHomepage opens the homepage of the entry at the cursor in the
+// default browser.
+func (c catalogScreen) openHomepage() catalogScreen {
+	entry := c.entries[c.cursor]
+	if entry.Homepage == "" {
+		c.toasts = c.toasts.push(severityWarning, entry.Name+" has no homepage")
+		return c
+	}
+	cmd := exec.Command("open", entry.Homepage)
+	if err := cmd.Start(); err != nil {
+		c.toasts = c.toasts.push(severityError, "open "+entry.Homepage+": "+err.Error())
+		return c
+	}
+	go cmd.Wait()
+	c.toasts = c.toasts.push(severityInfo, "Opening "+entry.Homepage)
+	return c
+}
+
+// launch opens the app at the cursor, so the catalog doubles as a
+// launcher once onboarding is done. The app starts off the update loop
+// and its outcome arrives as a toastMsg.
+func (c catalogScreen) launch() (catalogScreen, tea.Cmd) {
+	entry := c.entries[c.cursor]
+	if entry.Kind != catalog.KindGUI {
+		c.toasts = c.toasts.push(severityWarning, entry.Name+" is not an app; press w to find its binary")
+		return c, nil
+	}
+	c.toasts = c.toasts.push(severityInfo, "Launching "+entry.Name+"…")
+	return c, func() tea.Msg {
+		ctx, cancel := context.WithTimeout(context.Background(), 10*time.Second)
+		defer cancel()
+		if err := manager.LaunchApp(ctx, entry); err != nil {
+			return toastMsg{severityError, err.Error()}
+		}
+		return toastMsg{severitySuccess, "Launched " + entry.Name}
+	}
+}
+
+// revealBinary looks up where the binary of the entry at the cursor lives
+// and copies the path to the clipboard, off the update loop; the path
+// arrives as a toastMsg.
+func (c catalogScreen) revealBinary() tea.Cmd {
+	entry := c.entries[c.cursor]
+	return func() tea.Msg {
+		ctx, cancel := context.WithTimeout(context.Background(), 5*time.Second)
+		defer cancel()
+		path, err := manager.BinaryPath(ctx, entry)
+		if err != nil {
+			return toastMsg{severityError, err.Error()}
+		}
+		if clipboard.Copy(path) != nil {
+			return toastMsg{severityInfo, path}
+		}
+		return toastMsg{severitySuccess, path + " (copied)"}
+	}
+}
+
+// yank copies the command that installs the entry at the cursor, or
+// updates it when it is installed, for running it by hand elsewhere.
+func (c catalogScreen) yank() catalogScreen {
+	entry := c.entries[c.cursor]
+	action := manager.ActionInstall
+	if st := c.statuses[entry.ID]; st.State == manager.StateInstalled || st.State == manager.StateOutdated {
+		action = manager.ActionUpdate
+	}
+	command, ok := manager.ManualCommand(entry, action, c.opts.Backend)
+	if !ok {
+		c.toasts = c.toasts.push(severityError, fmt.Sprintf("%s has no %s recipe", entry.Name, action))
+		return c
+	}
+	if err := clipboard.Copy(command); err != nil {
+		c.toasts = c.toasts.push(severityError, "copy failed: "+err.Error())
+		return c
+	}
+	c.toasts = c.toasts.push(severitySuccess, "Copied: "+command)
+	return c
+}
+
+// onSudo continues an uninstall once the password was checked.
+func (c catalogScreen) onSudo(msg sudoMsg) catalogScreen {
+	if c.password == nil || !c.password.checking {
+		return c
+	}
+	dialog, ok := c.password.result(msg)
+	c.password = &dialog
+	if !ok {
+		return c
+	}
+	return c.uninstall(dialog.entry)
+}
+
+// applyAction shows the outcome of an action and the entry's new status.
+func (c catalogScreen) applyAction(msg actionMsg) catalogScreen {
+	c.statuses[msg.entry.ID] = msg.status
+	c = c.used(msg.entry.ID)
+	if msg.err != nil {
+		text := msg.err.Error()
+		if ctx := errs.ContextOf(msg.err); ctx.Command != "" {
+			text += " ($ " + ctx.Command + ")"
+		}
+		c.toasts = c.toasts.push(severityError, text)
+		if msg.event.Hint != "" {
+			c.toasts = c.toasts.push(severityWarning, "hint: "+msg.event.Hint)
+		}
+	} else {
+		c.toasts = c.toasts.push(severitySuccess, fmt.Sprintf("%s %s via %s", msg.action, msg.entry.Name, msg.event.Source))
+	}
+	return c
+}
+
+func (c catalogScreen) update(msg tea.KeyMsg) (catalogScreen, tea.Cmd, bool) {
+	if c.confirm != nil {
+		dialog, done, accepted := c.confirm.update(msg)
+		c.confirm = &dialog
+		if done {
+			c.confirm = nil
+			if accepted {
+				if dialog.typed {
+					c.typedID = dialog.entry.ID
+				}
+				c = c.uninstall(dialog.entry)
+			}
+		}
+		return c, nil, false
+	}
+	if c.password != nil {
+		dialog, submitted, cancelled := c.password.update(msg)
+		c.password = &dialog
+		switch {
+		case cancelled:
+			c.password = nil
+		case submitted != "":
+			c.engine.Submit("sudo", sudoJob(submitted))
+		}
+		return c, nil, false
+	}
+
+	switch msg.String() {
+	case "esc", "q":
+		if c.detail {
+			c.detail = false
+			return c, nil, false
+		}
+		return c, nil, true
+	case "up", "k":
+		if c.cursor > 0 {
+			c.cursor--
+		}
+	case "down", "j":
+		if c.cursor < len(c.entries)-1 {
+			c.cursor++
+		}
+	case "r":
+		c.refresh(manager.ProbeModeQuick)
+	case "D":
+		c.refresh(manager.ProbeModeDeep)
+	case "i":
+		c.detail = !c.detail
+	case "t":
+		c = c.nextTag()
+	case "m":
+		c = c.filterState(manager.StateNotInstalled)
+	case "o":
+		c = c.filterState(manager.StateOutdated)
+	case "e":
+		c = c.filterState(manager.StateError)
+	}
+	if len(c.entries) == 0 {
+		return c, nil, false
+	}
+	var cmd tea.Cmd
+	switch msg.String() {
+	case "H":
+		c = c.openHomepage()
+	case "l":
+		c, cmd = c.launch()
+	case "w":
+		cmd = c.revealBinary()
+	case "y":
+		c = c.yank()
+	case "*":
+		c = c.toggleFavorite()
+	case "s":
+		c = c.refreshOnly([]catalog.Entry{c.entries[c.cursor]}, c.entries[c.cursor].Name)
+	case "c":
+		c = c.refreshCategory()
+	case "x":
+		entry := c.entries[c.cursor]
+		recipes := manager.PreferredRecipes(entry, manager.ActionUninstall, c.opts.Backend)
+		if len(recipes) == 0 {
+			c.toasts = c.toasts.push(severityError, entry.Name+" has no uninstall recipe")
+			break
+		}
+		if entry.Kind == catalog.KindSDK {
+			c.confirm = newTypedConfirmDialog("Uninstall "+entry.Name+" and everything it installed?", recipes[0].Command, entry.ID, entry.Removes)
+		} else {
+			c.confirm = newConfirmDialog("Uninstall "+entry.Name+"?", recipes[0].Command)
+		}
+		c.confirm.entry = entry
+	}
+	return c, cmd, false
+}
+
+// trail names the levels open inside the catalog for the breadcrumb.
+func (c catalogScreen) trail() []string {
+	var trail []string
+	if c.detail && len(c.entries) > 0 {
+		trail = append(trail, c.entries[c.cursor].Name)
+	}
+	switch {
+	case c.confirm != nil:
+		trail = append(trail, "Confirm uninstall")
+	case c.password != nil:
+		trail = append(trail, "Password")
+	}
+	return trail
+}
+
+func renderState(st manager.Status, known bool) string {
+	if !known {
+		return mutedStyle.Render("…")
+	}
+	if st.DeepPending && st.CheckedAt.IsZero() {
+		return mutedStyle.Render("… deep probe pending")
+	}
+	var label string
+	switch st.State {
+	case manager.StateInstalled:
+		label = readyStyle.Render("● installed")
+	case manager.StateOutdated:
+		label = warningStyle.Render("● outdated")
+	case manager.StateNotInstalled:
+		label = mutedStyle.Render("○ missing")
+	case manager.StateError:
+		label = errorStyle.Render("✗ " + st.Error)
+	default:
+		label = mutedStyle.Render("? unknown")
+	}
+	if st.Version != "" {
+		label += " " + st.Version
+	}
+	if st.Warning != "" {
+		label += warningStyle.Render(" ⚠ " + st.Warning)
+	}
+	if st.DeepPending {
+		label += mutedStyle.Render(" (deep pending)")
+	}
+	return label
+}
+
+// stateKeys are the keys that filter the catalog by state.
+var stateKeys = map[manager.State]string{
+	manager.StateNotInstalled: "m",
+	manager.StateOutdated:     "o",
+	manager.StateError:        "e",
+}
+
+// statusLegend explains the state column, naming the key that filters by
+// each state.
+func statusLegend() string {
+	return strings.Join([]string{
+		readyStyle.Render("● installed"),
+		warningStyle.Render("● outdated") + mutedStyle.Render(" (o)"),
+		mutedStyle.Render("○ missing (m)"),
+		errorStyle.Render("✗ error") + mutedStyle.Render(" (e)"),
+	}, "   ")
+}
+
+// statusCounts tallies the probed states of a set of entries.
+type statusCounts struct {
+	total, installed, missing, outdated, check, pending int
+}
+
+// counts tallies the entries the tag filter lets through by state, overall
+// and for category.
+func (c catalogScreen) counts(category catalog.Category) (all, inCategory statusCounts) {
+	for _, entry := range c.tagged() {
+		tally := func(n *statusCounts) {
+			n.total++
+			st, known := c.statuses[entry.ID]
+			switch {
+			case !known:
+				n.pending++
+			case st.State == manager.StateInstalled:
+				n.installed++
+			case st.State == manager.StateOutdated:
+				n.outdated++
+			case st.State == manager.StateNotInstalled:
+				n.missing++
+			default:
+				// Errors and unknown states need a look by hand.
+				n.check++
+			}
+		}
+		tally(&all)
+		if entry.Category == category {
+			tally(&inCategory)
+		}
+	}
+	return all, inCategory
+}
+
+// render prints n as "12 entries · 5 installed · 1 outdated · ...",
+// leaving out zero counts.
+func (n statusCounts) render() string {
+	parts := []string{fmt.Sprintf("%d entries", n.total)}
+	for _, part := range []struct {
+		count int
+		label string
+		style lipgloss.Style
+	}{
+		{n.installed, "installed", readyStyle},
+		{n.outdated, "outdated", warningStyle},
+		{n.missing, "missing", mutedStyle},
+		{n.check, "to check", errorStyle},
+		{n.pending, "probing", mutedStyle},
+	} {
+		if part.count > 0 {
+			parts = append(parts, part.style.Render(fmt.Sprintf("%d %s", part.count, part.label)))
+		}
+	}
+	return strings.Join(parts, mutedStyle.Render(" · "))
+}
+
+// summary is the bar above the list: counts for everything listed and
+// for the selected entry's category.
+func (c catalogScreen) summary() string {
+	if len(c.entries) == 0 {
+		all, _ := c.counts("")
+		return all.render()
+	}
+	category := c.entries[c.cursor].Category
+	all, inCategory := c.counts(category)
+	return all.render() + mutedStyle.Render("   │   "+string(category)+": ") + inCategory.render()
+}
+
+func (c catalogScreen) view(width, height int) string {
+	if c.confirm != nil {
+		return c.confirm.view(width, height)
+	}
+	if c.password != nil {
+		return c.password.view(width, height)
+	}
+	title := titleStyle.Render("Software Catalog")
+	if c.tag != "" {
+		title += mutedStyle.Render("   tag: " + c.tag)
+	}
+	if c.state != manager.StateUnknown {
+		title += mutedStyle.Render("   only: " + c.state.String())
+	}
+	summary := c.summary()
+	var state string
+	switch {
+	case c.probing.quick():
+		state = warningStyle.Render("Refreshing…")
+	case c.probing.deep():
+		state = mutedStyle.Render("Running deep probes in background…")
+	}
+	if len(c.toasts) > 0 {
+		state = lipgloss.JoinVertical(lipgloss.Left, state, c.toasts.view())
+	}
+
+	// Besides the list: title, summary, legend, borders and footer.
+	visible := height - 13 - len(c.toasts)
+	if c.pinned > 0 {
+		// The section headers.
+		visible -= 3
+	}
+	if visible < 5 {
+		visible = 5
+	}
+	start := 0
+	if c.cursor >= visible {
+		start = c.cursor - visible + 1
+	}
+	end := start + visible
+	if end > len(c.entries) {
+		end = len(c.entries)
+	}
+
+	var rows []string
+	for i := start; i < end; i++ {
+		switch {
+		case i == 0 && c.pinned > 0:
+			rows = append(rows, mutedStyle.Render("★ Favorites / Recent"))
+		case i == c.pinned && c.pinned > 0:
+			rows = append(rows, "", mutedStyle.Render("All software"))
+		}
+		entry := c.entries[i]
+		st, known := c.statuses[entry.ID]
+		star := " "
+		if c.isFavorite(entry.ID) {
+			star = "★"
+		}
+		line := fmt.Sprintf("%s %-26s %-10s %s", star, entry.Name, entry.Category, renderState(st, known))
+		if i == c.cursor {
+			rows = append(rows, selectedMenuItemStyle.Render("❯ "+line))
+		} else {
+			rows = append(rows, menuItemStyle.Render(line))
+		}
+	}
+	if len(c.entries) == 0 && c.state != manager.StateUnknown {
+		rows = append(rows, mutedStyle.Render("Nothing is "+c.state.String()+"; press "+stateKeys[c.state]+" to list everything again."))
+	}
+	rows = append(rows, "", statusLegend())
+
+	help := catalogKeys.footer()
+	if !c.detail {
+		list := boxStyle.Width(width - 4).Render(strings.Join(rows, "\n"))
+		return lipgloss.JoinVertical(lipgloss.Left, title, summary, state, list, help)
+	}
+	listWidth := width * 3 / 5
+	list := boxStyle.Width(listWidth - 2).Render(strings.Join(rows, "\n"))
+	pane := boxStyle.Width(width - listWidth - 4).Render(c.detailView())
+	body := lipgloss.JoinHorizontal(lipgloss.Top, list, pane)
+	return lipgloss.JoinVertical(lipgloss.Left, title, summary, state, body, help)
+}
+
+// detailView renders everything known about the selected entry, the same
+// data `maziq software show` prints.
+func (c catalogScreen) detailView() string {
+	if len(c.entries) == 0 {
+		return mutedStyle.Render("Nothing selected.")
+	}
+	entry := c.entries[c.cursor]
+	lines := []string{
+		selectedMenuItemStyle.Render(entry.Name) + mutedStyle.Render(" "+entry.ID),
+		entry.Summary,
+		"",
+		mutedStyle.Render("Category: ") + string(entry.Category) + mutedStyle.Render("  Kind: ") + entry.Kind.String(),
+	}
+	if len(entry.Tags) > 0 {
+		lines = append(lines, mutedStyle.Render("Tags: ")+strings.Join(entry.Tags, ", "))
+	}
+	if entry.Homepage != "" {
+		lines = append(lines, mutedStyle.Render("Homepage: ")+entry.Homepage)
+	}
+	if entry.Docs != "" {
+		lines = append(lines, mutedStyle.Render("Docs: ")+entry.Docs)
+	}
+	if entry.License != "" {
+		lines = append(lines, mutedStyle.Render("License: ")+entry.License)
+	}
+	deps := strings.Join(entry.Dependencies, ", ")
+	if deps == "" {
+		deps = "none"
+	}
+	lines = append(lines, mutedStyle.Render("Depends on: ")+deps)
+	if len(entry.Recommended) > 0 {
+		lines = append(lines, mutedStyle.Render("Recommends: ")+strings.Join(entry.Recommended, ", "))
+	}
+	if len(entry.Conflicts) > 0 {
+		lines = append(lines, mutedStyle.Render("Conflicts with: ")+strings.Join(entry.Conflicts, ", "))
+	}
+	lines = append(lines, mutedStyle.Render("Probe: ")+entry.Probe.Describe())
+	if st := c.statuses[entry.ID]; c.provenances != nil && (st.State == manager.StateInstalled || st.State == manager.StateOutdated) {
+		lines = append(lines, mutedStyle.Render("Provenance: ")+c.provenances.Of(entry).String())
+	}
+	for _, action := range []manager.Action{manager.ActionInstall, manager.ActionUpdate, manager.ActionUninstall} {
+		lines = append(lines, "", mutedStyle.Render(string(action)+":"))
+		recipes := manager.Recipes(entry, action)
+		if len(recipes) == 0 {
+			lines = append(lines, "  none")
+		}
+		for _, recipe := range recipes {
+			line := fmt.Sprintf("  [%s] %s", recipe.Source, recipe.Command)
+			if recipe.Mode != catalog.ShellSh {
+				line += mutedStyle.Render(" (" + recipe.Mode.String() + ")")
+			}
+			lines = append(lines, line)
+		}
+	}
+	if len(entry.PostInstall) > 0 {
+		lines = append(lines, "", mutedStyle.Render("post-install:"))
+		for _, hook := range entry.PostInstall {
+			lines = append(lines, "  "+hook.Description)
+		}
+	}
+	if len(entry.Aliases) > 0 {
+		lines = append(lines, "", mutedStyle.Render("Aliases:"))
+		for _, alias := range entry.Aliases {
+			lines = append(lines, "  "+alias.Description)
+		}
+	}
+	return strings.Join(lines, "\n")
+}
@@ -0,0 +1,73 @@
+package tui
+
+import (
+	"fmt"
+
+	tea "github.com/charmbracelet/bubbletea"
+	"github.com/charmbracelet/lipgloss"
+
+	"github.com/hmziqrs/maziq/internal/catalog"
+	"github.com/hmziqrs/maziq/internal/prompt"
+)
+
+// confirmDialog is a modal yes/no prompt that shows the exact command an
+// action will run. It defaults to "No".
+type confirmDialog struct {
+	title   string
+	command string
+	// removes lists the directories the action deletes, if any.
+	removes []string
+	// typed is set when the answer had to be typed out rather than a yes.
+	typed   bool
+	field   promptField
+	// entry is the catalog entry the dialog was opened for, if any. It is
+	// acted on once the dialog resolves, wherever the cursor is by then.
+	entry   catalog.Entry
+}
+
+func newConfirmDialog(title, command string) *confirmDialog {
+	return &confirmDialog{title: title, command: command, field: newPromptField(prompt.Prompt{Kind: prompt.KindConfirm})}
+}
+
+// newTypedConfirmDialog asks for word to be typed out instead of a yes,
+// for actions that delete the directories in removes.
+func newTypedConfirmDialog(title, command, word string, removes []string) *confirmDialog {
+	field := newPromptField(prompt.Prompt{
+		Kind:  prompt.KindText,
+		Label: "Type " + word + " to confirm:",
+		Validate: func(answer string) error {
+			if answer != word {
+				return fmt.Errorf("type %s exactly, or esc to cancel", word)
+			}
+			return nil
+		},
+	})
+	field.input.Placeholder = word
+	return &confirmDialog{title: title, command: command, removes: removes, typed: true, field: field}
+}
+
+// update handles a key while the dialog is open. done reports that the user
+// answered and accepted what they answered.
+func (d confirmDialog) update(msg tea.KeyMsg) (dialog confirmDialog, done, accepted bool) {
+	field, answer, done, _ := d.field.update(msg)
+	d.field = field
+	return d, done, answer.Yes || answer.Text != ""
+}
+
+func (d confirmDialog) view(width, height int) string {
+	lines := []string{
+		warningStyle.Render(d.title),
+		"",
+		mutedStyle.Render("This will run:"),
+		d.command,
+	}
+	if len(d.removes) > 0 {
+		lines = append(lines, "", mutedStyle.Render("and delete:"))
+		for _, dir := range d.removes {
+			lines = append(lines, errorStyle.Render("  "+dir))
+		}
+	}
+	lines = append(lines, "", d.field.view(), d.field.footer())
+	box := dialogStyle.Width(min(width-8, 72)).Render(lipgloss.JoinVertical(lipgloss.Left, lines...))
+	return lipgloss.Place(width, height, lipgloss.Center, lipgloss.Center, box)
+}
@@ -0,0 +1,340 @@
+package tui
+
+import (
+	"context"
+	"errors"
+	"fmt"
+	"strings"
+
+	"github.com/charmbracelet/bubbles/textinput"
+	tea "github.com/charmbracelet/bubbletea"
+	"github.com/charmbracelet/lipgloss"
+
+	"github.com/hmziqrs/maziq/internal/catalog"
+	"github.com/hmziqrs/maziq/internal/manager"
+	"github.com/hmziqrs/maziq/internal/options"
+)
+
+// e2eScreen runs a lifecycle test (install, test, update, uninstall) on any
+// catalog entry, picked by searching the catalog.
+type e2eScreen struct {
+	engine   *manager.Engine
+	opts     options.Run
+	search   textinput.Model
+	matches  []catalog.Entry
+	cursor   int
+	// confirm guards starting the test, which removes the entry at the
+	// end; password collects the sudo password when a stage needs root.
+	confirm  *confirmDialog
+	password *passwordDialog
+	// typed is set once an SDK's id was typed out in confirm, which lets
+	// the uninstall stage remove it even if it was installed before.
+	typed    bool
+
+	entry    catalog.Entry
+	phase    runPhase
+	err      error
+	job      manager.JobID
+	actions  []manager.Action
+	states   []stepState
+	events   []manager.TaskEvent
+}
+
+func newE2EScreen(engine *manager.Engine, opts options.Run) e2eScreen {
+	search := textinput.New()
+	search.Placeholder = "search by id, name or tag"
+	search.Prompt = "/ "
+	return e2eScreen{engine: engine, opts: opts, search: search, actions: manager.LifecycleActions(false)}
+}
+
+// open focuses the search box and lists the matching entries.
+func (e *e2eScreen) open() {
+	e.search.Focus()
+	e.filter()
+}
+
+// filter lists the entries whose id, name or tags contain the search text.
+func (e *e2eScreen) filter() {
+	query := strings.ToLower(strings.TrimSpace(e.search.Value()))
+	e.matches = nil
+	for _, entry := range catalog.All() {
+		haystack := strings.ToLower(entry.ID + " " + entry.Name + " " + strings.Join(entry.Tags, " "))
+		if query == "" || strings.Contains(haystack, query) {
+			e.matches = append(e.matches, entry)
+		}
+	}
+	if e.cursor >= len(e.matches) {
+		e.cursor = max(len(e.matches)-1, 0)
+	}
+}
+
+func (e e2eScreen) active() bool {
+	return e.phase != phaseIdle
+}
+
+// typing reports whether keys go to the search box.
+func (e e2eScreen) typing() bool {
+	return e.password != nil || !e.active() && e.confirm == nil
+}
+
+// lifecyclePlan is the plan the test runs, used to ask for sudo up front.
+func (e e2eScreen) lifecyclePlan() manager.Plan {
+	var plan manager.Plan
+	for _, action := range e.actions {
+		if action != manager.ActionTest {
+			plan.Steps = append(plan.Steps, manager.Step{Entry: e.entry, Action: action})
+		}
+	}
+	return plan
+}
+
+// launch starts the test, asking for the sudo password first when it needs
+// one.
+func (e e2eScreen) launch() e2eScreen {
+	if needsPassword(e.lifecyclePlan(), e.opts) {
+		e.password = newPasswordDialog("Testing " + e.entry.Name + " runs commands with sudo.")
+		return e
+	}
+	return e.start()
+}
+
+// onSudo continues a launch once the password was checked.
+func (e e2eScreen) onSudo(msg sudoMsg) e2eScreen {
+	if e.password == nil || !e.password.checking {
+		return e
+	}
+	dialog, ok := e.password.result(msg)
+	if !ok {
+		e.password = &dialog
+		return e
+	}
+	e.password = nil
+	return e.start()
+}
+
+// start queues the lifecycle test on the engine, which forwards every
+// TaskEvent as a job payload. It waits behind template runs and other
+// queued installs, so two runs never install at once.
+func (e e2eScreen) start() e2eScreen {
+	entry := e.entry
+	sudo := manager.PlanNeedsSudo(e.lifecyclePlan(), e.opts.Backend)
+	opts := e.opts
+	if e.typed {
+		opts.ConfirmSDKRemoval = true
+	}
+	m := manager.New(opts)
+	e.job = e.engine.Enqueue("e2e "+entry.ID, func(ctx context.Context, emit func(any)) error {
+		if sudo {
+			go manager.SudoKeepAlive(ctx)
+		}
+		_, err := m.Lifecycle(ctx, entry, false, func(ev manager.TaskEvent) { emit(ev) })
+		return err
+	})
+	e.phase = e.phase.next(eventTracked)
+	for _, task := range e.engine.Tasks() {
+		if task.ID == e.job && task.State != manager.TaskPending {
+			e.phase = e.phase.next(eventStarted)
+		}
+	}
+	e.err = nil
+	e.states = make([]stepState, len(e.actions))
+	e.events = nil
+	return e
+}
+
+// onJobEvent applies an engine event belonging to the current test; step
+// events carry the stage they belong to.
+func (e e2eScreen) onJobEvent(msg manager.JobEvent) e2eScreen {
+	if msg.Job != e.job || !e.phase.busy() {
+		return e
+	}
+	if msg.Done {
+		e.phase = e.phase.next(eventDone)
+		e.err = msg.Err
+		return e
+	}
+	if _, ok := msg.Payload.(manager.JobStarted); ok {
+		e.phase = e.phase.next(eventStarted)
+		return e
+	}
+	ev, ok := msg.Payload.(manager.TaskEvent)
+	if !ok {
+		return e
+	}
+	e.events = append(e.events, ev)
+	stage := ev.Step - 1
+	if stage < 0 || stage >= len(e.states) {
+		return e
+	}
+	switch ev.Kind {
+	case manager.EventStepStarted:
+		e.states[stage] = stepRunning
+	case manager.EventStepFinished:
+		e.states[stage] = stepFailed
+		if ev.Execution != nil && ev.Execution.Success {
+			e.states[stage] = stepDone
+		}
+	}
+	return e
+}
+
+func (e e2eScreen) update(msg tea.KeyMsg) (e2eScreen, tea.Cmd, bool) {
+	if e.confirm != nil {
+		dialog, done, accepted := e.confirm.update(msg)
+		e.confirm = &dialog
+		if done {
+			e.confirm = nil
+			if accepted {
+				e.typed = dialog.typed
+				return e.launch(), nil, false
+			}
+			e.search.Focus()
+		}
+		return e, nil, false
+	}
+	if e.password != nil {
+		dialog, submitted, cancelled := e.password.update(msg)
+		e.password = &dialog
+		switch {
+		case cancelled:
+			e.password = nil
+		case submitted != "":
+			e.engine.Submit("sudo", sudoJob(submitted))
+		}
+		return e, nil, false
+	}
+
+	if e.active() {
+		switch msg.String() {
+		case "c":
+			if e.phase.busy() {
+				e.engine.Cancel(e.job)
+			}
+		case "esc", "q":
+			if e.phase.busy() {
+				return e, nil, true
+			}
+			e.phase = e.phase.next(eventDismissed)
+			e.open()
+		}
+		return e, nil, false
+	}
+
+	switch msg.String() {
+	case "esc":
+		e.search.Blur()
+		return e, nil, true
+	case "up", "ctrl+p":
+		if e.cursor > 0 {
+			e.cursor--
+		}
+		return e, nil, false
+	case "down", "ctrl+n":
+		if e.cursor < len(e.matches)-1 {
+			e.cursor++
+		}
+		return e, nil, false
+	case "enter":
+		if len(e.matches) == 0 {
+			return e, nil, false
+		}
+		e.entry = e.matches[e.cursor]
+		e.search.Blur()
+		var commands []string
+		for _, step := range e.lifecyclePlan().Steps {
+			if recipes := manager.PreferredRecipes(e.entry, step.Action, e.opts.Backend); len(recipes) > 0 {
+				commands = append(commands, recipes[0].Command)
+			}
+		}
+		title := "Install, test, update and then uninstall " + e.entry.Name + "?"
+		if e.entry.Kind == catalog.KindSDK {
+			e.confirm = newTypedConfirmDialog(title, strings.Join(commands, "\n"), e.entry.ID, e.entry.Removes)
+		} else {
+			e.confirm = newConfirmDialog(title, strings.Join(commands, "\n"))
+		}
+		return e, nil, false
+	}
+	var cmd tea.Cmd
+	e.search, cmd = e.search.Update(msg)
+	e.filter()
+	return e, cmd, false
+}
+
+func (e e2eScreen) view(width, height int) string {
+	if e.confirm != nil {
+		return e.confirm.view(width, height)
+	}
+	if e.password != nil {
+		return e.password.view(width, height)
+	}
+	if !e.active() {
+		return e.pickerView(width, height)
+	}
+
+	title := titleStyle.Render("E2E test: " + e.entry.Name)
+	var state string
+	switch {
+	case e.phase == phaseQueued:
+		state = mutedStyle.Render("● Queued behind other tasks")
+	case e.phase.busy():
+		state = warningStyle.Render("● Running")
+	case errors.Is(e.err, context.Canceled):
+		state = warningStyle.Render("● Cancelled")
+	case e.err != nil:
+		state = errorStyle.Render("● Failed: " + e.err.Error())
+		if detail := errorDetail(e.err); detail != "" {
+			state += "\n" + mutedStyle.Render("  "+detail)
+		}
+	default:
+		state = readyStyle.Render("● Passed")
+	}
+
+	var rows []string
+	for i, action := range e.actions {
+		rows = append(rows, fmt.Sprintf("%-12s %s", action, renderStep(e.states[i])))
+	}
+	stages := boxStyle.Width(width - 4).Render(strings.Join(rows, "\n"))
+
+	events := e.events
+	if logHeight := max(height-len(rows)-16, 3); len(events) > logHeight {
+		events = events[len(events)-logHeight:]
+	}
+	var lines []string
+	for _, ev := range events {
+		lines = append(lines, mutedStyle.Render(ev.Time.Format("15:04:05"))+" "+ev.Message)
+	}
+	if len(lines) == 0 {
+		lines = append(lines, mutedStyle.Render("No events yet."))
+	}
+	log := boxStyle.Width(width - 4).Render(strings.Join(lines, "\n"))
+	return lipgloss.JoinVertical(lipgloss.Left, title, state, stages, log, e2eRunKeys.footer())
+}
+
+func (e e2eScreen) pickerView(width, height int) string {
+	visible := max(height-12, 5)
+	start := 0
+	if e.cursor >= visible {
+		start = e.cursor - visible + 1
+	}
+	end := min(start+visible, len(e.matches))
+
+	var rows []string
+	for i := start; i < end; i++ {
+		entry := e.matches[i]
+		line := fmt.Sprintf("%-26s %-10s %s", entry.Name, entry.Category, mutedStyle.Render(entry.ID))
+		if i == e.cursor {
+			rows = append(rows, selectedMenuItemStyle.Render("❯ "+line))
+		} else {
+			rows = append(rows, menuItemStyle.Render(line))
+		}
+	}
+	if len(rows) == 0 {
+		rows = append(rows, mutedStyle.Render("No entries match."))
+	}
+	return lipgloss.JoinVertical(lipgloss.Left,
+		titleStyle.Render("E2E Testing"),
+		e.search.View(),
+		boxStyle.Width(width-4).Render(strings.Join(rows, "\n")),
+		e2eKeys.footer(),
+	)
+}
@@ -0,0 +1,19 @@
+package tui
+
+import "github.com/hmziqrs/maziq/internal/errs"
+
+// errorDetail describes what err concerns, such as
+// "step · install git: brew install git", or "" when it carries no code
+// or context.
+func errorDetail(err error) string {
+	code, ctx := errs.CodeOf(err), errs.ContextOf(err)
+	switch {
+	case code == "" && ctx.IsZero():
+		return ""
+	case ctx.IsZero():
+		return string(code)
+	case code == "":
+		return ctx.String()
+	}
+	return string(code) + " · " + ctx.String()
+}
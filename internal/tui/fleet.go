@@ -0,0 +1,220 @@
+package tui
+
+import (
+	"context"
+	"fmt"
+	"strings"
+	"time"
+
+	tea "github.com/charmbracelet/bubbletea"
+	"github.com/charmbracelet/lipgloss"
+
+	"github.com/hmziqrs/maziq/internal/fleet"
+)
+
+// fleetMsg is a progress event from one host.
+type fleetMsg fleet.Event
+
+// fleetDoneMsg carries the results once every host finished.
+type fleetDoneMsg []fleet.Result
+
+// hostRow is what the dashboard knows about one host.
+type hostRow struct {
+	name    string
+	started time.Time
+	ended   time.Time
+	step    string
+	message string
+	steps   int
+	done    bool
+	err     error
+}
+
+// fleetModel is the dashboard `maziq fleet` shows while a template runs
+// across an inventory: one row per host, then the result matrix.
+type fleetModel struct {
+	width   int
+	height  int
+	title   string
+	rows    []hostRow
+	events  <-chan tea.Msg
+	results []fleet.Result
+	cancel  context.CancelFunc
+}
+
+// RunFleet shows the fleet dashboard while run works through hosts, and
+// returns run's results. Quitting early cancels the remaining hosts.
+func RunFleet(title string, hosts []fleet.Host, run func(ctx context.Context, emit func(fleet.Event)) []fleet.Result) ([]fleet.Result, error) {
+	ctx, cancel := context.WithCancel(context.Background())
+	defer cancel()
+	events := make(chan tea.Msg, 64)
+	done := make(chan []fleet.Result, 1)
+	// closed once the dashboard is gone, so the run stops delivering.
+	gone := make(chan struct{})
+	send := func(msg tea.Msg) {
+		select {
+		case events <- msg:
+		case <-gone:
+		}
+	}
+	go func() {
+		results := run(ctx, func(ev fleet.Event) { send(fleetMsg(ev)) })
+		done <- results
+		send(fleetDoneMsg(results))
+	}()
+
+	m := fleetModel{title: title, events: events, cancel: cancel}
+	for _, h := range hosts {
+		m.rows = append(m.rows, hostRow{name: h.Name, message: "waiting"})
+	}
+	_, err := tea.NewProgram(m, tea.WithAltScreen()).Run()
+	close(gone)
+	cancel()
+	return <-done, err
+}
+
+func (m fleetModel) Init() tea.Cmd {
+	return m.next()
+}
+
+// next waits for the next event from the run.
+func (m fleetModel) next() tea.Cmd {
+	return func() tea.Msg { return <-m.events }
+}
+
+func (m fleetModel) Update(msg tea.Msg) (tea.Model, tea.Cmd) {
+	switch msg := msg.(type) {
+	case tea.WindowSizeMsg:
+		m.width, m.height = msg.Width, msg.Height
+	case fleetMsg:
+		for i := range m.rows {
+			row := &m.rows[i]
+			if row.name != msg.Host {
+				continue
+			}
+			if row.started.IsZero() {
+				row.started = msg.Time
+			}
+			if msg.Step != "" && msg.Step != row.step {
+				row.step = msg.Step
+				row.steps++
+			}
+			row.message = msg.Message
+			row.done, row.err = msg.Done, msg.Err
+			if msg.Done {
+				row.ended = msg.Time
+			}
+		}
+		return m, m.next()
+	case fleetDoneMsg:
+		m.results = msg
+	case tea.KeyMsg:
+		switch msg.String() {
+		case "ctrl+c":
+			m.cancel()
+			return m, tea.Quit
+		case "q", "esc":
+			// While hosts still run, the first q cancels them; the run
+			// then finishes and the matrix shows what happened.
+			if m.results == nil {
+				m.cancel()
+				return m, nil
+			}
+			return m, tea.Quit
+		}
+	}
+	return m, nil
+}
+
+func (m fleetModel) View() string {
+	width := max(m.width, 60)
+	finished := 0
+	var rows []string
+	rows = append(rows, mutedStyle.Render(fmt.Sprintf("%-20s %-10s %-8s %-22s %s", "HOST", "STATE", "ELAPSED", "STEP", "LAST")))
+	for _, r := range m.rows {
+		var state string
+		switch {
+		case r.done && r.err != nil:
+			state = errorStyle.Render(fmt.Sprintf("%-10s", "failed"))
+			finished++
+		case r.done:
+			state = readyStyle.Render(fmt.Sprintf("%-10s", "done"))
+			finished++
+		case r.started.IsZero():
+			state = mutedStyle.Render(fmt.Sprintf("%-10s", "waiting"))
+		default:
+			state = warningStyle.Render(fmt.Sprintf("%-10s", "running"))
+		}
+		elapsed := "-"
+		switch {
+		case r.done && !r.started.IsZero():
+			elapsed = r.ended.Sub(r.started).Round(time.Second).String()
+		case !r.started.IsZero():
+			elapsed = time.Since(r.started).Round(time.Second).String()
+		}
+		step := r.step
+		if r.steps > 0 {
+			step = fmt.Sprintf("%s (#%d)", r.step, r.steps)
+		}
+		message := r.message
+		if limit := width - 68; limit > 10 && len(message) > limit {
+			message = message[:limit-1] + "…"
+		}
+		rows = append(rows, fmt.Sprintf("%-20s %s %-8s %-22s %s", r.name, state, elapsed, step, message))
+	}
+
+	sections := []string{
+		titleStyle.Render(m.title),
+		mutedStyle.Render(fmt.Sprintf("%d of %d hosts finished", finished, len(m.rows))),
+		boxStyle.Width(width - 4).Render(strings.Join(rows, "\n")),
+	}
+	if m.results != nil {
+		sections = append(sections, boxStyle.Width(width-4).Render(renderMatrix(m.results)))
+	}
+	sections = append(sections, fleetKeys.footer())
+	return lipgloss.JoinVertical(lipgloss.Left, sections...)
+}
+
+// renderMatrix shows every host's result per step, hosts as rows.
+func renderMatrix(results []fleet.Result) string {
+	ids := fleet.Matrix(results)
+	if len(ids) == 0 {
+		return mutedStyle.Render("No host reported a step summary.")
+	}
+	header := fmt.Sprintf("%-20s", "HOST")
+	for _, id := range ids {
+		header += " " + fmt.Sprintf("%-*s", max(len(id), 4), id)
+	}
+	lines := []string{mutedStyle.Render(header)}
+	for _, r := range results {
+		line := fmt.Sprintf("%-20s", r.Host)
+		for _, id := range ids {
+			cell := r.Cell(id)
+			text := fmt.Sprintf("%-*s", max(len(id), 4), matrixMark(cell))
+			switch cell {
+			case "succeeded":
+				text = readyStyle.Render(text)
+			case "failed":
+				text = errorStyle.Render(text)
+			default:
+				text = mutedStyle.Render(text)
+			}
+			line += " " + text
+		}
+		lines = append(lines, line)
+	}
+	return strings.Join(lines, "\n")
+}
+
+// matrixMark abbreviates a step result for the matrix.
+func matrixMark(result string) string {
+	switch result {
+	case "succeeded":
+		return "✓"
+	case "failed":
+		return "✗"
+	case "skipped":
+		return "skip"
+	}
+	return "-"
+}
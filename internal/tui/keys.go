@@ -0,0 +1,161 @@
+package tui
+
+import (
+	"fmt"
+	"strings"
+
+	"github.com/charmbracelet/lipgloss"
+)
+
+// binding documents one key on a screen.
+type binding struct {
+	keys string
+	help string
+}
+
+// keySection is the set of bindings active on one screen or dialog.
+type keySection struct {
+	title    string
+	bindings []binding
+}
+
+// The keymap is the single source for both the footers and the help
+// overlay; update it whenever a screen gains or loses a key.
+var (
+	globalKeys = keySection{"Everywhere", []binding{
+		{"?", "Toggle this help"},
+		{"ctrl+c", "Quit immediately"},
+	}}
+	wizardKeys = keySection{"Setup wizard", []binding{
+		{"↑/↓", "Navigate"},
+		{"Enter", "Choose"},
+		{"Esc", "Skip"},
+	}}
+	menuKeys = keySection{"Main menu", []binding{
+		{"↑/↓ or j/k", "Navigate"},
+		{"Enter", "Select"},
+		{"q", "Quit"},
+	}}
+	catalogKeys = keySection{"Software catalog", []binding{
+		{"↑/↓", "Navigate"},
+		{"r", "Refresh"},
+		{"s/c", "Refresh selected entry or its category"},
+		{"D", "Deep probes"},
+		{"i", "Details"},
+		{"t", "Filter by tag"},
+		{"m/o/e", "Only missing, outdated or errors"},
+		{"H", "Open homepage"},
+		{"l", "Launch app"},
+		{"w", "Show and copy binary path"},
+		{"y", "Copy install/update command"},
+		{"*", "Toggle favorite"},
+		{"x", "Uninstall"},
+		{"Esc", "Close details, then back"},
+	}}
+	templateKeys = keySection{"Templates", []binding{
+		{"↑/↓", "Navigate"},
+		{"Enter", "Review plan"},
+		{"Esc", "Back"},
+	}}
+	reviewKeys = keySection{"Plan review", []binding{
+		{"↑/↓", "Navigate"},
+		{"K/J", "Move step up/down"},
+		{"L", "Toggle run last"},
+		{"Enter", "Run plan"},
+		{"Esc", "Back"},
+	}}
+	runKeys = keySection{"Template run", []binding{
+		{"n", "Queue another template"},
+		{"f", "Filter by entry"},
+		{"l", "Toggle full log"},
+		{"c", "Cancel run"},
+		{"Esc", "Back"},
+	}}
+	e2eKeys = keySection{"E2E picker", []binding{
+		{"type", "Search the catalog"},
+		{"↑/↓", "Navigate"},
+		{"Enter", "Run lifecycle test"},
+		{"Esc", "Back"},
+	}}
+	e2eRunKeys = keySection{"E2E run", []binding{
+		{"c", "Cancel run"},
+		{"Esc", "Back"},
+	}}
+	recoveryKeys = keySection{"Failed step", []binding{
+		{"←/→", "Choose"},
+		{"r/s/a", "Retry, skip or abort"},
+		{"l", "Open full log"},
+		{"Enter", "Confirm"},
+	}}
+	queueKeys = keySection{"Task queue", []binding{
+		{"↑/↓", "Navigate"},
+		{"Enter", "Show log"},
+		{"x", "Cancel task"},
+		{"Esc", "Back"},
+	}}
+	queueLogKeys = keySection{"Task log", []binding{
+		{"↑/↓", "Scroll"},
+		{"g/G", "Oldest/newest"},
+		{"Esc", "Back to the queue"},
+	}}
+	statsKeys = keySection{"Statistics", []binding{
+		{"r", "Reload history"},
+		{"Esc", "Back"},
+	}}
+	fleetKeys = keySection{"Fleet run", []binding{
+		{"q", "Cancel the remaining hosts, then quit"},
+		{"ctrl+c", "Quit immediately"},
+	}}
+	confirmKeys = keySection{"Confirmation dialog", []binding{
+		{"y/n", "Answer"},
+		{"←/→", "Switch"},
+		{"Enter", "Confirm"},
+	}}
+	inputKeys = keySection{"Text and password prompts", []binding{
+		{"Enter", "Submit"},
+		{"Esc", "Cancel"},
+	}}
+	selectKeys = keySection{"Choice prompts", []binding{
+		{"↑/↓", "Navigate"},
+		{"Enter", "Choose"},
+		{"Esc", "Cancel"},
+	}}
+	multiSelectKeys = keySection{"Multiple-choice prompts", []binding{
+		{"↑/↓", "Navigate"},
+		{"Space", "Toggle"},
+		{"Enter", "Done"},
+		{"Esc", "Cancel"},
+	}}
+)
+
+// keymap lists every section in the order the help overlay shows them.
+var keymap = []keySection{globalKeys, wizardKeys, menuKeys, catalogKeys, templateKeys, reviewKeys, runKeys, e2eKeys, e2eRunKeys, recoveryKeys, queueKeys, queueLogKeys, statsKeys, fleetKeys, confirmKeys, inputKeys, selectKeys, multiSelectKeys}
+
+// footer renders the section as a one-line hint, pointing at the overlay
+// for everything else.
+func (s keySection) footer() string {
+	parts := make([]string, 0, len(s.bindings)+1)
+	for _, b := range s.bindings {
+		parts = append(parts, b.keys+": "+b.help)
+	}
+	parts = append(parts, "?: Help")
+	return helpStyle.Render(strings.Join(parts, " • "))
+}
+
+// helpView renders the full-screen keybinding cheatsheet.
+func helpView(width, height int) string {
+	var blocks []string
+	for _, section := range keymap {
+		lines := []string{selectedMenuItemStyle.Render(section.title)}
+		for _, b := range section.bindings {
+			lines = append(lines, fmt.Sprintf("  %-12s %s", b.keys, b.help))
+		}
+		blocks = append(blocks, strings.Join(lines, "\n"))
+	}
+	body := lipgloss.JoinVertical(lipgloss.Left,
+		titleStyle.Render("Keyboard shortcuts"),
+		strings.Join(blocks, "\n\n"),
+		helpStyle.Render("Press ? or Esc to close"),
+	)
+	return lipgloss.Place(width, height, lipgloss.Center, lipgloss.Center, boxStyle.Render(body))
+}
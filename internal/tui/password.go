@@ -0,0 +1,97 @@
+package tui
+
+import (
+	"context"
+	"errors"
+
+	tea "github.com/charmbracelet/bubbletea"
+	"github.com/charmbracelet/lipgloss"
+
+	"github.com/hmziqrs/maziq/internal/catalog"
+	"github.com/hmziqrs/maziq/internal/manager"
+	"github.com/hmziqrs/maziq/internal/options"
+	"github.com/hmziqrs/maziq/internal/prompt"
+)
+
+// sudoMsg reports whether the password entered in a passwordDialog was
+// accepted by sudo.
+type sudoMsg struct{ err error }
+
+// sudoJob caches sudo credentials from password on the engine.
+func sudoJob(password string) manager.Job {
+	return func(ctx context.Context, emit func(any)) error {
+		err := manager.SudoAuthenticate(ctx, password, "")
+		emit(sudoMsg{err: err})
+		return err
+	}
+}
+
+// needsPassword reports whether plan runs sudo without an askpass helper or
+// cached credentials under opts, in which case the password is asked for
+// up front.
+func needsPassword(plan manager.Plan, opts options.Run) bool {
+	return manager.PlanNeedsSudo(plan, opts.Backend) &&
+		opts.Askpass == "" &&
+		!manager.SudoCached(context.Background())
+}
+
+// passwordDialog is a modal masked input for the sudo password. The
+// password is handed to sudo once and never stored.
+type passwordDialog struct {
+	field    promptField
+	reason   string
+	checking bool
+	// entry is the catalog entry the password is asked for, if any.
+	entry    catalog.Entry
+}
+
+func newPasswordDialog(reason string) *passwordDialog {
+	return &passwordDialog{field: newPromptField(prompt.Prompt{Kind: prompt.KindPassword}), reason: reason}
+}
+
+// update handles a key while the dialog is open. submitted carries the
+// password once the user presses enter; cancelled is set on esc.
+func (d passwordDialog) update(msg tea.KeyMsg) (dialog passwordDialog, submitted string, cancelled bool) {
+	if d.checking {
+		return d, "", false
+	}
+	field, answer, done, err := d.field.update(msg)
+	d.field = field
+	switch {
+	case errors.Is(err, prompt.ErrCancelled):
+		return d, "", true
+	case done:
+		d.checking = true
+		d.field.fail("")
+		return d, answer.Text, false
+	}
+	return d, "", false
+}
+
+// result applies sudo's verdict; ok is set when the run may go ahead.
+func (d passwordDialog) result(msg sudoMsg) (dialog passwordDialog, ok bool) {
+	d.checking = false
+	if msg.err != nil {
+		d.field.fail("Incorrect password, try again.")
+		return d, false
+	}
+	return d, true
+}
+
+func (d passwordDialog) view(width, height int) string {
+	status := ""
+	if d.checking {
+		status = warningStyle.Render("Checking…")
+	}
+	body := lipgloss.JoinVertical(lipgloss.Left,
+		warningStyle.Render("Administrator password required"),
+		"",
+		d.reason,
+		"",
+		d.field.view(),
+		status,
+		d.field.footer(),
+	)
+	box := dialogStyle.Width(min(width-8, 72)).Render(body)
+	return lipgloss.Place(width, height, lipgloss.Center, lipgloss.Center, box)
+}
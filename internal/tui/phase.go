@@ -0,0 +1,131 @@
+package tui
+
+// runPhase is where the run a screen follows is in its lifecycle. It
+// replaces separate running/queued/finished/choosing flags, which could
+// disagree.
+type runPhase int
+
+const (
+	// phaseIdle shows the picker; no run is on screen.
+	phaseIdle runPhase = iota
+	// phaseQueued waits in the engine's queue behind other tasks.
+	phaseQueued
+	phaseRunning
+	// phaseFinished keeps the outcome on screen until it is dismissed.
+	phaseFinished
+	// The choosing phases show the picker in front of a run, to queue
+	// another after it; the run goes on behind it.
+	phaseQueuedChoosing
+	phaseRunningChoosing
+	phaseFinishedChoosing
+)
+
+// runEvent moves a runPhase along.
+type runEvent int
+
+const (
+	// eventTracked puts a newly enqueued run on screen.
+	eventTracked runEvent = iota
+	// eventStarted is the engine picking the run up.
+	eventStarted
+	// eventDone is the run ending, however it ended.
+	eventDone
+	// eventDismissed returns from the outcome to the picker.
+	eventDismissed
+	// eventChoose opens the picker in front of the run.
+	eventChoose
+	// eventChosen closes it again, whether a template was picked or not.
+	eventChosen
+)
+
+// transitions lists the legal moves; anything else leaves the phase as it
+// is, so a late or duplicate engine event cannot put a screen in a state
+// it has no view for.
+var transitions = map[runPhase]map[runEvent]runPhase{
+	phaseIdle:             {eventTracked: phaseQueued},
+	phaseQueued:           {eventStarted: phaseRunning, eventDone: phaseFinished, eventChoose: phaseQueuedChoosing},
+	phaseRunning:          {eventDone: phaseFinished, eventChoose: phaseRunningChoosing},
+	phaseFinished:         {eventTracked: phaseQueued, eventDismissed: phaseIdle, eventChoose: phaseFinishedChoosing},
+	phaseQueuedChoosing:   {eventStarted: phaseRunningChoosing, eventDone: phaseFinishedChoosing, eventChosen: phaseQueued},
+	phaseRunningChoosing:  {eventDone: phaseFinishedChoosing, eventChosen: phaseRunning},
+	phaseFinishedChoosing: {eventTracked: phaseQueuedChoosing, eventChosen: phaseFinished},
+}
+
+// next is the phase after ev.
+func (p runPhase) next(ev runEvent) runPhase {
+	if to, ok := transitions[p][ev]; ok {
+		return to
+	}
+	return p
+}
+
+// busy reports that the run has not ended yet.
+func (p runPhase) busy() bool {
+	switch p {
+	case phaseQueued, phaseRunning, phaseQueuedChoosing, phaseRunningChoosing:
+		return true
+	}
+	return false
+}
+
+// choosing reports that the picker is shown in front of the run.
+func (p runPhase) choosing() bool {
+	return p == phaseQueuedChoosing || p == phaseRunningChoosing || p == phaseFinishedChoosing
+}
+
+// probePhase is which status probes the catalog is waiting for. A quick
+// refresh may be asked for while deep probes still run, so both can be
+// outstanding at once.
+type probePhase int
+
+const (
+	probesIdle probePhase = iota
+	// probesQuick waits for a quick pass; the deep pass follows it.
+	probesQuick
+	// probesDeep runs the slow deep probes in the background.
+	probesDeep
+	probesBoth
+)
+
+// probeEvent moves a probePhase along.
+type probeEvent int
+
+const (
+	quickRequested probeEvent = iota
+	deepRequested
+	quickFinished
+	deepFinished
+)
+
+// probeTransitions lists the legal moves. A refresh asked for while the
+// same pass runs, or a result nobody waits for, leaves the phase as it is.
+var probeTransitions = map[probePhase]map[probeEvent]probePhase{
+	probesIdle:  {quickRequested: probesQuick, deepRequested: probesDeep},
+	probesQuick: {deepRequested: probesBoth, quickFinished: probesIdle},
+	probesDeep:  {quickRequested: probesBoth, deepFinished: probesIdle},
+	probesBoth:  {quickFinished: probesDeep, deepFinished: probesQuick},
+}
+
+// next is the phase after ev.
+func (p probePhase) next(ev probeEvent) probePhase {
+	if to, ok := probeTransitions[p][ev]; ok {
+		return to
+	}
+	return p
+}
+
+// quick reports that a quick pass is outstanding.
+func (p probePhase) quick() bool {
+	return p == probesQuick || p == probesBoth
+}
+
+// deep reports that deep probes are running.
+func (p probePhase) deep() bool {
+	return p == probesDeep || p == probesBoth
+}
+
+// screenMoves lists the screens each screen may open. push ignores any
+// other move, so no screen is stacked where it was never reachable from.
+var screenMoves = map[screen][]screen{
+	screenMenu: {screenCatalog, screenTasks, screenQueue, screenE2E, screenStats, screenWizard},
+}
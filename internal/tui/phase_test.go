@@ -0,0 +1,164 @@
+package tui
+
+import (
+	"context"
+	"slices"
+	"testing"
+
+	tea "github.com/charmbracelet/bubbletea"
+
+	"github.com/hmziqrs/maziq/internal/config"
+	"github.com/hmziqrs/maziq/internal/manager"
+	"github.com/hmziqrs/maziq/internal/testenv"
+)
+
+// newTestModel returns the TUI past its first-run wizard, in a sandbox.
+func newTestModel(t *testing.T) (model, *manager.Engine) {
+	t.Helper()
+	testenv.New(t)
+	engine := manager.NewEngine()
+	t.Cleanup(engine.Shutdown)
+	cfg := config.Config{Role: "developer"}
+	return initialModel(engine, cfg, cfg.RunOptions()), engine
+}
+
+// key is the message Bubbletea sends for a key press.
+func key(name string) tea.KeyMsg {
+	switch name {
+	case "enter":
+		return tea.KeyMsg{Type: tea.KeyEnter}
+	case "esc":
+		return tea.KeyMsg{Type: tea.KeyEsc}
+	case "down":
+		return tea.KeyMsg{Type: tea.KeyDown}
+	}
+	return tea.KeyMsg{Type: tea.KeyRunes, Runes: []rune(name)}
+}
+
+// send feeds msgs to m's Update in order.
+func send(m model, msgs ...tea.Msg) model {
+	for _, msg := range msgs {
+		next, _ := m.Update(msg)
+		m = next.(model)
+	}
+	return m
+}
+
+// submitted counts the engine's tasks named name.
+func submitted(engine *manager.Engine, name string) int {
+	n := 0
+	for _, task := range engine.Tasks() {
+		if task.Name == name {
+			n++
+		}
+	}
+	return n
+}
+
+func TestScreenMoves(t *testing.T) {
+	m, engine := newTestModel(t)
+	if got := m.current(); got != screenMenu {
+		t.Fatalf("starts on screen %d, want the menu", got)
+	}
+	m = send(m, key("enter"))
+	if got := m.current(); got != screenCatalog {
+		t.Errorf("enter on the first item opened screen %d, want the catalog", got)
+	}
+	if got := m.push(screenTasks).current(); got != screenCatalog {
+		t.Errorf("the catalog opened screen %d on top of itself", got)
+	}
+	m = send(m, key("esc"), key("down"), key("enter"))
+	if got := m.current(); got != screenTasks {
+		t.Errorf("esc, down, enter opened screen %d, want the templates", got)
+	}
+	m = send(m, key("esc"))
+	if got := m.current(); got != screenMenu || len(m.stack) != 0 {
+		t.Errorf("esc left screen %d with stack %v, want the menu", got, m.stack)
+	}
+
+	first := initialModel(engine, config.Config{}, config.Config{}.RunOptions())
+	if got := first.current(); got != screenWizard {
+		t.Errorf("without a role the TUI starts on screen %d, want the wizard", got)
+	}
+}
+
+// The catalog runs a quick refresh, then deep probes, never two of the
+// same pass at once.
+func TestCatalogProbePhases(t *testing.T) {
+	m, engine := newTestModel(t)
+	quickDone := engineMsg{Payload: statusMsg{mode: manager.ProbeModeQuick}}
+	deepDone := engineMsg{Payload: statusMsg{mode: manager.ProbeModeDeep}}
+
+	steps := []struct {
+		name  string
+		msg   tea.Msg
+		want  probePhase
+		probe int
+	}{
+		{"open", key("enter"), probesQuick, 1},
+		{"refresh while refreshing", key("r"), probesQuick, 1},
+		{"deep while refreshing", key("D"), probesBoth, 2},
+		{"quick result", quickDone, probesDeep, 2},
+		{"late quick result", quickDone, probesDeep, 2},
+		{"refresh while deep", key("r"), probesBoth, 3},
+		{"deep result", deepDone, probesQuick, 3},
+		{"quick result starts deep", quickDone, probesDeep, 4},
+		{"deep result", deepDone, probesIdle, 4},
+		{"stray deep result", deepDone, probesIdle, 4},
+	}
+	for _, step := range steps {
+		m = send(m, step.msg)
+		if m.catalog.probing != step.want {
+			t.Errorf("%s: probing = %d, want %d", step.name, m.catalog.probing, step.want)
+		}
+		if got := submitted(engine, "probe"); got != step.probe {
+			t.Errorf("%s: %d probe jobs submitted, want %d", step.name, got, step.probe)
+		}
+	}
+}
+
+// The picker opened in front of a run follows the run behind it, and
+// closing it shows the run in whatever phase it reached.
+func TestTasksRunPhases(t *testing.T) {
+	m, engine := newTestModel(t)
+	block := func(ctx context.Context, emit func(any)) error {
+		<-ctx.Done()
+		return ctx.Err()
+	}
+	engine.Enqueue("ahead", block)
+	job := engine.Enqueue("template dev", block)
+
+	m = send(m, key("down"), key("enter"))
+	m.tasks = m.tasks.track(queuedRun{job: job, title: "dev"})
+	started := engineMsg{Job: job, Payload: manager.JobStarted{}}
+	done := engineMsg{Job: job, Done: true, Err: context.Canceled}
+
+	steps := []struct {
+		name   string
+		msg    tea.Msg
+		want   runPhase
+		screen screen
+	}{
+		{"queue another", key("n"), phaseQueuedChoosing, screenTasks},
+		{"run starts behind the picker", started, phaseRunningChoosing, screenTasks},
+		{"close the picker", key("esc"), phaseRunning, screenTasks},
+		{"leave a busy run", key("esc"), phaseRunning, screenMenu},
+		{"come back", key("enter"), phaseRunning, screenTasks},
+		{"queue another again", key("n"), phaseRunningChoosing, screenTasks},
+		{"run ends behind the picker", done, phaseFinishedChoosing, screenTasks},
+		{"close the picker again", key("esc"), phaseFinished, screenTasks},
+		{"late start", started, phaseFinished, screenTasks},
+		{"dismiss the outcome", key("esc"), phaseIdle, screenTasks},
+		{"n without a run", key("n"), phaseIdle, screenTasks},
+		{"leave", key("esc"), phaseIdle, screenMenu},
+	}
+	for _, step := range steps {
+		m = send(m, step.msg)
+		if m.tasks.phase != step.want || m.current() != step.screen {
+			t.Errorf("%s: phase %d on screen %d, want phase %d on screen %d", step.name, m.tasks.phase, m.current(), step.want, step.screen)
+		}
+		if choosing := slices.Contains(m.tasks.trail(), "Queue another"); choosing != step.want.choosing() {
+			t.Errorf("%s: breadcrumb %q, want the picker shown = %v", step.name, m.tasks.trail(), step.want.choosing())
+		}
+	}
+}
@@ -0,0 +1,162 @@
+package tui
+
+import (
+	"slices"
+
+	"github.com/charmbracelet/bubbles/textinput"
+	tea "github.com/charmbracelet/bubbletea"
+	"github.com/charmbracelet/lipgloss"
+
+	"github.com/hmziqrs/maziq/internal/prompt"
+)
+
+// promptField renders a prompt.Prompt inside a dialog and answers it from
+// keys, so dialogs share one set of input handling instead of each
+// matching keys of its own.
+type promptField struct {
+	prompt.Prompt
+	input    textinput.Model
+	cursor   int
+	selected []bool
+	yes      bool
+	err      string
+}
+
+func newPromptField(p prompt.Prompt) promptField {
+	f := promptField{Prompt: p, selected: make([]bool, len(p.Options)), yes: p.Default == "y"}
+	switch p.Kind {
+	case prompt.KindText, prompt.KindPassword:
+		f.input = textinput.New()
+		f.input.Placeholder = p.Default
+		if p.Kind == prompt.KindPassword {
+			f.input.Placeholder = "password"
+			f.input.EchoMode = textinput.EchoPassword
+			f.input.EchoCharacter = '•'
+		}
+		f.input.Focus()
+	case prompt.KindSelect, prompt.KindMultiSelect:
+		f.cursor = max(slices.Index(p.Options, p.Default), 0)
+	}
+	return f
+}
+
+// update handles a key. done reports that the field was answered, or
+// cancelled when err is prompt.ErrCancelled.
+func (f promptField) update(msg tea.KeyMsg) (field promptField, answer prompt.Answer, done bool, err error) {
+	if msg.String() == "esc" {
+		return f, prompt.Answer{}, true, prompt.ErrCancelled
+	}
+	switch f.Kind {
+	case prompt.KindConfirm:
+		switch msg.String() {
+		case "left", "right", "h", "l", "tab":
+			f.yes = !f.yes
+		case "y":
+			return f, prompt.Answer{Yes: true}, true, nil
+		case "n", "q":
+			return f, prompt.Answer{}, true, nil
+		case "enter":
+			return f, prompt.Answer{Yes: f.yes}, true, nil
+		}
+	case prompt.KindSelect, prompt.KindMultiSelect:
+		switch msg.String() {
+		case "up", "k":
+			f.cursor = max(f.cursor-1, 0)
+		case "down", "j":
+			f.cursor = min(f.cursor+1, len(f.Options)-1)
+		case " ":
+			if f.Kind == prompt.KindMultiSelect && len(f.Options) > 0 {
+				f.selected[f.cursor] = !f.selected[f.cursor]
+			}
+		case "enter":
+			if len(f.Options) == 0 {
+				return f, prompt.Answer{}, true, prompt.ErrCancelled
+			}
+			if f.Kind == prompt.KindSelect {
+				return f, prompt.Answer{Selected: []int{f.cursor}}, true, nil
+			}
+			var selected []int
+			for i, on := range f.selected {
+				if on {
+					selected = append(selected, i)
+				}
+			}
+			return f, prompt.Answer{Selected: selected}, true, nil
+		}
+	default:
+		if msg.String() == "enter" {
+			value := f.input.Value()
+			if value == "" {
+				value = f.Default
+			}
+			if value == "" {
+				return f, prompt.Answer{}, false, nil
+			}
+			if f.Validate != nil {
+				if err := f.Validate(value); err != nil {
+					f.err = err.Error()
+					return f, prompt.Answer{}, false, nil
+				}
+			}
+			f.input.SetValue("")
+			f.err = ""
+			return f, prompt.Answer{Text: value}, true, nil
+		}
+		f.input, _ = f.input.Update(msg)
+	}
+	return f, prompt.Answer{}, false, nil
+}
+
+// fail shows err under the field, e.g. when the caller rejects an answer.
+func (f *promptField) fail(err string) { f.err = err }
+
+func (f promptField) view() string {
+	var body string
+	switch f.Kind {
+	case prompt.KindConfirm:
+		yes, no := menuItemStyle.Render("Yes"), selectedMenuItemStyle.Render("❯ No")
+		if f.yes {
+			yes, no = selectedMenuItemStyle.Render("❯ Yes"), menuItemStyle.Render("No")
+		}
+		body = yes + "   " + no
+	case prompt.KindSelect, prompt.KindMultiSelect:
+		lines := make([]string, len(f.Options))
+		for i, option := range f.Options {
+			if f.Kind == prompt.KindMultiSelect {
+				box := "[ ] "
+				if f.selected[i] {
+					box = "[x] "
+				}
+				option = box + option
+			}
+			if i == f.cursor {
+				lines[i] = selectedMenuItemStyle.Render("❯ " + option)
+			} else {
+				lines[i] = menuItemStyle.Render(option)
+			}
+		}
+		body = lipgloss.JoinVertical(lipgloss.Left, lines...)
+	default:
+		body = f.input.View()
+	}
+	if f.Label != "" {
+		body = lipgloss.JoinVertical(lipgloss.Left, f.Label, body)
+	}
+	if f.err != "" {
+		body = lipgloss.JoinVertical(lipgloss.Left, body, errorStyle.Render(f.err))
+	}
+	return body
+}
+
+// footer is the key hint for the field's kind.
+func (f promptField) footer() string {
+	switch f.Kind {
+	case prompt.KindConfirm:
+		return confirmKeys.footer()
+	case prompt.KindSelect:
+		return selectKeys.footer()
+	case prompt.KindMultiSelect:
+		return multiSelectKeys.footer()
+	}
+	return inputKeys.footer()
+}
@@ -0,0 +1,224 @@
+package tui
+
+import (
+	"fmt"
+	"strings"
+	"time"
+
+	tea "github.com/charmbracelet/bubbletea"
+	"github.com/charmbracelet/lipgloss"
+
+	"github.com/hmziqrs/maziq/internal/manager"
+)
+
+// maxLogLines bounds the log kept per task.
+const maxLogLines = 2000
+
+// queueScreen lists every task the engine knows about, pending, running
+// and finished, and keeps each one's log for inspection.
+type queueScreen struct {
+	engine  *manager.Engine
+	tasks   []manager.Task
+	cursor  int
+	logs    map[manager.JobID][]string
+	// viewing is the task whose full log is shown; 0 shows the list.
+	viewing manager.JobID
+	offset  int
+}
+
+func newQueueScreen(engine *manager.Engine) queueScreen {
+	return queueScreen{engine: engine, logs: map[manager.JobID][]string{}}
+}
+
+// open refreshes the task list, dropping the logs of tasks the engine
+// no longer lists.
+func (q *queueScreen) open() {
+	q.tasks = q.engine.Tasks()
+	q.cursor = min(q.cursor, max(len(q.tasks)-1, 0))
+	known := make(map[manager.JobID]bool, len(q.tasks))
+	for _, t := range q.tasks {
+		known[t.ID] = true
+	}
+	for id := range q.logs {
+		if !known[id] {
+			delete(q.logs, id)
+		}
+	}
+}
+
+// onJobEvent appends ev to its task's log and refreshes the list.
+func (q queueScreen) onJobEvent(ev manager.JobEvent) queueScreen {
+	var line string
+	switch payload := ev.Payload.(type) {
+	case manager.TaskEvent:
+		line = payload.Time.Format("15:04:05") + " " + payload.Message
+		if exec := payload.Execution; exec != nil && !exec.Success {
+			for _, out := range []string{exec.Stdout, exec.Stderr} {
+				if out = strings.TrimRight(out, "\n"); out != "" {
+					line += "\n" + out
+				}
+			}
+		}
+	case manager.JobStarted:
+		line = time.Now().Format("15:04:05") + " started"
+	case statusMsg:
+		line = time.Now().Format("15:04:05") + fmt.Sprintf(" probed %d entries", len(payload.statuses))
+	case actionMsg:
+		line = time.Now().Format("15:04:05") + " $ " + payload.event.Command
+		if payload.err != nil {
+			line += "\n" + payload.err.Error()
+		}
+	}
+	if ev.Done {
+		line = time.Now().Format("15:04:05") + " finished"
+		if ev.Err != nil {
+			line += ": " + ev.Err.Error()
+		}
+	}
+	if line != "" {
+		log := append(q.logs[ev.Job], strings.Split(line, "\n")...)
+		if len(log) > maxLogLines {
+			log = log[len(log)-maxLogLines:]
+		}
+		q.logs[ev.Job] = log
+	}
+	q.open()
+	return q
+}
+
+// counts returns how many tasks are running and how many wait in the queue.
+func (q queueScreen) counts() (running, pending int) {
+	for _, t := range q.engine.Tasks() {
+		switch t.State {
+		case manager.TaskRunning:
+			running++
+		case manager.TaskPending:
+			pending++
+		}
+	}
+	return running, pending
+}
+
+func (q queueScreen) update(msg tea.KeyMsg) (queueScreen, tea.Cmd, bool) {
+	key := msg.String()
+	if q.viewing != 0 {
+		switch key {
+		case "esc", "q", "enter":
+			q.viewing = 0
+		case "up", "k":
+			q.offset = min(q.offset+1, max(len(q.logs[q.viewing])-1, 0))
+		case "down", "j":
+			q.offset = max(q.offset-1, 0)
+		case "g":
+			q.offset = max(len(q.logs[q.viewing])-1, 0)
+		case "G":
+			q.offset = 0
+		}
+		return q, nil, false
+	}
+	switch key {
+	case "esc", "q":
+		return q, nil, true
+	case "up", "k":
+		if q.cursor > 0 {
+			q.cursor--
+		}
+	case "down", "j":
+		if q.cursor < len(q.tasks)-1 {
+			q.cursor++
+		}
+	case "enter":
+		if q.cursor < len(q.tasks) {
+			q.viewing = q.tasks[q.cursor].ID
+			q.offset = 0
+		}
+	case "x":
+		if q.cursor < len(q.tasks) {
+			if t := q.tasks[q.cursor]; t.State <= manager.TaskRunning {
+				q.engine.Cancel(t.ID)
+			}
+		}
+	}
+	q.open()
+	return q, nil, false
+}
+
+func renderTaskState(s manager.TaskState) string {
+	text := fmt.Sprintf("%-10s", s)
+	switch s {
+	case manager.TaskRunning:
+		return warningStyle.Render(text)
+	case manager.TaskDone:
+		return readyStyle.Render(text)
+	case manager.TaskFailed:
+		return errorStyle.Render(text)
+	}
+	return mutedStyle.Render(text)
+}
+
+func (q queueScreen) view(width, height int) string {
+	if q.viewing != 0 {
+		return q.logView(width, height)
+	}
+	var rows []string
+	rows = append(rows, mutedStyle.Render(fmt.Sprintf("  %-5s %-32s %-10s %s", "#", "TASK", "STATE", "TIME")))
+	visible := max(height-10, 5)
+	start := max(q.cursor-visible+1, 0)
+	for i := start; i < len(q.tasks) && i < start+visible; i++ {
+		t := q.tasks[i]
+		var when string
+		switch t.State {
+		case manager.TaskPending:
+			when = "queued " + time.Since(t.Created).Round(time.Second).String() + " ago"
+		case manager.TaskRunning:
+			when = "for " + time.Since(t.Started).Round(time.Second).String()
+		default:
+			when = t.Ended.Sub(t.Started).Round(time.Second).String()
+			if t.Started.IsZero() {
+				when = "never started"
+			}
+		}
+		name := t.Name
+		if len(name) > 32 {
+			name = name[:31] + "…"
+		}
+		row := fmt.Sprintf("%-5d %-32s %s %s", t.ID, name, renderTaskState(t.State), mutedStyle.Render(when))
+		if i == q.cursor {
+			rows = append(rows, selectedMenuItemStyle.Render("❯ ")+row)
+		} else {
+			rows = append(rows, "  "+row)
+		}
+	}
+	if len(q.tasks) == 0 {
+		rows = append(rows, mutedStyle.Render("No tasks yet; refreshes, template runs and uninstalls show up here."))
+	}
+	running, pending := q.counts()
+	return lipgloss.JoinVertical(lipgloss.Left,
+		titleStyle.Render("Task Queue"),
+		mutedStyle.Render(fmt.Sprintf("%d running, %d pending", running, pending)),
+		boxStyle.Width(width-4).Render(strings.Join(rows, "\n")),
+		queueKeys.footer(),
+	)
+}
+
+// logView shows the selected task's log, scrolled up by offset lines.
+func (q queueScreen) logView(width, height int) string {
+	name := fmt.Sprintf("#%d", q.viewing)
+	for _, t := range q.tasks {
+		if t.ID == q.viewing {
+			name += " " + t.Name + " (" + t.State.String() + ")"
+		}
+	}
+	log := q.logs[q.viewing]
+	visible := max(height-8, 5)
+	end := max(len(log)-q.offset, 0)
+	lines := log[max(end-visible, 0):end]
+	if len(log) == 0 {
+		lines = []string{mutedStyle.Render("Nothing logged for this task.")}
+	}
+	return lipgloss.JoinVertical(lipgloss.Left,
+		titleStyle.Render("Log of "+name),
+		boxStyle.Width(width-4).Render(strings.Join(lines, "\n")),
+		queueLogKeys.footer(),
+	)
+}
@@ -0,0 +1,120 @@
+package tui
+
+import (
+	"strings"
+
+	tea "github.com/charmbracelet/bubbletea"
+	"github.com/charmbracelet/lipgloss"
+
+	"github.com/hmziqrs/maziq/internal/manager"
+)
+
+// recoveryChoices are the dialog's options; the empty Recovery opens the
+// failed command's full output instead of answering.
+var recoveryChoices = []struct {
+	key      string
+	label    string
+	recovery manager.Recovery
+}{
+	{"r", "Retry", manager.RecoveryRetry},
+	{"s", "Skip", manager.RecoverySkip},
+	{"a", "Abort", manager.RecoveryAbort},
+	{"l", "Open full log", ""},
+}
+
+// recoveryDialog asks what to do about a failed step while the run waits
+// for the answer.
+type recoveryDialog struct {
+	request manager.RecoveryRequest
+	cursor  int
+	// log shows the failed command's output instead of the choices.
+	log     bool
+}
+
+// update handles a key while the dialog is open. done reports that an
+// answer was sent to the run.
+func (d recoveryDialog) update(msg tea.KeyMsg) (dialog recoveryDialog, done bool) {
+	key := msg.String()
+	if d.log {
+		if key == "esc" || key == "q" || key == "l" || key == "enter" {
+			d.log = false
+		}
+		return d, false
+	}
+	choice := -1
+	switch key {
+	case "left", "h", "shift+tab":
+		d.cursor = (d.cursor + len(recoveryChoices) - 1) % len(recoveryChoices)
+	case "right", "tab":
+		d.cursor = (d.cursor + 1) % len(recoveryChoices)
+	case "enter":
+		choice = d.cursor
+	default:
+		for i, c := range recoveryChoices {
+			if key == c.key {
+				choice = i
+			}
+		}
+	}
+	if choice < 0 {
+		return d, false
+	}
+	if recoveryChoices[choice].recovery == "" {
+		d.log = true
+		return d, false
+	}
+	d.request.Reply <- recoveryChoices[choice].recovery
+	return d, true
+}
+
+func (d recoveryDialog) view(width, height int) string {
+	req := d.request
+	if d.log {
+		return d.logView(width, height)
+	}
+	var choices []string
+	for i, c := range recoveryChoices {
+		if i == d.cursor {
+			choices = append(choices, selectedMenuItemStyle.Render("❯ "+c.label))
+		} else {
+			choices = append(choices, menuItemStyle.Render(c.label))
+		}
+	}
+	lines := []string{
+		errorStyle.Render(string(req.Step.Action) + " " + req.Step.Entry.Name + " failed"),
+		"",
+		req.Err.Error(),
+	}
+	if detail := errorDetail(req.Err); detail != "" {
+		lines = append(lines, mutedStyle.Render(detail))
+	}
+	if req.Event.Hint != "" {
+		lines = append(lines, warningStyle.Render("hint: "+req.Event.Hint))
+	}
+	lines = append(lines, "", strings.Join(choices, "   "), recoveryKeys.footer())
+	box := dialogStyle.Width(min(width-8, 80)).Render(lipgloss.JoinVertical(lipgloss.Left, lines...))
+	return lipgloss.Place(width, height, lipgloss.Center, lipgloss.Center, box)
+}
+
+// logView shows the tail of the failed command's output that fits.
+func (d recoveryDialog) logView(width, height int) string {
+	ev := d.request.Event
+	var lines []string
+	lines = append(lines, mutedStyle.Render("$ "+ev.Command))
+	for _, stream := range []string{ev.Stdout, ev.Stderr} {
+		if out := strings.TrimRight(stream, "\n"); out != "" {
+			lines = append(lines, strings.Split(out, "\n")...)
+		}
+	}
+	if len(lines) == 1 {
+		lines = append(lines, mutedStyle.Render("The command printed nothing."))
+	}
+	if visible := max(height-8, 5); len(lines) > visible {
+		lines = append([]string{lines[0], mutedStyle.Render("[…]")}, lines[len(lines)-visible+2:]...)
+	}
+	return lipgloss.JoinVertical(lipgloss.Left,
+		titleStyle.Render("Output of "+d.request.Step.Entry.ID),
+		boxStyle.Width(width-4).Render(strings.Join(lines, "\n")),
+		mutedStyle.Render("Esc back to the choices"),
+	)
+}
@@ -0,0 +1,116 @@
+package tui
+
+import (
+	"fmt"
+	"strings"
+
+	tea "github.com/charmbracelet/bubbletea"
+	"github.com/charmbracelet/lipgloss"
+
+	"github.com/hmziqrs/maziq/internal/manager"
+)
+
+// planReview shows a template's plan before it runs and lets the user
+// reorder it within the dependency order and push steps to the end.
+type planReview struct {
+	title   string
+	plan    manager.Plan
+	cursor  int
+	// last holds the ids marked "run last".
+	last    map[string]bool
+	// message says why the last move was refused.
+	message string
+}
+
+func newPlanReview(title string, plan manager.Plan) *planReview {
+	return &planReview{title: title, plan: plan, last: map[string]bool{}}
+}
+
+// result is the plan to run: the reordered steps with the "run last" ones
+// moved to the end.
+func (r planReview) result() manager.Plan {
+	return r.plan.RunLast(r.last)
+}
+
+// update handles a key. done reports that the review was closed and
+// accepted whether the plan should run.
+func (r planReview) update(msg tea.KeyMsg) (review planReview, done, accepted bool) {
+	r.message = ""
+	switch msg.String() {
+	case "up", "k":
+		if r.cursor > 0 {
+			r.cursor--
+		}
+	case "down", "j":
+		if r.cursor < len(r.plan.Steps)-1 {
+			r.cursor++
+		}
+	case "K", "shift+up":
+		r = r.move(-1)
+	case "J", "shift+down":
+		r = r.move(1)
+	case "L":
+		if len(r.plan.Steps) > 0 {
+			id := r.plan.Steps[r.cursor].Entry.ID
+			r.last[id] = !r.last[id]
+			if !r.last[id] {
+				delete(r.last, id)
+			}
+		}
+	case "enter":
+		return r, true, true
+	case "esc", "q":
+		return r, true, false
+	}
+	return r, false, false
+}
+
+func (r planReview) move(delta int) planReview {
+	plan, ok := r.plan.MoveStep(r.cursor, delta)
+	if !ok {
+		if j := r.cursor + delta; j >= 0 && j < len(r.plan.Steps) {
+			a, b := r.plan.Steps[r.cursor].Entry.ID, r.plan.Steps[j].Entry.ID
+			r.message = fmt.Sprintf("%s cannot move past %s: one needs the other first", a, b)
+		}
+		return r
+	}
+	r.plan = plan
+	r.cursor += delta
+	return r
+}
+
+func (r planReview) view(width, height int) string {
+	order := r.result().IDs()
+	position := make(map[string]int, len(order))
+	for i, id := range order {
+		position[id] = i + 1
+	}
+
+	visible := max(height-10, 5)
+	start := max(r.cursor-visible+1, 0)
+	var rows []string
+	for i := start; i < len(r.plan.Steps) && i < start+visible; i++ {
+		step := r.plan.Steps[i]
+		line := fmt.Sprintf("%3d  %-26s %s", position[step.Entry.ID], step.Entry.Name, mutedStyle.Render(step.Entry.ID))
+		if r.last[step.Entry.ID] {
+			line += warningStyle.Render("  run last")
+		}
+		if i == r.cursor {
+			rows = append(rows, selectedMenuItemStyle.Render("❯ ")+line)
+		} else {
+			rows = append(rows, "  "+line)
+		}
+	}
+	if len(rows) == 0 {
+		rows = append(rows, mutedStyle.Render("The plan is empty."))
+	}
+	sections := []string{
+		titleStyle.Render(fmt.Sprintf("Review %s (%d steps)", r.title, len(r.plan.Steps))),
+		boxStyle.Width(width - 4).Render(strings.Join(rows, "\n")),
+	}
+	if r.message != "" {
+		sections = append(sections, errorStyle.Render(r.message))
+	}
+	sections = append(sections, reviewKeys.footer())
+	return lipgloss.JoinVertical(lipgloss.Left, sections...)
+}
@@ -0,0 +1,86 @@
+package tui
+
+import (
+	"fmt"
+	"strings"
+	"time"
+
+	tea "github.com/charmbracelet/bubbletea"
+	"github.com/charmbracelet/lipgloss"
+
+	"github.com/hmziqrs/maziq/internal/history"
+	"github.com/hmziqrs/maziq/internal/manager"
+)
+
+// statsScreen shows what the local history says about past runs, the same
+// numbers as `maziq stats`.
+type statsScreen struct {
+	stats history.Stats
+	err   error
+}
+
+// open reloads the history; it is a local file, so this is quick.
+func (s *statsScreen) open() {
+	records, err := history.Load()
+	s.stats, s.err = history.Summarize(records), err
+}
+
+func (s statsScreen) update(msg tea.KeyMsg) (statsScreen, tea.Cmd, bool) {
+	switch msg.String() {
+	case "r":
+		s.open()
+	case "esc", "q":
+		return s, nil, true
+	}
+	return s, nil, false
+}
+
+func (s statsScreen) view(width, height int) string {
+	title := titleStyle.Render("Statistics")
+	if s.err != nil {
+		return lipgloss.JoinVertical(lipgloss.Left, title, errorStyle.Render("Could not read history: "+s.err.Error()), statsKeys.footer())
+	}
+	st := s.stats
+	if st.Records == 0 {
+		return lipgloss.JoinVertical(lipgloss.Left, title, mutedStyle.Render("No history yet; run an install first."), statsKeys.footer())
+	}
+
+	totals := []string{
+		fmt.Sprintf("%-16s %d", "Installs", st.Installs),
+		fmt.Sprintf("%-16s %d", "Updates", st.Updates),
+		fmt.Sprintf("%-16s %d", "Uninstalls", st.Uninstalls),
+		fmt.Sprintf("%-16s %d of %d", "Failed runs", st.Failures, st.Records),
+	}
+	if o := st.LastOnboarding; o != nil {
+		totals = append(totals, fmt.Sprintf("%-16s %s on %s, %s, %d steps (%d failed)",
+			"Last onboarding", o.Template, o.Started.Format("2006-01-02 15:04"), manager.FormatETA(o.Duration), o.Steps, o.Failed))
+	} else {
+		totals = append(totals, fmt.Sprintf("%-16s %s", "Last onboarding", mutedStyle.Render("never")))
+	}
+
+	backends := []string{mutedStyle.Render(fmt.Sprintf("%-16s %-6s %s", "BACKEND", "RUNS", "AVERAGE"))}
+	for _, b := range st.Backends {
+		source := b.Source
+		if source == "" {
+			source = "none"
+		}
+		backends = append(backends, fmt.Sprintf("%-16s %-6d %s", source, b.Runs, b.Average.Round(time.Second)))
+	}
+
+	failed := []string{mutedStyle.Render(fmt.Sprintf("%-22s %-9s %s", "MOST FAILED", "FAILURES", "RUNS"))}
+	for _, f := range st.MostFailed {
+		failed = append(failed, fmt.Sprintf("%-22s %-9d %d", f.Software, f.Failures, f.Runs))
+	}
+	if len(st.MostFailed) == 0 {
+		failed = append(failed, readyStyle.Render("No failures recorded."))
+	}
+
+	box := boxStyle.Width(width - 4)
+	return lipgloss.JoinVertical(lipgloss.Left,
+		title,
+		box.Render(strings.Join(totals, "\n")),
+		box.Render(strings.Join(backends, "\n")),
+		box.Render(strings.Join(failed, "\n")),
+		statsKeys.footer(),
+	)
+}
@@ -0,0 +1,99 @@
+package tui
+
+import (
+	"github.com/charmbracelet/lipgloss"
+	"github.com/muesli/termenv"
+)
+
+// Styles
+var (
+	// Colors
+	primaryColor   = lipgloss.Color("#00D9FF")
+	secondaryColor = lipgloss.Color("#7C3AED")
+	accentColor    = lipgloss.Color("#10B981")
+	mutedColor     = lipgloss.Color("#6B7280")
+	warningColor   = lipgloss.Color("#F59E0B")
+	errorColor     = lipgloss.Color("#EF4444")
+
+	// Title style
+	titleStyle = lipgloss.NewStyle().
+			Foreground(primaryColor).
+			Bold(true).
+			Padding(0, 1).
+			MarginTop(1).
+			MarginBottom(1)
+
+	// Logo ASCII art style
+	logoStyle = lipgloss.NewStyle().
+			Foreground(secondaryColor).
+			Bold(true)
+
+	// Subtitle style
+	subtitleStyle = lipgloss.NewStyle().
+			Foreground(mutedColor).
+			Italic(true).
+			MarginBottom(1)
+
+	// Box style for content sections
+	boxStyle = lipgloss.NewStyle().
+			Border(lipgloss.RoundedBorder()).
+			BorderForeground(primaryColor).
+			Padding(1, 2).
+			MarginTop(1).
+			MarginBottom(1)
+
+	// Modal dialog style
+	dialogStyle = lipgloss.NewStyle().
+			Border(lipgloss.RoundedBorder()).
+			BorderForeground(warningColor).
+			Padding(1, 2)
+
+	// Menu item styles
+	menuItemStyle = lipgloss.NewStyle().
+			Foreground(lipgloss.Color("#E5E7EB")).
+			PaddingLeft(2)
+
+	selectedMenuItemStyle = lipgloss.NewStyle().
+				Foreground(primaryColor).
+				Bold(true).
+				PaddingLeft(0)
+
+	// Help style
+	helpStyle = lipgloss.NewStyle().
+			Foreground(mutedColor).
+			Padding(1, 0)
+
+	// Status indicator styles
+	readyStyle = lipgloss.NewStyle().
+			Foreground(accentColor).
+			Bold(true)
+
+	errorStyle = lipgloss.NewStyle().
+			Foreground(errorColor).
+			Bold(true)
+
+	warningStyle = lipgloss.NewStyle().
+			Foreground(warningColor).
+			Bold(true)
+
+	mutedStyle = lipgloss.NewStyle().
+			Foreground(mutedColor)
+
+	// Progress gauge styles
+	gaugeFilledStyle = lipgloss.NewStyle().
+				Foreground(accentColor)
+
+	gaugeEmptyStyle = lipgloss.NewStyle().
+			Foreground(mutedColor)
+)
+
+// applyTheme adapts the styles to the configured theme: "dark" (the
+// default), "light" for light terminal backgrounds, or "mono" for no color.
+func applyTheme(name string) {
+	switch name {
+	case "light":
+		menuItemStyle = menuItemStyle.Foreground(lipgloss.Color("#1F2937"))
+	case "mono":
+		lipgloss.SetColorProfile(termenv.Ascii)
+	}
+}
@@ -0,0 +1,688 @@
+package tui
+
+import (
+	"context"
+	"errors"
+	"fmt"
+	"slices"
+	"strings"
+	"time"
+
+	tea "github.com/charmbracelet/bubbletea"
+	"github.com/charmbracelet/lipgloss"
+
+	"github.com/hmziqrs/maziq/internal/catalog"
+	"github.com/hmziqrs/maziq/internal/manager"
+	"github.com/hmziqrs/maziq/internal/options"
+	"github.com/hmziqrs/maziq/internal/prompt"
+	"github.com/hmziqrs/maziq/internal/templates"
+)
+
+// resolveTemplatePlan resolves the install plan of the named template.
+func resolveTemplatePlan(name string, opts options.Run) (manager.Plan, error) {
+	t, err := templates.Load(name)
+	if err != nil {
+		return manager.Plan{}, err
+	}
+	return manager.ResolveTemplate(t, manager.ActionInstall, opts)
+}
+
+// destructiveCommands lists the plan's recipes under backend that need
+// confirmation.
+func destructiveCommands(plan manager.Plan, backend options.Backend) []string {
+	var out []string
+	for _, step := range plan.Steps {
+		for _, recipe := range manager.PreferredRecipes(step.Entry, step.Action, backend) {
+			if manager.Destructive(step.Action, recipe) {
+				out = append(out, recipe.Command)
+			}
+		}
+	}
+	return out
+}
+
+// tickMsg refreshes the elapsed time while a run is in progress.
+type tickMsg time.Time
+
+func tick() tea.Cmd {
+	return tea.Tick(time.Second, func(now time.Time) tea.Msg {
+		return tickMsg(now)
+	})
+}
+
+type stepState int
+
+const (
+	stepPending stepState = iota
+	stepRunning
+	stepSkipped
+	stepDone
+	stepFailed
+)
+
+type tasksScreen struct {
+	engine    *manager.Engine
+	opts      options.Run
+	templates []string
+	// usage is each template's estimated disk usage from catalog sizes.
+	usage     map[string]manager.DiskUsage
+	cursor    int
+	title     string
+	// phase is where the run on screen is in its lifecycle, and whether
+	// the picker is shown in front of it to queue another.
+	phase     runPhase
+	err       error
+	job       manager.JobID
+	// later holds runs queued while this one was shown; the screen moves
+	// on to the next when the current one finishes.
+	later     []queuedRun
+	// next is the template being started and pickErr why it could not be.
+	next      string
+	pickErr   error
+	// confirm guards starting pending when its plan has destructive steps;
+	// password collects the sudo password when it needs root.
+	confirm   *confirmDialog
+	password  *passwordDialog
+	pending   manager.Plan
+	// prefetch downloads the first steps of pending while a dialog is open.
+	prefetch  *manager.Prefetch
+	// review shows the plan of the chosen template before it runs.
+	review    *planReview
+	// recovery asks what to do about a failed step; the run waits for it.
+	recovery  *recoveryDialog
+
+	ids     []string
+	states  map[string]stepState
+	events  []manager.TaskEvent
+	// reasons says why a step was skipped or failed.
+	reasons map[string]string
+
+	// Progress: current is the entry being worked on, completed counts
+	// finished or skipped steps. estimates holds historical durations.
+	current        string
+	currentStarted time.Time
+	completed      int
+	started        time.Time
+	ended          time.Time
+	estimates      map[string]time.Duration
+
+	// filter narrows the task panel and log to one software id; "" shows all.
+	filter  string
+	picker  *promptField
+	fullLog bool
+}
+
+// queuedRun is a template run waiting behind the one on screen.
+type queuedRun struct {
+	job   manager.JobID
+	title string
+	plan  manager.Plan
+}
+
+// newTasksScreen lists the templates with preferred preselected; runs
+// follow opts.
+func newTasksScreen(engine *manager.Engine, preferred string, opts options.Run) tasksScreen {
+	t := tasksScreen{engine: engine, opts: opts, templates: templates.List(), usage: map[string]manager.DiskUsage{}}
+	for i, name := range t.templates {
+		if name == preferred {
+			t.cursor = i
+		}
+		if plan, err := resolveTemplatePlan(name, opts); err == nil {
+			t.usage[name] = manager.Usage(plan, manager.PlanSizes(plan))
+		}
+	}
+	return t
+}
+
+func (t tasksScreen) active() bool {
+	return t.phase != phaseIdle && !t.phase.choosing()
+}
+
+// onJobEvent applies an engine event belonging to the current run.
+func (t tasksScreen) onJobEvent(msg manager.JobEvent) tasksScreen {
+	if msg.Done {
+		for i, run := range t.later {
+			if run.job == msg.Job {
+				// Cancelled from the queue screen before it started.
+				t.later = slices.Delete(t.later, i, i+1)
+				return t
+			}
+		}
+	}
+	if msg.Job != t.job {
+		return t
+	}
+	if msg.Done {
+		t.phase = t.phase.next(eventDone)
+		t.err = msg.Err
+		t.ended = time.Now()
+		t.recovery = nil
+		if len(t.later) > 0 {
+			next := t.later[0]
+			t.later = t.later[1:]
+			t = t.track(next)
+		}
+		return t
+	}
+	if _, ok := msg.Payload.(manager.JobStarted); ok {
+		t.phase = t.phase.next(eventStarted)
+		t.started = time.Now()
+		return t
+	}
+	if req, ok := msg.Payload.(manager.RecoveryRequest); ok {
+		t.recovery = &recoveryDialog{request: req}
+		return t
+	}
+	if ev, ok := msg.Payload.(manager.TaskEvent); ok {
+		t.events = append(t.events, ev)
+		if !slices.Contains(t.ids, ev.SoftwareID) {
+			// Preflight checks and other run-wide messages are logged only.
+			return t
+		}
+		switch ev.Kind {
+		case manager.EventStepStarted:
+			t.states[ev.SoftwareID] = stepRunning
+			t.current = ev.SoftwareID
+			t.currentStarted = ev.Time
+		case manager.EventStepFinished:
+			switch {
+			case ev.Skipped:
+				t.states[ev.SoftwareID] = stepSkipped
+				t.reasons[ev.SoftwareID] = ev.Reason
+				t.completed++
+			case ev.Execution != nil && ev.Execution.Success:
+				t.states[ev.SoftwareID] = stepDone
+				t.completed++
+			default:
+				t.states[ev.SoftwareID] = stepFailed
+				t.reasons[ev.SoftwareID] = ev.Reason
+				if ev.Execution != nil && ev.Execution.Hint != "" {
+					t.reasons[ev.SoftwareID] += " → " + ev.Execution.Hint
+				}
+			}
+		}
+	}
+	return t
+}
+
+// launch starts plan, asking for the sudo password first when it needs one.
+func (t tasksScreen) launch(plan manager.Plan) (tasksScreen, tea.Cmd) {
+	if needsPassword(plan, t.opts) {
+		t.pending = plan
+		t = t.startPrefetch(plan)
+		t.password = newPasswordDialog("Template " + t.next + " runs commands with sudo.")
+		return t, nil
+	}
+	return t.start(plan), tick()
+}
+
+// onSudo continues a launch once the password was checked.
+func (t tasksScreen) onSudo(msg sudoMsg) (tasksScreen, tea.Cmd) {
+	if t.password == nil || !t.password.checking {
+		return t, nil
+	}
+	dialog, ok := t.password.result(msg)
+	if !ok {
+		t.password = &dialog
+		return t, nil
+	}
+	t.password = nil
+	return t.start(t.pending), tick()
+}
+
+// start queues plan on the engine, which forwards every TaskEvent as a job
+// payload. The progress view follows it right away, or once the run on
+// screen finishes.
+func (t tasksScreen) start(plan manager.Plan) tasksScreen {
+	sudo := manager.PlanNeedsSudo(plan, t.opts.Backend)
+	opts := t.opts
+	prefetch := t.prefetch
+	t.prefetch = nil
+	run := queuedRun{title: t.next, plan: plan}
+	run.job = t.engine.Enqueue("template "+t.next, func(ctx context.Context, emit func(any)) error {
+		prefetch.Stop()
+		if sudo {
+			go manager.SudoKeepAlive(ctx)
+		}
+		m := manager.New(opts)
+		m.Recover = manager.AskRecovery(emit)
+		return m.RunPlan(ctx, plan, func(ev manager.TaskEvent) { emit(ev) })
+	})
+	t.phase = t.phase.next(eventChosen)
+	if t.phase.busy() {
+		t.later = append(t.later, run)
+		return t
+	}
+	return t.track(run)
+}
+
+// startPrefetch begins downloading plan's first steps while the user
+// answers a dialog, unless queued work is running that it could race for
+// Homebrew's lock.
+func (t tasksScreen) startPrefetch(plan manager.Plan) tasksScreen {
+	if t.prefetch != nil {
+		return t
+	}
+	for _, task := range t.engine.Tasks() {
+		if task.Queued && task.State <= manager.TaskRunning {
+			return t
+		}
+	}
+	t.prefetch = manager.StartPrefetch(plan, t.opts)
+	return t
+}
+
+// dropPrefetch stops downloading ahead once the run was called off.
+func (t tasksScreen) dropPrefetch() tasksScreen {
+	go t.prefetch.Stop()
+	t.prefetch = nil
+	return t
+}
+
+// track resets the progress view to follow run.
+func (t tasksScreen) track(run queuedRun) tasksScreen {
+	plan := run.plan
+	t.job = run.job
+	t.title = run.title
+	t.phase = t.phase.next(eventTracked)
+	for _, task := range t.engine.Tasks() {
+		if task.ID == run.job && task.State != manager.TaskPending {
+			t.phase = t.phase.next(eventStarted)
+		}
+	}
+	t.err = nil
+	t.ids = plan.IDs()
+	t.estimates = manager.Estimate(plan)
+	t.states = map[string]stepState{}
+	t.reasons = map[string]string{}
+	t.events = nil
+	t.filter = ""
+	t.current = ""
+	t.completed = 0
+	t.started = time.Now()
+	t.ended = time.Time{}
+	return t
+}
+
+func (t tasksScreen) update(msg tea.KeyMsg) (tasksScreen, tea.Cmd, bool) {
+	if t.confirm != nil {
+		dialog, done, accepted := t.confirm.update(msg)
+		t.confirm = &dialog
+		if !done {
+			return t, nil, false
+		}
+		t.confirm = nil
+		if accepted {
+			var cmd tea.Cmd
+			t, cmd = t.launch(t.pending)
+			return t, cmd, false
+		}
+		t = t.dropPrefetch()
+		return t, nil, false
+	}
+	if t.password != nil {
+		dialog, submitted, cancelled := t.password.update(msg)
+		t.password = &dialog
+		switch {
+		case cancelled:
+			t.password = nil
+			t = t.dropPrefetch()
+		case submitted != "":
+			t.engine.Submit("sudo", sudoJob(submitted))
+		}
+		return t, nil, false
+	}
+	if t.review != nil {
+		review, done, accepted := t.review.update(msg)
+		t.review = &review
+		if !done {
+			return t, nil, false
+		}
+		t.review = nil
+		if !accepted {
+			return t, nil, false
+		}
+		plan := review.result()
+		if commands := destructiveCommands(plan, t.opts.Backend); len(commands) > 0 {
+			t.pending = plan
+			t = t.startPrefetch(plan)
+			t.confirm = newConfirmDialog("Template "+t.next+" runs destructive commands. Continue?", strings.Join(commands, "\n"))
+			return t, nil, false
+		}
+		var cmd tea.Cmd
+		t, cmd = t.launch(plan)
+		return t, cmd, false
+	}
+	if t.recovery != nil {
+		dialog, done := t.recovery.update(msg)
+		t.recovery = &dialog
+		if done {
+			t.recovery = nil
+		}
+		return t, nil, false
+	}
+
+	key := msg.String()
+	if t.picker != nil {
+		field, answer, done, err := t.picker.update(msg)
+		t.picker = &field
+		if done {
+			if err == nil {
+				t.filter = t.filterOptions()[answer.Selected[0]]
+			}
+			t.picker = nil
+		}
+		return t, nil, false
+	}
+
+	if !t.active() {
+		switch key {
+		case "esc", "q":
+			if t.phase.choosing() {
+				t.phase = t.phase.next(eventChosen)
+				t.pickErr = nil
+				return t, nil, false
+			}
+			return t, nil, true
+		case "up", "k":
+			if t.cursor > 0 {
+				t.cursor--
+			}
+		case "down", "j":
+			if t.cursor < len(t.templates)-1 {
+				t.cursor++
+			}
+		case "enter":
+			if len(t.templates) == 0 {
+				return t, nil, false
+			}
+			name := t.templates[t.cursor]
+			plan, err := resolveTemplatePlan(name, t.opts)
+			t.next = name
+			t.pickErr = err
+			if err != nil {
+				return t, nil, false
+			}
+			t.review = newPlanReview(name, plan)
+			return t, nil, false
+		}
+		return t, nil, false
+	}
+
+	switch key {
+	case "n":
+		t.phase = t.phase.next(eventChoose)
+	case "f":
+		labels := append([]string{"All entries"}, t.ids...)
+		picker := newPromptField(prompt.Prompt{Kind: prompt.KindSelect, Options: labels})
+		t.picker = &picker
+	case "l":
+		t.fullLog = !t.fullLog
+	case "c":
+		if t.phase.busy() {
+			t.engine.Cancel(t.job)
+		}
+	case "esc", "q":
+		if t.phase.busy() {
+			return t, nil, true
+		}
+		t.phase = t.phase.next(eventDismissed)
+		t.err = nil
+		t.fullLog = false
+	}
+	return t, nil, false
+}
+
+// trail names the levels open inside the templates screen for the
+// breadcrumb.
+func (t tasksScreen) trail() []string {
+	var trail []string
+	switch {
+	case t.review != nil:
+		trail = append(trail, "Review "+t.next)
+	case t.active():
+		trail = append(trail, t.title)
+	}
+	switch {
+	case t.confirm != nil:
+		trail = append(trail, "Confirm")
+	case t.password != nil:
+		trail = append(trail, "Password")
+	case t.recovery != nil:
+		trail = append(trail, "Failed step")
+	case t.picker != nil:
+		trail = append(trail, "Filter")
+	case t.phase.choosing():
+		trail = append(trail, "Queue another")
+	}
+	return trail
+}
+
+// filterOptions lists the ids that can be picked, with "" meaning all.
+func (t tasksScreen) filterOptions() []string {
+	return append([]string{""}, t.ids...)
+}
+
+func (t tasksScreen) visibleEvents() []manager.TaskEvent {
+	if t.filter == "" {
+		return t.events
+	}
+	var out []manager.TaskEvent
+	for _, ev := range t.events {
+		if ev.SoftwareID == t.filter {
+			out = append(out, ev)
+		}
+	}
+	return out
+}
+
+func (t tasksScreen) elapsed() time.Duration {
+	end := t.ended
+	if end.IsZero() {
+		end = time.Now()
+	}
+	return end.Sub(t.started).Truncate(time.Second)
+}
+
+// remaining estimates the time left from the historical durations of the
+// steps not finished yet, less what the current step already spent.
+func (t tasksScreen) remaining() time.Duration {
+	var left time.Duration
+	for _, id := range t.ids {
+		switch t.states[id] {
+		case stepPending:
+			left += t.estimates[id]
+		case stepRunning:
+			if spent := time.Since(t.currentStarted); spent < t.estimates[id] {
+				left += t.estimates[id] - spent
+			}
+		}
+	}
+	return left
+}
+
+// progressView renders the run gauge and a "step 7/23 — installing X" line.
+func (t tasksScreen) progressView(width int) string {
+	total := len(t.ids)
+	var label string
+	switch {
+	case t.phase == phaseRunning && t.current != "":
+		name := t.current
+		if entry, ok := catalog.Lookup(t.current); ok {
+			name = entry.Name
+		}
+		label = fmt.Sprintf("step %d/%d — installing %s", min(t.completed+1, total), total, name)
+	case t.phase == phaseQueued:
+		label = fmt.Sprintf("step 0/%d — waiting for the tasks queued before it", total)
+	case t.phase == phaseRunning:
+		label = fmt.Sprintf("step 0/%d — checking installed software", total)
+	default:
+		label = fmt.Sprintf("%d/%d steps completed", t.completed, total)
+	}
+	label += mutedStyle.Render("   elapsed " + t.elapsed().String())
+	if t.phase.busy() {
+		label += mutedStyle.Render("   ETA " + manager.FormatETA(t.remaining()))
+	}
+	return renderGauge(width, t.completed, total) + "\n" + label
+}
+
+func renderGauge(width, done, total int) string {
+	width -= 5
+	if width < 10 {
+		width = 10
+	}
+	filled, percent := 0, 0
+	if total > 0 {
+		filled = width * done / total
+		percent = 100 * done / total
+	}
+	return gaugeFilledStyle.Render(strings.Repeat("█", filled)) +
+		gaugeEmptyStyle.Render(strings.Repeat("░", width-filled)) +
+		fmt.Sprintf(" %3d%%", percent)
+}
+
+func renderStep(state stepState) string {
+	switch state {
+	case stepRunning:
+		return warningStyle.Render("◐ running")
+	case stepSkipped:
+		return mutedStyle.Render("↷ skipped")
+	case stepDone:
+		return readyStyle.Render("✓ done")
+	case stepFailed:
+		return errorStyle.Render("✗ failed")
+	}
+	return mutedStyle.Render("○ pending")
+}
+
+func (t tasksScreen) view(width, height int) string {
+	if t.confirm != nil {
+		return t.confirm.view(width, height)
+	}
+	if t.password != nil {
+		return t.password.view(width, height)
+	}
+	if t.recovery != nil {
+		return t.recovery.view(width, height)
+	}
+	if t.review != nil {
+		return t.review.view(width, height)
+	}
+	if !t.active() {
+		return t.pickerView(width)
+	}
+
+	title := titleStyle.Render("Template run: " + t.title)
+	var state string
+	switch {
+	case t.phase == phaseQueued:
+		state = mutedStyle.Render("● Queued behind other tasks")
+	case t.phase == phaseRunning:
+		state = warningStyle.Render("● Running")
+	case errors.Is(t.err, context.Canceled):
+		state = warningStyle.Render("● Cancelled")
+	case t.err != nil:
+		state = errorStyle.Render("● Failed: " + t.err.Error())
+		if detail := errorDetail(t.err); detail != "" {
+			state += "\n" + mutedStyle.Render("  "+detail)
+		}
+	default:
+		state = readyStyle.Render("● Finished")
+	}
+	if t.phase == phaseFinished {
+		counts := manager.Tally(manager.Summarize(t.ids, t.events))
+		state += mutedStyle.Render(fmt.Sprintf("   %d succeeded, %d skipped, %d failed",
+			counts[manager.ResultSucceeded], counts[manager.ResultSkipped], counts[manager.ResultFailed]))
+	}
+	if t.filter != "" {
+		state += mutedStyle.Render("   filter: " + t.filter)
+	}
+	if len(t.later) > 0 {
+		var names []string
+		for _, run := range t.later {
+			names = append(names, run.title)
+		}
+		state += mutedStyle.Render("   next: " + strings.Join(names, ", "))
+	}
+	sections := []string{title, state, t.progressView(width - 4)}
+
+	if t.picker != nil {
+		sections = append(sections, boxStyle.Width(width-4).Render(t.picker.view()))
+		sections = append(sections, t.picker.footer())
+		return lipgloss.JoinVertical(lipgloss.Left, sections...)
+	}
+
+	logHeight := height - 12
+	if !t.fullLog {
+		var rows []string
+		for _, id := range t.ids {
+			if t.filter != "" && id != t.filter {
+				continue
+			}
+			row := fmt.Sprintf("%-24s %s", id, renderStep(t.states[id]))
+			if reason := t.reasons[id]; reason != "" {
+				row += mutedStyle.Render("  " + reason)
+			}
+			rows = append(rows, row)
+		}
+		if len(rows) > height/2 {
+			rows = rows[:height/2]
+		}
+		sections = append(sections, boxStyle.Width(width-4).Render(strings.Join(rows, "\n")))
+		logHeight -= len(rows) + 4
+	}
+
+	events := t.visibleEvents()
+	if logHeight < 3 {
+		logHeight = 3
+	}
+	if len(events) > logHeight {
+		events = events[len(events)-logHeight:]
+	}
+	var lines []string
+	for _, ev := range events {
+		lines = append(lines, mutedStyle.Render(ev.Time.Format("15:04:05"))+" "+ev.Message)
+	}
+	if len(lines) == 0 {
+		lines = append(lines, mutedStyle.Render("No events yet."))
+	}
+	sections = append(sections, boxStyle.Width(width-4).Render(strings.Join(lines, "\n")))
+	sections = append(sections, runKeys.footer())
+	return lipgloss.JoinVertical(lipgloss.Left, sections...)
+}
+
+func (t tasksScreen) pickerView(width int) string {
+	var rows []string
+	for i, name := range t.templates {
+		if i == t.cursor {
+			rows = append(rows, selectedMenuItemStyle.Render("❯ "+name))
+		} else {
+			rows = append(rows, menuItemStyle.Render(name))
+		}
+	}
+	if len(rows) == 0 {
+		rows = append(rows, mutedStyle.Render("No templates found."))
+	}
+	title := "Templates"
+	if t.phase.choosing() {
+		title = "Queue another template after " + t.title
+	}
+	sections := []string{
+		titleStyle.Render(title),
+		boxStyle.Width(width - 4).Render(strings.Join(rows, "\n")),
+	}
+	if len(t.templates) > 0 {
+		if usage := t.usage[t.templates[t.cursor]]; usage.Installed > 0 {
+			line := fmt.Sprintf("Estimated disk usage: %s download, %s installed", manager.FormatSize(usage.Download), manager.FormatSize(usage.Installed))
+			if usage.Unknown > 0 {
+				line += fmt.Sprintf(" (+%d entries without size information)", usage.Unknown)
+			}
+			sections = append(sections, mutedStyle.Render(line))
+		}
+	}
+	if t.pickErr != nil {
+		sections = append(sections, errorStyle.Render(t.pickErr.Error()))
+	}
+	sections = append(sections, templateKeys.footer())
+	return lipgloss.JoinVertical(lipgloss.Left, sections...)
+}
@@ -0,0 +1,97 @@
+package tui
+
+import (
+	"time"
+
+	tea "github.com/charmbracelet/bubbletea"
+	"github.com/charmbracelet/lipgloss"
+)
+
+type severity int
+
+const (
+	severityInfo severity = iota
+	severitySuccess
+	severityWarning
+	severityError
+)
+
+// toastLifetime is how long a toast of each severity stays on screen;
+// failures linger so they are not missed behind quicker updates.
+var toastLifetime = map[severity]time.Duration{
+	severityInfo:    4 * time.Second,
+	severitySuccess: 6 * time.Second,
+	severityWarning: 10 * time.Second,
+	severityError:   15 * time.Second,
+}
+
+// maxToasts caps the stack; the oldest toast makes room for a new one.
+const maxToasts = 4
+
+type toast struct {
+	text     string
+	severity severity
+	expires  time.Time
+}
+
+// toasts is a stack of transient notifications, oldest first.
+type toasts []toast
+
+// push adds a notification. Repeating the newest one only extends it.
+func (t toasts) push(sev severity, text string) toasts {
+	expires := time.Now().Add(toastLifetime[sev])
+	if n := len(t); n > 0 && t[n-1].text == text && t[n-1].severity == sev {
+		t[n-1].expires = expires
+		return t
+	}
+	t = append(t, toast{text: text, severity: sev, expires: expires})
+	if len(t) > maxToasts {
+		t = t[len(t)-maxToasts:]
+	}
+	return t
+}
+
+// prune drops the notifications that expired by now.
+func (t toasts) prune(now time.Time) toasts {
+	var kept toasts
+	for _, n := range t {
+		if now.Before(n.expires) {
+			kept = append(kept, n)
+		}
+	}
+	return kept
+}
+
+func (t toasts) view() string {
+	lines := make([]string, 0, len(t))
+	for _, n := range t {
+		var line string
+		switch n.severity {
+		case severitySuccess:
+			line = readyStyle.Render("✓ " + n.text)
+		case severityWarning:
+			line = warningStyle.Render("⚠ " + n.text)
+		case severityError:
+			line = errorStyle.Render("✗ " + n.text)
+		default:
+			line = mutedStyle.Render("• " + n.text)
+		}
+		lines = append(lines, line)
+	}
+	return lipgloss.JoinVertical(lipgloss.Left, lines...)
+}
+
+// toastMsg delivers a toast from a command that ran off the update loop.
+type toastMsg struct {
+	severity severity
+	text     string
+}
+
+// toastTickMsg expires toasts; it keeps ticking while any are shown.
+type toastTickMsg time.Time
+
+func toastTick() tea.Cmd {
+	return tea.Tick(time.Second, func(now time.Time) tea.Msg {
+		return toastTickMsg(now)
+	})
+}
@@ -0,0 +1,76 @@
+package tui
+
+import (
+	"fmt"
+
+	tea "github.com/charmbracelet/bubbletea"
+	"github.com/charmbracelet/lipgloss"
+
+	"github.com/hmziqrs/maziq/internal/config"
+	"github.com/hmziqrs/maziq/internal/templates"
+)
+
+// wizardScreen runs on first launch: it asks what kind of machine this is
+// and turns the answer into a template saved for future runs.
+type wizardScreen struct {
+	cursor int
+	err    error
+}
+
+// update handles a key on the wizard. done reports that the user chose a
+// role, returned in role, or skipped the wizard, leaving role nil.
+func (w wizardScreen) update(msg tea.KeyMsg) (screen wizardScreen, role *templates.Role, done bool) {
+	switch msg.String() {
+	case "up", "k":
+		if w.cursor > 0 {
+			w.cursor--
+		}
+	case "down", "j":
+		if w.cursor < len(templates.Roles)-1 {
+			w.cursor++
+		}
+	case "enter":
+		return w, &templates.Roles[w.cursor], true
+	case "esc", "q":
+		return w, nil, true
+	}
+	return w, nil, false
+}
+
+// finishWizard saves the role's template and records the choice in config.toml,
+// so the wizard does not run again. Skipping records the role "custom".
+func finishWizard(cfg config.Config, role *templates.Role) (config.Config, error) {
+	cfg.Role = "custom"
+	if role != nil {
+		t := templates.RoleTemplate(*role)
+		if _, err := templates.Save(t, true); err != nil {
+			return cfg, err
+		}
+		cfg.Role = role.ID
+		cfg.Template = t.Name
+	}
+	return cfg, config.Save(cfg)
+}
+
+func (w wizardScreen) view(width, height int) string {
+	rows := []string{
+		titleStyle.Render("Welcome to maziq"),
+		"What kind of machine is this?",
+		"",
+	}
+	for i, role := range templates.Roles {
+		if i == w.cursor {
+			rows = append(rows, selectedMenuItemStyle.Render("❯ "+role.Name))
+		} else {
+			rows = append(rows, menuItemStyle.Render(role.Name))
+		}
+		rows = append(rows, mutedStyle.Render("    "+role.Summary))
+	}
+	if w.err != nil {
+		rows = append(rows, "", errorStyle.Render(fmt.Sprintf("Could not save: %v", w.err)))
+	}
+	rows = append(rows, "", mutedStyle.Render("Your choice becomes a template you can edit under "+templates.Dir()+"."))
+	rows = append(rows, wizardKeys.footer())
+	box := boxStyle.Width(min(width-4, 80)).Render(lipgloss.JoinVertical(lipgloss.Left, rows...))
+	return lipgloss.Place(width, height, lipgloss.Center, lipgloss.Center, box)
+}
@@ -0,0 +1,117 @@
+// Package maziq exposes maziq's planning and execution engine so other Go
+// tools can embed it without shelling out to the CLI.
+//
+// The typical flow is PlanTemplate → ExecutePlan, with ProbeStatusAll for
+// read-only status reporting. See the examples/ directory for complete
+// programs.
+package maziq
+
+import (
+	"context"
+
+	"github.com/hmziqrs/maziq/internal/catalog"
+	"github.com/hmziqrs/maziq/internal/manager"
+	"github.com/hmziqrs/maziq/internal/options"
+	"github.com/hmziqrs/maziq/internal/templates"
+)
+
+type (
+	// Entry is a catalog entry.
+	Entry = catalog.Entry
+	// Action is install, update or uninstall.
+	Action = manager.Action
+	// Plan is an ordered, dependency-resolved list of steps.
+	Plan = manager.Plan
+	// Step is one entry/action pair in a Plan.
+	Step = manager.Step
+	// Status is the detected state of an entry.
+	Status = manager.Status
+	// State is installed, missing, outdated, ...
+	State = manager.State
+	// Event is a progress event emitted while a plan executes.
+	Event = manager.TaskEvent
+	// EventKind says what an Event reports.
+	EventKind = manager.EventKind
+	// ExecutionEvent describes a finished command.
+	ExecutionEvent = manager.ExecutionEvent
+	// RunError lists every step that failed in a ContinueOnError run; use
+	// errors.As to reach it.
+	RunError = manager.RunError
+)
+
+// Kinds of Event, in the order a run emits them.
+const (
+	EventPlanComputed = manager.EventPlanComputed
+	EventStepStarted  = manager.EventStepStarted
+	EventStepOutput   = manager.EventStepOutput
+	EventStepFinished = manager.EventStepFinished
+	EventRunFinished  = manager.EventRunFinished
+)
+
+// Actions accepted by Plan* functions.
+const (
+	ActionInstall   = manager.ActionInstall
+	ActionUpdate    = manager.ActionUpdate
+	ActionUninstall = manager.ActionUninstall
+)
+
+// States reported in Status.
+const (
+	StateUnknown      = manager.StateUnknown
+	StateNotInstalled = manager.StateNotInstalled
+	StateInstalled    = manager.StateInstalled
+	StateOutdated     = manager.StateOutdated
+	StateError        = manager.StateError
+)
+
+// Catalog returns every entry maziq knows about.
+func Catalog() []Entry {
+	return catalog.All()
+}
+
+// Templates returns the names of all available templates.
+func Templates() []string {
+	return templates.List()
+}
+
+// PlanTemplate resolves the plan that applies action to every entry of the
+// named template, dependencies first.
+func PlanTemplate(name string, action Action) (Plan, error) {
+	t, err := templates.Load(name)
+	if err != nil {
+		return Plan{}, err
+	}
+	return manager.ResolveTemplate(t, action, options.Defaults())
+}
+
+// PlanIDs resolves the plan that applies action to the given entries.
+func PlanIDs(ids []string, action Action) (Plan, error) {
+	return manager.Resolve(ids, action, options.Defaults())
+}
+
+// ExecuteOptions tunes ExecutePlan.
+type ExecuteOptions struct {
+	// DryRun reports every step without running any command.
+	DryRun          bool
+	// ContinueOnError runs past failed steps, skipping only the steps that
+	// depend on them.
+	ContinueOnError bool
+}
+
+// ExecutePlan runs plan, invoking onEvent for every progress event. It
+// stops at the first failing step and returns its error, or with
+// ContinueOnError returns a *RunError listing every failure.
+func ExecutePlan(ctx context.Context, plan Plan, opts ExecuteOptions, onEvent func(Event)) error {
+	if onEvent == nil {
+		onEvent = func(Event) {}
+	}
+	run := options.Defaults()
+	run.DryRun = opts.DryRun
+	run.ContinueOnError = opts.ContinueOnError
+	return manager.New(run).RunPlan(ctx, plan, onEvent)
+}
+
+// ProbeStatusAll probes every catalog entry, including slow deep probes.
+func ProbeStatusAll(ctx context.Context) map[string]Status {
+	return manager.NewProber(0).ProbeAll(ctx, catalog.All(), manager.ProbeModeFull)
+}
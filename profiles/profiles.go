@@ -0,0 +1,9 @@
+// Package profiles embeds the built-in configurator profiles shipped with maziq.
+package profiles
+
+import "embed"
+
+// FS holds every built-in *.toml profile and the files they reference.
+//
+//go:embed *.toml vscode
+var FS embed.FS
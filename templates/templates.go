@@ -0,0 +1,9 @@
+// Package templates embeds the built-in onboarding templates shipped with maziq.
+package templates
+
+import "embed"
+
+// FS holds every built-in *.toml template.
+//
+//go:embed *.toml
+var FS embed.FS